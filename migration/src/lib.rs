@@ -29,6 +29,31 @@ mod m20260113_223518_add_pricing_to_medical_appointments;
 mod m20260304_201910_add_payment_method_to_medical_appointment;
 mod m20260308_000001_fix_schema_drift;
 mod m20260310_175025_add_revenue_share_percentage_to_user_practitioner_office;
+mod m20260401_090000_add_role_and_suspension_to_users;
+mod m20260405_090000_create_audit_logs_table;
+mod m20260409_090000_add_export_to_audit_action;
+mod m20260413_090000_add_anonymized_at_to_patients;
+mod m20260417_090000_create_patient_accesses_table;
+mod m20260421_090000_add_identity_details_to_patients;
+mod m20260425_090000_add_insurances_and_payer_shares;
+mod m20260429_090000_create_payments_table;
+mod m20260503_090000_create_check_deposits_table;
+mod m20260507_090000_create_expenses_table;
+mod m20260511_090000_create_retrocession_statements_table;
+mod m20260515_090000_create_revenue_share_rates_table;
+mod m20260519_090000_add_retrocession_kinds;
+mod m20260523_090000_create_scheduled_exports;
+mod m20260527_090000_create_acts_tables;
+mod m20260531_090000_create_invoice_templates_table;
+mod m20260604_090000_add_locales;
+mod m20260608_090000_add_email_signature_to_invoice_templates;
+mod m20260612_090000_create_email_logs_table;
+mod m20260616_090000_make_audit_logs_append_only;
+mod m20260620_090000_add_consent_token_expiry_to_patient_accesses;
+mod m20260624_090000_add_claimed_until_to_scheduled_exports;
+mod m20260628_090000_add_act_snapshot_to_medical_appointments;
+mod m20260702_090000_keep_only_additional_legal_mentions;
+mod m20260706_090000_add_sent_email_to_email_logs;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -64,6 +89,31 @@ impl MigratorTrait for Migrator {
       Box::new(
         m20260310_175025_add_revenue_share_percentage_to_user_practitioner_office::Migration,
       ),
+      Box::new(m20260401_090000_add_role_and_suspension_to_users::Migration),
+      Box::new(m20260405_090000_create_audit_logs_table::Migration),
+      Box::new(m20260409_090000_add_export_to_audit_action::Migration),
+      Box::new(m20260413_090000_add_anonymized_at_to_patients::Migration),
+      Box::new(m20260417_090000_create_patient_accesses_table::Migration),
+      Box::new(m20260421_090000_add_identity_details_to_patients::Migration),
+      Box::new(m20260425_090000_add_insurances_and_payer_shares::Migration),
+      Box::new(m20260429_090000_create_payments_table::Migration),
+      Box::new(m20260503_090000_create_check_deposits_table::Migration),
+      Box::new(m20260507_090000_create_expenses_table::Migration),
+      Box::new(m20260511_090000_create_retrocession_statements_table::Migration),
+      Box::new(m20260515_090000_create_revenue_share_rates_table::Migration),
+      Box::new(m20260519_090000_add_retrocession_kinds::Migration),
+      Box::new(m20260523_090000_create_scheduled_exports::Migration),
+      Box::new(m20260527_090000_create_acts_tables::Migration),
+      Box::new(m20260531_090000_create_invoice_templates_table::Migration),
+      Box::new(m20260604_090000_add_locales::Migration),
+      Box::new(m20260608_090000_add_email_signature_to_invoice_templates::Migration),
+      Box::new(m20260612_090000_create_email_logs_table::Migration),
+      Box::new(m20260616_090000_make_audit_logs_append_only::Migration),
+      Box::new(m20260620_090000_add_consent_token_expiry_to_patient_accesses::Migration),
+      Box::new(m20260624_090000_add_claimed_until_to_scheduled_exports::Migration),
+      Box::new(m20260628_090000_add_act_snapshot_to_medical_appointments::Migration),
+      Box::new(m20260702_090000_keep_only_additional_legal_mentions::Migration),
+      Box::new(m20260706_090000_add_sent_email_to_email_logs::Migration),
     ]
   }
 }
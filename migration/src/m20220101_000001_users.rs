@@ -37,9 +37,19 @@ impl MigrationTrait for Migration {
               .primary_key(),
           )
           .col(ColumnDef::new(Users::Pid).uuid().not_null().unique_key())
-          .col(ColumnDef::new(Users::Email).string().not_null().unique_key())
+          .col(
+            ColumnDef::new(Users::Email)
+              .string()
+              .not_null()
+              .unique_key(),
+          )
           .col(ColumnDef::new(Users::Password).string().not_null())
-          .col(ColumnDef::new(Users::ApiKey).string().not_null().unique_key())
+          .col(
+            ColumnDef::new(Users::ApiKey)
+              .string()
+              .not_null()
+              .unique_key(),
+          )
           .col(ColumnDef::new(Users::Name).string().not_null())
           .col(ColumnDef::new(Users::ResetToken).string())
           .col(ColumnDef::new(Users::ResetSentAt).timestamp_with_time_zone())
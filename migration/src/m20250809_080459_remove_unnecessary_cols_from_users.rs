@@ -41,7 +41,12 @@ impl MigrationTrait for Migration {
       .alter_table(
         Table::alter()
           .table(Users::Table)
-          .add_column(ColumnDef::new(Users::ApiKey).string().not_null().unique_key())
+          .add_column(
+            ColumnDef::new(Users::ApiKey)
+              .string()
+              .not_null()
+              .unique_key(),
+          )
           .add_column(ColumnDef::new(Users::ResetToken).string())
           .add_column(ColumnDef::new(Users::ResetSentAt).timestamp_with_time_zone())
           .add_column(ColumnDef::new(Users::EmailVerificationToken).string())
@@ -28,7 +28,12 @@ impl MigrationTrait for Migration {
               .primary_key(),
           )
           .col(ColumnDef::new(Patients::Name).string().not_null())
-          .col(ColumnDef::new(Patients::Ssn).string().not_null().unique_key())
+          .col(
+            ColumnDef::new(Patients::Ssn)
+              .string()
+              .not_null()
+              .unique_key(),
+          )
           .col(ColumnDef::new(Patients::Pid).uuid().not_null().unique_key())
           .to_owned(),
       )
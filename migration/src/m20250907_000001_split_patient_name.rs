@@ -9,16 +9,8 @@ impl MigrationTrait for Migration {
     m.alter_table(
       Table::alter()
         .table(Alias::new("patients"))
-        .add_column(
-          ColumnDef::new(Alias::new("first_name"))
-            .string()
-            .not_null(),
-        )
-        .add_column(
-          ColumnDef::new(Alias::new("last_name"))
-            .string()
-            .not_null(),
-        )
+        .add_column(ColumnDef::new(Alias::new("first_name")).string().not_null())
+        .add_column(ColumnDef::new(Alias::new("last_name")).string().not_null())
         .drop_column(Alias::new("name"))
         .to_owned(),
     )
@@ -29,15 +21,11 @@ impl MigrationTrait for Migration {
     m.alter_table(
       Table::alter()
         .table(Alias::new("patients"))
-        .add_column(
-          ColumnDef::new(Alias::new("name"))
-            .string()
-            .not_null(),
-        )
+        .add_column(ColumnDef::new(Alias::new("name")).string().not_null())
         .drop_column(Alias::new("first_name"))
         .drop_column(Alias::new("last_name"))
         .to_owned(),
     )
     .await
   }
-}
\ No newline at end of file
+}
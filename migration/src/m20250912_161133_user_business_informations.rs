@@ -35,13 +35,28 @@ impl MigrationTrait for Migration {
               .primary_key(),
           )
           .col(ColumnDef::new(UserBusinessInformations::AdeliNumber).string())
-          .col(ColumnDef::new(UserBusinessInformations::RppsNumber).string().not_null())
-          .col(ColumnDef::new(UserBusinessInformations::SiretNumber).string().not_null())
-          .col(ColumnDef::new(UserBusinessInformations::UserId).integer().not_null())
+          .col(
+            ColumnDef::new(UserBusinessInformations::RppsNumber)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(UserBusinessInformations::SiretNumber)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(UserBusinessInformations::UserId)
+              .integer()
+              .not_null(),
+          )
           .foreign_key(
             ForeignKey::create()
               .name("fk-user_business_informations-user_id")
-              .from(UserBusinessInformations::Table, UserBusinessInformations::UserId)
+              .from(
+                UserBusinessInformations::Table,
+                UserBusinessInformations::UserId,
+              )
               .to(Users::Table, Users::Id)
               .on_delete(ForeignKeyAction::Cascade)
               .on_update(ForeignKeyAction::Cascade),
@@ -53,7 +68,11 @@ impl MigrationTrait for Migration {
 
   async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
     manager
-      .drop_table(Table::drop().table(UserBusinessInformations::Table).to_owned())
+      .drop_table(
+        Table::drop()
+          .table(UserBusinessInformations::Table)
+          .to_owned(),
+      )
       .await
   }
 }
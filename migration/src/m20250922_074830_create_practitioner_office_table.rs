@@ -57,11 +57,31 @@ impl MigrationTrait for Migration {
               .auto_increment()
               .primary_key(),
           )
-          .col(ColumnDef::new(PractitionerOffices::Name).string().not_null())
-          .col(ColumnDef::new(PractitionerOffices::AddressLine1).string().not_null())
-          .col(ColumnDef::new(PractitionerOffices::AddressZipCode).string().not_null())
-          .col(ColumnDef::new(PractitionerOffices::AddressCity).string().not_null())
-          .col(ColumnDef::new(PractitionerOffices::AddressCountry).string().not_null())
+          .col(
+            ColumnDef::new(PractitionerOffices::Name)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PractitionerOffices::AddressLine1)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PractitionerOffices::AddressZipCode)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PractitionerOffices::AddressCity)
+              .string()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PractitionerOffices::AddressCountry)
+              .string()
+              .not_null(),
+          )
           .to_owned(),
       )
       .await?;
@@ -79,12 +99,23 @@ impl MigrationTrait for Migration {
               .auto_increment()
               .primary_key(),
           )
-          .col(ColumnDef::new(UserPractitionerOffices::UserId).integer().not_null())
-          .col(ColumnDef::new(UserPractitionerOffices::PractitionerOfficeId).integer().not_null())
+          .col(
+            ColumnDef::new(UserPractitionerOffices::UserId)
+              .integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(UserPractitionerOffices::PractitionerOfficeId)
+              .integer()
+              .not_null(),
+          )
           .foreign_key(
             ForeignKey::create()
               .name("fk-user_practitioner_offices-user_id")
-              .from(UserPractitionerOffices::Table, UserPractitionerOffices::UserId)
+              .from(
+                UserPractitionerOffices::Table,
+                UserPractitionerOffices::UserId,
+              )
               .to(Users::Table, Users::Id)
               .on_delete(ForeignKeyAction::Cascade)
               .on_update(ForeignKeyAction::Cascade),
@@ -92,7 +123,10 @@ impl MigrationTrait for Migration {
           .foreign_key(
             ForeignKey::create()
               .name("fk-user_practitioner_offices-practitioner_office_id")
-              .from(UserPractitionerOffices::Table, UserPractitionerOffices::PractitionerOfficeId)
+              .from(
+                UserPractitionerOffices::Table,
+                UserPractitionerOffices::PractitionerOfficeId,
+              )
               .to(PractitionerOffices::Table, PractitionerOffices::Id)
               .on_delete(ForeignKeyAction::Cascade)
               .on_update(ForeignKeyAction::Cascade),
@@ -157,7 +191,11 @@ impl MigrationTrait for Migration {
 
     // Drop tables
     manager
-      .drop_table(Table::drop().table(UserPractitionerOffices::Table).to_owned())
+      .drop_table(
+        Table::drop()
+          .table(UserPractitionerOffices::Table)
+          .to_owned(),
+      )
       .await?;
 
     manager
@@ -35,4 +35,3 @@ impl MigrationTrait for Migration {
     Ok(())
   }
 }
-
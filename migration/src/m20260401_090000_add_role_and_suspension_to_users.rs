@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(UserRoleEnum::Enum)
+          .values([UserRoleEnum::Practitioner, UserRoleEnum::Admin])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Users::Table)
+          .add_column(
+            ColumnDef::new(Users::Role)
+              .enumeration(
+                UserRoleEnum::Enum,
+                [UserRoleEnum::Practitioner, UserRoleEnum::Admin],
+              )
+              .not_null()
+              .default("practitioner"),
+          )
+          .add_column(
+            ColumnDef::new(Users::SuspendedAt)
+              .timestamp_with_time_zone()
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Users::Table)
+          .drop_column(Users::Role)
+          .drop_column(Users::SuspendedAt)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(UserRoleEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Role,
+  SuspendedAt,
+}
+
+#[derive(Iden)]
+enum UserRoleEnum {
+  #[iden = "user_role"]
+  Enum,
+  #[iden = "practitioner"]
+  Practitioner,
+  #[iden = "admin"]
+  Admin,
+}
@@ -0,0 +1,155 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(AuditActionEnum::Enum)
+          .values([
+            AuditActionEnum::Read,
+            AuditActionEnum::Create,
+            AuditActionEnum::Update,
+            AuditActionEnum::Delete,
+            AuditActionEnum::Search,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(AuditLogs::Table)
+          .if_not_exists()
+          .col(pk_auto(AuditLogs::Id))
+          .col(integer(AuditLogs::UserId))
+          .col(
+            ColumnDef::new(AuditLogs::Action)
+              .enumeration(
+                AuditActionEnum::Enum,
+                [
+                  AuditActionEnum::Read,
+                  AuditActionEnum::Create,
+                  AuditActionEnum::Update,
+                  AuditActionEnum::Delete,
+                  AuditActionEnum::Search,
+                ],
+              )
+              .not_null(),
+          )
+          .col(string(AuditLogs::ResourceType))
+          .col(integer(AuditLogs::ResourceId))
+          .col(boolean(AuditLogs::Granted))
+          .col(string_null(AuditLogs::IpAddress))
+          .col(timestamp_with_time_zone(AuditLogs::CreatedAt).default(Expr::current_timestamp()))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_audit_logs_user_id")
+              .from(AuditLogs::Table, AuditLogs::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_audit_logs_user_id_created_at")
+          .table(AuditLogs::Table)
+          .col(AuditLogs::UserId)
+          .col(AuditLogs::CreatedAt)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_audit_logs_resource")
+          .table(AuditLogs::Table)
+          .col(AuditLogs::ResourceType)
+          .col(AuditLogs::ResourceId)
+          .to_owned(),
+      )
+      .await?;
+
+    // The audit trail is append-only: reject any attempt to rewrite an entry
+    let db = manager.get_connection();
+    db.execute_unprepared(
+      r#"
+      CREATE OR REPLACE FUNCTION audit_logs_prevent_update() RETURNS trigger AS $$
+      BEGIN
+        RAISE EXCEPTION 'audit_logs is append-only';
+      END;
+      $$ LANGUAGE plpgsql;
+
+      CREATE TRIGGER audit_logs_no_update
+        BEFORE UPDATE ON audit_logs
+        FOR EACH ROW EXECUTE FUNCTION audit_logs_prevent_update();
+      "#,
+    )
+    .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AuditLogs::Table).to_owned())
+      .await?;
+
+    let db = manager.get_connection();
+    db.execute_unprepared("DROP FUNCTION IF EXISTS audit_logs_prevent_update()")
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(AuditActionEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum AuditLogs {
+  Table,
+  Id,
+  UserId,
+  Action,
+  ResourceType,
+  ResourceId,
+  Granted,
+  IpAddress,
+  CreatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum AuditActionEnum {
+  #[iden = "audit_action"]
+  Enum,
+  #[iden = "read"]
+  Read,
+  #[iden = "create"]
+  Create,
+  #[iden = "update"]
+  Update,
+  #[iden = "delete"]
+  Delete,
+  #[iden = "search"]
+  Search,
+}
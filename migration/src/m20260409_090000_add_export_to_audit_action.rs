@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_type(
+        Type::alter()
+          .name(AuditActionEnum::Enum)
+          .add_value(AuditActionEnum::Export)
+          .if_not_exists()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+    // Postgres cannot drop a value from an enum type
+    Ok(())
+  }
+}
+
+#[derive(Iden)]
+enum AuditActionEnum {
+  #[iden = "audit_action"]
+  Enum,
+  #[iden = "export"]
+  Export,
+}
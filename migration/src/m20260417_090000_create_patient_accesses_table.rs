@@ -0,0 +1,159 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PatientAccessLevelEnum::Enum)
+          .values([PatientAccessLevelEnum::Read, PatientAccessLevelEnum::Write])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PatientAccessStatusEnum::Enum)
+          .values([
+            PatientAccessStatusEnum::Pending,
+            PatientAccessStatusEnum::Granted,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PatientAccesses::Table)
+          .if_not_exists()
+          .col(pk_auto(PatientAccesses::Id))
+          .col(integer(PatientAccesses::PatientId))
+          .col(integer(PatientAccesses::UserId))
+          .col(
+            ColumnDef::new(PatientAccesses::Level)
+              .enumeration(
+                PatientAccessLevelEnum::Enum,
+                [PatientAccessLevelEnum::Read, PatientAccessLevelEnum::Write],
+              )
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(PatientAccesses::Status)
+              .enumeration(
+                PatientAccessStatusEnum::Enum,
+                [
+                  PatientAccessStatusEnum::Pending,
+                  PatientAccessStatusEnum::Granted,
+                ],
+              )
+              .not_null()
+              .default("pending"),
+          )
+          .col(string_null(PatientAccesses::ConsentToken).unique_key())
+          .col(
+            timestamp_with_time_zone(PatientAccesses::CreatedAt).default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(PatientAccesses::UpdatedAt).default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_patient_accesses_patient_id")
+              .from(PatientAccesses::Table, PatientAccesses::PatientId)
+              .to(Patients::Table, Patients::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_patient_accesses_user_id")
+              .from(PatientAccesses::Table, PatientAccesses::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_patient_accesses_patient_id_user_id")
+          .table(PatientAccesses::Table)
+          .col(PatientAccesses::PatientId)
+          .col(PatientAccesses::UserId)
+          .unique()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PatientAccesses::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(PatientAccessStatusEnum::Enum).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(PatientAccessLevelEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum PatientAccesses {
+  Table,
+  Id,
+  PatientId,
+  UserId,
+  Level,
+  Status,
+  ConsentToken,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Patients {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum PatientAccessLevelEnum {
+  #[iden = "patient_access_level"]
+  Enum,
+  #[iden = "read"]
+  Read,
+  #[iden = "write"]
+  Write,
+}
+
+#[derive(Iden)]
+enum PatientAccessStatusEnum {
+  #[iden = "patient_access_status"]
+  Enum,
+  #[iden = "pending"]
+  Pending,
+  #[iden = "granted"]
+  Granted,
+}
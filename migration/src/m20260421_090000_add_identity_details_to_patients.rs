@@ -0,0 +1,77 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PatientSexEnum::Enum)
+          .values([PatientSexEnum::Female, PatientSexEnum::Male])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Patients::Table)
+          .add_column(date_null(Patients::BirthDate))
+          .add_column(string_null(Patients::PhoneNumber))
+          .add_column(
+            ColumnDef::new(Patients::Sex)
+              .enumeration(
+                PatientSexEnum::Enum,
+                [PatientSexEnum::Female, PatientSexEnum::Male],
+              )
+              .null(),
+          )
+          .add_column(string_null(Patients::ReferringDoctor))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Patients::Table)
+          .drop_column(Patients::BirthDate)
+          .drop_column(Patients::PhoneNumber)
+          .drop_column(Patients::Sex)
+          .drop_column(Patients::ReferringDoctor)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(PatientSexEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Patients {
+  Table,
+  BirthDate,
+  PhoneNumber,
+  Sex,
+  ReferringDoctor,
+}
+
+#[derive(Iden)]
+enum PatientSexEnum {
+  #[iden = "patient_sex"]
+  Enum,
+  #[iden = "female"]
+  Female,
+  #[iden = "male"]
+  Male,
+}
@@ -0,0 +1,141 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(InsuranceKindEnum::Enum)
+          .values([InsuranceKindEnum::Amo, InsuranceKindEnum::Mutuelle])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PatientInsurances::Table)
+          .if_not_exists()
+          .col(pk_auto(PatientInsurances::Id))
+          .col(integer(PatientInsurances::PatientId))
+          .col(
+            ColumnDef::new(PatientInsurances::Kind)
+              .enumeration(
+                InsuranceKindEnum::Enum,
+                [InsuranceKindEnum::Amo, InsuranceKindEnum::Mutuelle],
+              )
+              .not_null(),
+          )
+          .col(string(PatientInsurances::InsurerName))
+          .col(string_null(PatientInsurances::MemberNumber))
+          .col(date_null(PatientInsurances::ValidUntil))
+          .col(
+            timestamp_with_time_zone(PatientInsurances::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(PatientInsurances::UpdatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_patient_insurances_patient_id")
+              .from(PatientInsurances::Table, PatientInsurances::PatientId)
+              .to(Patients::Table, Patients::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_patient_insurances_patient_id")
+          .table(PatientInsurances::Table)
+          .col(PatientInsurances::PatientId)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(MedicalAppointments::Table)
+          .add_column(integer(MedicalAppointments::AmoShareInCents).default(0))
+          .add_column(integer(MedicalAppointments::MutuelleShareInCents).default(0))
+          .add_column(date_null(MedicalAppointments::AmoPaidAt))
+          .add_column(date_null(MedicalAppointments::MutuellePaidAt))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(MedicalAppointments::Table)
+          .drop_column(MedicalAppointments::AmoShareInCents)
+          .drop_column(MedicalAppointments::MutuelleShareInCents)
+          .drop_column(MedicalAppointments::AmoPaidAt)
+          .drop_column(MedicalAppointments::MutuellePaidAt)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(PatientInsurances::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(InsuranceKindEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum PatientInsurances {
+  Table,
+  Id,
+  PatientId,
+  Kind,
+  InsurerName,
+  MemberNumber,
+  ValidUntil,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Patients {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum MedicalAppointments {
+  Table,
+  AmoShareInCents,
+  MutuelleShareInCents,
+  AmoPaidAt,
+  MutuellePaidAt,
+}
+
+#[derive(Iden)]
+enum InsuranceKindEnum {
+  #[iden = "insurance_kind"]
+  Enum,
+  #[iden = "amo"]
+  Amo,
+  #[iden = "mutuelle"]
+  Mutuelle,
+}
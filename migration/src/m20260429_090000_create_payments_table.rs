@@ -0,0 +1,108 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Payments::Table)
+          .if_not_exists()
+          .col(pk_auto(Payments::Id))
+          .col(integer(Payments::MedicalAppointmentId))
+          .col(integer(Payments::AmountInCents))
+          .col(
+            ColumnDef::new(Payments::Method)
+              .enumeration(
+                PaymentMethodEnum::Enum,
+                [
+                  PaymentMethodEnum::Card,
+                  PaymentMethodEnum::Cash,
+                  PaymentMethodEnum::Check,
+                  PaymentMethodEnum::Transfer,
+                ],
+              )
+              .not_null(),
+          )
+          .col(date(Payments::PaidOn))
+          .col(string_null(Payments::Reference))
+          .col(timestamp_with_time_zone(Payments::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone(Payments::UpdatedAt).default(Expr::current_timestamp()))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_payments_medical_appointment_id")
+              .from(Payments::Table, Payments::MedicalAppointmentId)
+              .to(MedicalAppointments::Table, MedicalAppointments::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_payments_medical_appointment_id")
+          .table(Payments::Table)
+          .col(Payments::MedicalAppointmentId)
+          .to_owned(),
+      )
+      .await?;
+
+    // Appointments flagged with a payment method were settled on the spot
+    let db = manager.get_connection();
+    db.execute_unprepared(
+      "INSERT INTO payments (medical_appointment_id, amount_in_cents, method, paid_on)
+       SELECT id, price_in_cents - amo_share_in_cents - mutuelle_share_in_cents, payment_method, date
+       FROM medical_appointments
+       WHERE payment_method IS NOT NULL
+         AND price_in_cents - amo_share_in_cents - mutuelle_share_in_cents > 0",
+    )
+    .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Payments::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Payments {
+  Table,
+  Id,
+  MedicalAppointmentId,
+  AmountInCents,
+  Method,
+  PaidOn,
+  Reference,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum MedicalAppointments {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum PaymentMethodEnum {
+  #[iden = "payment_method"]
+  Enum,
+  #[iden = "card"]
+  Card,
+  #[iden = "cash"]
+  Cash,
+  #[iden = "check"]
+  Check,
+  #[iden = "transfer"]
+  Transfer,
+}
@@ -0,0 +1,106 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(CheckDeposits::Table)
+          .if_not_exists()
+          .col(pk_auto(CheckDeposits::Id))
+          .col(integer(CheckDeposits::UserId))
+          .col(string(CheckDeposits::Reference))
+          .col(date(CheckDeposits::DepositedOn))
+          .col(
+            timestamp_with_time_zone(CheckDeposits::CreatedAt).default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(CheckDeposits::UpdatedAt).default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_check_deposits_user_id")
+              .from(CheckDeposits::Table, CheckDeposits::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_check_deposits_user_id_reference")
+          .table(CheckDeposits::Table)
+          .col(CheckDeposits::UserId)
+          .col(CheckDeposits::Reference)
+          .unique()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Payments::Table)
+          .add_column(integer_null(Payments::CheckDepositId))
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk_payments_check_deposit_id")
+              .from_tbl(Payments::Table)
+              .from_col(Payments::CheckDepositId)
+              .to_tbl(CheckDeposits::Table)
+              .to_col(CheckDeposits::Id)
+              .on_delete(ForeignKeyAction::SetNull)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Payments::Table)
+          .drop_foreign_key(Alias::new("fk_payments_check_deposit_id"))
+          .drop_column(Payments::CheckDepositId)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(CheckDeposits::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum CheckDeposits {
+  Table,
+  Id,
+  UserId,
+  Reference,
+  DepositedOn,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Payments {
+  Table,
+  CheckDepositId,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
@@ -0,0 +1,169 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const EXPENSE_CATEGORIES: [ExpenseCategoryEnum; 14] = [
+  ExpenseCategoryEnum::BankFees,
+  ExpenseCategoryEnum::Fees,
+  ExpenseCategoryEnum::Insurance,
+  ExpenseCategoryEnum::Memberships,
+  ExpenseCategoryEnum::Office,
+  ExpenseCategoryEnum::Other,
+  ExpenseCategoryEnum::Purchases,
+  ExpenseCategoryEnum::Rent,
+  ExpenseCategoryEnum::Retrocession,
+  ExpenseCategoryEnum::SocialContributions,
+  ExpenseCategoryEnum::Supplies,
+  ExpenseCategoryEnum::Taxes,
+  ExpenseCategoryEnum::Travel,
+  ExpenseCategoryEnum::Vehicle,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(ExpenseCategoryEnum::Enum)
+          .values(EXPENSE_CATEGORIES)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Expenses::Table)
+          .if_not_exists()
+          .col(pk_auto(Expenses::Id))
+          .col(integer(Expenses::UserId))
+          .col(date(Expenses::SpentOn))
+          .col(
+            ColumnDef::new(Expenses::Category)
+              .enumeration(ExpenseCategoryEnum::Enum, EXPENSE_CATEGORIES)
+              .not_null(),
+          )
+          .col(string(Expenses::Label))
+          .col(integer(Expenses::AmountInCents))
+          .col(
+            ColumnDef::new(Expenses::PaymentMethod)
+              .enumeration(
+                PaymentMethodEnum::Enum,
+                [
+                  PaymentMethodEnum::Card,
+                  PaymentMethodEnum::Cash,
+                  PaymentMethodEnum::Check,
+                  PaymentMethodEnum::Transfer,
+                ],
+              )
+              .null(),
+          )
+          .col(timestamp_with_time_zone(Expenses::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone(Expenses::UpdatedAt).default(Expr::current_timestamp()))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_expenses_user_id")
+              .from(Expenses::Table, Expenses::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_expenses_user_id_spent_on")
+          .table(Expenses::Table)
+          .col(Expenses::UserId)
+          .col(Expenses::SpentOn)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Expenses::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(ExpenseCategoryEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Expenses {
+  Table,
+  Id,
+  UserId,
+  SpentOn,
+  Category,
+  Label,
+  AmountInCents,
+  PaymentMethod,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum ExpenseCategoryEnum {
+  #[iden = "expense_category"]
+  Enum,
+  #[iden = "bank_fees"]
+  BankFees,
+  #[iden = "fees"]
+  Fees,
+  #[iden = "insurance"]
+  Insurance,
+  #[iden = "memberships"]
+  Memberships,
+  #[iden = "office"]
+  Office,
+  #[iden = "other"]
+  Other,
+  #[iden = "purchases"]
+  Purchases,
+  #[iden = "rent"]
+  Rent,
+  #[iden = "retrocession"]
+  Retrocession,
+  #[iden = "social_contributions"]
+  SocialContributions,
+  #[iden = "supplies"]
+  Supplies,
+  #[iden = "taxes"]
+  Taxes,
+  #[iden = "travel"]
+  Travel,
+  #[iden = "vehicle"]
+  Vehicle,
+}
+
+#[derive(Iden)]
+enum PaymentMethodEnum {
+  #[iden = "payment_method"]
+  Enum,
+  #[iden = "card"]
+  Card,
+  #[iden = "cash"]
+  Cash,
+  #[iden = "check"]
+  Check,
+  #[iden = "transfer"]
+  Transfer,
+}
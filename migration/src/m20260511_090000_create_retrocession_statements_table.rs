@@ -0,0 +1,135 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(PractitionerOffices::Table)
+          .add_column(string_null(PractitionerOffices::ContactEmail))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(RetrocessionStatements::Table)
+          .if_not_exists()
+          .col(pk_auto(RetrocessionStatements::Id))
+          .col(integer(RetrocessionStatements::UserId))
+          .col(integer(RetrocessionStatements::PractitionerOfficeId))
+          .col(date(RetrocessionStatements::Month))
+          .col(integer(RetrocessionStatements::AppointmentsCount))
+          .col(big_integer(RetrocessionStatements::FeesInCents))
+          .col(decimal_len(
+            RetrocessionStatements::RevenueSharePercentage,
+            5,
+            2,
+          ))
+          .col(big_integer(RetrocessionStatements::RetrocessionInCents))
+          .col(string_null(RetrocessionStatements::SentTo))
+          .col(timestamp_with_time_zone_null(
+            RetrocessionStatements::SentAt,
+          ))
+          .col(
+            timestamp_with_time_zone(RetrocessionStatements::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(RetrocessionStatements::UpdatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_retrocession_statements_user_id")
+              .from(
+                RetrocessionStatements::Table,
+                RetrocessionStatements::UserId,
+              )
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_retrocession_statements_practitioner_office_id")
+              .from(
+                RetrocessionStatements::Table,
+                RetrocessionStatements::PractitionerOfficeId,
+              )
+              .to(PractitionerOffices::Table, PractitionerOffices::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_retrocession_statements_user_office_month")
+          .table(RetrocessionStatements::Table)
+          .col(RetrocessionStatements::UserId)
+          .col(RetrocessionStatements::PractitionerOfficeId)
+          .col(RetrocessionStatements::Month)
+          .unique()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(
+        Table::drop()
+          .table(RetrocessionStatements::Table)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(PractitionerOffices::Table)
+          .drop_column(PractitionerOffices::ContactEmail)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum RetrocessionStatements {
+  Table,
+  Id,
+  UserId,
+  PractitionerOfficeId,
+  Month,
+  AppointmentsCount,
+  FeesInCents,
+  RevenueSharePercentage,
+  RetrocessionInCents,
+  SentTo,
+  SentAt,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum PractitionerOffices {
+  Table,
+  Id,
+  ContactEmail,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
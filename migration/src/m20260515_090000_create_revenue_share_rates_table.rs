@@ -0,0 +1,148 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(RevenueShareRates::Table)
+          .if_not_exists()
+          .col(pk_auto(RevenueShareRates::Id))
+          .col(integer(RevenueShareRates::UserId))
+          .col(integer(RevenueShareRates::PractitionerOfficeId))
+          .col(decimal_len(RevenueShareRates::Percentage, 5, 2))
+          .col(date(RevenueShareRates::ValidFrom))
+          .col(
+            timestamp_with_time_zone(RevenueShareRates::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(RevenueShareRates::UpdatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_revenue_share_rates_user_id")
+              .from(RevenueShareRates::Table, RevenueShareRates::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_revenue_share_rates_practitioner_office_id")
+              .from(
+                RevenueShareRates::Table,
+                RevenueShareRates::PractitionerOfficeId,
+              )
+              .to(PractitionerOffices::Table, PractitionerOffices::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_revenue_share_rates_user_office_valid_from")
+          .table(RevenueShareRates::Table)
+          .col(RevenueShareRates::UserId)
+          .col(RevenueShareRates::PractitionerOfficeId)
+          .col(RevenueShareRates::ValidFrom)
+          .unique()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(AccountingExports::Table)
+          .if_not_exists()
+          .col(pk_auto(AccountingExports::Id))
+          .col(integer(AccountingExports::UserId))
+          .col(date(AccountingExports::StartDate))
+          .col(date(AccountingExports::EndDate))
+          .col(
+            timestamp_with_time_zone(AccountingExports::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(AccountingExports::UpdatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_accounting_exports_user_id")
+              .from(AccountingExports::Table, AccountingExports::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Until now a single percentage applied to every appointment of the office
+    let db = manager.get_connection();
+    db.execute_unprepared(
+      "INSERT INTO revenue_share_rates (user_id, practitioner_office_id, percentage, valid_from)
+       SELECT user_id, practitioner_office_id, revenue_share_percentage, DATE '1970-01-01'
+       FROM user_practitioner_offices",
+    )
+    .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AccountingExports::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(RevenueShareRates::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum RevenueShareRates {
+  Table,
+  Id,
+  UserId,
+  PractitionerOfficeId,
+  Percentage,
+  ValidFrom,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AccountingExports {
+  Table,
+  Id,
+  UserId,
+  StartDate,
+  EndDate,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum PractitionerOffices {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
@@ -0,0 +1,141 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const RETROCESSION_KINDS: [RetrocessionKindEnum; 4] = [
+  RetrocessionKindEnum::CappedPercentage,
+  RetrocessionKindEnum::FixedMonthlyFee,
+  RetrocessionKindEnum::PerAppointmentFee,
+  RetrocessionKindEnum::Percentage,
+];
+
+fn kind_column<T: IntoIden>(name: T) -> ColumnDef {
+  ColumnDef::new(name)
+    .enumeration(RetrocessionKindEnum::Enum, RETROCESSION_KINDS)
+    .not_null()
+    .default("percentage")
+    .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(RetrocessionKindEnum::Enum)
+          .values(RETROCESSION_KINDS)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(UserPractitionerOffices::Table)
+          .add_column(kind_column(UserPractitionerOffices::RetrocessionKind))
+          .add_column(integer_null(
+            UserPractitionerOffices::RetrocessionAmountInCents,
+          ))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(RevenueShareRates::Table)
+          .add_column(kind_column(RevenueShareRates::Kind))
+          .add_column(integer_null(RevenueShareRates::AmountInCents))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(RetrocessionStatements::Table)
+          .add_column(kind_column(RetrocessionStatements::RetrocessionKind))
+          .add_column(integer_null(
+            RetrocessionStatements::RetrocessionAmountInCents,
+          ))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(RetrocessionStatements::Table)
+          .drop_column(RetrocessionStatements::RetrocessionKind)
+          .drop_column(RetrocessionStatements::RetrocessionAmountInCents)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(RevenueShareRates::Table)
+          .drop_column(RevenueShareRates::Kind)
+          .drop_column(RevenueShareRates::AmountInCents)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(UserPractitionerOffices::Table)
+          .drop_column(UserPractitionerOffices::RetrocessionKind)
+          .drop_column(UserPractitionerOffices::RetrocessionAmountInCents)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(RetrocessionKindEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum UserPractitionerOffices {
+  Table,
+  RetrocessionKind,
+  RetrocessionAmountInCents,
+}
+
+#[derive(Iden)]
+enum RevenueShareRates {
+  Table,
+  Kind,
+  AmountInCents,
+}
+
+#[derive(Iden)]
+enum RetrocessionStatements {
+  Table,
+  RetrocessionKind,
+  RetrocessionAmountInCents,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum RetrocessionKindEnum {
+  #[iden = "retrocession_kind"]
+  Enum,
+  #[iden = "capped_percentage"]
+  CappedPercentage,
+  #[iden = "fixed_monthly_fee"]
+  FixedMonthlyFee,
+  #[iden = "per_appointment_fee"]
+  PerAppointmentFee,
+  #[iden = "percentage"]
+  Percentage,
+}
@@ -0,0 +1,141 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const EXPORT_FREQUENCIES: [ExportFrequencyEnum; 3] = [
+  ExportFrequencyEnum::Monthly,
+  ExportFrequencyEnum::Quarterly,
+  ExportFrequencyEnum::Yearly,
+];
+
+const EXPORT_FORMATS: [ExportFormatEnum; 3] = [
+  ExportFormatEnum::Csv,
+  ExportFormatEnum::Fec,
+  ExportFormatEnum::Xlsx,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(ExportFrequencyEnum::Enum)
+          .values(EXPORT_FREQUENCIES)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(ExportFormatEnum::Enum)
+          .values(EXPORT_FORMATS)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(ScheduledExports::Table)
+          .if_not_exists()
+          .col(pk_auto(ScheduledExports::Id))
+          .col(integer_uniq(ScheduledExports::UserId))
+          .col(
+            ColumnDef::new(ScheduledExports::Frequency)
+              .enumeration(ExportFrequencyEnum::Enum, EXPORT_FREQUENCIES)
+              .not_null()
+              .default("monthly"),
+          )
+          .col(
+            ColumnDef::new(ScheduledExports::Format)
+              .enumeration(ExportFormatEnum::Enum, EXPORT_FORMATS)
+              .not_null()
+              .default("xlsx"),
+          )
+          .col(string(ScheduledExports::RecipientEmail))
+          .col(date_null(ScheduledExports::LastPeriodStart))
+          .col(
+            timestamp_with_time_zone(ScheduledExports::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(ScheduledExports::UpdatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_scheduled_exports_user_id")
+              .from(ScheduledExports::Table, ScheduledExports::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(ScheduledExports::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(ExportFormatEnum::Enum).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(ExportFrequencyEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum ScheduledExports {
+  Table,
+  Id,
+  UserId,
+  Frequency,
+  Format,
+  RecipientEmail,
+  /// First day of the last period whose export was sent
+  LastPeriodStart,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum ExportFrequencyEnum {
+  #[iden = "export_frequency"]
+  Enum,
+  #[iden = "monthly"]
+  Monthly,
+  #[iden = "quarterly"]
+  Quarterly,
+  #[iden = "yearly"]
+  Yearly,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum ExportFormatEnum {
+  #[iden = "export_format"]
+  Enum,
+  #[iden = "csv"]
+  Csv,
+  #[iden = "fec"]
+  Fec,
+  #[iden = "xlsx"]
+  Xlsx,
+}
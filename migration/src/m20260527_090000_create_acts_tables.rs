@@ -0,0 +1,164 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Acts::Table)
+          .if_not_exists()
+          .col(pk_auto(Acts::Id))
+          .col(integer(Acts::UserId))
+          .col(string(Acts::Label))
+          .col(string_null(Acts::Code))
+          .col(integer(Acts::DefaultPriceInCents))
+          .col(timestamp_with_time_zone(Acts::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone(Acts::UpdatedAt).default(Expr::current_timestamp()))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_acts_user_id")
+              .from(Acts::Table, Acts::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(ActOfficePrices::Table)
+          .if_not_exists()
+          .col(pk_auto(ActOfficePrices::Id))
+          .col(integer(ActOfficePrices::ActId))
+          .col(integer(ActOfficePrices::PractitionerOfficeId))
+          .col(integer(ActOfficePrices::PriceInCents))
+          .col(
+            timestamp_with_time_zone(ActOfficePrices::CreatedAt).default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(ActOfficePrices::UpdatedAt).default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_act_office_prices_act_id")
+              .from(ActOfficePrices::Table, ActOfficePrices::ActId)
+              .to(Acts::Table, Acts::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_act_office_prices_practitioner_office_id")
+              .from(
+                ActOfficePrices::Table,
+                ActOfficePrices::PractitionerOfficeId,
+              )
+              .to(PractitionerOffices::Table, PractitionerOffices::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_act_office_prices_act_office")
+          .table(ActOfficePrices::Table)
+          .col(ActOfficePrices::ActId)
+          .col(ActOfficePrices::PractitionerOfficeId)
+          .unique()
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(MedicalAppointments::Table)
+          .add_column(integer_null(MedicalAppointments::ActId))
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk_medical_appointments_act_id")
+              .from_tbl(MedicalAppointments::Table)
+              .from_col(MedicalAppointments::ActId)
+              .to_tbl(Acts::Table)
+              .to_col(Acts::Id)
+              .on_delete(ForeignKeyAction::SetNull)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(MedicalAppointments::Table)
+          .drop_foreign_key(Alias::new("fk_medical_appointments_act_id"))
+          .drop_column(MedicalAppointments::ActId)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(ActOfficePrices::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_table(Table::drop().table(Acts::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Acts {
+  Table,
+  Id,
+  UserId,
+  Label,
+  /// NGAP or CCAM code of the act
+  Code,
+  DefaultPriceInCents,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum ActOfficePrices {
+  Table,
+  Id,
+  ActId,
+  PractitionerOfficeId,
+  PriceInCents,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum MedicalAppointments {
+  Table,
+  ActId,
+}
+
+#[derive(Iden)]
+enum PractitionerOffices {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(InvoiceTemplates::Table)
+          .if_not_exists()
+          .col(pk_auto(InvoiceTemplates::Id))
+          .col(integer_uniq(InvoiceTemplates::UserId))
+          .col(string(InvoiceTemplates::Title).default("Note d'honoraires acquittée"))
+          .col(string_len(InvoiceTemplates::AccentColor, 7).default("#000000"))
+          .col(text_null(InvoiceTemplates::HeaderText))
+          .col(text_null(InvoiceTemplates::FooterText))
+          .col(text_null(InvoiceTemplates::LegalMentions))
+          .col(string_null(InvoiceTemplates::LogoFileName))
+          .col(boolean(InvoiceTemplates::ShowBirthDate).default(true))
+          .col(boolean(InvoiceTemplates::ShowSsn).default(true))
+          .col(boolean(InvoiceTemplates::ShowPatientAddress).default(true))
+          .col(
+            timestamp_with_time_zone(InvoiceTemplates::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            timestamp_with_time_zone(InvoiceTemplates::UpdatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_invoice_templates_user_id")
+              .from(InvoiceTemplates::Table, InvoiceTemplates::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(InvoiceTemplates::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum InvoiceTemplates {
+  Table,
+  Id,
+  UserId,
+  Title,
+  /// `#RRGGBB` colour of the title and frames
+  AccentColor,
+  HeaderText,
+  FooterText,
+  LegalMentions,
+  LogoFileName,
+  ShowBirthDate,
+  ShowSsn,
+  ShowPatientAddress,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
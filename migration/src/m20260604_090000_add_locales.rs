@@ -0,0 +1,94 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const LOCALES: [LocaleEnum; 2] = [LocaleEnum::En, LocaleEnum::Fr];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(LocaleEnum::Enum)
+          .values(LOCALES)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Users::Table)
+          .add_column(
+            ColumnDef::new(Users::Locale)
+              .enumeration(LocaleEnum::Enum, LOCALES)
+              .not_null()
+              .default("fr"),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // Patients without a locale get the one of their practitioner
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Patients::Table)
+          .add_column(
+            ColumnDef::new(Patients::Locale)
+              .enumeration(LocaleEnum::Enum, LOCALES)
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Patients::Table)
+          .drop_column(Patients::Locale)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Users::Table)
+          .drop_column(Users::Locale)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(LocaleEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Locale,
+}
+
+#[derive(Iden)]
+enum Patients {
+  Table,
+  Locale,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum LocaleEnum {
+  #[iden = "locale"]
+  Enum,
+  #[iden = "en"]
+  En,
+  #[iden = "fr"]
+  Fr,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(InvoiceTemplates::Table)
+          .add_column(
+            ColumnDef::new(InvoiceTemplates::EmailSignature)
+              .text()
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(InvoiceTemplates::Table)
+          .drop_column(InvoiceTemplates::EmailSignature)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum InvoiceTemplates {
+  Table,
+  EmailSignature,
+}
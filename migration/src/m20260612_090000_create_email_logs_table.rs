@@ -0,0 +1,149 @@
+use sea_orm_migration::{
+  prelude::{extension::postgres::Type, *},
+  schema::*,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const EMAIL_STATUSES: [EmailStatusEnum; 3] = [
+  EmailStatusEnum::Pending,
+  EmailStatusEnum::Sent,
+  EmailStatusEnum::Failed,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(EmailStatusEnum::Enum)
+          .values(EMAIL_STATUSES)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(EmailLogs::Table)
+          .if_not_exists()
+          .col(pk_auto(EmailLogs::Id))
+          .col(integer_null(EmailLogs::UserId))
+          .col(integer_null(EmailLogs::PatientId))
+          .col(integer_null(EmailLogs::MedicalAppointmentId))
+          .col(string(EmailLogs::Recipient))
+          .col(string(EmailLogs::Subject))
+          .col(string_null(EmailLogs::Template))
+          .col(json_binary(EmailLogs::AttachmentNames))
+          .col(
+            ColumnDef::new(EmailLogs::Status)
+              .enumeration(EmailStatusEnum::Enum, EMAIL_STATUSES)
+              .not_null()
+              .default("pending"),
+          )
+          .col(text_null(EmailLogs::Error))
+          .col(timestamp_with_time_zone_null(EmailLogs::SentAt))
+          .col(timestamp_with_time_zone(EmailLogs::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone(EmailLogs::UpdatedAt).default(Expr::current_timestamp()))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_email_logs_user_id")
+              .from(EmailLogs::Table, EmailLogs::UserId)
+              .to(Users::Table, Users::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_email_logs_patient_id")
+              .from(EmailLogs::Table, EmailLogs::PatientId)
+              .to(Patients::Table, Patients::Id)
+              .on_delete(ForeignKeyAction::Cascade)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk_email_logs_medical_appointment_id")
+              .from(EmailLogs::Table, EmailLogs::MedicalAppointmentId)
+              .to(MedicalAppointments::Table, MedicalAppointments::Id)
+              .on_delete(ForeignKeyAction::SetNull)
+              .on_update(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_email_logs_patient_id")
+          .table(EmailLogs::Table)
+          .col(EmailLogs::PatientId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(EmailLogs::Table).to_owned())
+      .await?;
+
+    manager
+      .drop_type(Type::drop().name(EmailStatusEnum::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum EmailLogs {
+  Table,
+  Id,
+  /// Practitioner on whose behalf, or account to which, the email was sent
+  UserId,
+  PatientId,
+  MedicalAppointmentId,
+  Recipient,
+  Subject,
+  /// Name of the email template, none for plain text emails
+  Template,
+  AttachmentNames,
+  Status,
+  /// Error returned by the SMTP server, or met while building the email
+  Error,
+  SentAt,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum Patients {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum MedicalAppointments {
+  Table,
+  Id,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum EmailStatusEnum {
+  #[iden = "email_status"]
+  Enum,
+  #[iden = "pending"]
+  Pending,
+  #[iden = "sent"]
+  Sent,
+  #[iden = "failed"]
+  Failed,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::{prelude::*, sea_orm::Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Closes the gaps left in the append-only audit trail:
+/// - entries could still be deleted, only updates were rejected
+/// - deleting a user cascaded to, and wiped, their audit history
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let db = manager.get_database_backend();
+
+    let stmts = [
+      "DROP TRIGGER IF EXISTS audit_logs_no_update ON audit_logs",
+      "CREATE TRIGGER audit_logs_no_update
+        BEFORE UPDATE OR DELETE ON audit_logs
+        FOR EACH ROW EXECUTE FUNCTION audit_logs_prevent_update()",
+      "ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS fk_audit_logs_user_id",
+      "ALTER TABLE audit_logs ADD CONSTRAINT fk_audit_logs_user_id
+        FOREIGN KEY (user_id) REFERENCES users (id)
+        ON DELETE RESTRICT ON UPDATE CASCADE",
+    ];
+
+    for sql in stmts {
+      conn.execute(Statement::from_string(db, sql)).await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let db = manager.get_database_backend();
+
+    let stmts = [
+      "DROP TRIGGER IF EXISTS audit_logs_no_update ON audit_logs",
+      "CREATE TRIGGER audit_logs_no_update
+        BEFORE UPDATE ON audit_logs
+        FOR EACH ROW EXECUTE FUNCTION audit_logs_prevent_update()",
+      "ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS fk_audit_logs_user_id",
+      "ALTER TABLE audit_logs ADD CONSTRAINT fk_audit_logs_user_id
+        FOREIGN KEY (user_id) REFERENCES users (id)
+        ON DELETE CASCADE ON UPDATE CASCADE",
+    ];
+
+    for sql in stmts {
+      conn.execute(Statement::from_string(db, sql)).await?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(PatientAccesses::Table)
+          .add_column(
+            ColumnDef::new(PatientAccesses::ConsentTokenExpiresAt)
+              .timestamp_with_time_zone()
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(PatientAccesses::Table)
+          .drop_column(PatientAccesses::ConsentTokenExpiresAt)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum PatientAccesses {
+  Table,
+  ConsentTokenExpiresAt,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(ScheduledExports::Table)
+          .add_column(
+            ColumnDef::new(ScheduledExports::ClaimedUntil)
+              .timestamp_with_time_zone()
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(ScheduledExports::Table)
+          .drop_column(ScheduledExports::ClaimedUntil)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum ScheduledExports {
+  Table,
+  /// Until when the period after `last_period_start` is being sent by an
+  /// instance of the application, so that no other one sends it too
+  ClaimedUntil,
+}
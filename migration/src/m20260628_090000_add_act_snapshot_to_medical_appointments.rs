@@ -0,0 +1,58 @@
+use sea_orm_migration::{prelude::*, sea_orm::Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Appointments keep the label and code their act had when they were created,
+/// so that renaming or deleting the act leaves past invoices and exports as
+/// they were
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(MedicalAppointments::Table)
+          .add_column(
+            ColumnDef::new(MedicalAppointments::ActLabel)
+              .string()
+              .null(),
+          )
+          .add_column(ColumnDef::new(MedicalAppointments::ActCode).string().null())
+          .to_owned(),
+      )
+      .await?;
+
+    let conn = manager.get_connection();
+    conn
+      .execute(Statement::from_string(
+        manager.get_database_backend(),
+        "UPDATE medical_appointments
+          SET act_label = acts.label, act_code = acts.code
+          FROM acts
+          WHERE acts.id = medical_appointments.act_id",
+      ))
+      .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(MedicalAppointments::Table)
+          .drop_column(MedicalAppointments::ActLabel)
+          .drop_column(MedicalAppointments::ActCode)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum MedicalAppointments {
+  Table,
+  ActLabel,
+  ActCode,
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::{prelude::*, sea_orm::Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The VAT exemption mention is now always printed on the invoices, the legal
+/// mentions of the templates only keep the additional ones
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .get_connection()
+      .execute(Statement::from_string(
+        manager.get_database_backend(),
+        "UPDATE invoice_templates
+          SET legal_mentions = NULLIF(
+            TRIM(BOTH E' \\n\\r' FROM REPLACE(legal_mentions, 'TVA non applicable, art. 261-4-1° du CGI', '')),
+            ''
+          )
+          WHERE legal_mentions LIKE '%TVA non applicable, art. 261-4-1° du CGI%'",
+      ))
+      .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+    // The mention is printed whatever the stored legal mentions, there is
+    // nothing to restore
+    Ok(())
+  }
+}
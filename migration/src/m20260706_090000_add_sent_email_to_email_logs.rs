@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Invoice emails keep what was sent, PDF included, so that resending one
+/// delivers the very same invoice to the same address
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(EmailLogs::Table)
+          .add_column(ColumnDef::new(EmailLogs::SentEmail).json_binary().null())
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(EmailLogs::Table)
+          .drop_column(EmailLogs::SentEmail)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum EmailLogs {
+  Table,
+  SentEmail,
+}
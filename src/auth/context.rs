@@ -15,13 +15,18 @@ use crate::{
 
 pub struct AuthContext {
   pub current_user: Option<(users::Model, Option<user_business_informations::Model>)>,
+  pub ip_address: Option<String>,
   authorized: bool,
   complete: bool,
   pub error: Option<MyErrors>,
 }
 
 impl AuthContext {
-  pub async fn new(auth_header: Option<&str>, state: &AppState) -> Self {
+  pub async fn new(
+    auth_header: Option<&str>,
+    ip_address: Option<String>,
+    state: &AppState,
+  ) -> Self {
     let (current_user, error) = match auth_header {
       Some(header) => Self::validate_auth_header(header, state).await,
       None => (None, None),
@@ -29,6 +34,7 @@ impl AuthContext {
 
     Self {
       current_user,
+      ip_address,
       authorized: false,
       complete: false,
       error: error.map(|e| e.into()),
@@ -101,6 +107,10 @@ impl AuthContext {
       return (None, Some(AuthenticationError::AccessKeyNotVerified));
     }
 
+    if user_result.0.is_suspended() {
+      return (None, Some(AuthenticationError::AccountSuspended));
+    }
+
     (Some(user_result), None)
   }
 
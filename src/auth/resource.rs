@@ -1,7 +1,31 @@
 use std::future::Future;
 
+use crate::models::_entities::sea_orm_active_enums::AuditAction;
+
+/// Level of access a user has on a resource, from the weakest to the strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Permission {
+  Read,
+  Write,
+  Own,
+}
+
+impl Permission {
+  /// The minimal permission required to perform `action` on a resource
+  pub fn required_for(action: &AuditAction) -> Self {
+    match action {
+      AuditAction::Read | AuditAction::Search => Permission::Read,
+      AuditAction::Create | AuditAction::Update => Permission::Write,
+      // Only the owner answers the patient's GDPR access requests
+      AuditAction::Delete | AuditAction::Export => Permission::Own,
+    }
+  }
+}
+
 pub trait Resource {
-  fn is_owned_by_user(&self, user_id: i32) -> impl Future<Output = bool>;
+  fn permission_for_user(&self, user_id: i32) -> impl Future<Output = Option<Permission>>;
+
+  fn resource_id(&self) -> i32;
 
   fn resource_name(&self) -> String;
 }
@@ -1,8 +1,15 @@
 use crate::{
-  auth::{context::AuthContext, resource::Resource},
-  models::my_errors::{
-    authentication_error::AuthenticationError, unexpected_error::UnexpectedError, MyErrors,
+  auth::{
+    context::AuthContext,
+    resource::{Permission, Resource},
   },
+  models::{
+    _entities::sea_orm_active_enums::AuditAction,
+    my_errors::{
+      authentication_error::AuthenticationError, unexpected_error::UnexpectedError, MyErrors,
+    },
+  },
+  services,
 };
 
 pub struct AuthStatement {
@@ -47,16 +54,64 @@ impl AuthStatement {
     )
   }
 
-  pub async fn user_owning_resource<T: Resource>(self, resource: &T) -> Self {
-    let is_owned = match &self.auth_context.current_user {
-      Some(user) => resource.is_owned_by_user(user.0.id).await,
-      None => false,
+  pub fn admin_user(self) -> Self {
+    self.check(
+      |s| {
+        s.auth_context
+          .current_user
+          .as_ref()
+          .is_some_and(|user| user.0.is_admin())
+      },
+      Some(AuthenticationError::AccessDenied(Some("admin".to_string())).into()),
+    )
+  }
+
+  /// Checks that the current user holds the permission required to perform
+  /// `action` on `resource`, and records the access in the audit trail.
+  pub async fn user_accessing_resource<T: Resource>(
+    self,
+    resource: &T,
+    action: AuditAction,
+  ) -> Self {
+    let permission = match &self.auth_context.current_user {
+      Some(user) => resource.permission_for_user(user.0.id).await,
+      None => None,
     };
+    let is_allowed = permission.is_some_and(|p| p >= Permission::required_for(&action));
 
-    self.check(
-      |_| is_owned,
-      Some(AuthenticationError::AccessDenied(Some(resource.resource_name())).into()),
+    self
+      .check(
+        |_| is_allowed,
+        Some(AuthenticationError::AccessDenied(Some(resource.resource_name())).into()),
+      )
+      .record_access(resource, action)
+      .await
+  }
+
+  /// Writes an audit log entry for the current user accessing `resource`.
+  /// The entry records whether the checks so far granted the access, and a
+  /// failure to write it denies the access altogether.
+  pub async fn record_access<T: Resource>(mut self, resource: &T, action: AuditAction) -> Self {
+    let Some(user) = &self.auth_context.current_user else {
+      return self;
+    };
+
+    let recorded = services::audit::record_access(
+      user.0.id,
+      resource,
+      action,
+      self.ok_so_far,
+      self.auth_context.ip_address.clone(),
     )
+    .await;
+
+    if let Err(error) = recorded {
+      tracing::error!("Failed to write audit log: {}", error);
+      self.ok_so_far = false;
+      self.error = Some(UnexpectedError::new("audit_log_failed".to_string()).into());
+    }
+
+    self
   }
 
   #[allow(dead_code)]
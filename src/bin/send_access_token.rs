@@ -6,7 +6,8 @@ use opencab::{
     _entities::users::Entity as Users,
     my_errors::{unexpected_error::UnexpectedError, MyErrors},
   },
-  workers::mailer::{self, args::EmailArgs},
+  services,
+  workers::mailer,
 };
 use sea_orm::{Database, EntityTrait};
 
@@ -30,17 +31,11 @@ async fn main() -> Result<(), MyErrors> {
     .await?
     .ok_or(UnexpectedError::new("user_not_found".to_string()))?;
 
-  match user_to_invite.access_key {
-    Some(access_key) => {
-      let email_args = EmailArgs::new_text(
-          user_to_invite.email,
-          "Votre code d'accès à OpenCab".to_string(),
-          format!("Bonjour,\n\nVoici votre code d'accès à la plateforme OpenCab: {}\nVous pouvez l'utiliser juste après vous être connecté: {}/login", access_key, config.app.base_url)
-      );
-
+  match services::user::access_key_email(&user_to_invite, &config.app.base_url) {
+    Some(email_args) => {
       println!("Sending email...");
 
-      mailer::worker::process_email(email_args, &config).await?;
+      mailer::worker::process_email(email_args, &db, &config).await?;
 
       println!("Email sent successfully !")
     }
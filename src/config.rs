@@ -9,6 +9,10 @@ pub struct Config {
   pub logger: LoggerConfig,
   pub cors: CorsConfig,
   pub app: AppConfig,
+  #[serde(default)]
+  pub retention: RetentionConfig,
+  #[serde(default)]
+  pub scheduled_exports: ScheduledExportsConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -57,6 +61,39 @@ pub struct AppConfig {
   pub base_url: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionConfig {
+  /// How long anonymized patients and their appointments are kept for accounting purposes
+  #[serde(default = "default_patient_retention_days")]
+  pub patient_retention_days: u32,
+  #[serde(default = "default_purge_interval_hours")]
+  pub purge_interval_hours: u64,
+}
+
+impl Default for RetentionConfig {
+  fn default() -> Self {
+    Self {
+      patient_retention_days: default_patient_retention_days(),
+      purge_interval_hours: default_purge_interval_hours(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledExportsConfig {
+  /// How often the scheduled exports are checked for a newly completed period
+  #[serde(default = "default_check_interval_minutes")]
+  pub check_interval_minutes: u64,
+}
+
+impl Default for ScheduledExportsConfig {
+  fn default() -> Self {
+    Self {
+      check_interval_minutes: default_check_interval_minutes(),
+    }
+  }
+}
+
 // Default value functions
 fn default_binding() -> String {
   "localhost".to_string()
@@ -66,6 +103,18 @@ fn default_jwt_expiration() -> u64 {
   604800 // 7 days
 }
 
+fn default_patient_retention_days() -> u32 {
+  3650 // 10 years, the legal retention period of accounting records
+}
+
+fn default_purge_interval_hours() -> u64 {
+  24
+}
+
+fn default_check_interval_minutes() -> u64 {
+  60
+}
+
 fn default_log_level() -> String {
   "info".to_string()
 }
@@ -105,6 +154,26 @@ impl Config {
       builder = builder.set_override("server.port", port)?;
     }
 
-    builder.build()?.try_deserialize()
+    let config: Self = builder.build()?.try_deserialize()?;
+    config.validate()?;
+
+    Ok(config)
+  }
+
+  /// Reject the values the background workers cannot run with
+  fn validate(&self) -> Result<(), ConfigError> {
+    if self.retention.purge_interval_hours == 0 {
+      return Err(ConfigError::Message(
+        "retention.purge_interval_hours must be at least 1".to_string(),
+      ));
+    }
+
+    if self.scheduled_exports.check_interval_minutes == 0 {
+      return Err(ConfigError::Message(
+        "scheduled_exports.check_interval_minutes must be at least 1".to_string(),
+      ));
+    }
+
+    Ok(())
   }
 }
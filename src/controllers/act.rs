@@ -0,0 +1,98 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::status,
+  Json,
+};
+use sea_orm::{EntityTrait, ModelTrait};
+
+use crate::{
+  app_state::AppState,
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::{acts, sea_orm_active_enums::AuditAction},
+    acts::ActParams,
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+  services,
+  views::act::ActResponse,
+};
+
+#[debug_handler]
+pub async fn list(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<Vec<ActResponse>>, MyErrors> {
+  let catalogue = services::acts::catalogue(&current_user).await?;
+
+  Ok(Json(
+    catalogue
+      .iter()
+      .map(|(act, office_prices)| ActResponse::new(act, office_prices))
+      .collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn create(
+  State(_state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(params): Json<ActParams>,
+) -> Result<Json<ActResponse>, MyErrors> {
+  let (act, office_prices) = services::acts::create(&current_user, &params).await?;
+
+  authorize
+    .authenticated_user()
+    .record_access(&act, AuditAction::Create)
+    .await
+    .run_complete()?;
+
+  Ok(Json(ActResponse::new(&act, &office_prices)))
+}
+
+#[debug_handler]
+pub async fn update(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(act_id): Path<i32>,
+  Json(params): Json<ActParams>,
+) -> Result<Json<ActResponse>, MyErrors> {
+  let act = acts::Entity::find_by_id(act_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&act, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  let (act, office_prices) = services::acts::update(act, &current_user, &params).await?;
+
+  Ok(Json(ActResponse::new(&act, &office_prices)))
+}
+
+/// Appointments of a deleted act keep their price, without act
+#[debug_handler]
+pub async fn delete(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(act_id): Path<i32>,
+) -> Result<status::StatusCode, MyErrors> {
+  let act = acts::Entity::find_by_id(act_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&act, AuditAction::Delete)
+    .await
+    .run_complete()?;
+
+  act.delete(&state.db).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
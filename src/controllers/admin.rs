@@ -0,0 +1,117 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::status,
+  Json,
+};
+use sea_orm::{EntityTrait, IntoActiveModel};
+
+use crate::{
+  app_state::{AppState, WorkerJob},
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::users,
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+  services,
+  views::admin::{AdminUserResponse, UsageStatsResponse},
+};
+
+async fn find_user(state: &AppState, user_id: i32) -> Result<users::Model, MyErrors> {
+  Ok(
+    users::Entity::find_by_id(user_id)
+      .one(&state.db)
+      .await?
+      .ok_or(ApplicationError::NotFound)?,
+  )
+}
+
+#[debug_handler]
+pub async fn list_users(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+) -> Result<Json<Vec<AdminUserResponse>>, MyErrors> {
+  authorize.admin_user().run_complete()?;
+
+  let users_with_usage = services::admin::list_users_with_usage(&state.db).await?;
+
+  Ok(Json(
+    users_with_usage
+      .iter()
+      .map(|(user, usage)| AdminUserResponse::new(user, usage))
+      .collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn approve_user(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(user_id): Path<i32>,
+) -> Result<status::StatusCode, MyErrors> {
+  authorize.admin_user().run_complete()?;
+
+  find_user(&state, user_id)
+    .await?
+    .into_active_model()
+    .approve(&state.db)
+    .await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn suspend_user(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(user_id): Path<i32>,
+) -> Result<status::StatusCode, MyErrors> {
+  authorize.admin_user().run_complete()?;
+
+  if current_user.id == user_id {
+    return Err(ApplicationError::new("cannot_suspend_yourself").into());
+  }
+
+  find_user(&state, user_id)
+    .await?
+    .into_active_model()
+    .suspend(&state.db)
+    .await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn resend_access_key(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(user_id): Path<i32>,
+) -> Result<status::StatusCode, MyErrors> {
+  authorize.admin_user().run_complete()?;
+
+  let user = find_user(&state, user_id).await?;
+
+  let email_args = services::user::access_key_email(&user, &state.config.app.base_url)
+    .ok_or(ApplicationError::new("no_access_key_registered"))?;
+
+  state
+    .worker_transmitter
+    .send(WorkerJob::Email(email_args))
+    .await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn stats(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+) -> Result<Json<UsageStatsResponse>, MyErrors> {
+  authorize.admin_user().run_complete()?;
+
+  let usage_stats = services::admin::usage_stats(&state.db).await?;
+
+  Ok(Json(UsageStatsResponse::new(&usage_stats)))
+}
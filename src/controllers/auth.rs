@@ -15,7 +15,7 @@ use crate::{
   },
   services::{self},
   views::auth::{CurrentResponse, LoginResponse},
-  workers::mailer::args::EmailArgs,
+  workers::mailer::{args::EmailArgs, template::EmailTemplate},
 };
 use axum::{
   debug_handler,
@@ -72,14 +72,11 @@ pub async fn forgot(
     state.config.app.base_url, secured_token
   );
 
-  let email_args = EmailArgs::new_text(
-    user.email,
-    "Réinitialisation du mot de passe".to_string(),
-    format!(
-      "Bonjour,\n\nVoici le lien pour réinitialiser votre mot de passe: {}",
-      secured_url
-    ),
-  );
+  let email = EmailTemplate::PasswordReset {
+    reset_url: secured_url,
+  }
+  .render(&user.locale, None);
+  let email_args = EmailArgs::from_template(user.email, email).for_user(user.id);
 
   state
     .worker_transmitter
@@ -138,6 +135,10 @@ pub async fn login(
     return Err(AuthenticationError::InvalidCredentials.into());
   }
 
+  if user.is_suspended() {
+    return Err(AuthenticationError::AccountSuspended.into());
+  }
+
   if !user.is_access_key_verified {
     return Err(MyErrors {
       code: StatusCode::SEE_OTHER,
@@ -0,0 +1,103 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::header,
+  response::IntoResponse,
+  Json,
+};
+use chrono::NaiveDate;
+use sea_orm::EntityTrait;
+use serde::Deserialize;
+
+use crate::{
+  app_state::AppState,
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::{check_deposits, sea_orm_active_enums::AuditAction},
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+  services,
+  views::check_deposit::{CheckDepositResponse, PendingCheckResponse},
+};
+
+#[derive(Deserialize)]
+pub struct CreateCheckDepositParams {
+  pub deposited_on: Option<String>,
+}
+
+#[debug_handler]
+pub async fn pending(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<Vec<PendingCheckResponse>>, MyErrors> {
+  let checks = services::check_deposits::pending_checks(&current_user).await?;
+
+  Ok(Json(checks.iter().map(PendingCheckResponse::new).collect()))
+}
+
+#[debug_handler]
+pub async fn list(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<Vec<CheckDepositResponse>>, MyErrors> {
+  let deposits = services::check_deposits::deposits_for_user(&current_user).await?;
+
+  Ok(Json(
+    deposits.iter().map(CheckDepositResponse::new).collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn create(
+  State(_state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(params): Json<CreateCheckDepositParams>,
+) -> Result<Json<CheckDepositResponse>, MyErrors> {
+  let deposited_on = match params.deposited_on.as_deref() {
+    Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")?,
+    None => chrono::Local::now().date_naive(),
+  };
+
+  let summary = services::check_deposits::deposit(&current_user, deposited_on).await?;
+
+  authorize
+    .authenticated_user()
+    .record_access(&summary.deposit, AuditAction::Create)
+    .await
+    .run_complete()?;
+
+  Ok(Json(CheckDepositResponse::new(&summary)))
+}
+
+#[debug_handler]
+pub async fn slip(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(deposit_id): Path<i32>,
+) -> Result<impl IntoResponse, MyErrors> {
+  let deposit = check_deposits::Entity::find_by_id(deposit_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&deposit, AuditAction::Export)
+    .await
+    .run_complete()?;
+
+  let slip = services::check_deposits::slip(&deposit, &current_user).await?;
+
+  Ok((
+    [
+      (header::CONTENT_TYPE, "application/pdf".to_string()),
+      (
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", slip.filename),
+      ),
+    ],
+    slip.data,
+  ))
+}
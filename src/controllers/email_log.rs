@@ -0,0 +1,81 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::status,
+  Json,
+};
+use sea_orm::{ColumnTrait, QueryFilter};
+
+use crate::{
+  app_state::{AppState, WorkerJob},
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::{email_logs, patients, sea_orm_active_enums::AuditAction},
+    my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
+  },
+  services,
+  views::email_log::EmailLogResponse,
+};
+
+async fn find_patient(state: &AppState, patient_id: i32) -> Result<patients::Model, MyErrors> {
+  Ok(
+    patients::Entity::find_active_by_id(patient_id)
+      .one(&state.db)
+      .await?
+      .ok_or(ApplicationError::NotFound)?,
+  )
+}
+
+#[debug_handler]
+pub async fn list(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(patient_id): Path<i32>,
+) -> Result<Json<Vec<EmailLogResponse>>, MyErrors> {
+  let patient = find_patient(&state, patient_id).await?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Read)
+    .await
+    .run_complete()?;
+
+  let email_logs = email_logs::Entity::find_for_patient(current_user.id, patient.id)
+    .all(&state.db)
+    .await?;
+
+  Ok(Json(email_logs.iter().map(EmailLogResponse::new).collect()))
+}
+
+#[debug_handler]
+pub async fn resend(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path((patient_id, email_log_id)): Path<(i32, i32)>,
+) -> Result<status::StatusCode, MyErrors> {
+  let patient = find_patient(&state, patient_id).await?;
+
+  // Sends mail on the practitioner's behalf
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  let email_log = email_logs::Entity::find_for_patient(current_user.id, patient.id)
+    .filter(email_logs::Column::Id.eq(email_log_id))
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  let email = services::invoice::resent_invoice_email(&email_log)?;
+
+  state
+    .worker_transmitter
+    .send(WorkerJob::Email(email))
+    .await
+    .map_err(|_| UnexpectedError::ShouldNotHappen)?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,126 @@
+use axum::{
+  debug_handler,
+  extract::{Path, Query, State},
+  http::{header, status},
+  response::IntoResponse,
+  Json,
+};
+use chrono::Datelike;
+use sea_orm::{EntityTrait, ModelTrait};
+use serde::Deserialize;
+
+use crate::{
+  app_state::AppState,
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::{expenses, sea_orm_active_enums::AuditAction},
+    expenses::CreateExpenseParams,
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+  services::{self, appointments::ToExcel},
+  views::{expense::ExpenseResponse, ledger::FiscalYearReportResponse},
+};
+
+const EXCEL_CONTENT_TYPE: &str =
+  "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+#[derive(Deserialize)]
+pub struct FiscalYearParams {
+  pub year: Option<i32>,
+}
+
+impl FiscalYearParams {
+  fn year(&self) -> i32 {
+    self
+      .year
+      .unwrap_or_else(|| chrono::Local::now().date_naive().year())
+  }
+}
+
+#[debug_handler]
+pub async fn list(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<FiscalYearParams>,
+) -> Result<Json<Vec<ExpenseResponse>>, MyErrors> {
+  let (start_date, end_date) = services::ledger::fiscal_year_bounds(params.year())?;
+
+  let expenses = expenses::Entity::find_for_period(current_user.id, start_date, end_date)
+    .all(&state.db)
+    .await?;
+
+  Ok(Json(expenses.iter().map(ExpenseResponse::new).collect()))
+}
+
+#[debug_handler]
+pub async fn create(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(params): Json<CreateExpenseParams>,
+) -> Result<Json<ExpenseResponse>, MyErrors> {
+  let expense = expenses::ActiveModel::create(&state.db, &params, current_user.id).await?;
+
+  authorize
+    .authenticated_user()
+    .record_access(&expense, AuditAction::Create)
+    .await
+    .run_complete()?;
+
+  Ok(Json(ExpenseResponse::new(&expense)))
+}
+
+#[debug_handler]
+pub async fn delete(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(expense_id): Path<i32>,
+) -> Result<status::StatusCode, MyErrors> {
+  let expense = expenses::Entity::find_by_id(expense_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&expense, AuditAction::Delete)
+    .await
+    .run_complete()?;
+
+  expense.delete(&state.db).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn fiscal_year_report(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<FiscalYearParams>,
+) -> Result<Json<FiscalYearReportResponse>, MyErrors> {
+  let report = services::ledger::fiscal_year_report(&current_user, params.year()).await?;
+
+  Ok(Json(FiscalYearReportResponse::new(&report)))
+}
+
+#[debug_handler]
+pub async fn download_fiscal_year_report(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<FiscalYearParams>,
+) -> Result<impl IntoResponse, MyErrors> {
+  let year = params.year();
+  let report = services::ledger::fiscal_year_report(&current_user, year).await?;
+  let workbook_buffer = report.to_excel()?.save_to_buffer()?;
+
+  Ok((
+    [
+      (header::CONTENT_TYPE, EXCEL_CONTENT_TYPE.to_string()),
+      (
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"livre_des_recettes_{}.xlsx\"", year),
+      ),
+    ],
+    workbook_buffer,
+  ))
+}
@@ -1,6 +1,6 @@
 use axum::{
   debug_handler,
-  extract::{Path, State},
+  extract::{Path, Query, State},
   http::status,
   Json,
 };
@@ -13,18 +13,61 @@ use crate::{
   auth::statement::AuthStatement,
   middleware::auth::AuthenticatedUser,
   models::{
-    _entities::{medical_appointments, sea_orm_active_enums::PaymentMethod},
-    medical_appointments::{CreateMedicalAppointmentParams, UpdateMedicalAppointmentParams},
+    _entities::{
+      medical_appointments, patients,
+      sea_orm_active_enums::{AuditAction, PaymentMethod},
+    },
+    medical_appointments::{
+      CreateMedicalAppointmentParams, PayerShares, UpdateMedicalAppointmentParams,
+    },
     my_errors::{application_error::ApplicationError, MyErrors},
+    payments::PaymentStatus,
   },
+  services,
+  views::medical_appointments::CalendarAppointmentResponse,
 };
 
 #[derive(Debug, Deserialize)]
 pub struct MedicalAppointmentPayload {
   date: String,
   practitioner_office_id: i32,
-  price_in_cents: i32,
+  /// Act of the catalogue, whose price is used when `price_in_cents` is left out
+  act_id: Option<i32>,
+  price_in_cents: Option<i32>,
   payment_method: Option<PaymentMethod>,
+  #[serde(default)]
+  amo_share_in_cents: i32,
+  #[serde(default)]
+  mutuelle_share_in_cents: i32,
+  amo_paid_at: Option<String>,
+  mutuelle_paid_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarParams {
+  start_date: String,
+  end_date: String,
+  payment_status: Option<PaymentStatus>,
+}
+
+fn parse_optional_date(date: &Option<String>) -> Result<Option<NaiveDate>, MyErrors> {
+  Ok(
+    date
+      .as_deref()
+      .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+      .transpose()?,
+  )
+}
+
+impl MedicalAppointmentPayload {
+  fn payer_shares(&self) -> Result<PayerShares, MyErrors> {
+    Ok(PayerShares {
+      amo_share_in_cents: self.amo_share_in_cents,
+      mutuelle_share_in_cents: self.mutuelle_share_in_cents,
+      amo_paid_at: parse_optional_date(&self.amo_paid_at)?,
+      mutuelle_paid_at: parse_optional_date(&self.mutuelle_paid_at)?,
+    })
+  }
 }
 
 pub async fn delete(
@@ -39,7 +82,7 @@ pub async fn delete(
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&medical_appointment)
+    .user_accessing_resource(&medical_appointment, AuditAction::Delete)
     .await
     .run_complete()?;
 
@@ -62,25 +105,38 @@ pub async fn update(
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&medical_appointment)
+    .user_accessing_resource(&medical_appointment, AuditAction::Update)
     .await
     .run_complete()?;
 
   // Parse date string in YYYY-MM-DD format
   let appointment_date = NaiveDate::parse_from_str(&params.date, "%Y-%m-%d")?;
 
+  let (act, price_in_cents) = services::acts::appointment_act(
+    &state.db,
+    medical_appointment.user_id,
+    params.act_id,
+    params.practitioner_office_id,
+    params.price_in_cents,
+  )
+  .await?;
+
   let medical_appointments_params = UpdateMedicalAppointmentParams {
     date: appointment_date,
     practitioner_office_id: params.practitioner_office_id,
-    price_in_cents: params.price_in_cents,
-    payment_method: params.payment_method,
+    act,
+    price_in_cents,
+    payment_method: params.payment_method.clone(),
+    payer_shares: params.payer_shares()?,
   };
 
-  medical_appointment
+  let medical_appointment = medical_appointment
     .into_active_model()
     .update(&state.db, &medical_appointments_params)
     .await?;
 
+  services::payments::settle_on_the_spot(&state.db, &medical_appointment).await?;
+
   Ok(status::StatusCode::NO_CONTENT)
 }
 
@@ -92,21 +148,72 @@ pub async fn create(
   Path(patient_id): Path<i32>,
   Json(params): Json<MedicalAppointmentPayload>,
 ) -> Result<status::StatusCode, MyErrors> {
-  authorize.authenticated_user().run_complete()?;
+  let patient = patients::Entity::find_active_by_id(patient_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Create)
+    .await
+    .run_complete()?;
 
   // Parse date string in YYYY-MM-DD format
   let appointment_date = NaiveDate::parse_from_str(&params.date, "%Y-%m-%d")?;
 
+  let (act, price_in_cents) = services::acts::appointment_act(
+    &state.db,
+    current_user.id,
+    params.act_id,
+    params.practitioner_office_id,
+    params.price_in_cents,
+  )
+  .await?;
+
   let medical_appointments_params = CreateMedicalAppointmentParams {
     date: appointment_date,
     practitioner_office_id: params.practitioner_office_id,
-    price_in_cents: params.price_in_cents,
+    act,
+    price_in_cents,
     user_id: current_user.id,
     patient_id,
-    payment_method: params.payment_method,
+    payment_method: params.payment_method.clone(),
+    payer_shares: params.payer_shares()?,
   };
 
-  medical_appointments::ActiveModel::create(&state.db, &medical_appointments_params).await?;
+  let medical_appointment =
+    medical_appointments::ActiveModel::create(&state.db, &medical_appointments_params).await?;
+
+  services::payments::settle_on_the_spot(&state.db, &medical_appointment).await?;
 
   Ok(status::StatusCode::NO_CONTENT)
 }
+
+#[debug_handler]
+pub async fn calendar(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<CalendarParams>,
+) -> Result<Json<Vec<CalendarAppointmentResponse>>, MyErrors> {
+  let start_date = NaiveDate::parse_from_str(&params.start_date, "%Y-%m-%d")?;
+  let end_date = NaiveDate::parse_from_str(&params.end_date, "%Y-%m-%d")?;
+
+  if start_date > end_date {
+    return Err(ApplicationError::new("start_date_before_end_date").into());
+  }
+
+  let appointments = services::payments::appointments_between(
+    &current_user,
+    start_date,
+    end_date,
+    params.payment_status,
+  )
+  .await?;
+
+  Ok(Json(
+    appointments
+      .iter()
+      .map(|(settlement, patient)| CalendarAppointmentResponse::new(settlement, patient))
+      .collect(),
+  ))
+}
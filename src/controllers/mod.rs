@@ -1,5 +1,15 @@
+pub mod act;
+pub mod admin;
 pub mod auth;
+pub mod check_deposit;
+pub mod email_log;
+pub mod expense;
 pub mod medical_appointment;
 pub mod patient;
+pub mod patient_access;
+pub mod patient_insurance;
+pub mod payment;
 pub mod practitioner_office;
+pub mod retrocession_statement;
+pub mod stats;
 pub mod user;
@@ -1,18 +1,30 @@
 use axum::{
   debug_handler,
   extract::{Path, Query, State},
-  http::status,
+  http::{header, status},
+  response::IntoResponse,
   Json,
 };
 use base64::Engine;
-use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, QueryFilter, QueryOrder};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize)]
 pub struct SearchBySSNParams {
   pub ssn: String,
 }
 
+#[derive(Deserialize)]
+pub struct MergePatientParams {
+  pub duplicate_patient_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct MedicalAppointmentsFilterParams {
+  pub payment_status: Option<PaymentStatus>,
+}
+
 #[derive(Deserialize)]
 pub struct SearchParams {
   pub q: String,
@@ -24,12 +36,18 @@ use crate::{
   auth::statement::AuthStatement,
   middleware::auth::AuthenticatedUser,
   models::{
-    _entities::{medical_appointments, patients, practitioner_offices},
+    _entities::{
+      medical_appointments, patients, practitioner_offices, sea_orm_active_enums::AuditAction,
+    },
     my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
     patients::{CreatePatientParams, Model},
+    payments::PaymentStatus,
   },
   services::{self, invoice::GenerateInvoiceParams},
-  views::{medical_appointments::MedicalAppointmentResponse, patient::PatientResponse},
+  views::{
+    medical_appointments::MedicalAppointmentResponse,
+    patient::{DuplicateGroupResponse, PatientResponse},
+  },
 };
 
 #[debug_handler]
@@ -38,13 +56,13 @@ pub async fn get(
   authorize: AuthStatement,
   Path(patient_id): Path<i32>,
 ) -> Result<Json<PatientResponse>, MyErrors> {
-  let patient = patients::Entity::find_by_id(patient_id)
+  let patient = patients::Entity::find_active_by_id(patient_id)
     .one(&state.db)
     .await?
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&patient)
+    .user_accessing_resource(&patient, AuditAction::Read)
     .await
     .run_complete()?;
 
@@ -69,13 +87,13 @@ pub async fn update(
   Path(patient_id): Path<i32>,
   Json(patient_params): Json<CreatePatientParams>,
 ) -> Result<Json<serde_json::Value>, MyErrors> {
-  let patient = patients::Entity::find_by_id(patient_id)
+  let patient = patients::Entity::find_active_by_id(patient_id)
     .one(&state.db)
     .await?
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&patient)
+    .user_accessing_resource(&patient, AuditAction::Update)
     .await
     .run_complete()?;
 
@@ -90,17 +108,17 @@ pub async fn delete(
   authorize: AuthStatement,
   Path(patient_id): Path<i32>,
 ) -> Result<status::StatusCode, MyErrors> {
-  let patient = patients::Entity::find_by_id(patient_id)
+  let patient = patients::Entity::find_active_by_id(patient_id)
     .one(&state.db)
     .await?
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&patient)
+    .user_accessing_resource(&patient, AuditAction::Delete)
     .await
     .run_complete()?;
 
-  patient.delete(&state.db).await?;
+  services::patients::anonymize(patient).await?;
 
   Ok(status::StatusCode::NO_CONTENT)
 }
@@ -108,9 +126,23 @@ pub async fn delete(
 #[debug_handler]
 pub async fn search_by_ssn(
   State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
   Query(params): Query<SearchBySSNParams>,
 ) -> Result<Json<Vec<PatientResponse>>, MyErrors> {
-  let found_patients = Model::search_by_ssn(&state.db, &params.ssn).await?;
+  // Records of other practitioners are only returned once they have been shared
+  let mut found_patients = Vec::new();
+  for patient in Model::search_by_ssn(&state.db, &params.ssn).await? {
+    if services::patient_sharing::can_access(&patient, &current_user).await {
+      found_patients.push(patient);
+    }
+  }
+
+  let mut authorize = authorize.authenticated_user();
+  for patient in &found_patients {
+    authorize = authorize.record_access(patient, AuditAction::Search).await;
+  }
+  authorize.run_complete()?;
 
   let serialized_patients: Vec<PatientResponse> =
     found_patients.iter().map(PatientResponse::new).collect();
@@ -152,20 +184,33 @@ pub async fn search(
 #[debug_handler]
 pub async fn generate_invoice(
   State(state): State<AppState>,
+  authorize: AuthStatement,
   AuthenticatedUser(current_user, user_bi): AuthenticatedUser,
   Path(patient_id): Path<i32>,
   Json(params): Json<GenerateInvoiceParams>,
 ) -> Result<Json<serde_json::Value>, MyErrors> {
-  if params.amount <= 0.0 {
-    return Err(ApplicationError::UnprocessableEntity.into());
-  }
+  if let Some(amount) = params.amount {
+    if amount <= 0.0 {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
 
-  if params.amount > (i32::MAX as f32 / 100.0) {
-    return Err(ApplicationError::UnprocessableEntity.into());
+    if amount > (i32::MAX as f32 / 100.0) {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
   }
 
+  let patient = patients::Entity::find_active_by_id(patient_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Create)
+    .await
+    .run_complete()?;
+
   let invoice_generated =
-    services::invoice::generate_patient_invoice(&patient_id, &params, &current_user).await?;
+    services::invoice::generate_patient_invoice(patient, &params, &current_user).await?;
 
   if params.should_be_sent_by_email {
     match &user_bi {
@@ -193,8 +238,9 @@ pub async fn get_medical_appointments(
   State(state): State<AppState>,
   AuthenticatedUser(current_user, _): AuthenticatedUser,
   Path(patient_id): Path<i32>,
+  Query(params): Query<MedicalAppointmentsFilterParams>,
 ) -> Result<Json<Vec<MedicalAppointmentResponse>>, MyErrors> {
-  let medical_appointments = medical_appointments::Entity::find()
+  let (medical_appointments, offices): (Vec<_>, Vec<_>) = medical_appointments::Entity::find()
     .filter(medical_appointments::Column::PatientId.eq(patient_id))
     .filter(medical_appointments::Column::UserId.eq(current_user.id))
     .order_by_desc(medical_appointments::Column::Date)
@@ -202,13 +248,106 @@ pub async fn get_medical_appointments(
     .all(&state.db)
     .await?
     .into_iter()
-    .map(|appointment| {
-      Ok(MedicalAppointmentResponse::new(
-        &appointment.0,
-        &appointment.1.ok_or(UnexpectedError::ShouldNotHappen)?,
-      ))
-    })
-    .collect::<Result<Vec<_>, MyErrors>>()?;
+    .unzip();
+
+  let offices_by_id: HashMap<i32, practitioner_offices::Model> = offices
+    .into_iter()
+    .flatten()
+    .map(|office| (office.id, office))
+    .collect();
+
+  let medical_appointments =
+    services::payments::settlements(&state.db, medical_appointments, params.payment_status)
+      .await?
+      .iter()
+      .map(|settlement| {
+        Ok(MedicalAppointmentResponse::new(
+          settlement,
+          offices_by_id
+            .get(&settlement.appointment.practitioner_office_id)
+            .ok_or(UnexpectedError::ShouldNotHappen)?,
+        ))
+      })
+      .collect::<Result<Vec<_>, MyErrors>>()?;
 
   Ok(Json(medical_appointments))
 }
+
+#[debug_handler]
+pub async fn export(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(patient_id): Path<i32>,
+) -> Result<impl IntoResponse, MyErrors> {
+  let patient = patients::Entity::find_active_by_id(patient_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Export)
+    .await
+    .run_complete()?;
+
+  let archive = services::patient_export::export_archive(&patient, &current_user).await?;
+
+  Ok((
+    [
+      (header::CONTENT_TYPE, "application/zip".to_string()),
+      (
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", archive.filename),
+      ),
+    ],
+    archive.data,
+  ))
+}
+
+#[debug_handler]
+pub async fn duplicates(
+  State(_state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<Vec<DuplicateGroupResponse>>, MyErrors> {
+  let groups = services::patient_dedup::find_duplicates(&current_user).await?;
+
+  let mut authorize = authorize.authenticated_user();
+  for patient in groups.iter().flat_map(|group| &group.patients) {
+    authorize = authorize.record_access(patient, AuditAction::Search).await;
+  }
+  authorize.run_complete()?;
+
+  Ok(Json(
+    groups.iter().map(DuplicateGroupResponse::new).collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn merge(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(patient_id): Path<i32>,
+  Json(params): Json<MergePatientParams>,
+) -> Result<Json<PatientResponse>, MyErrors> {
+  let survivor = patients::Entity::find_active_by_id(patient_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  let duplicate = patients::Entity::find_active_by_id(params.duplicate_patient_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&survivor, AuditAction::Update)
+    .await
+    .user_accessing_resource(&duplicate, AuditAction::Delete)
+    .await
+    .run_complete()?;
+
+  let merged = services::patient_dedup::merge(&survivor, duplicate).await?;
+
+  Ok(Json(PatientResponse::new(&merged)))
+}
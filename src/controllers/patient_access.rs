@@ -0,0 +1,147 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::status,
+  Json,
+};
+use sea_orm::EntityTrait;
+use serde::Deserialize;
+
+use crate::{
+  app_state::{AppState, WorkerJob},
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::{
+      patient_accesses,
+      sea_orm_active_enums::{AuditAction, PatientAccessLevel},
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+  services,
+  views::patient_access::PatientAccessResponse,
+};
+
+#[derive(Deserialize)]
+pub struct RequestAccessParams {
+  pub ssn: String,
+  pub level: PatientAccessLevel,
+}
+
+#[derive(Deserialize)]
+pub struct GrantAccessParams {
+  pub level: PatientAccessLevel,
+}
+
+async fn find_access(
+  state: &AppState,
+  access_id: i32,
+) -> Result<patient_accesses::Model, MyErrors> {
+  Ok(
+    patient_accesses::Entity::find_by_id(access_id)
+      .one(&state.db)
+      .await?
+      .ok_or(ApplicationError::NotFound)?,
+  )
+}
+
+#[debug_handler]
+pub async fn request_access(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(params): Json<RequestAccessParams>,
+) -> Result<status::StatusCode, MyErrors> {
+  let requests =
+    services::patient_sharing::request_access(&current_user, &params.ssn, params.level).await?;
+
+  let mut authorize = authorize.authenticated_user();
+  for request in &requests {
+    authorize = authorize
+      .record_access(&request.access, AuditAction::Create)
+      .await;
+  }
+  authorize.run_complete()?;
+
+  for request in &requests {
+    for email_args in services::patient_sharing::access_request_emails(
+      request,
+      &current_user,
+      &state.config.app.base_url,
+    ) {
+      state
+        .worker_transmitter
+        .send(WorkerJob::Email(email_args))
+        .await?;
+    }
+  }
+
+  // Same answer whether or not the number matches a patient of another
+  // practitioner, so that it cannot be used to probe their patient base
+  Ok(status::StatusCode::ACCEPTED)
+}
+
+#[debug_handler]
+pub async fn list(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<Vec<PatientAccessResponse>>, MyErrors> {
+  let accesses = services::patient_sharing::accesses_for_owner(&current_user).await?;
+
+  Ok(Json(
+    accesses
+      .iter()
+      .map(|(access, patient, requester)| PatientAccessResponse::new(access, patient, requester))
+      .collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn grant(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(access_id): Path<i32>,
+  Json(params): Json<GrantAccessParams>,
+) -> Result<status::StatusCode, MyErrors> {
+  let access = find_access(&state, access_id).await?;
+
+  authorize
+    .user_accessing_resource(&access, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  services::patient_sharing::grant(access, params.level).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn revoke(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(access_id): Path<i32>,
+) -> Result<status::StatusCode, MyErrors> {
+  let access = find_access(&state, access_id).await?;
+
+  authorize
+    .user_accessing_resource(&access, AuditAction::Delete)
+    .await
+    .run_complete()?;
+
+  services::patient_sharing::revoke(access).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn consent(
+  State(_state): State<AppState>,
+  authorize: AuthStatement,
+  Path(token): Path<String>,
+) -> Result<status::StatusCode, MyErrors> {
+  authorize.non_authenticated_user().run_complete()?;
+
+  services::patient_sharing::consent(&token).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,95 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::status,
+  Json,
+};
+use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, QueryFilter};
+
+use crate::{
+  app_state::AppState,
+  auth::statement::AuthStatement,
+  models::{
+    _entities::{patient_insurances, patients, sea_orm_active_enums::AuditAction},
+    my_errors::{application_error::ApplicationError, MyErrors},
+    patient_insurances::CreatePatientInsuranceParams,
+  },
+  views::patient_insurance::PatientInsuranceResponse,
+};
+
+async fn find_patient(state: &AppState, patient_id: i32) -> Result<patients::Model, MyErrors> {
+  Ok(
+    patients::Entity::find_active_by_id(patient_id)
+      .one(&state.db)
+      .await?
+      .ok_or(ApplicationError::NotFound)?,
+  )
+}
+
+#[debug_handler]
+pub async fn list(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(patient_id): Path<i32>,
+) -> Result<Json<Vec<PatientInsuranceResponse>>, MyErrors> {
+  let patient = find_patient(&state, patient_id).await?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Read)
+    .await
+    .run_complete()?;
+
+  let insurances = patient_insurances::Entity::find_for_patient(patient.id)
+    .all(&state.db)
+    .await?;
+
+  Ok(Json(
+    insurances
+      .iter()
+      .map(PatientInsuranceResponse::new)
+      .collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn create(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path(patient_id): Path<i32>,
+  Json(params): Json<CreatePatientInsuranceParams>,
+) -> Result<Json<PatientInsuranceResponse>, MyErrors> {
+  let patient = find_patient(&state, patient_id).await?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  let insurance = patient_insurances::ActiveModel::create(&state.db, &params, patient.id).await?;
+
+  Ok(Json(PatientInsuranceResponse::new(&insurance)))
+}
+
+#[debug_handler]
+pub async fn delete(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path((patient_id, insurance_id)): Path<(i32, i32)>,
+) -> Result<status::StatusCode, MyErrors> {
+  let patient = find_patient(&state, patient_id).await?;
+
+  authorize
+    .user_accessing_resource(&patient, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  let insurance = patient_insurances::Entity::find_by_id(insurance_id)
+    .filter(patient_insurances::Column::PatientId.eq(patient.id))
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  insurance.delete(&state.db).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,101 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::status,
+  Json,
+};
+use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, QueryFilter};
+
+use crate::{
+  app_state::AppState,
+  auth::statement::AuthStatement,
+  models::{
+    _entities::{medical_appointments, payments, sea_orm_active_enums::AuditAction},
+    my_errors::{application_error::ApplicationError, MyErrors},
+    payments::CreatePaymentParams,
+  },
+  services,
+  views::payment::PaymentResponse,
+};
+
+async fn find_appointment(
+  state: &AppState,
+  patient_id: i32,
+  appointment_id: i32,
+) -> Result<medical_appointments::Model, MyErrors> {
+  Ok(
+    medical_appointments::Entity::find_by_id(appointment_id)
+      .filter(medical_appointments::Column::PatientId.eq(patient_id))
+      .one(&state.db)
+      .await?
+      .ok_or(ApplicationError::NotFound)?,
+  )
+}
+
+#[debug_handler]
+pub async fn list(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path((patient_id, appointment_id)): Path<(i32, i32)>,
+) -> Result<Json<Vec<PaymentResponse>>, MyErrors> {
+  let appointment = find_appointment(&state, patient_id, appointment_id).await?;
+
+  authorize
+    .user_accessing_resource(&appointment, AuditAction::Read)
+    .await
+    .run_complete()?;
+
+  let payments = payments::Entity::find_for_appointment(appointment.id)
+    .all(&state.db)
+    .await?;
+
+  Ok(Json(payments.iter().map(PaymentResponse::new).collect()))
+}
+
+#[debug_handler]
+pub async fn create(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path((patient_id, appointment_id)): Path<(i32, i32)>,
+  Json(params): Json<CreatePaymentParams>,
+) -> Result<Json<PaymentResponse>, MyErrors> {
+  let appointment = find_appointment(&state, patient_id, appointment_id).await?;
+
+  authorize
+    .user_accessing_resource(&appointment, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  let payment = services::payments::record(&appointment, &params).await?;
+
+  Ok(Json(PaymentResponse::new(&payment)))
+}
+
+#[debug_handler]
+pub async fn delete(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  Path((patient_id, appointment_id, payment_id)): Path<(i32, i32, i32)>,
+) -> Result<status::StatusCode, MyErrors> {
+  let appointment = find_appointment(&state, patient_id, appointment_id).await?;
+
+  authorize
+    .user_accessing_resource(&appointment, AuditAction::Update)
+    .await
+    .run_complete()?;
+
+  let payment = payments::Entity::find_by_id(payment_id)
+    .filter(payments::Column::MedicalAppointmentId.eq(appointment.id))
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  // A check handed to the bank is listed on a printed slip, it cannot vanish
+  if payment.check_deposit_id.is_some() {
+    return Err(ApplicationError::new("payment_already_deposited").into());
+  }
+
+  payment.delete(&state.db).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
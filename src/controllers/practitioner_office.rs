@@ -3,6 +3,7 @@ use axum::{
   extract::{Path, State},
   Json,
 };
+use chrono::NaiveDate;
 use sea_orm::{prelude::Decimal, EntityTrait, IntoActiveModel, ModelTrait};
 use serde::Deserialize;
 
@@ -11,17 +12,43 @@ use crate::{
   auth::statement::AuthStatement,
   middleware::auth::AuthenticatedUser,
   models::{
-    _entities::practitioner_offices,
+    _entities::{
+      practitioner_offices,
+      sea_orm_active_enums::{AuditAction, RetrocessionKind},
+    },
     my_errors::{application_error::ApplicationError, MyErrors},
     practitioner_offices::PractitionerOfficeParams,
+    revenue_share_rates::{self, RetrocessionTerms},
   },
   services,
+  views::practitioner_office::RevenueShareRateResponse,
 };
 
 #[derive(Deserialize)]
 pub struct OfficeParams {
   pub office: PractitionerOfficeParams,
   pub revenue_share_percentage: Decimal,
+  /// Percentage of the fees by default
+  pub retrocession_kind: Option<RetrocessionKind>,
+  /// Monthly fee, fee per appointment or monthly cap, depending on the kind
+  pub retrocession_amount_in_cents: Option<i32>,
+  /// `YYYY-MM-DD` day from which the terms apply, today by default
+  pub revenue_share_valid_from: Option<String>,
+}
+
+impl OfficeParams {
+  fn terms(&self) -> Result<RetrocessionTerms, MyErrors> {
+    let terms = match self.retrocession_kind.clone() {
+      Some(kind) => RetrocessionTerms {
+        kind,
+        percentage: self.revenue_share_percentage,
+        amount_in_cents: self.retrocession_amount_in_cents,
+      },
+      None => RetrocessionTerms::percentage(self.revenue_share_percentage),
+    };
+    terms.validate()?;
+    Ok(terms)
+  }
 }
 
 #[debug_handler]
@@ -30,18 +57,9 @@ pub async fn create(
   AuthenticatedUser(current_user, _): AuthenticatedUser,
   Json(params): Json<OfficeParams>,
 ) -> Result<Json<serde_json::Value>, MyErrors> {
-  if params.revenue_share_percentage < Decimal::ZERO
-    || params.revenue_share_percentage > Decimal::ONE_HUNDRED
-  {
-    return Err(ApplicationError::BadRequest.into());
-  }
+  let terms = params.terms()?;
 
-  services::practitioner_office::create(
-    &params.office,
-    &current_user,
-    params.revenue_share_percentage,
-  )
-  .await?;
+  services::practitioner_office::create(&params.office, &current_user, terms).await?;
 
   Ok(Json(serde_json::json!({ "success": true })))
 }
@@ -60,27 +78,55 @@ pub async fn update(
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&office)
+    .user_accessing_resource(&office, AuditAction::Update)
     .await
     .run_complete()?;
 
-  if params.revenue_share_percentage < Decimal::ZERO
-    || params.revenue_share_percentage > Decimal::ONE_HUNDRED
-  {
-    return Err(ApplicationError::BadRequest.into());
-  }
+  let terms = params.terms()?;
+
+  let revenue_share_valid_from = match params.revenue_share_valid_from.as_deref() {
+    Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")?,
+    None => chrono::Local::now().date_naive(),
+  };
 
   services::practitioner_office::update(
     office.into_active_model(),
     &params.office,
     &current_user,
-    params.revenue_share_percentage,
+    &terms,
+    revenue_share_valid_from,
   )
   .await?;
 
   Ok(Json(serde_json::json!({ "success": true })))
 }
 
+#[debug_handler]
+pub async fn revenue_share_rates(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(office_id): Path<i32>,
+) -> Result<Json<Vec<RevenueShareRateResponse>>, MyErrors> {
+  let office = practitioner_offices::Entity::find_by_id(office_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&office, AuditAction::Read)
+    .await
+    .run_complete()?;
+
+  let rates = revenue_share_rates::Entity::find_for_office(current_user.id, office.id)
+    .all(&state.db)
+    .await?;
+
+  Ok(Json(
+    rates.iter().map(RevenueShareRateResponse::new).collect(),
+  ))
+}
+
 #[debug_handler]
 pub async fn destroy(
   State(state): State<AppState>,
@@ -93,7 +139,7 @@ pub async fn destroy(
     .ok_or(ApplicationError::NotFound)?;
 
   authorize
-    .user_owning_resource(&office)
+    .user_accessing_resource(&office, AuditAction::Delete)
     .await
     .run_complete()?;
 
@@ -0,0 +1,169 @@
+use axum::{
+  debug_handler,
+  extract::{Path, State},
+  http::header,
+  response::IntoResponse,
+  Json,
+};
+use sea_orm::EntityTrait;
+use serde::Deserialize;
+
+use crate::{
+  app_state::{AppState, WorkerJob},
+  auth::statement::AuthStatement,
+  middleware::auth::AuthenticatedUser,
+  models::{
+    _entities::{practitioner_offices, retrocession_statements, sea_orm_active_enums::AuditAction},
+    my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
+  },
+  services::{self, retrocession::StatementDocument},
+  views::retrocession_statement::RetrocessionStatementResponse,
+};
+
+#[derive(Deserialize)]
+pub struct CreateRetrocessionStatementParams {
+  /// `YYYY-MM`
+  pub month: String,
+}
+
+async fn find_office(
+  state: &AppState,
+  authorize: AuthStatement,
+  office_id: i32,
+  action: AuditAction,
+) -> Result<practitioner_offices::Model, MyErrors> {
+  let office = practitioner_offices::Entity::find_by_id(office_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&office, action)
+    .await
+    .run_complete()?;
+
+  Ok(office)
+}
+
+async fn find_statement(
+  state: &AppState,
+  authorize: AuthStatement,
+  statement_id: i32,
+  action: AuditAction,
+) -> Result<retrocession_statements::Model, MyErrors> {
+  let statement = retrocession_statements::Entity::find_by_id(statement_id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  authorize
+    .user_accessing_resource(&statement, action)
+    .await
+    .run_complete()?;
+
+  Ok(statement)
+}
+
+fn download(document: StatementDocument, content_type: &str) -> impl IntoResponse {
+  (
+    [
+      (header::CONTENT_TYPE, content_type.to_string()),
+      (
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", document.filename),
+      ),
+    ],
+    document.data,
+  )
+}
+
+#[debug_handler]
+pub async fn list(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(office_id): Path<i32>,
+) -> Result<Json<Vec<RetrocessionStatementResponse>>, MyErrors> {
+  let office = find_office(&state, authorize, office_id, AuditAction::Read).await?;
+
+  let statements = services::retrocession::statements_for_office(&current_user, office.id).await?;
+
+  Ok(Json(
+    statements
+      .iter()
+      .map(RetrocessionStatementResponse::new)
+      .collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn create(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(office_id): Path<i32>,
+  Json(params): Json<CreateRetrocessionStatementParams>,
+) -> Result<Json<RetrocessionStatementResponse>, MyErrors> {
+  let office = find_office(&state, authorize, office_id, AuditAction::Create).await?;
+
+  let statement = services::retrocession::generate(&current_user, office.id, &params.month).await?;
+
+  Ok(Json(RetrocessionStatementResponse::new(&statement)))
+}
+
+#[debug_handler]
+pub async fn pdf(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(statement_id): Path<i32>,
+) -> Result<impl IntoResponse, MyErrors> {
+  let statement = find_statement(&state, authorize, statement_id, AuditAction::Export).await?;
+
+  let detail = services::retrocession::detail(statement, &current_user).await?;
+  let document = services::retrocession::pdf(&detail, &current_user).await?;
+
+  Ok(download(document, "application/pdf"))
+}
+
+#[debug_handler]
+pub async fn spreadsheet(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(statement_id): Path<i32>,
+) -> Result<impl IntoResponse, MyErrors> {
+  let statement = find_statement(&state, authorize, statement_id, AuditAction::Export).await?;
+
+  let detail = services::retrocession::detail(statement, &current_user).await?;
+  let document = services::retrocession::spreadsheet(&detail)?;
+
+  Ok(download(
+    document,
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+  ))
+}
+
+#[debug_handler]
+pub async fn send(
+  State(state): State<AppState>,
+  authorize: AuthStatement,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Path(statement_id): Path<i32>,
+) -> Result<Json<RetrocessionStatementResponse>, MyErrors> {
+  let statement = find_statement(&state, authorize, statement_id, AuditAction::Export).await?;
+
+  let detail = services::retrocession::detail(statement, &current_user).await?;
+  let email_args = services::retrocession::statement_email(&detail, &current_user).await?;
+  let sent_to = email_args.to.clone();
+
+  state
+    .worker_transmitter
+    .send(WorkerJob::Email(email_args))
+    .await
+    .map_err(|_| UnexpectedError::ShouldNotHappen)?;
+
+  let statement = services::retrocession::mark_as_sent(detail.statement, sent_to).await?;
+
+  Ok(Json(RetrocessionStatementResponse::new(&statement)))
+}
@@ -0,0 +1,43 @@
+use axum::{
+  debug_handler,
+  extract::{Query, State},
+  Json,
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{
+  app_state::AppState,
+  middleware::auth::AuthenticatedUser,
+  models::my_errors::{application_error::ApplicationError, MyErrors},
+  services,
+  views::stats::ActivityStatsResponse,
+};
+
+#[derive(Deserialize)]
+pub struct StatsParams {
+  start_date: String,
+  end_date: String,
+}
+
+#[debug_handler]
+pub async fn index(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<StatsParams>,
+) -> Result<Json<ActivityStatsResponse>, MyErrors> {
+  let start_date = NaiveDate::parse_from_str(params.start_date.as_str(), "%Y-%m-%d")?;
+  let end_date = NaiveDate::parse_from_str(params.end_date.as_str(), "%Y-%m-%d")?;
+
+  if start_date > end_date {
+    return Err(ApplicationError::new("start_date_before_end_date").into());
+  }
+
+  let stats = services::stats::activity(&current_user, start_date, end_date).await?;
+
+  Ok(Json(ActivityStatsResponse::new(
+    params.start_date,
+    params.end_date,
+    &stats,
+  )))
+}
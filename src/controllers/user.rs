@@ -2,18 +2,30 @@ use crate::{
   app_state::{AppState, WorkerJob},
   middleware::auth::AuthenticatedUser,
   models::{
-    _entities::prelude::UserBusinessInformations,
+    _entities::{
+      prelude::UserBusinessInformations,
+      sea_orm_active_enums::{ExportFormat, Locale},
+    },
+    accounting_exports,
+    invoice_templates::{self, InvoiceTemplate},
     my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
+    scheduled_exports::{self, ScheduledExportParams},
     user_business_informations::CreateBusinessInformation,
   },
   services::{self, storage::StorageService},
-  views::practitioner_office::PractitionerOffice,
+  views::{
+    audit_log::AuditLogResponse,
+    practitioner_office::PractitionerOffice,
+    receivables::{PayerReceivableResponse, UnpaidBalanceResponse},
+    user::{InvoiceTemplateResponse, ScheduledExportResponse},
+  },
   workers::appointments_export,
 };
 use axum::{
   debug_handler,
-  extract::{Multipart, State},
-  http::status,
+  extract::{Multipart, Query, State},
+  http::{header, status},
+  response::IntoResponse,
   Json,
 };
 use chrono::NaiveDate;
@@ -25,6 +37,40 @@ use serde::Deserialize;
 pub struct ExtractMedicalAppointmentsParams {
   start_date: String,
   end_date: String,
+  format: Option<ExportFormat>,
+}
+
+impl ExtractMedicalAppointmentsParams {
+  fn period(&self) -> Result<(NaiveDate, NaiveDate), MyErrors> {
+    let start_date = NaiveDate::parse_from_str(self.start_date.as_str(), "%Y-%m-%d")?;
+    let end_date = NaiveDate::parse_from_str(self.end_date.as_str(), "%Y-%m-%d")?;
+
+    if start_date >= end_date {
+      return Err(ApplicationError::new("start_date_before_end_date").into());
+    }
+
+    Ok((start_date, end_date))
+  }
+
+  fn format(&self) -> ExportFormat {
+    self.format.clone().unwrap_or(ExportFormat::Xlsx)
+  }
+}
+
+#[derive(Deserialize)]
+pub struct ReceivablesParams {
+  start_date: String,
+  end_date: String,
+}
+
+#[derive(Deserialize)]
+pub struct AuditLogsParams {
+  page: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct LocaleParams {
+  locale: Locale,
 }
 
 #[debug_handler]
@@ -38,6 +84,20 @@ pub async fn save_business_info(
   Ok(Json(serde_json::json!({ "success": true })))
 }
 
+#[debug_handler]
+pub async fn save_locale(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(params): Json<LocaleParams>,
+) -> Result<Json<serde_json::Value>, MyErrors> {
+  current_user
+    .into_active_model()
+    .set_locale(&state.db, params.locale)
+    .await?;
+
+  Ok(Json(serde_json::json!({ "success": true })))
+}
+
 #[debug_handler]
 pub async fn my_offices(
   State(state): State<AppState>,
@@ -59,17 +119,15 @@ pub async fn extract_medical_appointments(
   AuthenticatedUser(current_user, _): AuthenticatedUser,
   Json(params): Json<ExtractMedicalAppointmentsParams>,
 ) -> Result<status::StatusCode, MyErrors> {
-  let start_date = NaiveDate::parse_from_str(params.start_date.as_str(), "%Y-%m-%d")?;
-  let end_date = NaiveDate::parse_from_str(params.end_date.as_str(), "%Y-%m-%d")?;
-
-  if start_date >= end_date {
-    return Err(ApplicationError::new("start_date_before_end_date").into());
-  }
+  let (start_date, end_date) = params.period()?;
 
   let args = appointments_export::Args {
+    recipient: current_user.email.clone(),
     user: current_user,
     start_date,
     end_date,
+    format: params.format(),
+    scheduled_export_id: None,
   };
 
   state
@@ -80,6 +138,179 @@ pub async fn extract_medical_appointments(
   Ok(status::StatusCode::NO_CONTENT)
 }
 
+/// Same export as `extract_medical_appointments`, handed over right away
+/// instead of by email
+#[debug_handler]
+pub async fn download_medical_appointments(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<ExtractMedicalAppointmentsParams>,
+) -> Result<impl IntoResponse, MyErrors> {
+  let (start_date, end_date) = params.period()?;
+
+  let export = services::exporters::export_appointments(
+    &state.db,
+    &current_user,
+    start_date,
+    end_date,
+    &params.format(),
+  )
+  .await?;
+
+  // Revenue share rates of the exported days can no longer change
+  accounting_exports::ActiveModel::create(&state.db, current_user.id, start_date, end_date).await?;
+
+  Ok((
+    [
+      (header::CONTENT_TYPE, export.content_type.to_string()),
+      (
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", export.filename),
+      ),
+    ],
+    export.data,
+  ))
+}
+
+#[debug_handler]
+pub async fn scheduled_export(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<ScheduledExportResponse>, MyErrors> {
+  let scheduled_export = scheduled_exports::Entity::find_for_user(current_user.id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  Ok(Json(ScheduledExportResponse::new(&scheduled_export)))
+}
+
+#[debug_handler]
+pub async fn save_scheduled_export(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(params): Json<ScheduledExportParams>,
+) -> Result<Json<ScheduledExportResponse>, MyErrors> {
+  let scheduled_export = services::scheduled_exports::save(
+    &state.db,
+    &current_user,
+    &params,
+    chrono::Local::now().date_naive(),
+  )
+  .await?;
+
+  Ok(Json(ScheduledExportResponse::new(&scheduled_export)))
+}
+
+#[debug_handler]
+pub async fn delete_scheduled_export(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<status::StatusCode, MyErrors> {
+  scheduled_exports::Entity::find_for_user(current_user.id)
+    .one(&state.db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?
+    .delete(&state.db)
+    .await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
+/// Template with the public URL of its logo, the default template when the
+/// practitioner never customised it
+fn invoice_template_response(
+  stored: Option<invoice_templates::Model>,
+) -> Result<InvoiceTemplateResponse, MyErrors> {
+  let Some(stored) = stored else {
+    return Ok(InvoiceTemplateResponse::new(
+      InvoiceTemplate::default(),
+      None,
+    ));
+  };
+
+  let logo_url = match &stored.logo_file_name {
+    Some(logo_file_name) => Some(StorageService::new()?.file_url(logo_file_name)),
+    None => None,
+  };
+
+  Ok(InvoiceTemplateResponse::new(stored.template(), logo_url))
+}
+
+#[debug_handler]
+pub async fn invoice_template(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<InvoiceTemplateResponse>, MyErrors> {
+  let stored = invoice_templates::Entity::find_for_user(current_user.id)
+    .one(&state.db)
+    .await?;
+
+  Ok(Json(invoice_template_response(stored)?))
+}
+
+#[debug_handler]
+pub async fn save_invoice_template(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Json(template): Json<InvoiceTemplate>,
+) -> Result<Json<InvoiceTemplateResponse>, MyErrors> {
+  let stored =
+    invoice_templates::ActiveModel::save_template(&state.db, current_user.id, &template).await?;
+
+  Ok(Json(invoice_template_response(Some(stored))?))
+}
+
+#[debug_handler]
+pub async fn upload_invoice_logo(
+  State(state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  mut multipart: Multipart,
+) -> Result<status::StatusCode, MyErrors> {
+  let field = multipart
+    .next_field()
+    .await
+    .map_err(|_| ApplicationError::BadRequest)?
+    .ok_or(ApplicationError::BadRequest)?;
+
+  if field.name() != Some("logo") {
+    return Err(ApplicationError::BadRequest.into());
+  }
+
+  let logo_data = field
+    .bytes()
+    .await
+    .map_err(|_| ApplicationError::UnprocessableEntity)?;
+
+  let img = image::load_from_memory(&logo_data).map_err(|e| {
+    tracing::error!("Failed to load image: {}", e);
+    ApplicationError::UnprocessableEntity
+  })?;
+
+  // Unlike signatures, logos keep their proportions
+  let resized = img.resize(600, 300, FilterType::Lanczos3);
+
+  let mut png_bytes: Vec<u8> = Vec::new();
+  resized
+    .write_to(&mut std::io::Cursor::new(&mut png_bytes), ImageFormat::Png)
+    .map_err(|e| {
+      tracing::error!("Failed to encode image: {}", e);
+      ApplicationError::UnprocessableEntity
+    })?;
+
+  // A new name on every upload, so that cached copies of the previous logo
+  // are never served
+  let filename = format!("logo_{}_{}", current_user.id, uuid::Uuid::new_v4());
+
+  StorageService::new()?
+    .upload_file(&png_bytes, &filename, "image/png")
+    .await?;
+
+  invoice_templates::ActiveModel::set_logo(&state.db, current_user.id, Some(filename)).await?;
+
+  Ok(status::StatusCode::NO_CONTENT)
+}
+
 #[debug_handler]
 pub async fn get_signature_url(
   State(_state): State<AppState>,
@@ -91,7 +322,7 @@ pub async fn get_signature_url(
     .signature_file_name
     .ok_or(UnexpectedError::ShouldNotHappen)?;
 
-  Ok(storage.signature_url(&signature_filename))
+  Ok(storage.file_url(&signature_filename))
 }
 
 #[debug_handler]
@@ -140,7 +371,7 @@ pub async fn upload_signature(
 
   let storage_service = services::storage::StorageService::new()?;
   storage_service
-    .upload_signature(&png_bytes, &filename, "image/png")
+    .upload_file(&png_bytes, &filename, "image/png")
     .await?;
 
   let mut business_information = current_user
@@ -155,3 +386,62 @@ pub async fn upload_signature(
 
   Ok(status::StatusCode::NO_CONTENT)
 }
+
+#[debug_handler]
+pub async fn audit_logs(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<AuditLogsParams>,
+) -> Result<Json<serde_json::Value>, MyErrors> {
+  let page = params.page.unwrap_or(1).max(1);
+
+  let (audit_logs, total_pages) = services::audit::paginated_for_user(&current_user, page).await?;
+
+  let audit_log_responses: Vec<AuditLogResponse> =
+    audit_logs.iter().map(AuditLogResponse::new).collect();
+
+  Ok(Json(serde_json::json!({
+    "paginated_data": audit_log_responses,
+    "pagination": {
+      "page": page,
+      "total_pages": total_pages,
+      "has_more": page < total_pages
+    }
+  })))
+}
+
+#[debug_handler]
+pub async fn receivables(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+  Query(params): Query<ReceivablesParams>,
+) -> Result<Json<Vec<PayerReceivableResponse>>, MyErrors> {
+  let start_date = NaiveDate::parse_from_str(params.start_date.as_str(), "%Y-%m-%d")?;
+  let end_date = NaiveDate::parse_from_str(params.end_date.as_str(), "%Y-%m-%d")?;
+
+  if start_date >= end_date {
+    return Err(ApplicationError::new("start_date_before_end_date").into());
+  }
+
+  let receivables =
+    services::receivables::outstanding_by_payer(&current_user, start_date, end_date).await?;
+
+  Ok(Json(
+    receivables
+      .iter()
+      .map(PayerReceivableResponse::new)
+      .collect(),
+  ))
+}
+
+#[debug_handler]
+pub async fn unpaid_balances(
+  State(_state): State<AppState>,
+  AuthenticatedUser(current_user, _): AuthenticatedUser,
+) -> Result<Json<Vec<UnpaidBalanceResponse>>, MyErrors> {
+  let balances = services::payments::unpaid_balances(&current_user).await?;
+
+  Ok(Json(
+    balances.iter().map(UnpaidBalanceResponse::new).collect(),
+  ))
+}
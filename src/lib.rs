@@ -3,6 +3,7 @@ pub mod auth;
 pub mod config;
 pub mod controllers;
 pub mod initializers;
+pub mod locales;
 pub mod middleware;
 pub mod models;
 pub mod router;
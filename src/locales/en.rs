@@ -0,0 +1,57 @@
+use super::Messages;
+
+pub static MESSAGES: Messages = Messages {
+  date_format: "%d/%m/%Y",
+  amount: "€{}",
+
+  invoice_title: "Paid fee note",
+  adeli_number: "Adeli No.:",
+  rpps_number: "RPPS No.:",
+  siret_number: "SIRET No.:",
+  phone_number: "Phone:",
+  received_from: "Received from:",
+  birth_date: "Born on:",
+  ssn: "Social security number:",
+  address: "Address:",
+  act: "Procedure:",
+  fee: "Fee:",
+  made_at: "{}, {}",
+
+  invoice_filename: "{} Fee note - {} {}.pdf",
+  invoice_email_subject: "Fee note {}",
+  invoice_email_body: "Please find attached your invoice for the consultation of {}",
+
+  export_email_subject: "Your appointments from {} to {}",
+  export_email_body: "Hello,\n\nPlease find attached all your appointments of the selected period",
+
+  statement_subject: "Retrocession statement {} - {}",
+  statement_title: "Retrocession statement - {}",
+  statement_sheet_name: "Retrocession {}",
+  statement_practitioner: "Practitioner: {}",
+  statement_office: "Office: {}",
+  statement_period: "Period from {} to {}",
+  statement_date: "Date",
+  statement_appointments: "Appointments",
+  statement_fees: "Fees",
+  statement_appointments_count: "Number of appointments",
+  statement_total_fees: "Total fees received",
+  statement_terms: "Retrocession terms",
+  statement_amount_due: "Amount due to the office holder",
+  statement_email_body: "Hello,\n\nPlease find attached the retrocession statement of {} for the office {}.\nAmount due: {}",
+  percentage_terms: "{}%",
+  capped_percentage_terms: "{}% capped at {} per month",
+  fixed_monthly_fee_terms: "Flat fee of {} per month",
+  per_appointment_fee_terms: "{} per appointment",
+
+  access_request_subject: "Patient record access request",
+  access_request_body: "Hello,\n\n{} is asking for a {} access to the record of your patient {}.\nYou can accept or decline this request from your account: {}",
+  patient_consent_subject: "Sharing of your patient record",
+  patient_consent_body: "Hello,\n\n{} would like a {} access to your record, currently followed by {}.\nIf you consent, follow this link within {} days: {}",
+  read_access: "read",
+  write_access: "read and write",
+
+  password_reset_subject: "Password reset",
+  password_reset_body: "Hello,\n\nHere is the link to reset your password: {}",
+  access_key_subject: "Your OpenCab access code",
+  access_key_body: "Hello,\n\nHere is your access code to the OpenCab platform: {}\nYou can use it right after logging in: {}",
+};
@@ -0,0 +1,58 @@
+use super::Messages;
+
+pub static MESSAGES: Messages = Messages {
+  date_format: "%d/%m/%Y",
+  amount: "{}€",
+
+  invoice_title: "Note d'honoraires acquittée",
+  adeli_number: "N° Adeli :",
+  rpps_number: "N°RPPS :",
+  siret_number: "N°SIRET :",
+  phone_number: "Tel :",
+  received_from: "Reçu de :",
+  birth_date: "Né(e) le :",
+  ssn: "Numéro de sécurité sociale :",
+  address: "Adresse :",
+  act: "Acte :",
+  fee: "Honoraire :",
+  made_at: "Fait à {}, le {}",
+
+  invoice_filename: "{} Note d'honoraires - {} {}.pdf",
+  invoice_email_subject: "Note d'honoraires {}",
+  invoice_email_body: "Vous trouverez ci-joint votre facture pour la consultation du {}",
+
+  export_email_subject: "Vos RDV du {} au {}",
+  export_email_body:
+    "Bonjour,\n\nVous trouverez tous vos rendez-vous de la période sélectionnée en pièce jointe",
+
+  statement_subject: "Relevé de rétrocession {} - {}",
+  statement_title: "Relevé de rétrocession - {}",
+  statement_sheet_name: "Rétrocession {}",
+  statement_practitioner: "Praticien : {}",
+  statement_office: "Cabinet : {}",
+  statement_period: "Période du {} au {}",
+  statement_date: "Date",
+  statement_appointments: "Consultations",
+  statement_fees: "Honoraires",
+  statement_appointments_count: "Nombre de consultations",
+  statement_total_fees: "Total des honoraires encaissés",
+  statement_terms: "Conditions de rétrocession",
+  statement_amount_due: "Montant dû au titulaire",
+  statement_email_body: "Bonjour,\n\nVous trouverez ci-joint le relevé de rétrocession du mois {} pour le cabinet {}.\nMontant dû : {}",
+  percentage_terms: "{} %",
+  capped_percentage_terms: "{} % plafonné à {} par mois",
+  fixed_monthly_fee_terms: "Forfait de {} par mois",
+  per_appointment_fee_terms: "{} par consultation",
+
+  access_request_subject: "Demande d'accès à un dossier patient",
+  access_request_body: "Bonjour,\n\n{} demande un accès {} au dossier de votre patient {}.\nVous pouvez accepter ou refuser cette demande depuis votre espace: {}",
+  patient_consent_subject: "Partage de votre dossier patient",
+  patient_consent_body: "Bonjour,\n\n{} souhaite accéder {} à votre dossier, actuellement suivi par {}.\nSi vous y consentez, suivez ce lien dans les {} jours: {}",
+  read_access: "en lecture",
+  write_access: "en lecture et écriture",
+
+  password_reset_subject: "Réinitialisation du mot de passe",
+  password_reset_body: "Bonjour,\n\nVoici le lien pour réinitialiser votre mot de passe: {}",
+  access_key_subject: "Votre code d'accès à OpenCab",
+  access_key_body: "Bonjour,\n\nVoici votre code d'accès à la plateforme OpenCab: {}\nVous pouvez l'utiliser juste après vous être connecté: {}",
+};
@@ -0,0 +1,112 @@
+//! Catalogue of the texts of invoices and emails, one `Messages` per locale.
+//! Templates hold `{}` placeholders, filled in order by [`fill`].
+
+mod en;
+mod fr;
+
+use crate::models::_entities::sea_orm_active_enums::Locale;
+
+pub struct Messages {
+  /// `chrono` format of the dates printed in invoices and emails
+  pub date_format: &'static str,
+  /// Amount in euros, e.g. `50.00€`
+  pub amount: &'static str,
+
+  // Invoice PDF
+  pub invoice_title: &'static str,
+  pub adeli_number: &'static str,
+  pub rpps_number: &'static str,
+  pub siret_number: &'static str,
+  pub phone_number: &'static str,
+  pub received_from: &'static str,
+  pub birth_date: &'static str,
+  pub ssn: &'static str,
+  pub address: &'static str,
+  pub act: &'static str,
+  pub fee: &'static str,
+  /// City, then date
+  pub made_at: &'static str,
+
+  // Invoice email, sent to the patient
+  /// Practitioner's and patient's names, then date
+  pub invoice_filename: &'static str,
+  /// Date
+  pub invoice_email_subject: &'static str,
+  /// Date, the practitioner's signature follows
+  pub invoice_email_body: &'static str,
+
+  // Appointments export email, sent to the practitioner or their accountant
+  /// Start, then end of the period
+  pub export_email_subject: &'static str,
+  pub export_email_body: &'static str,
+
+  // Retrocession statement, sent to the office holder
+  /// Office name, then month
+  pub statement_subject: &'static str,
+  /// Month
+  pub statement_title: &'static str,
+  /// Month, on at most 31 characters
+  pub statement_sheet_name: &'static str,
+  /// Practitioner's name
+  pub statement_practitioner: &'static str,
+  /// Office name and address
+  pub statement_office: &'static str,
+  /// First, then last day of the month
+  pub statement_period: &'static str,
+  pub statement_date: &'static str,
+  pub statement_appointments: &'static str,
+  pub statement_fees: &'static str,
+  pub statement_appointments_count: &'static str,
+  pub statement_total_fees: &'static str,
+  pub statement_terms: &'static str,
+  pub statement_amount_due: &'static str,
+  /// Month, office name, then amount due, the practitioner's signature follows
+  pub statement_email_body: &'static str,
+  /// Percentage
+  pub percentage_terms: &'static str,
+  /// Percentage, then monthly cap
+  pub capped_percentage_terms: &'static str,
+  /// Monthly fee
+  pub fixed_monthly_fee_terms: &'static str,
+  /// Fee per appointment
+  pub per_appointment_fee_terms: &'static str,
+
+  // Patient sharing emails
+  pub access_request_subject: &'static str,
+  /// Requester's name, access level, patient's name, then link to the requests
+  pub access_request_body: &'static str,
+  pub patient_consent_subject: &'static str,
+  /// Requester's name, access level, owner's name, validity in days, then
+  /// consent link
+  pub patient_consent_body: &'static str,
+  pub read_access: &'static str,
+  pub write_access: &'static str,
+
+  // Account emails
+  pub password_reset_subject: &'static str,
+  /// Reset link
+  pub password_reset_body: &'static str,
+  pub access_key_subject: &'static str,
+  /// Access key, then login URL
+  pub access_key_body: &'static str,
+}
+
+impl Locale {
+  pub fn messages(&self) -> &'static Messages {
+    match self {
+      Self::En => &en::MESSAGES,
+      Self::Fr => &fr::MESSAGES,
+    }
+  }
+}
+
+/// Replace the `{}` placeholders of `template` by `values`, in order
+pub fn fill(template: &str, values: &[&str]) -> String {
+  let mut parts = template.split("{}");
+  let mut filled = parts.next().unwrap_or_default().to_string();
+  for (index, part) in parts.enumerate() {
+    filled.push_str(values.get(index).copied().unwrap_or_default());
+    filled.push_str(part);
+  }
+  filled
+}
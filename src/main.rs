@@ -6,6 +6,7 @@ mod auth;
 mod config;
 mod controllers;
 mod initializers;
+mod locales;
 mod middleware;
 mod models;
 mod router;
@@ -46,12 +47,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
   initializers::app_services::init_services(&db);
 
   let worker_config = state.config.clone();
+  let worker_db = db.clone();
   tokio::spawn(async move {
-    workers::start_worker_pool(worker_receiver, worker_config).await;
+    workers::start_worker_pool(worker_receiver, worker_db, worker_config).await;
   });
 
   tracing::info!("Worker pool started");
 
+  let retention_config = state.config.clone();
+  let retention_db = db.clone();
+  tokio::spawn(async move {
+    workers::retention_purge::start_scheduler(retention_db, retention_config).await;
+  });
+
+  let export_scheduler_state = state.clone();
+  tokio::spawn(async move {
+    workers::export_scheduler::start_scheduler(export_scheduler_state).await;
+  });
+
   let app = router::create_router(state.clone());
 
   let addr = format!("{}:{}", config.server.binding, config.server.port);
@@ -66,10 +79,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
   );
 
   // Run server with graceful shutdown
-  axum::serve(listener, app)
-    .with_graceful_shutdown(shutdown_signal())
-    .await
-    .expect("Server error");
+  axum::serve(
+    listener,
+    app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+  )
+  .with_graceful_shutdown(shutdown_signal())
+  .await
+  .expect("Server error");
 
   Ok(())
 }
@@ -1,5 +1,7 @@
+use std::net::SocketAddr;
+
 use axum::{
-  extract::{FromRequestParts, Request},
+  extract::{ConnectInfo, FromRequestParts, Request},
   http::request::Parts,
   middleware::Next,
   response::Response,
@@ -32,6 +34,27 @@ pub async fn authenticated_request(
   Ok(next.run(request).await)
 }
 
+/// Client IP address, taken from the last `X-Forwarded-For` hop when running
+/// behind a proxy (e.g. Cloud Run) and from the socket otherwise. The earlier
+/// hops are sent by the client and can be forged, the last one is appended by
+/// the proxy itself.
+fn client_ip(parts: &Parts) -> Option<String> {
+  let forwarded_ip = parts
+    .headers
+    .get("X-Forwarded-For")
+    .and_then(|h| h.to_str().ok())
+    .and_then(|h| h.split(',').next_back())
+    .map(|ip| ip.trim().to_string())
+    .filter(|ip| !ip.is_empty());
+
+  forwarded_ip.or_else(|| {
+    parts
+      .extensions
+      .get::<ConnectInfo<SocketAddr>>()
+      .map(|ConnectInfo(addr)| addr.ip().to_string())
+  })
+}
+
 impl FromRequestParts<AppState> for AuthStatement {
   type Rejection = MyErrors;
 
@@ -44,7 +67,11 @@ impl FromRequestParts<AppState> for AuthStatement {
       .get("Authorization")
       .and_then(|h| h.to_str().ok());
 
-    Ok(AuthContext::new(auth_header, state).await.authorize())
+    Ok(
+      AuthContext::new(auth_header, client_ip(parts), state)
+        .await
+        .authorize(),
+    )
   }
 }
 
@@ -60,7 +87,7 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
       .get("Authorization")
       .and_then(|h| h.to_str().ok());
 
-    let auth_context = AuthContext::new(auth_headers, state).await;
+    let auth_context = AuthContext::new(auth_headers, client_ip(parts), state).await;
 
     match auth_context.current_user {
       Some(user) => Ok(AuthenticatedUser(user.0, user.1)),
@@ -17,10 +17,27 @@ pub struct Model {
   pub updated_at: DateTimeWithTimeZone,
   pub price_in_cents: i32,
   pub payment_method: Option<PaymentMethod>,
+  pub amo_share_in_cents: i32,
+  pub mutuelle_share_in_cents: i32,
+  pub amo_paid_at: Option<Date>,
+  pub mutuelle_paid_at: Option<Date>,
+  pub act_id: Option<i32>,
+  pub act_label: Option<String>,
+  pub act_code: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::acts::Entity",
+    from = "Column::ActId",
+    to = "super::acts::Column::Id",
+    on_update = "Cascade",
+    on_delete = "SetNull"
+  )]
+  Acts,
+  #[sea_orm(has_many = "super::email_logs::Entity")]
+  EmailLogs,
   #[sea_orm(
     belongs_to = "super::patients::Entity",
     from = "Column::PatientId",
@@ -29,6 +46,8 @@ pub enum Relation {
     on_delete = "Cascade"
   )]
   Patients,
+  #[sea_orm(has_many = "super::payments::Entity")]
+  Payments,
   #[sea_orm(
     belongs_to = "super::practitioner_offices::Entity",
     from = "Column::PractitionerOfficeId",
@@ -47,12 +66,30 @@ pub enum Relation {
   Users,
 }
 
+impl Related<super::acts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Acts.def()
+  }
+}
+
+impl Related<super::email_logs::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::EmailLogs.def()
+  }
+}
+
 impl Related<super::patients::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::Patients.def()
   }
 }
 
+impl Related<super::payments::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Payments.def()
+  }
+}
+
 impl Related<super::practitioner_offices::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::PractitionerOffices.def()
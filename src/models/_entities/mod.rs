@@ -1,10 +1,23 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.20
 
-pub mod prelude;
-
+pub mod accounting_exports;
+pub mod act_office_prices;
+pub mod acts;
+pub mod audit_logs;
+pub mod check_deposits;
+pub mod email_logs;
+pub mod expenses;
+pub mod invoice_templates;
 pub mod medical_appointments;
+pub mod patient_accesses;
+pub mod patient_insurances;
 pub mod patients;
+pub mod payments;
 pub mod practitioner_offices;
+pub mod prelude;
+pub mod retrocession_statements;
+pub mod revenue_share_rates;
+pub mod scheduled_exports;
 pub mod sea_orm_active_enums;
 pub mod user_business_informations;
 pub mod user_practitioner_offices;
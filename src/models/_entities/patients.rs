@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.20
 
+use super::sea_orm_active_enums::{Locale, PatientSex};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -22,12 +23,24 @@ pub struct Model {
   pub address_country: String,
   pub email: String,
   pub user_id: i32,
+  pub anonymized_at: Option<DateTimeWithTimeZone>,
+  pub birth_date: Option<Date>,
+  pub phone_number: Option<String>,
+  pub sex: Option<PatientSex>,
+  pub referring_doctor: Option<String>,
+  pub locale: Option<Locale>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::email_logs::Entity")]
+  EmailLogs,
   #[sea_orm(has_many = "super::medical_appointments::Entity")]
   MedicalAppointments,
+  #[sea_orm(has_many = "super::patient_accesses::Entity")]
+  PatientAccesses,
+  #[sea_orm(has_many = "super::patient_insurances::Entity")]
+  PatientInsurances,
   #[sea_orm(
     belongs_to = "super::users::Entity",
     from = "Column::UserId",
@@ -38,12 +51,30 @@ pub enum Relation {
   Users,
 }
 
+impl Related<super::email_logs::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::EmailLogs.def()
+  }
+}
+
 impl Related<super::medical_appointments::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::MedicalAppointments.def()
   }
 }
 
+impl Related<super::patient_accesses::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PatientAccesses.def()
+  }
+}
+
+impl Related<super::patient_insurances::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PatientInsurances.def()
+  }
+}
+
 impl Related<super::users::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::Users.def()
@@ -15,22 +15,47 @@ pub struct Model {
   pub address_zip_code: String,
   pub address_city: String,
   pub address_country: String,
+  pub contact_email: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::act_office_prices::Entity")]
+  ActOfficePrices,
   #[sea_orm(has_many = "super::medical_appointments::Entity")]
   MedicalAppointments,
+  #[sea_orm(has_many = "super::retrocession_statements::Entity")]
+  RetrocessionStatements,
+  #[sea_orm(has_many = "super::revenue_share_rates::Entity")]
+  RevenueShareRates,
   #[sea_orm(has_many = "super::user_practitioner_offices::Entity")]
   UserPractitionerOffices,
 }
 
+impl Related<super::act_office_prices::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ActOfficePrices.def()
+  }
+}
+
 impl Related<super::medical_appointments::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::MedicalAppointments.def()
   }
 }
 
+impl Related<super::retrocession_statements::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::RetrocessionStatements.def()
+  }
+}
+
+impl Related<super::revenue_share_rates::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::RevenueShareRates.def()
+  }
+}
+
 impl Related<super::user_practitioner_offices::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::UserPractitionerOffices.def()
@@ -3,6 +3,132 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "audit_action")]
+pub enum AuditAction {
+  #[sea_orm(string_value = "create")]
+  Create,
+  #[sea_orm(string_value = "delete")]
+  Delete,
+  #[sea_orm(string_value = "export")]
+  Export,
+  #[sea_orm(string_value = "read")]
+  Read,
+  #[sea_orm(string_value = "search")]
+  Search,
+  #[sea_orm(string_value = "update")]
+  Update,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "email_status")]
+pub enum EmailStatus {
+  #[sea_orm(string_value = "failed")]
+  Failed,
+  #[sea_orm(string_value = "pending")]
+  Pending,
+  #[sea_orm(string_value = "sent")]
+  Sent,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "expense_category")]
+pub enum ExpenseCategory {
+  #[sea_orm(string_value = "bank_fees")]
+  BankFees,
+  #[sea_orm(string_value = "fees")]
+  Fees,
+  #[sea_orm(string_value = "insurance")]
+  Insurance,
+  #[sea_orm(string_value = "memberships")]
+  Memberships,
+  #[sea_orm(string_value = "office")]
+  Office,
+  #[sea_orm(string_value = "other")]
+  Other,
+  #[sea_orm(string_value = "purchases")]
+  Purchases,
+  #[sea_orm(string_value = "rent")]
+  Rent,
+  #[sea_orm(string_value = "retrocession")]
+  Retrocession,
+  #[sea_orm(string_value = "social_contributions")]
+  SocialContributions,
+  #[sea_orm(string_value = "supplies")]
+  Supplies,
+  #[sea_orm(string_value = "taxes")]
+  Taxes,
+  #[sea_orm(string_value = "travel")]
+  Travel,
+  #[sea_orm(string_value = "vehicle")]
+  Vehicle,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "export_format")]
+pub enum ExportFormat {
+  #[sea_orm(string_value = "csv")]
+  Csv,
+  #[sea_orm(string_value = "fec")]
+  Fec,
+  #[sea_orm(string_value = "xlsx")]
+  Xlsx,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "export_frequency")]
+pub enum ExportFrequency {
+  #[sea_orm(string_value = "monthly")]
+  Monthly,
+  #[sea_orm(string_value = "quarterly")]
+  Quarterly,
+  #[sea_orm(string_value = "yearly")]
+  Yearly,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "insurance_kind")]
+pub enum InsuranceKind {
+  #[sea_orm(string_value = "amo")]
+  Amo,
+  #[sea_orm(string_value = "mutuelle")]
+  Mutuelle,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "locale")]
+pub enum Locale {
+  #[sea_orm(string_value = "en")]
+  En,
+  #[sea_orm(string_value = "fr")]
+  Fr,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+  rs_type = "String",
+  db_type = "Enum",
+  enum_name = "patient_access_level"
+)]
+pub enum PatientAccessLevel {
+  #[sea_orm(string_value = "read")]
+  Read,
+  #[sea_orm(string_value = "write")]
+  Write,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+  rs_type = "String",
+  db_type = "Enum",
+  enum_name = "patient_access_status"
+)]
+pub enum PatientAccessStatus {
+  #[sea_orm(string_value = "granted")]
+  Granted,
+  #[sea_orm(string_value = "pending")]
+  Pending,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "patient_sex")]
+pub enum PatientSex {
+  #[sea_orm(string_value = "female")]
+  Female,
+  #[sea_orm(string_value = "male")]
+  Male,
+}
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "payment_method")]
 pub enum PaymentMethod {
@@ -79,3 +205,23 @@ pub enum Profession {
   #[sea_orm(string_value = "psychotherapist")]
   Psychotherapist,
 }
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "retrocession_kind")]
+pub enum RetrocessionKind {
+  #[sea_orm(string_value = "capped_percentage")]
+  CappedPercentage,
+  #[sea_orm(string_value = "fixed_monthly_fee")]
+  FixedMonthlyFee,
+  #[sea_orm(string_value = "per_appointment_fee")]
+  PerAppointmentFee,
+  #[sea_orm(string_value = "percentage")]
+  Percentage,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "user_role")]
+pub enum UserRole {
+  #[sea_orm(string_value = "admin")]
+  Admin,
+  #[sea_orm(string_value = "practitioner")]
+  Practitioner,
+}
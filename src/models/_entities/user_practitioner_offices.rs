@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.20
 
+use super::sea_orm_active_enums::RetrocessionKind;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +15,8 @@ pub struct Model {
   pub practitioner_office_id: i32,
   #[sea_orm(column_type = "Decimal(Some((5, 2)))")]
   pub revenue_share_percentage: Decimal,
+  pub retrocession_kind: RetrocessionKind,
+  pub retrocession_amount_in_cents: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
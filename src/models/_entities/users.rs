@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.20
 
+use super::sea_orm_active_enums::{Locale, UserRole};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -19,32 +20,123 @@ pub struct Model {
   pub last_name: String,
   pub access_key: Option<String>,
   pub is_access_key_verified: bool,
+  pub role: UserRole,
+  pub suspended_at: Option<DateTimeWithTimeZone>,
+  pub locale: Locale,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+  #[sea_orm(has_many = "super::accounting_exports::Entity")]
+  AccountingExports,
+  #[sea_orm(has_many = "super::acts::Entity")]
+  Acts,
+  #[sea_orm(has_many = "super::audit_logs::Entity")]
+  AuditLogs,
+  #[sea_orm(has_many = "super::check_deposits::Entity")]
+  CheckDeposits,
+  #[sea_orm(has_many = "super::email_logs::Entity")]
+  EmailLogs,
+  #[sea_orm(has_many = "super::expenses::Entity")]
+  Expenses,
+  #[sea_orm(has_one = "super::invoice_templates::Entity")]
+  InvoiceTemplates,
   #[sea_orm(has_many = "super::medical_appointments::Entity")]
   MedicalAppointments,
+  #[sea_orm(has_many = "super::patient_accesses::Entity")]
+  PatientAccesses,
   #[sea_orm(has_many = "super::patients::Entity")]
   Patients,
+  #[sea_orm(has_many = "super::retrocession_statements::Entity")]
+  RetrocessionStatements,
+  #[sea_orm(has_many = "super::revenue_share_rates::Entity")]
+  RevenueShareRates,
+  #[sea_orm(has_one = "super::scheduled_exports::Entity")]
+  ScheduledExports,
   #[sea_orm(has_one = "super::user_business_informations::Entity")]
   UserBusinessInformations,
   #[sea_orm(has_many = "super::user_practitioner_offices::Entity")]
   UserPractitionerOffices,
 }
 
+impl Related<super::accounting_exports::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::AccountingExports.def()
+  }
+}
+
+impl Related<super::acts::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Acts.def()
+  }
+}
+
+impl Related<super::audit_logs::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::AuditLogs.def()
+  }
+}
+
+impl Related<super::check_deposits::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::CheckDeposits.def()
+  }
+}
+
+impl Related<super::email_logs::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::EmailLogs.def()
+  }
+}
+
+impl Related<super::expenses::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Expenses.def()
+  }
+}
+
+impl Related<super::invoice_templates::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::InvoiceTemplates.def()
+  }
+}
+
 impl Related<super::medical_appointments::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::MedicalAppointments.def()
   }
 }
 
+impl Related<super::patient_accesses::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PatientAccesses.def()
+  }
+}
+
 impl Related<super::patients::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::Patients.def()
   }
 }
 
+impl Related<super::retrocession_statements::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::RetrocessionStatements.def()
+  }
+}
+
+impl Related<super::revenue_share_rates::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::RevenueShareRates.def()
+  }
+}
+
+impl Related<super::scheduled_exports::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::ScheduledExports.def()
+  }
+}
+
 impl Related<super::user_business_informations::Entity> for Entity {
   fn to() -> RelationDef {
     Relation::UserBusinessInformations.def()
@@ -0,0 +1,58 @@
+use sea_orm::{entity::prelude::*, ActiveValue};
+
+use crate::models::{_entities::accounting_exports, my_errors::MyErrors};
+
+pub use super::_entities::accounting_exports::{ActiveModel, Entity, Model};
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    start_date: Date,
+    end_date: Date,
+  ) -> Result<Model, MyErrors> {
+    Ok(
+      ActiveModel {
+        user_id: ActiveValue::Set(user_id),
+        start_date: ActiveValue::Set(start_date),
+        end_date: ActiveValue::Set(end_date),
+        ..Default::default()
+      }
+      .insert(db)
+      .await?,
+    )
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  /// Exports of `user_id` covering at least one day of `[start, end)`, where
+  /// a `None` end leaves the range open
+  pub fn find_overlapping(user_id: i32, start: Date, end: Option<Date>) -> Select<Entity> {
+    let mut query = Self::find()
+      .filter(accounting_exports::Column::UserId.eq(user_id))
+      .filter(accounting_exports::Column::EndDate.gte(start));
+    if let Some(end) = end {
+      query = query.filter(accounting_exports::Column::StartDate.lt(end));
+    }
+    query
+  }
+}
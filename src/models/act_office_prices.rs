@@ -0,0 +1,60 @@
+use sea_orm::{entity::prelude::*, ActiveValue};
+
+use crate::models::{_entities::act_office_prices, acts::OfficePriceParams, my_errors::MyErrors};
+
+pub use super::_entities::act_office_prices::{ActiveModel, Entity, Model};
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Replace the office prices of `act_id` with `office_prices`
+  pub async fn replace_for_act<T: ConnectionTrait>(
+    db: &T,
+    act_id: i32,
+    office_prices: &[OfficePriceParams],
+  ) -> Result<Vec<Model>, MyErrors> {
+    Entity::delete_many()
+      .filter(act_office_prices::Column::ActId.eq(act_id))
+      .exec(db)
+      .await?;
+
+    let mut prices = Vec::with_capacity(office_prices.len());
+    for office_price in office_prices {
+      prices.push(
+        ActiveModel {
+          act_id: ActiveValue::Set(act_id),
+          practitioner_office_id: ActiveValue::Set(office_price.practitioner_office_id),
+          price_in_cents: ActiveValue::Set(office_price.price_in_cents),
+          ..Default::default()
+        }
+        .insert(db)
+        .await?,
+      );
+    }
+
+    Ok(prices)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_acts(act_ids: impl IntoIterator<Item = i32>) -> Select<Entity> {
+    Self::find().filter(act_office_prices::Column::ActId.is_in(act_ids))
+  }
+}
@@ -0,0 +1,133 @@
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder, TryIntoModel};
+use serde::Deserialize;
+
+use crate::{
+  auth::resource::{Permission, Resource},
+  models::{
+    _entities::acts,
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+};
+
+pub use super::_entities::acts::{ActiveModel, Entity, Model};
+
+/// Price of an act in a given office, instead of its default price
+#[derive(Debug, Clone, Deserialize)]
+pub struct OfficePriceParams {
+  pub practitioner_office_id: i32,
+  pub price_in_cents: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActParams {
+  pub label: String,
+  /// NGAP or CCAM code
+  pub code: Option<String>,
+  pub default_price_in_cents: i32,
+  #[serde(default)]
+  pub office_prices: Vec<OfficePriceParams>,
+}
+
+impl ActParams {
+  fn label(&self) -> Result<String, MyErrors> {
+    let label = self.label.trim();
+    if label.is_empty() || label.len() > 255 {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+    Ok(label.to_string())
+  }
+
+  /// Codes are written in capitals, e.g. `AMK 8` or `GS`
+  fn code(&self) -> Option<String> {
+    self
+      .code
+      .as_deref()
+      .map(|code| code.trim().to_uppercase())
+      .filter(|code| !code.is_empty())
+  }
+
+  fn validate_prices(&self) -> Result<(), MyErrors> {
+    let is_valid = self.default_price_in_cents > 0
+      && self
+        .office_prices
+        .iter()
+        .all(|office_price| office_price.price_in_cents > 0);
+
+    if !is_valid {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    params: &ActParams,
+  ) -> Result<Model, MyErrors> {
+    ActiveModel {
+      user_id: ActiveValue::Set(user_id),
+      ..Default::default()
+    }
+    .update_from(db, params)
+    .await
+  }
+
+  pub async fn update_from<T: ConnectionTrait>(
+    mut self,
+    db: &T,
+    params: &ActParams,
+  ) -> Result<Model, MyErrors> {
+    params.validate_prices()?;
+
+    self.label = ActiveValue::Set(params.label()?);
+    self.code = ActiveValue::Set(params.code());
+    self.default_price_in_cents = ActiveValue::Set(params.default_price_in_cents);
+
+    Ok(self.save(db).await?.try_into_model()?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_user(user_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(acts::Column::UserId.eq(user_id))
+      .order_by_asc(acts::Column::Label)
+      .order_by_asc(acts::Column::Id)
+  }
+}
+
+impl Resource for Model {
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    (self.user_id == user_id).then_some(Permission::Own)
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
+  }
+
+  fn resource_name(&self) -> String {
+    "acts".to_string()
+  }
+}
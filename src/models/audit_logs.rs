@@ -0,0 +1,57 @@
+use sea_orm::{entity::prelude::*, ActiveValue};
+
+use crate::models::{_entities::sea_orm_active_enums::AuditAction, my_errors::MyErrors};
+
+pub use super::_entities::audit_logs::{ActiveModel, Column, Entity, Model};
+
+pub struct CreateAuditLogParams {
+  pub user_id: i32,
+  pub action: AuditAction,
+  pub resource_type: String,
+  pub resource_id: i32,
+  pub granted: bool,
+  pub ip_address: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    // The audit trail is append-only, entries can never be rewritten
+    if !insert {
+      return Err(DbErr::Custom("audit_logs_are_append_only".to_string()));
+    }
+
+    Ok(self)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    params: &CreateAuditLogParams,
+  ) -> Result<Model, MyErrors> {
+    let created_audit_log = ActiveModel {
+      user_id: ActiveValue::Set(params.user_id),
+      action: ActiveValue::Set(params.action.clone()),
+      resource_type: ActiveValue::Set(params.resource_type.clone()),
+      resource_id: ActiveValue::Set(params.resource_id),
+      granted: ActiveValue::Set(params.granted),
+      ip_address: ActiveValue::Set(params.ip_address.clone()),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_audit_log)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
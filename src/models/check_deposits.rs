@@ -0,0 +1,79 @@
+use chrono::Datelike;
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder};
+
+use crate::{
+  auth::resource::{Permission, Resource},
+  models::{_entities::check_deposits, my_errors::MyErrors},
+};
+
+pub use super::_entities::check_deposits::{ActiveModel, Entity, Model};
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Create a deposit numbered after the previous ones of the same year,
+  /// e.g. `REM-2026-004` for the fourth slip of 2026.
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    deposited_on: Date,
+  ) -> Result<Model, MyErrors> {
+    let prefix = format!("REM-{}-", deposited_on.year());
+    let deposits_this_year = Entity::find()
+      .filter(check_deposits::Column::UserId.eq(user_id))
+      .filter(check_deposits::Column::Reference.starts_with(&prefix))
+      .count(db)
+      .await?;
+
+    let created_deposit = ActiveModel {
+      user_id: ActiveValue::Set(user_id),
+      reference: ActiveValue::Set(format!("{}{:03}", prefix, deposits_this_year + 1)),
+      deposited_on: ActiveValue::Set(deposited_on),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_deposit)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_user(user_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(check_deposits::Column::UserId.eq(user_id))
+      .order_by_desc(check_deposits::Column::DepositedOn)
+      .order_by_desc(check_deposits::Column::Id)
+  }
+}
+
+impl Resource for Model {
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    (self.user_id == user_id).then_some(Permission::Own)
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
+  }
+
+  fn resource_name(&self) -> String {
+    "check_deposits".to_string()
+  }
+}
@@ -0,0 +1,102 @@
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder};
+
+use crate::models::{
+  _entities::{email_logs, sea_orm_active_enums::EmailStatus},
+  my_errors::MyErrors,
+};
+
+pub use super::_entities::email_logs::{ActiveModel, Entity, Model};
+
+pub struct CreateEmailLogParams {
+  pub user_id: Option<i32>,
+  pub patient_id: Option<i32>,
+  pub medical_appointment_id: Option<i32>,
+  pub recipient: String,
+  pub subject: String,
+  pub template: Option<String>,
+  pub attachment_names: Vec<String>,
+  /// The email as sent, kept for the ones that can be sent again
+  pub sent_email: Option<Json>,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {
+  pub fn attachment_names(&self) -> Vec<String> {
+    serde_json::from_value(self.attachment_names.clone()).unwrap_or_default()
+  }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Record an email about to be sent, pending until the SMTP server answers
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    params: &CreateEmailLogParams,
+  ) -> Result<Model, MyErrors> {
+    let created_email_log = ActiveModel {
+      user_id: ActiveValue::Set(params.user_id),
+      patient_id: ActiveValue::Set(params.patient_id),
+      medical_appointment_id: ActiveValue::Set(params.medical_appointment_id),
+      recipient: ActiveValue::Set(params.recipient.clone()),
+      subject: ActiveValue::Set(params.subject.clone()),
+      template: ActiveValue::Set(params.template.clone()),
+      attachment_names: ActiveValue::Set(serde_json::json!(params.attachment_names)),
+      sent_email: ActiveValue::Set(params.sent_email.clone()),
+      status: ActiveValue::Set(EmailStatus::Pending),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_email_log)
+  }
+
+  /// Record the outcome of sending the email of `email_log`
+  pub async fn record_result<T: ConnectionTrait>(
+    db: &T,
+    email_log: Model,
+    result: &Result<(), MyErrors>,
+  ) -> Result<Model, MyErrors> {
+    let mut email_log: ActiveModel = email_log.into();
+
+    match result {
+      Ok(()) => {
+        email_log.status = ActiveValue::Set(EmailStatus::Sent);
+        email_log.error = ActiveValue::Set(None);
+        email_log.sent_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+      }
+      Err(error) => {
+        email_log.status = ActiveValue::Set(EmailStatus::Failed);
+        email_log.error = ActiveValue::Set(Some(error.msg.clone()));
+      }
+    }
+
+    Ok(email_log.update(db).await?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  /// Emails sent by `user_id` about `patient_id`, most recent first
+  pub fn find_for_patient(user_id: i32, patient_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(email_logs::Column::UserId.eq(user_id))
+      .filter(email_logs::Column::PatientId.eq(patient_id))
+      .order_by_desc(email_logs::Column::CreatedAt)
+      .order_by_desc(email_logs::Column::Id)
+  }
+}
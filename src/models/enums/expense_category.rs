@@ -0,0 +1,23 @@
+use crate::models::_entities::sea_orm_active_enums::ExpenseCategory;
+
+impl ExpenseCategory {
+  /// Label of the matching line of the 2035-A declaration
+  pub fn to_french(&self) -> &str {
+    match self {
+      Self::Purchases => "Achats",
+      Self::Rent => "Loyers et charges locatives",
+      Self::Retrocession => "Honoraires rétrocédés",
+      Self::Supplies => "Petit outillage",
+      Self::Insurance => "Primes d'assurance",
+      Self::Vehicle => "Frais de véhicules",
+      Self::Travel => "Autres frais de déplacement",
+      Self::SocialContributions => "Charges sociales personnelles",
+      Self::Taxes => "Impôts et taxes",
+      Self::Fees => "Honoraires ne constituant pas des rétrocessions",
+      Self::Office => "Fournitures de bureau, frais de documentation et de PTT",
+      Self::Memberships => "Cotisations syndicales et professionnelles",
+      Self::BankFees => "Frais financiers",
+      Self::Other => "Autres frais divers de gestion",
+    }
+  }
+}
@@ -1 +1,3 @@
+pub mod expense_category;
+pub mod payment_method;
 pub mod profession;
@@ -0,0 +1,12 @@
+use crate::models::_entities::sea_orm_active_enums::PaymentMethod;
+
+impl PaymentMethod {
+  pub fn to_french(&self) -> &str {
+    match self {
+      Self::Card => "Carte bancaire",
+      Self::Cash => "Espèces",
+      Self::Check => "Chèque",
+      Self::Transfer => "Virement",
+    }
+  }
+}
@@ -1,4 +1,4 @@
-use crate::models::_entities::sea_orm_active_enums::Profession;
+use crate::models::_entities::sea_orm_active_enums::{Locale, Profession};
 
 impl Profession {
   pub fn to_french(&self) -> &str {
@@ -35,4 +35,46 @@ impl Profession {
       Self::Psychotherapist => "PSYCHOTHÉRAPEUTE",
     }
   }
+
+  pub fn to_english(&self) -> &str {
+    match self {
+      Self::GeneralPractitioner => "GENERAL PRACTITIONER",
+      Self::Pediatrician => "PEDIATRICIAN",
+      Self::Gynecologist => "GYNECOLOGIST",
+      Self::Psychiatrist => "PSYCHIATRIST",
+      Self::Gastroenterologist => "GASTROENTEROLOGIST",
+      Self::EntSpecialist => "ENT SPECIALIST",
+      Self::Endocrinologist => "ENDOCRINOLOGIST",
+      Self::Cardiologist => "CARDIOLOGIST",
+      Self::Angiologist => "ANGIOLOGIST",
+      Self::Nephrologist => "NEPHROLOGIST",
+      Self::Neurologist => "NEUROLOGIST",
+      Self::Pulmonologist => "PULMONOLOGIST",
+      Self::Rheumatologist => "RHEUMATOLOGIST",
+      Self::Dermatologist => "DERMATOLOGIST",
+      Self::Dentist => "DENTIST",
+      Self::Midwife => "MIDWIFE",
+      Self::Physiotherapist => "PHYSIOTHERAPIST",
+      Self::Nurse => "NURSE",
+      Self::Psychologist => "PSYCHOLOGIST",
+      Self::Osteopath => "OSTEOPATH D.O.",
+      Self::Audiologist => "AUDIOLOGIST",
+      Self::Chiropractor => "CHIROPRACTOR",
+      Self::GeneticCounselor => "GENETIC COUNSELOR",
+      Self::Dietitian => "DIETITIAN",
+      Self::OccupationalTherapist => "OCCUPATIONAL THERAPIST",
+      Self::SpeechTherapist => "SPEECH THERAPIST",
+      Self::Orthoptist => "ORTHOPTIST",
+      Self::Podiatrist => "PODIATRIST",
+      Self::Psychomotrician => "PSYCHOMOTOR THERAPIST",
+      Self::Psychotherapist => "PSYCHOTHERAPIST",
+    }
+  }
+
+  pub fn label(&self, locale: &Locale) -> &str {
+    match locale {
+      Locale::En => self.to_english(),
+      Locale::Fr => self.to_french(),
+    }
+  }
 }
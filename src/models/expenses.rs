@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder};
+use serde::Deserialize;
+
+use crate::{
+  auth::resource::{Permission, Resource},
+  models::{
+    _entities::{
+      expenses,
+      sea_orm_active_enums::{ExpenseCategory, PaymentMethod},
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+};
+
+pub use super::_entities::expenses::{ActiveModel, Entity, Model};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateExpenseParams {
+  pub spent_on: String,
+  pub category: ExpenseCategory,
+  pub label: String,
+  pub amount_in_cents: i32,
+  pub payment_method: Option<PaymentMethod>,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    params: &CreateExpenseParams,
+    user_id: i32,
+  ) -> Result<Model, MyErrors> {
+    let label = params.label.trim();
+    if label.is_empty() || label.len() > 255 || params.amount_in_cents <= 0 {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    let spent_on = NaiveDate::parse_from_str(&params.spent_on, "%Y-%m-%d")
+      .map_err(|_| ApplicationError::UnprocessableEntity)?;
+
+    let created_expense = ActiveModel {
+      user_id: ActiveValue::Set(user_id),
+      spent_on: ActiveValue::Set(spent_on),
+      category: ActiveValue::Set(params.category.clone()),
+      label: ActiveValue::Set(label.to_string()),
+      amount_in_cents: ActiveValue::Set(params.amount_in_cents),
+      payment_method: ActiveValue::Set(params.payment_method.clone()),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_expense)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_period(user_id: i32, start_date: Date, end_date: Date) -> Select<Entity> {
+    Self::find()
+      .filter(expenses::Column::UserId.eq(user_id))
+      .filter(expenses::Column::SpentOn.between(start_date, end_date))
+      .order_by_asc(expenses::Column::SpentOn)
+      .order_by_asc(expenses::Column::Id)
+  }
+}
+
+impl Resource for Model {
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    (self.user_id == user_id).then_some(Permission::Own)
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
+  }
+
+  fn resource_name(&self) -> String {
+    "expenses".to_string()
+  }
+}
@@ -0,0 +1,180 @@
+use sea_orm::{entity::prelude::*, ActiveValue, TryIntoModel};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+  _entities::invoice_templates,
+  my_errors::{application_error::ApplicationError, MyErrors},
+};
+
+pub use super::_entities::invoice_templates::{ActiveModel, Entity, Model};
+
+/// Mention required on the notes of practitioners exempt from VAT
+pub const VAT_EXEMPTION_MENTION: &str = "TVA non applicable, art. 261-4-1° du CGI";
+
+const MAX_TEXT_LENGTH: usize = 500;
+
+/// How a practitioner's notes d'honoraires look
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InvoiceTemplate {
+  pub title: String,
+  /// `#RRGGBB` colour of the title and frames
+  pub accent_color: String,
+  /// Printed below the practitioner's details, e.g. opening hours
+  pub header_text: Option<String>,
+  /// Printed at the bottom of the page, e.g. payment terms
+  pub footer_text: Option<String>,
+  /// Mentions printed in addition to [`VAT_EXEMPTION_MENTION`], which is
+  /// always printed
+  pub legal_mentions: Option<String>,
+  /// Closes the emails sending the notes, instead of the practitioner's name,
+  /// profession and phone number
+  pub email_signature: Option<String>,
+  pub show_birth_date: bool,
+  pub show_ssn: bool,
+  pub show_patient_address: bool,
+}
+
+impl Default for InvoiceTemplate {
+  fn default() -> Self {
+    Self {
+      title: "Note d'honoraires acquittée".to_string(),
+      accent_color: "#000000".to_string(),
+      header_text: None,
+      footer_text: None,
+      legal_mentions: None,
+      email_signature: None,
+      show_birth_date: true,
+      show_ssn: true,
+      show_patient_address: true,
+    }
+  }
+}
+
+fn is_hex_color(color: &str) -> bool {
+  color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Trimmed `text`, `None` when blank
+fn optional_text(text: &Option<String>) -> Result<Option<String>, MyErrors> {
+  let text = text
+    .as_deref()
+    .map(str::trim)
+    .filter(|text| !text.is_empty());
+  if text.is_some_and(|text| text.chars().count() > MAX_TEXT_LENGTH) {
+    return Err(ApplicationError::UnprocessableEntity.into());
+  }
+  Ok(text.map(str::to_string))
+}
+
+impl InvoiceTemplate {
+  /// Template with its texts trimmed, or an error when it cannot be printed
+  pub fn validated(&self) -> Result<Self, MyErrors> {
+    let title = self.title.trim();
+    if title.is_empty() || title.chars().count() > 100 || !is_hex_color(&self.accent_color) {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    Ok(Self {
+      title: title.to_string(),
+      accent_color: self.accent_color.to_uppercase(),
+      header_text: optional_text(&self.header_text)?,
+      footer_text: optional_text(&self.footer_text)?,
+      legal_mentions: optional_text(&self.legal_mentions)?,
+      email_signature: optional_text(&self.email_signature)?,
+      ..self.clone()
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {
+  pub fn template(&self) -> InvoiceTemplate {
+    InvoiceTemplate {
+      title: self.title.clone(),
+      accent_color: self.accent_color.clone(),
+      header_text: self.header_text.clone(),
+      footer_text: self.footer_text.clone(),
+      legal_mentions: self.legal_mentions.clone(),
+      email_signature: self.email_signature.clone(),
+      show_birth_date: self.show_birth_date,
+      show_ssn: self.show_ssn,
+      show_patient_address: self.show_patient_address,
+    }
+  }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Stored template of `user_id`, created with the default template when
+  /// there is none yet
+  async fn for_user<T: ConnectionTrait>(db: &T, user_id: i32) -> Result<ActiveModel, MyErrors> {
+    Ok(match Entity::find_for_user(user_id).one(db).await? {
+      Some(stored) => stored.into(),
+      None => {
+        let mut created = ActiveModel {
+          user_id: ActiveValue::Set(user_id),
+          ..Default::default()
+        };
+        created.set_template(&InvoiceTemplate::default());
+        created
+      }
+    })
+  }
+
+  fn set_template(&mut self, template: &InvoiceTemplate) {
+    self.title = ActiveValue::Set(template.title.clone());
+    self.accent_color = ActiveValue::Set(template.accent_color.clone());
+    self.header_text = ActiveValue::Set(template.header_text.clone());
+    self.footer_text = ActiveValue::Set(template.footer_text.clone());
+    self.legal_mentions = ActiveValue::Set(template.legal_mentions.clone());
+    self.email_signature = ActiveValue::Set(template.email_signature.clone());
+    self.show_birth_date = ActiveValue::Set(template.show_birth_date);
+    self.show_ssn = ActiveValue::Set(template.show_ssn);
+    self.show_patient_address = ActiveValue::Set(template.show_patient_address);
+  }
+
+  pub async fn save_template<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    template: &InvoiceTemplate,
+  ) -> Result<Model, MyErrors> {
+    let template = template.validated()?;
+
+    let mut stored = Self::for_user(db, user_id).await?;
+    stored.set_template(&template);
+
+    Ok(stored.save(db).await?.try_into_model()?)
+  }
+
+  pub async fn set_logo<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    logo_file_name: Option<String>,
+  ) -> Result<Model, MyErrors> {
+    let mut stored = Self::for_user(db, user_id).await?;
+    stored.logo_file_name = ActiveValue::Set(logo_file_name);
+
+    Ok(stored.save(db).await?.try_into_model()?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_user(user_id: i32) -> Select<Entity> {
+    Self::find().filter(invoice_templates::Column::UserId.eq(user_id))
+  }
+}
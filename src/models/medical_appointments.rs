@@ -1,17 +1,50 @@
-use sea_orm::{entity::prelude::*, ActiveValue};
+use sea_orm::{entity::prelude::*, ActiveValue, TryIntoModel};
 
 use crate::{
-  auth::resource::Resource,
-  models::{_entities::sea_orm_active_enums::PaymentMethod, my_errors::MyErrors},
+  auth::resource::{Permission, Resource},
+  models::{
+    _entities::sea_orm_active_enums::PaymentMethod,
+    acts,
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
 };
 
 pub use super::_entities::medical_appointments::{ActiveModel, Entity, Model};
 
+/// Part of the price expected from the Assurance Maladie (AMO) and the mutuelle,
+/// the patient paying the remainder, along with the dates those were received.
+#[derive(Debug, Clone, Default)]
+pub struct PayerShares {
+  pub amo_share_in_cents: i32,
+  pub mutuelle_share_in_cents: i32,
+  pub amo_paid_at: Option<Date>,
+  pub mutuelle_paid_at: Option<Date>,
+}
+
+impl PayerShares {
+  fn validate(&self, price_in_cents: i32) -> Result<(), MyErrors> {
+    let is_valid = self.amo_share_in_cents >= 0
+      && self.mutuelle_share_in_cents >= 0
+      && self
+        .amo_share_in_cents
+        .checked_add(self.mutuelle_share_in_cents)
+        .is_some_and(|insured_share| insured_share <= price_in_cents);
+
+    if !is_valid {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    Ok(())
+  }
+}
+
 pub struct UpdateMedicalAppointmentParams {
   pub date: Date,
+  pub act: Option<acts::Model>,
   pub price_in_cents: i32,
   pub practitioner_office_id: i32,
   pub payment_method: Option<PaymentMethod>,
+  pub payer_shares: PayerShares,
 }
 
 pub struct CreateMedicalAppointmentParams {
@@ -19,8 +52,10 @@ pub struct CreateMedicalAppointmentParams {
   pub patient_id: i32,
   pub practitioner_office_id: i32,
   pub date: Date,
+  pub act: Option<acts::Model>,
   pub price_in_cents: i32,
   pub payment_method: Option<PaymentMethod>,
+  pub payer_shares: PayerShares,
 }
 
 #[async_trait::async_trait]
@@ -40,7 +75,21 @@ impl ActiveModelBehavior for ActiveModel {
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+  /// Act as it was when the appointment was created, e.g. `Consultation (GS)`,
+  /// as printed on invoices and exports even once the act is renamed or deleted
+  pub fn act_full_label(&self) -> Option<String> {
+    let label = self.act_label.as_ref()?;
+    Some(match &self.act_code {
+      Some(code) => format!("{} ({})", label, code),
+      None => label.clone(),
+    })
+  }
+
+  pub fn patient_share_in_cents(&self) -> i32 {
+    self.price_in_cents - self.amo_share_in_cents - self.mutuelle_share_in_cents
+  }
+}
 
 // implement your write-oriented logic here
 impl ActiveModel {
@@ -48,28 +97,48 @@ impl ActiveModel {
     mut self,
     db: &T,
     params: &UpdateMedicalAppointmentParams,
-  ) -> Result<(), MyErrors> {
+  ) -> Result<Model, MyErrors> {
+    params.payer_shares.validate(params.price_in_cents)?;
+
     self.date = ActiveValue::Set(params.date);
     self.practitioner_office_id = ActiveValue::Set(params.practitioner_office_id);
+    // The label of the act is only taken again when the act itself changes
+    let act_id = params.act.as_ref().map(|act| act.id);
+    if self.act_id.clone().take() != Some(act_id) {
+      self.act_id = ActiveValue::Set(act_id);
+      self.act_label = ActiveValue::Set(params.act.as_ref().map(|act| act.label.clone()));
+      self.act_code = ActiveValue::Set(params.act.as_ref().and_then(|act| act.code.clone()));
+    }
     self.price_in_cents = ActiveValue::Set(params.price_in_cents);
     self.payment_method = ActiveValue::Set(params.payment_method.clone());
+    self.amo_share_in_cents = ActiveValue::Set(params.payer_shares.amo_share_in_cents);
+    self.mutuelle_share_in_cents = ActiveValue::Set(params.payer_shares.mutuelle_share_in_cents);
+    self.amo_paid_at = ActiveValue::Set(params.payer_shares.amo_paid_at);
+    self.mutuelle_paid_at = ActiveValue::Set(params.payer_shares.mutuelle_paid_at);
 
-    self.save(db).await?;
-
-    Ok(())
+    Ok(self.save(db).await?.try_into_model()?)
   }
 
   pub async fn create<T: ConnectionTrait>(
     db: &T,
     params: &CreateMedicalAppointmentParams,
   ) -> Result<Model, MyErrors> {
+    params.payer_shares.validate(params.price_in_cents)?;
+
     let created_medical_appointment = ActiveModel {
       user_id: ActiveValue::Set(params.user_id),
       patient_id: ActiveValue::Set(params.patient_id),
       practitioner_office_id: ActiveValue::Set(params.practitioner_office_id),
       date: ActiveValue::Set(params.date),
+      act_id: ActiveValue::Set(params.act.as_ref().map(|act| act.id)),
+      act_label: ActiveValue::Set(params.act.as_ref().map(|act| act.label.clone())),
+      act_code: ActiveValue::Set(params.act.as_ref().and_then(|act| act.code.clone())),
       price_in_cents: ActiveValue::Set(params.price_in_cents),
       payment_method: ActiveValue::Set(params.payment_method.clone()),
+      amo_share_in_cents: ActiveValue::Set(params.payer_shares.amo_share_in_cents),
+      mutuelle_share_in_cents: ActiveValue::Set(params.payer_shares.mutuelle_share_in_cents),
+      amo_paid_at: ActiveValue::Set(params.payer_shares.amo_paid_at),
+      mutuelle_paid_at: ActiveValue::Set(params.payer_shares.mutuelle_paid_at),
       ..Default::default()
     }
     .insert(db)
@@ -83,8 +152,12 @@ impl ActiveModel {
 impl Entity {}
 
 impl Resource for Model {
-  async fn is_owned_by_user(&self, user_id: i32) -> bool {
-    self.user_id == user_id
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    (self.user_id == user_id).then_some(Permission::Own)
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
   }
 
   fn resource_name(&self) -> String {
@@ -1,9 +1,23 @@
 pub mod _entities;
+pub mod accounting_exports;
+pub mod act_office_prices;
+pub mod acts;
+pub mod audit_logs;
+pub mod check_deposits;
+pub mod email_logs;
 pub mod enums;
+pub mod expenses;
+pub mod invoice_templates;
 pub mod medical_appointments;
 pub mod my_errors;
+pub mod patient_accesses;
+pub mod patient_insurances;
 pub mod patients;
+pub mod payments;
 pub mod practitioner_offices;
+pub mod retrocession_statements;
+pub mod revenue_share_rates;
+pub mod scheduled_exports;
 pub mod user_business_informations;
 pub mod user_practitioner_offices;
 pub mod users;
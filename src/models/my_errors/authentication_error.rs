@@ -7,6 +7,7 @@ pub enum AuthenticationError {
   InvalidToken,
   InvalidClaims,
   AccessKeyNotVerified,
+  AccountSuspended,
   AccessDenied(Option<String>),
 }
 
@@ -33,6 +34,10 @@ impl From<AuthenticationError> for MyErrors {
         code: StatusCode::UNAUTHORIZED,
         msg: "access_key_not_verified".to_string(),
       },
+      AuthenticationError::AccountSuspended => MyErrors {
+        code: StatusCode::FORBIDDEN,
+        msg: "account_suspended".to_string(),
+      },
       AuthenticationError::AccessDenied(to) => MyErrors {
         code: StatusCode::FORBIDDEN,
         msg: format!("access_denied_to_{}", to.unwrap_or("resource".to_string())),
@@ -0,0 +1,137 @@
+use sea_orm::{entity::prelude::*, ActiveValue};
+
+use crate::{
+  auth::resource::{Permission, Resource},
+  initializers,
+  models::{
+    _entities::{
+      patient_accesses, patients,
+      sea_orm_active_enums::{PatientAccessLevel, PatientAccessStatus},
+    },
+    my_errors::MyErrors,
+  },
+};
+
+pub use super::_entities::patient_accesses::{ActiveModel, Entity, Model};
+
+/// How long the patient can use the link sent to give their consent
+pub const CONSENT_TOKEN_VALIDITY_DAYS: i64 = 14;
+
+pub struct CreatePatientAccessParams {
+  pub patient_id: i32,
+  pub user_id: i32,
+  pub level: PatientAccessLevel,
+}
+
+impl From<&PatientAccessLevel> for Permission {
+  fn from(level: &PatientAccessLevel) -> Self {
+    match level {
+      PatientAccessLevel::Read => Permission::Read,
+      PatientAccessLevel::Write => Permission::Write,
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Register a pending access request, along with the token the patient can
+  /// use to give their consent
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    params: &CreatePatientAccessParams,
+  ) -> Result<Model, MyErrors> {
+    let created_access = ActiveModel {
+      patient_id: ActiveValue::Set(params.patient_id),
+      user_id: ActiveValue::Set(params.user_id),
+      level: ActiveValue::Set(params.level.clone()),
+      status: ActiveValue::Set(PatientAccessStatus::Pending),
+      consent_token: ActiveValue::Set(Some(Uuid::new_v4().to_string())),
+      consent_token_expires_at: ActiveValue::Set(Some(
+        (chrono::Utc::now() + chrono::Duration::days(CONSENT_TOKEN_VALIDITY_DAYS)).into(),
+      )),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_access)
+  }
+
+  pub async fn grant<T: ConnectionTrait>(
+    self,
+    db: &T,
+    level: PatientAccessLevel,
+  ) -> Result<Model, MyErrors> {
+    let mut access = self;
+
+    access.level = ActiveValue::Set(level);
+    access.status = ActiveValue::Set(PatientAccessStatus::Granted);
+    access.consent_token = ActiveValue::Set(None);
+    access.consent_token_expires_at = ActiveValue::Set(None);
+
+    Ok(access.update(db).await?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_by_consent_token(token: &str) -> Select<Entity> {
+    Self::find()
+      .filter(patient_accesses::Column::ConsentToken.eq(token))
+      .filter(patient_accesses::Column::ConsentTokenExpiresAt.gt(chrono::Utc::now()))
+      .filter(patient_accesses::Column::Status.eq(PatientAccessStatus::Pending))
+  }
+
+  pub fn find_granted(patient_id: i32, user_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(patient_accesses::Column::PatientId.eq(patient_id))
+      .filter(patient_accesses::Column::UserId.eq(user_id))
+      .filter(patient_accesses::Column::Status.eq(PatientAccessStatus::Granted))
+  }
+}
+
+/// Only the practitioner owning the patient manages the accesses to their record,
+/// while the requester can only see their own request.
+impl Resource for Model {
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    let services = initializers::get_services();
+
+    let patient = patients::Entity::find_by_id(self.patient_id)
+      .one(&services.db)
+      .await
+      .ok()??;
+
+    if patient.user_id == user_id {
+      Some(Permission::Own)
+    } else if self.user_id == user_id {
+      Some(Permission::Read)
+    } else {
+      None
+    }
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
+  }
+
+  fn resource_name(&self) -> String {
+    "patient_access".to_string()
+  }
+}
@@ -0,0 +1,91 @@
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder};
+use serde::Deserialize;
+
+use crate::models::{
+  _entities::{patient_insurances, sea_orm_active_enums::InsuranceKind},
+  my_errors::{application_error::ApplicationError, MyErrors},
+};
+
+pub use super::_entities::patient_insurances::{ActiveModel, Entity, Model};
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePatientInsuranceParams {
+  pub kind: InsuranceKind,
+  pub insurer_name: String,
+  pub member_number: Option<String>,
+  pub valid_until: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {
+  pub fn is_valid_on(&self, date: Date) -> bool {
+    self
+      .valid_until
+      .is_none_or(|valid_until| date <= valid_until)
+  }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    params: &CreatePatientInsuranceParams,
+    patient_id: i32,
+  ) -> Result<Model, MyErrors> {
+    let insurer_name = params.insurer_name.trim();
+    if insurer_name.is_empty() || insurer_name.len() >= 100 {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    let valid_until = params
+      .valid_until
+      .as_deref()
+      .filter(|date| !date.is_empty())
+      .map(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+      .transpose()
+      .map_err(|_| ApplicationError::UnprocessableEntity)?;
+
+    let created_insurance = ActiveModel {
+      patient_id: ActiveValue::Set(patient_id),
+      kind: ActiveValue::Set(params.kind.clone()),
+      insurer_name: ActiveValue::Set(insurer_name.to_string()),
+      member_number: ActiveValue::Set(
+        params
+          .member_number
+          .as_deref()
+          .map(str::trim)
+          .filter(|number| !number.is_empty())
+          .map(str::to_string),
+      ),
+      valid_until: ActiveValue::Set(valid_until),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_insurance)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_patient(patient_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(patient_insurances::Column::PatientId.eq(patient_id))
+      .order_by_asc(patient_insurances::Column::Kind)
+  }
+}
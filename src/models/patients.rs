@@ -1,12 +1,22 @@
 use crate::{
-  auth::resource::Resource,
+  auth::resource::{Permission, Resource},
+  initializers,
   models::{
-    _entities::patients,
+    _entities::{
+      patients,
+      sea_orm_active_enums::{Locale, PatientSex},
+      users,
+    },
     my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
+    patient_accesses,
   },
   services::crypto::Crypto,
-  validators::address::is_address_valid,
+  validators::{
+    address::is_address_valid,
+    patient::{is_birth_date_valid, is_phone_number_valid, is_referring_doctor_valid},
+  },
 };
+use chrono::NaiveDate;
 
 pub use super::_entities::patients::{ActiveModel, Entity, Model};
 use sea_orm::{entity::prelude::*, ActiveValue, IntoActiveModel};
@@ -22,9 +32,57 @@ pub struct CreatePatientParams {
   pub address_zip_code: String,
   pub address_city: String,
   pub email: String,
+  pub birth_date: Option<String>,
+  pub phone_number: Option<String>,
+  pub sex: Option<PatientSex>,
+  pub referring_doctor: Option<String>,
+  /// Language of the invoices and emails, the practitioner's one when unset
+  pub locale: Option<Locale>,
+}
+
+/// Optional identity details of a patient, once validated
+struct IdentityDetails {
+  birth_date: Option<NaiveDate>,
+  phone_number: Option<String>,
+  referring_doctor: Option<String>,
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+  value
+    .as_deref()
+    .map(str::trim)
+    .filter(|v| !v.is_empty())
+    .map(str::to_string)
+}
+
+impl CreatePatientParams {
+  fn identity_details(&self) -> Result<IdentityDetails, MyErrors> {
+    let birth_date = non_empty(&self.birth_date)
+      .map(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d"))
+      .transpose()
+      .map_err(|_| ApplicationError::UnprocessableEntity)?;
+    let phone_number = non_empty(&self.phone_number);
+    let referring_doctor = non_empty(&self.referring_doctor);
+
+    let is_valid = birth_date.is_none_or(is_birth_date_valid)
+      && phone_number.as_deref().is_none_or(is_phone_number_valid)
+      && referring_doctor
+        .as_deref()
+        .is_none_or(is_referring_doctor_valid);
+    if !is_valid {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    Ok(IdentityDetails {
+      birth_date,
+      phone_number,
+      referring_doctor,
+    })
+  }
 }
 
 pub const DEFAULT_EMAIL: &str = "default@mail.com";
+pub const ANONYMIZED_PLACEHOLDER: &str = "ANONYME";
 
 // Encryption utilities for SSN
 // implement your read-oriented logic here
@@ -55,6 +113,14 @@ impl Model {
     Ok(patients)
   }
 
+  /// Language in which the documents of this patient are produced
+  pub fn document_locale(&self, practitioner: &users::Model) -> Locale {
+    self
+      .locale
+      .clone()
+      .unwrap_or_else(|| practitioner.locale.clone())
+  }
+
   pub async fn search_by_pid<C: ConnectionTrait>(
     db: &C,
     pid: Uuid,
@@ -95,6 +161,7 @@ impl ActiveModel {
       return Err(ApplicationError::UnprocessableEntity.into());
     }
 
+    let details = params.identity_details()?;
     let ssn_encrypted = Model::encrypt_ssn(&params.ssn)?;
     let ssn_hashed = Model::hash_ssn(&params.ssn)?;
 
@@ -109,6 +176,11 @@ impl ActiveModel {
         address_zip_code: ActiveValue::Set(params.address_zip_code.clone()),
         address_city: ActiveValue::Set(params.address_city.clone()),
         address_country: ActiveValue::Set("FRANCE".to_string()),
+        birth_date: ActiveValue::Set(details.birth_date),
+        phone_number: ActiveValue::Set(details.phone_number),
+        sex: ActiveValue::Set(params.sex.clone()),
+        referring_doctor: ActiveValue::Set(details.referring_doctor),
+        locale: ActiveValue::Set(params.locale.clone()),
         user_id: ActiveValue::Set(linked_to_user_id),
         ..Default::default()
       }
@@ -131,6 +203,7 @@ impl ActiveModel {
     if !is_address_valid(&params.address_line_1, &params.address_zip_code) {
       return Err(ApplicationError::UnprocessableEntity.into());
     }
+    let details = params.identity_details()?;
 
     patient.first_name = ActiveValue::Set(params.first_name.trim().to_string());
     patient.last_name = ActiveValue::Set(params.last_name.trim().to_string());
@@ -138,19 +211,66 @@ impl ActiveModel {
     patient.address_line_1 = ActiveValue::Set(params.address_line_1.trim().to_string());
     patient.address_zip_code = ActiveValue::Set(params.address_zip_code.trim().to_string());
     patient.address_city = ActiveValue::Set(params.address_city.trim().to_string());
+    patient.birth_date = ActiveValue::Set(details.birth_date);
+    patient.phone_number = ActiveValue::Set(details.phone_number);
+    patient.sex = ActiveValue::Set(params.sex.clone());
+    patient.referring_doctor = ActiveValue::Set(details.referring_doctor);
+    patient.locale = ActiveValue::Set(params.locale.clone());
 
     patient.update(db).await?;
 
     Ok(())
   }
+
+  /// Scrub every identifying field of the patient while keeping the row, so that
+  /// the linked medical appointments remain available for accounting purposes.
+  pub async fn anonymize<T: ConnectionTrait>(self, db: &T) -> Result<Model, MyErrors> {
+    let mut patient = self;
+
+    patient.first_name = ActiveValue::Set(ANONYMIZED_PLACEHOLDER.to_string());
+    patient.last_name = ActiveValue::Set(ANONYMIZED_PLACEHOLDER.to_string());
+    patient.email = ActiveValue::Set(DEFAULT_EMAIL.to_string());
+    patient.ssn = ActiveValue::Set(String::new());
+    patient.hashed_ssn = ActiveValue::Set(String::new());
+    patient.address_line_1 = ActiveValue::Set(String::new());
+    patient.address_zip_code = ActiveValue::Set(String::new());
+    patient.address_city = ActiveValue::Set(String::new());
+    patient.birth_date = ActiveValue::Set(None);
+    patient.phone_number = ActiveValue::Set(None);
+    patient.sex = ActiveValue::Set(None);
+    patient.referring_doctor = ActiveValue::Set(None);
+    patient.locale = ActiveValue::Set(None);
+    patient.anonymized_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+
+    Ok(patient.update(db).await?)
+  }
 }
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+  /// Find a patient by id, ignoring the ones that have been anonymized
+  pub fn find_active_by_id(patient_id: i32) -> Select<Entity> {
+    Self::find_by_id(patient_id).filter(patients::Column::AnonymizedAt.is_null())
+  }
+}
 
 impl Resource for Model {
-  async fn is_owned_by_user(&self, user_id: i32) -> bool {
-    self.user_id == user_id
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    if self.user_id == user_id {
+      return Some(Permission::Own);
+    }
+
+    let services = initializers::get_services();
+
+    patient_accesses::Entity::find_granted(self.id, user_id)
+      .one(&services.db)
+      .await
+      .ok()?
+      .map(|access| Permission::from(&access.level))
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
   }
 
   fn resource_name(&self) -> String {
@@ -0,0 +1,127 @@
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::{
+  _entities::{medical_appointments, payments, sea_orm_active_enums::PaymentMethod},
+  my_errors::{application_error::ApplicationError, MyErrors},
+};
+
+pub use super::_entities::payments::{ActiveModel, Entity, Model};
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePaymentParams {
+  pub amount_in_cents: i32,
+  pub method: PaymentMethod,
+  pub paid_on: String,
+  pub reference: Option<String>,
+}
+
+/// Settlement state of the patient share of an appointment, derived from its payments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+  Paid,
+  PartiallyPaid,
+  Unpaid,
+}
+
+impl PaymentStatus {
+  pub fn of(appointment: &medical_appointments::Model, paid_in_cents: i64) -> Self {
+    if paid_in_cents >= i64::from(appointment.patient_share_in_cents()) {
+      Self::Paid
+    } else if paid_in_cents > 0 {
+      Self::PartiallyPaid
+    } else {
+      Self::Unpaid
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  pub async fn create<T: ConnectionTrait>(
+    db: &T,
+    params: &CreatePaymentParams,
+    medical_appointment_id: i32,
+  ) -> Result<Model, MyErrors> {
+    if params.amount_in_cents <= 0 {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    let paid_on = chrono::NaiveDate::parse_from_str(&params.paid_on, "%Y-%m-%d")
+      .map_err(|_| ApplicationError::UnprocessableEntity)?;
+
+    let reference = params
+      .reference
+      .as_deref()
+      .map(str::trim)
+      .filter(|reference| !reference.is_empty());
+    if reference.is_some_and(|reference| reference.len() > 100) {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    let created_payment = ActiveModel {
+      medical_appointment_id: ActiveValue::Set(medical_appointment_id),
+      amount_in_cents: ActiveValue::Set(params.amount_in_cents),
+      method: ActiveValue::Set(params.method.clone()),
+      paid_on: ActiveValue::Set(paid_on),
+      reference: ActiveValue::Set(reference.map(str::to_string)),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(created_payment)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_appointment(medical_appointment_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(payments::Column::MedicalAppointmentId.eq(medical_appointment_id))
+      .order_by_asc(payments::Column::PaidOn)
+      .order_by_asc(payments::Column::Id)
+  }
+
+  /// Total amount received for each of the given appointments
+  pub async fn paid_by_appointment<T: ConnectionTrait>(
+    db: &T,
+    medical_appointment_ids: Vec<i32>,
+  ) -> Result<HashMap<i32, i64>, MyErrors> {
+    let totals: Vec<(i32, Option<i64>)> = Self::find()
+      .select_only()
+      .column(payments::Column::MedicalAppointmentId)
+      .column_as(payments::Column::AmountInCents.sum(), "paid_in_cents")
+      .filter(payments::Column::MedicalAppointmentId.is_in(medical_appointment_ids))
+      .group_by(payments::Column::MedicalAppointmentId)
+      .into_tuple()
+      .all(db)
+      .await?;
+
+    Ok(
+      totals
+        .into_iter()
+        .map(|(appointment_id, paid_in_cents)| (appointment_id, paid_in_cents.unwrap_or(0)))
+        .collect(),
+    )
+  }
+}
@@ -1,6 +1,6 @@
 pub use super::_entities::practitioner_offices::{ActiveModel, Entity, Model};
 use crate::{
-  auth::resource::Resource,
+  auth::resource::{Permission, Resource},
   initializers,
   models::{
     _entities::{practitioner_offices, user_practitioner_offices},
@@ -33,6 +33,24 @@ pub struct PractitionerOfficeParams {
   pub address_line_1: String,
   pub address_zip_code: String,
   pub address_city: String,
+  /// Where the monthly retrocession statements are sent
+  #[serde(default)]
+  pub contact_email: Option<String>,
+}
+
+impl PractitionerOfficeParams {
+  /// Trimmed contact email, `None` when left blank
+  pub fn contact_email(&self) -> Result<Option<String>, MyErrors> {
+    match self.contact_email.as_deref().map(str::trim) {
+      None | Some("") => Ok(None),
+      Some(email) => {
+        email
+          .parse::<lettre::Address>()
+          .map_err(|_| ApplicationError::UnprocessableEntity)?;
+        Ok(Some(email.to_string()))
+      }
+    }
+  }
 }
 
 // implement your read-oriented logic here
@@ -55,6 +73,7 @@ impl ActiveModel {
         address_zip_code: ActiveValue::Set(params.address_zip_code.trim().to_string()),
         address_city: ActiveValue::Set(params.address_city.trim().to_string()),
         address_country: ActiveValue::Set("FRANCE".to_string()),
+        contact_email: ActiveValue::Set(params.contact_email()?),
         ..Default::default()
       }
       .insert(db)
@@ -67,7 +86,7 @@ impl ActiveModel {
 impl Entity {}
 
 impl Resource for Model {
-  async fn is_owned_by_user(&self, user_id: i32) -> bool {
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
     let services = initializers::get_services();
 
     let result = user_practitioner_offices::Entity::find()
@@ -77,11 +96,15 @@ impl Resource for Model {
       .await;
 
     match result {
-      Ok(association) => association.is_some(),
-      Err(_) => false,
+      Ok(association) => association.map(|_| Permission::Own),
+      Err(_) => None,
     }
   }
 
+  fn resource_id(&self) -> i32 {
+    self.id
+  }
+
   fn resource_name(&self) -> String {
     "practitioner_office".to_string()
   }
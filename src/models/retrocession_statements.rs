@@ -0,0 +1,170 @@
+use chrono::{Datelike, Months};
+use sea_orm::{entity::prelude::*, ActiveValue, IntoActiveModel, QueryOrder, TryIntoModel};
+
+use crate::{
+  auth::resource::{Permission, Resource},
+  models::{
+    _entities::retrocession_statements,
+    my_errors::{application_error::ApplicationError, MyErrors},
+    revenue_share_rates::RetrocessionTerms,
+  },
+};
+
+pub use super::_entities::retrocession_statements::{ActiveModel, Entity, Model};
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+/// Amounts of a month of work in an office
+#[derive(Debug, Clone)]
+pub struct StatementFigures {
+  pub appointments_count: i32,
+  pub fees_in_cents: i64,
+  pub retrocession_in_cents: i64,
+  /// Terms in force at the end of the month
+  pub terms: RetrocessionTerms,
+}
+
+/// Parse a `YYYY-MM` month into its first day
+pub fn parse_month(month: &str) -> Result<Date, MyErrors> {
+  Date::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+    .map_err(|_| ApplicationError::UnprocessableEntity.into())
+}
+
+/// Last day of the month starting on `month`
+pub fn month_end(month: Date) -> Date {
+  month
+    .checked_add_months(Months::new(1))
+    .and_then(|next_month| next_month.pred_opt())
+    .unwrap_or(month)
+}
+
+// implement your read-oriented logic here
+impl Model {
+  /// Last day of the month covered by the statement
+  pub fn month_end(&self) -> Date {
+    month_end(self.month)
+  }
+
+  /// e.g. `03/2026`
+  pub fn month_label(&self) -> String {
+    format!("{:02}/{}", self.month.month(), self.month.year())
+  }
+
+  /// Retrocession terms applied at the end of the month
+  pub fn terms(&self) -> RetrocessionTerms {
+    RetrocessionTerms {
+      kind: self.retrocession_kind.clone(),
+      percentage: self.revenue_share_percentage,
+      amount_in_cents: self.retrocession_amount_in_cents,
+    }
+  }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Record the statement of `month`, replacing the figures of a previous
+  /// statement for the same month. A statement already sent is what the
+  /// office holds, and cannot be replaced anymore.
+  pub async fn upsert<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    practitioner_office_id: i32,
+    month: Date,
+    figures: &StatementFigures,
+  ) -> Result<Model, MyErrors> {
+    let existing = Entity::find()
+      .filter(retrocession_statements::Column::UserId.eq(user_id))
+      .filter(retrocession_statements::Column::PractitionerOfficeId.eq(practitioner_office_id))
+      .filter(retrocession_statements::Column::Month.eq(month))
+      .one(db)
+      .await?;
+
+    let mut statement = match existing {
+      Some(statement) if statement.sent_at.is_some() => {
+        return Err(ApplicationError::UnprocessableEntity.into())
+      }
+      Some(statement) => statement.into_active_model(),
+      None => ActiveModel {
+        user_id: ActiveValue::Set(user_id),
+        practitioner_office_id: ActiveValue::Set(practitioner_office_id),
+        month: ActiveValue::Set(month),
+        ..Default::default()
+      },
+    };
+
+    statement.appointments_count = ActiveValue::Set(figures.appointments_count);
+    statement.fees_in_cents = ActiveValue::Set(figures.fees_in_cents);
+    statement.revenue_share_percentage = ActiveValue::Set(figures.terms.percentage);
+    statement.retrocession_kind = ActiveValue::Set(figures.terms.kind.clone());
+    statement.retrocession_amount_in_cents = ActiveValue::Set(figures.terms.amount_in_cents);
+    statement.retrocession_in_cents = ActiveValue::Set(figures.retrocession_in_cents);
+
+    Ok(statement.save(db).await?.try_into_model()?)
+  }
+
+  pub async fn mark_as_sent<T: ConnectionTrait>(
+    self,
+    db: &T,
+    sent_to: String,
+  ) -> Result<Model, MyErrors> {
+    let mut statement = self;
+    statement.sent_to = ActiveValue::Set(Some(sent_to));
+    statement.sent_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+    Ok(statement.update(db).await?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_office(user_id: i32, practitioner_office_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(retrocession_statements::Column::UserId.eq(user_id))
+      .filter(retrocession_statements::Column::PractitionerOfficeId.eq(practitioner_office_id))
+      .order_by_desc(retrocession_statements::Column::Month)
+  }
+
+  /// Sent statements of the office covering at least one day of
+  /// `[start, end)`, where a `None` end leaves the range open
+  pub fn find_sent_overlapping(
+    user_id: i32,
+    practitioner_office_id: i32,
+    start: Date,
+    end: Option<Date>,
+  ) -> Select<Entity> {
+    let mut query = Self::find()
+      .filter(retrocession_statements::Column::UserId.eq(user_id))
+      .filter(retrocession_statements::Column::PractitionerOfficeId.eq(practitioner_office_id))
+      .filter(retrocession_statements::Column::SentAt.is_not_null())
+      .filter(retrocession_statements::Column::Month.gte(start.with_day(1).unwrap_or(start)));
+    if let Some(end) = end {
+      query = query.filter(retrocession_statements::Column::Month.lt(end));
+    }
+    query
+  }
+}
+
+impl Resource for Model {
+  async fn permission_for_user(&self, user_id: i32) -> Option<Permission> {
+    (self.user_id == user_id).then_some(Permission::Own)
+  }
+
+  fn resource_id(&self) -> i32 {
+    self.id
+  }
+
+  fn resource_name(&self) -> String {
+    "retrocession_statements".to_string()
+  }
+}
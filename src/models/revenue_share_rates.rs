@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use sea_orm::{entity::prelude::*, ActiveValue, IntoActiveModel, QueryOrder, TryIntoModel};
+
+use crate::{
+  locales::fill,
+  models::{
+    _entities::{
+      revenue_share_rates,
+      sea_orm_active_enums::{Locale, RetrocessionKind},
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+  },
+};
+
+pub use super::_entities::revenue_share_rates::{ActiveModel, Entity, Model};
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+/// How the amount owed to the office holder is computed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrocessionTerms {
+  pub kind: RetrocessionKind,
+  /// Share of each fee, for the percentage kinds
+  pub percentage: Decimal,
+  /// Monthly fee, fee per appointment or monthly cap, depending on the kind
+  pub amount_in_cents: Option<i32>,
+}
+
+impl RetrocessionTerms {
+  pub fn percentage(percentage: Decimal) -> Self {
+    Self {
+      kind: RetrocessionKind::Percentage,
+      percentage,
+      amount_in_cents: None,
+    }
+  }
+
+  pub fn validate(&self) -> Result<(), MyErrors> {
+    let uses_percentage = matches!(
+      self.kind,
+      RetrocessionKind::Percentage | RetrocessionKind::CappedPercentage
+    );
+    if uses_percentage
+      && (self.percentage < Decimal::ZERO || self.percentage > Decimal::ONE_HUNDRED)
+    {
+      return Err(ApplicationError::BadRequest.into());
+    }
+
+    let needs_amount = self.kind != RetrocessionKind::Percentage;
+    if needs_amount && self.amount_in_cents.is_none_or(|amount| amount <= 0) {
+      return Err(ApplicationError::new("retrocession_amount_required").into());
+    }
+
+    Ok(())
+  }
+
+  fn amount(&self) -> Decimal {
+    Decimal::from(self.amount_in_cents.unwrap_or_default())
+  }
+
+  /// Part of a single appointment owed to the office holder, in cents. A
+  /// fixed monthly fee does not depend on the appointments.
+  pub fn appointment_share(&self, price_in_cents: i32) -> Decimal {
+    match self.kind {
+      RetrocessionKind::Percentage | RetrocessionKind::CappedPercentage => {
+        Decimal::from(price_in_cents) * self.percentage / Decimal::ONE_HUNDRED
+      }
+      RetrocessionKind::PerAppointmentFee => self.amount(),
+      RetrocessionKind::FixedMonthlyFee => Decimal::ZERO,
+    }
+  }
+
+  /// Amount owed for a month under these terms, given the sum of the
+  /// appointment shares of that month, rounded to the cent
+  pub fn monthly_amount(&self, appointment_shares: Decimal) -> i64 {
+    self.prorated_amount(appointment_shares, Decimal::ONE)
+  }
+
+  /// Amount owed for the `month_fraction` of a month covered by a period,
+  /// given the sum of the appointment shares of that period: the monthly fee
+  /// or cap is only due for that fraction of the month
+  pub fn prorated_amount(&self, appointment_shares: Decimal, month_fraction: Decimal) -> i64 {
+    let amount = self.amount() * month_fraction;
+    let owed = match self.kind {
+      RetrocessionKind::FixedMonthlyFee => amount + appointment_shares,
+      RetrocessionKind::CappedPercentage => appointment_shares.min(amount),
+      RetrocessionKind::Percentage | RetrocessionKind::PerAppointmentFee => appointment_shares,
+    };
+    owed.round().try_into().unwrap_or_default()
+  }
+
+  /// e.g. `20 % plafonné à 500.00€ par mois`
+  pub fn label(&self, locale: &Locale) -> String {
+    let messages = locale.messages();
+    let amount = fill(
+      messages.amount,
+      &[&format!(
+        "{:.2}",
+        f64::from(self.amount_in_cents.unwrap_or_default()) / 100.0
+      )],
+    );
+    let percentage = self.percentage.normalize().to_string();
+    match self.kind {
+      RetrocessionKind::Percentage => fill(messages.percentage_terms, &[&percentage]),
+      RetrocessionKind::CappedPercentage => {
+        fill(messages.capped_percentage_terms, &[&percentage, &amount])
+      }
+      RetrocessionKind::FixedMonthlyFee => fill(messages.fixed_monthly_fee_terms, &[&amount]),
+      RetrocessionKind::PerAppointmentFee => fill(messages.per_appointment_fee_terms, &[&amount]),
+    }
+  }
+}
+
+/// Validity start of the rate set when the practitioner joins an office, so
+/// that it covers every appointment recorded before any later change
+pub fn initial_valid_from() -> Date {
+  Date::from_ymd_opt(1970, 1, 1).unwrap_or_default()
+}
+
+/// Retrocession terms of a practitioner, by office and validity date
+#[derive(Debug, Default)]
+pub struct RateSchedule {
+  rates_by_office: HashMap<i32, Vec<Model>>,
+}
+
+impl RateSchedule {
+  pub fn new(rates: Vec<Model>) -> Self {
+    let mut rates_by_office: HashMap<i32, Vec<Model>> = HashMap::new();
+    for rate in rates {
+      rates_by_office
+        .entry(rate.practitioner_office_id)
+        .or_default()
+        .push(rate);
+    }
+    for rates in rates_by_office.values_mut() {
+      rates.sort_by_key(|rate| rate.valid_from);
+    }
+    Self { rates_by_office }
+  }
+
+  /// Terms in force in the office on `date`
+  pub fn terms_on(&self, office_id: i32, date: Date) -> Option<RetrocessionTerms> {
+    self
+      .rates_by_office
+      .get(&office_id)?
+      .iter()
+      .rev()
+      .find(|rate| rate.valid_from <= date)
+      .map(Model::terms)
+  }
+
+  /// Offices for which terms have been set
+  pub fn office_ids(&self) -> Vec<i32> {
+    self.rates_by_office.keys().copied().collect()
+  }
+
+  /// Day on which the rate in force on `date` gets replaced, if ever
+  pub fn next_change_after(&self, office_id: i32, date: Date) -> Option<Date> {
+    self
+      .rates_by_office
+      .get(&office_id)?
+      .iter()
+      .map(|rate| rate.valid_from)
+      .find(|valid_from| *valid_from > date)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {
+  pub fn terms(&self) -> RetrocessionTerms {
+    RetrocessionTerms {
+      kind: self.kind.clone(),
+      percentage: self.percentage,
+      amount_in_cents: self.amount_in_cents,
+    }
+  }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Set the terms applied from `valid_from` on, replacing the ones that
+  /// started the same day if any
+  pub async fn set<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    practitioner_office_id: i32,
+    terms: &RetrocessionTerms,
+    valid_from: Date,
+  ) -> Result<Model, MyErrors> {
+    let existing = Entity::find()
+      .filter(revenue_share_rates::Column::UserId.eq(user_id))
+      .filter(revenue_share_rates::Column::PractitionerOfficeId.eq(practitioner_office_id))
+      .filter(revenue_share_rates::Column::ValidFrom.eq(valid_from))
+      .one(db)
+      .await?;
+
+    let mut rate = match existing {
+      Some(rate) => rate.into_active_model(),
+      None => ActiveModel {
+        user_id: ActiveValue::Set(user_id),
+        practitioner_office_id: ActiveValue::Set(practitioner_office_id),
+        valid_from: ActiveValue::Set(valid_from),
+        ..Default::default()
+      },
+    };
+    rate.kind = ActiveValue::Set(terms.kind.clone());
+    rate.percentage = ActiveValue::Set(terms.percentage);
+    rate.amount_in_cents = ActiveValue::Set(terms.amount_in_cents);
+
+    Ok(rate.save(db).await?.try_into_model()?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_user(user_id: i32) -> Select<Entity> {
+    Self::find()
+      .filter(revenue_share_rates::Column::UserId.eq(user_id))
+      .order_by_asc(revenue_share_rates::Column::ValidFrom)
+  }
+
+  pub fn find_for_office(user_id: i32, practitioner_office_id: i32) -> Select<Entity> {
+    Self::find_for_user(user_id)
+      .filter(revenue_share_rates::Column::PractitionerOfficeId.eq(practitioner_office_id))
+  }
+}
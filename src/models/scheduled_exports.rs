@@ -0,0 +1,77 @@
+use sea_orm::{entity::prelude::*, ActiveValue, TryIntoModel};
+use serde::Deserialize;
+use validator::ValidateEmail;
+
+use crate::models::{
+  _entities::{
+    scheduled_exports,
+    sea_orm_active_enums::{ExportFormat, ExportFrequency},
+  },
+  my_errors::{application_error::ApplicationError, MyErrors},
+};
+
+pub use super::_entities::scheduled_exports::{ActiveModel, Entity, Model};
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduledExportParams {
+  pub frequency: ExportFrequency,
+  pub format: ExportFormat,
+  pub recipient_email: String,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut this = self;
+    if !insert && this.updated_at.is_unchanged() {
+      this.updated_at = ActiveValue::Set(chrono::Utc::now().into())
+    }
+    Ok(this)
+  }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+  /// Create or replace the scheduled export of `user_id`. `last_period_start`
+  /// is the period considered already sent, so that saving the settings does
+  /// not trigger an export of a period the user may have extracted by hand.
+  pub async fn save_for_user<T: ConnectionTrait>(
+    db: &T,
+    user_id: i32,
+    params: &ScheduledExportParams,
+    last_period_start: Date,
+  ) -> Result<Model, MyErrors> {
+    let recipient_email = params.recipient_email.trim();
+    if !recipient_email.validate_email() {
+      return Err(ApplicationError::UnprocessableEntity.into());
+    }
+
+    let existing = Entity::find_for_user(user_id).one(db).await?;
+    let mut scheduled_export = match existing {
+      Some(scheduled_export) => scheduled_export.into(),
+      None => ActiveModel {
+        user_id: ActiveValue::Set(user_id),
+        ..Default::default()
+      },
+    };
+    scheduled_export.frequency = ActiveValue::Set(params.frequency.clone());
+    scheduled_export.format = ActiveValue::Set(params.format.clone());
+    scheduled_export.recipient_email = ActiveValue::Set(recipient_email.to_string());
+    scheduled_export.last_period_start = ActiveValue::Set(Some(last_period_start));
+
+    Ok(scheduled_export.save(db).await?.try_into_model()?)
+  }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+  pub fn find_for_user(user_id: i32) -> Select<Entity> {
+    Self::find().filter(scheduled_exports::Column::UserId.eq(user_id))
+  }
+}
@@ -51,7 +51,12 @@ impl ActiveModelBehavior for ActiveModel {
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+  /// The business itself, the SIRET adds the establishment
+  pub fn siren(&self) -> &str {
+    self.siret_number.get(..9).unwrap_or(&self.siret_number)
+  }
+}
 
 // implement your write-oriented logic here
 impl ActiveModel {
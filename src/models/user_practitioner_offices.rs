@@ -1,4 +1,8 @@
-use crate::models::{_entities::user_practitioner_offices, my_errors::MyErrors};
+use crate::models::{
+  _entities::user_practitioner_offices,
+  my_errors::MyErrors,
+  revenue_share_rates::{self, RetrocessionTerms},
+};
 
 pub use super::_entities::user_practitioner_offices::{ActiveModel, Entity, Model};
 use sea_orm::{entity::prelude::*, ActiveValue};
@@ -21,29 +25,51 @@ impl ActiveModelBehavior for ActiveModel {
 
 pub struct CreateLinkParams {
   pub user_id: i32,
-  pub revenue_share_percentage: Decimal,
+  pub terms: RetrocessionTerms,
   pub practitioner_office_id: i32,
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+  /// Retrocession terms in force today
+  pub fn terms(&self) -> RetrocessionTerms {
+    RetrocessionTerms {
+      kind: self.retrocession_kind.clone(),
+      percentage: self.revenue_share_percentage,
+      amount_in_cents: self.retrocession_amount_in_cents,
+    }
+  }
+}
 
 // implement your write-oriented logic here
 impl ActiveModel {
+  /// Link the practitioner to the office, along with the first retrocession
+  /// terms of that office
   pub async fn create<T: ConnectionTrait>(
     db: &T,
     params: &CreateLinkParams,
   ) -> Result<Model, MyErrors> {
-    return Ok(
-      user_practitioner_offices::ActiveModel {
-        user_id: ActiveValue::Set(params.user_id),
-        practitioner_office_id: ActiveValue::Set(params.practitioner_office_id),
-        revenue_share_percentage: ActiveValue::Set(params.revenue_share_percentage),
-        ..Default::default()
-      }
-      .insert(db)
-      .await?,
-    );
+    let link = user_practitioner_offices::ActiveModel {
+      user_id: ActiveValue::Set(params.user_id),
+      practitioner_office_id: ActiveValue::Set(params.practitioner_office_id),
+      revenue_share_percentage: ActiveValue::Set(params.terms.percentage),
+      retrocession_kind: ActiveValue::Set(params.terms.kind.clone()),
+      retrocession_amount_in_cents: ActiveValue::Set(params.terms.amount_in_cents),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    revenue_share_rates::ActiveModel::set(
+      db,
+      link.user_id,
+      link.practitioner_office_id,
+      &params.terms,
+      revenue_share_rates::initial_valid_from(),
+    )
+    .await?;
+
+    Ok(link)
   }
 }
 
@@ -9,11 +9,12 @@ use validator::Validate;
 use crate::{
   auth::password,
   models::{
-    ModelError, ModelResult,
     _entities::{
-      prelude::UserBusinessInformations, user_business_informations, user_practitioner_offices,
+      prelude::UserBusinessInformations,
+      sea_orm_active_enums::{Locale, UserRole},
+      user_business_informations, user_practitioner_offices,
     },
-    practitioner_offices,
+    practitioner_offices, ModelError, ModelResult,
   },
   services,
 };
@@ -73,6 +74,14 @@ impl Model {
     format!("{} {}", &self.first_name, &self.last_name)
   }
 
+  pub fn is_admin(&self) -> bool {
+    self.role == UserRole::Admin
+  }
+
+  pub fn is_suspended(&self) -> bool {
+    self.suspended_at.is_some()
+  }
+
   pub async fn get_my_offices(
     &self,
     db: &DatabaseConnection,
@@ -187,6 +196,27 @@ impl ActiveModel {
     Ok(())
   }
 
+  /// Grants access to the platform and lifts any previous suspension
+  pub async fn approve(mut self, db: &DatabaseConnection) -> ModelResult<Model> {
+    self.is_access_key_verified = ActiveValue::Set(true);
+    self.suspended_at = ActiveValue::Set(None);
+
+    Ok(self.update(db).await?)
+  }
+
+  pub async fn suspend(mut self, db: &DatabaseConnection) -> ModelResult<Model> {
+    self.suspended_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+
+    Ok(self.update(db).await?)
+  }
+
+  /// Language of the emails sent to the user, and of their patients' documents by default
+  pub async fn set_locale(mut self, db: &DatabaseConnection, locale: Locale) -> ModelResult<Model> {
+    self.locale = ActiveValue::Set(locale);
+
+    Ok(self.update(db).await?)
+  }
+
   pub async fn update_password(
     mut self,
     db: &DatabaseConnection,
@@ -21,6 +21,10 @@ pub fn create_router(state: AppState) -> Router {
     .route(
       "/api/auth/_check_access_key",
       post(controllers::auth::check_access_key),
+    )
+    .route(
+      "/api/patient_accesses/_consent/{token}",
+      post(controllers::patient_access::consent),
     );
 
   // Protected routes (require authentication)
@@ -43,6 +47,18 @@ pub fn create_router(state: AppState) -> Router {
       get(controllers::patient::search_by_ssn),
     )
     .route("/api/patient/_search", get(controllers::patient::search))
+    .route(
+      "/api/patient/_duplicates",
+      get(controllers::patient::duplicates),
+    )
+    .route(
+      "/api/patient/{patient_id}/_merge",
+      post(controllers::patient::merge),
+    )
+    .route(
+      "/api/patient/{patient_id}/_export",
+      get(controllers::patient::export),
+    )
     .route(
       "/api/patient/{patient_id}/_generate_invoice",
       post(controllers::patient::generate_invoice),
@@ -57,6 +73,110 @@ pub fn create_router(state: AppState) -> Router {
       put(controllers::medical_appointment::update)
         .delete(controllers::medical_appointment::delete),
     )
+    .route(
+      "/api/patient/{patient_id}/medical_appointments/{appointment_id}/payments",
+      get(controllers::payment::list).post(controllers::payment::create),
+    )
+    .route(
+      "/api/patient/{patient_id}/medical_appointments/{appointment_id}/payments/{payment_id}",
+      delete(controllers::payment::delete),
+    )
+    .route(
+      "/api/medical_appointments",
+      get(controllers::medical_appointment::calendar),
+    )
+    .route(
+      "/api/patient/{patient_id}/emails",
+      get(controllers::email_log::list),
+    )
+    .route(
+      "/api/patient/{patient_id}/emails/{email_log_id}/_resend",
+      post(controllers::email_log::resend),
+    )
+    .route(
+      "/api/patient/{patient_id}/insurances",
+      get(controllers::patient_insurance::list).post(controllers::patient_insurance::create),
+    )
+    .route(
+      "/api/patient/{patient_id}/insurances/{insurance_id}",
+      delete(controllers::patient_insurance::delete),
+    )
+    // Patient access routes
+    .route(
+      "/api/patient/_request_access",
+      post(controllers::patient_access::request_access),
+    )
+    .route(
+      "/api/patient_accesses",
+      get(controllers::patient_access::list),
+    )
+    .route(
+      "/api/patient_accesses/{access_id}/_grant",
+      post(controllers::patient_access::grant),
+    )
+    .route(
+      "/api/patient_accesses/{access_id}",
+      delete(controllers::patient_access::revoke),
+    )
+    // Check deposit routes
+    .route(
+      "/api/check_deposits",
+      get(controllers::check_deposit::list).post(controllers::check_deposit::create),
+    )
+    .route(
+      "/api/check_deposits/_pending",
+      get(controllers::check_deposit::pending),
+    )
+    .route(
+      "/api/check_deposits/{deposit_id}/_slip",
+      get(controllers::check_deposit::slip),
+    )
+    // Retrocession statement routes
+    .route(
+      "/api/practitioner_office/{office_id}/retrocession_statements",
+      get(controllers::retrocession_statement::list)
+        .post(controllers::retrocession_statement::create),
+    )
+    .route(
+      "/api/retrocession_statements/{statement_id}/_pdf",
+      get(controllers::retrocession_statement::pdf),
+    )
+    .route(
+      "/api/retrocession_statements/{statement_id}/_xlsx",
+      get(controllers::retrocession_statement::spreadsheet),
+    )
+    .route(
+      "/api/retrocession_statements/{statement_id}/_send",
+      post(controllers::retrocession_statement::send),
+    )
+    // Expense routes
+    .route(
+      "/api/expenses",
+      get(controllers::expense::list).post(controllers::expense::create),
+    )
+    .route(
+      "/api/expenses/{expense_id}",
+      delete(controllers::expense::delete),
+    )
+    .route(
+      "/api/expenses/_fiscal_year_report",
+      get(controllers::expense::fiscal_year_report),
+    )
+    .route(
+      "/api/expenses/_fiscal_year_report/_download",
+      get(controllers::expense::download_fiscal_year_report),
+    )
+    // Act routes
+    .route(
+      "/api/acts",
+      get(controllers::act::list).post(controllers::act::create),
+    )
+    .route(
+      "/api/acts/{act_id}",
+      put(controllers::act::update).delete(controllers::act::delete),
+    )
+    // Stats routes
+    .route("/api/stats", get(controllers::stats::index))
     // User routes
     .route(
       "/api/user/_save_business_information",
@@ -66,7 +186,32 @@ pub fn create_router(state: AppState) -> Router {
       "/api/user/_extract_medical_appointments",
       post(controllers::user::extract_medical_appointments),
     )
+    .route(
+      "/api/user/_extract_medical_appointments/_download",
+      get(controllers::user::download_medical_appointments),
+    )
+    .route(
+      "/api/user/scheduled_export",
+      get(controllers::user::scheduled_export)
+        .put(controllers::user::save_scheduled_export)
+        .delete(controllers::user::delete_scheduled_export),
+    )
+    .route("/api/user/locale", put(controllers::user::save_locale))
     .route("/api/user/my_offices", get(controllers::user::my_offices))
+    .route("/api/user/audit_logs", get(controllers::user::audit_logs))
+    .route("/api/user/receivables", get(controllers::user::receivables))
+    .route(
+      "/api/user/unpaid_balances",
+      get(controllers::user::unpaid_balances),
+    )
+    .route(
+      "/api/user/invoice_template",
+      get(controllers::user::invoice_template).put(controllers::user::save_invoice_template),
+    )
+    .route(
+      "/api/user/invoice_template/_upload_logo",
+      post(controllers::user::upload_invoice_logo),
+    )
     .route(
       "/api/user/signature/_get_url",
       post(controllers::user::get_signature_url),
@@ -88,6 +233,25 @@ pub fn create_router(state: AppState) -> Router {
       "/api/practitioner_office/{office_id}",
       delete(controllers::practitioner_office::destroy),
     )
+    .route(
+      "/api/practitioner_office/{office_id}/revenue_share_rates",
+      get(controllers::practitioner_office::revenue_share_rates),
+    )
+    // Admin routes
+    .route("/api/admin/users", get(controllers::admin::list_users))
+    .route(
+      "/api/admin/users/{user_id}/_approve",
+      post(controllers::admin::approve_user),
+    )
+    .route(
+      "/api/admin/users/{user_id}/_suspend",
+      post(controllers::admin::suspend_user),
+    )
+    .route(
+      "/api/admin/users/{user_id}/_resend_access_key",
+      post(controllers::admin::resend_access_key),
+    )
+    .route("/api/admin/stats", get(controllers::admin::stats))
     // Apply auth middleware to all protected routes
     .layer(middleware::from_fn_with_state(
       state.clone(),
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+
+use sea_orm::{
+  ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, TransactionTrait,
+};
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{act_office_prices, acts, user_practitioner_offices},
+    acts::{ActParams, OfficePriceParams},
+    my_errors::{application_error::ApplicationError, MyErrors},
+    users::users,
+  },
+};
+
+/// An act of the catalogue, along with its prices in specific offices
+pub type CatalogueAct = (acts::Model, Vec<act_office_prices::Model>);
+
+pub async fn catalogue(user: &users::Model) -> Result<Vec<CatalogueAct>, MyErrors> {
+  let db = &get_services().db;
+
+  let acts = acts::Entity::find_for_user(user.id).all(db).await?;
+  let office_prices = act_office_prices::Entity::find_for_acts(acts.iter().map(|act| act.id))
+    .all(db)
+    .await?;
+
+  Ok(
+    acts
+      .into_iter()
+      .map(|act| {
+        let prices = office_prices
+          .iter()
+          .filter(|price| price.act_id == act.id)
+          .cloned()
+          .collect();
+        (act, prices)
+      })
+      .collect(),
+  )
+}
+
+/// Office prices can only be set once per office, and for offices the user
+/// works in
+async fn check_office_prices<T: ConnectionTrait>(
+  db: &T,
+  user: &users::Model,
+  office_prices: &[OfficePriceParams],
+) -> Result<(), MyErrors> {
+  let office_ids: HashSet<i32> = office_prices
+    .iter()
+    .map(|office_price| office_price.practitioner_office_id)
+    .collect();
+  if office_ids.len() != office_prices.len() {
+    return Err(ApplicationError::UnprocessableEntity.into());
+  }
+
+  let linked_offices = user_practitioner_offices::Entity::find()
+    .filter(user_practitioner_offices::Column::UserId.eq(user.id))
+    .filter(user_practitioner_offices::Column::PractitionerOfficeId.is_in(office_ids.clone()))
+    .all(db)
+    .await?;
+  if linked_offices.len() != office_ids.len() {
+    return Err(ApplicationError::UnprocessableEntity.into());
+  }
+
+  Ok(())
+}
+
+pub async fn create(user: &users::Model, params: &ActParams) -> Result<CatalogueAct, MyErrors> {
+  let db_transaction = get_services().db.begin().await?;
+
+  check_office_prices(&db_transaction, user, &params.office_prices).await?;
+  let act = acts::ActiveModel::create(&db_transaction, user.id, params).await?;
+  let office_prices =
+    act_office_prices::ActiveModel::replace_for_act(&db_transaction, act.id, &params.office_prices)
+      .await?;
+
+  db_transaction.commit().await?;
+
+  Ok((act, office_prices))
+}
+
+pub async fn update(
+  act: acts::Model,
+  user: &users::Model,
+  params: &ActParams,
+) -> Result<CatalogueAct, MyErrors> {
+  let db_transaction = get_services().db.begin().await?;
+
+  check_office_prices(&db_transaction, user, &params.office_prices).await?;
+  let act = act
+    .into_active_model()
+    .update_from(&db_transaction, params)
+    .await?;
+  let office_prices =
+    act_office_prices::ActiveModel::replace_for_act(&db_transaction, act.id, &params.office_prices)
+      .await?;
+
+  db_transaction.commit().await?;
+
+  Ok((act, office_prices))
+}
+
+/// Price of `act` in the office, its default price unless the office has its own
+pub async fn price_at_office<T: ConnectionTrait>(
+  db: &T,
+  act: &acts::Model,
+  practitioner_office_id: i32,
+) -> Result<i32, MyErrors> {
+  let office_price = act_office_prices::Entity::find()
+    .filter(act_office_prices::Column::ActId.eq(act.id))
+    .filter(act_office_prices::Column::PractitionerOfficeId.eq(practitioner_office_id))
+    .one(db)
+    .await?;
+
+  Ok(office_price.map_or(act.default_price_in_cents, |price| price.price_in_cents))
+}
+
+/// Act and price of an appointment of `user_id` in one of their offices. The
+/// price typed in wins over the one of the catalogue, which is only used when
+/// the price is left out.
+pub async fn appointment_act<T: ConnectionTrait>(
+  db: &T,
+  user_id: i32,
+  act_id: Option<i32>,
+  practitioner_office_id: i32,
+  price_in_cents: Option<i32>,
+) -> Result<(Option<acts::Model>, i32), MyErrors> {
+  user_practitioner_offices::Entity::find_by_id((user_id, practitioner_office_id))
+    .one(db)
+    .await?
+    .ok_or(ApplicationError::UnprocessableEntity)?;
+
+  let act = match act_id {
+    Some(act_id) => Some(
+      acts::Entity::find_by_id(act_id)
+        .filter(acts::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or(ApplicationError::UnprocessableEntity)?,
+    ),
+    None => None,
+  };
+
+  let price_in_cents = match (price_in_cents, &act) {
+    (Some(price_in_cents), _) => price_in_cents,
+    (None, Some(act)) => price_at_office(db, act, practitioner_office_id).await?,
+    (None, None) => return Err(ApplicationError::UnprocessableEntity.into()),
+  };
+
+  Ok((act, price_in_cents))
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+  ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+  QuerySelect,
+};
+
+use crate::models::{
+  _entities::{medical_appointments, patients, practitioner_offices, users},
+  my_errors::MyErrors,
+};
+
+/// Aggregated figures about the platform usage. Only counts are exposed here so
+/// that administrators never get to see patient data.
+#[derive(Debug)]
+pub struct UsageStats {
+  pub users_count: u64,
+  pub pending_users_count: u64,
+  pub suspended_users_count: u64,
+  pub patients_count: u64,
+  pub medical_appointments_count: u64,
+  pub practitioner_offices_count: u64,
+}
+
+/// Per-user counters displayed next to each account in the back-office
+#[derive(Default, Clone, Copy)]
+pub struct UserUsage {
+  pub patients_count: i64,
+  pub medical_appointments_count: i64,
+}
+
+pub async fn list_users_with_usage(
+  db: &DatabaseConnection,
+) -> Result<Vec<(users::Model, UserUsage)>, MyErrors> {
+  let all_users = users::Entity::find()
+    .order_by_asc(users::Column::CreatedAt)
+    .all(db)
+    .await?;
+
+  let patients_by_user: HashMap<i32, i64> = patients::Entity::find()
+    .select_only()
+    .column(patients::Column::UserId)
+    .column_as(patients::Column::Id.count(), "count")
+    .group_by(patients::Column::UserId)
+    .into_tuple::<(i32, i64)>()
+    .all(db)
+    .await?
+    .into_iter()
+    .collect();
+
+  let appointments_by_user: HashMap<i32, i64> = medical_appointments::Entity::find()
+    .select_only()
+    .column(medical_appointments::Column::UserId)
+    .column_as(medical_appointments::Column::Id.count(), "count")
+    .group_by(medical_appointments::Column::UserId)
+    .into_tuple::<(i32, i64)>()
+    .all(db)
+    .await?
+    .into_iter()
+    .collect();
+
+  Ok(
+    all_users
+      .into_iter()
+      .map(|user| {
+        let usage = UserUsage {
+          patients_count: *patients_by_user.get(&user.id).unwrap_or(&0),
+          medical_appointments_count: *appointments_by_user.get(&user.id).unwrap_or(&0),
+        };
+        (user, usage)
+      })
+      .collect(),
+  )
+}
+
+pub async fn usage_stats(db: &DatabaseConnection) -> Result<UsageStats, MyErrors> {
+  Ok(UsageStats {
+    users_count: users::Entity::find().count(db).await?,
+    pending_users_count: users::Entity::find()
+      .filter(users::Column::IsAccessKeyVerified.eq(false))
+      .count(db)
+      .await?,
+    suspended_users_count: users::Entity::find()
+      .filter(users::Column::SuspendedAt.is_not_null())
+      .count(db)
+      .await?,
+    patients_count: patients::Entity::find().count(db).await?,
+    medical_appointments_count: medical_appointments::Entity::find().count(db).await?,
+    practitioner_offices_count: practitioner_offices::Entity::find().count(db).await?,
+  })
+}
@@ -1,11 +1,18 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, Months, NaiveDate};
 use rust_xlsxwriter::*;
-use sea_orm::{ActiveEnum, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+  prelude::Decimal, ActiveEnum, ColumnTrait, DatabaseConnection, EntityTrait, Iterable,
+  QueryFilter, QueryOrder,
+};
 use std::collections::HashMap;
 
 use crate::models::{
-  _entities::{medical_appointments, patients, practitioner_offices, user_practitioner_offices},
+  _entities::{
+    medical_appointments, patients, practitioner_offices, sea_orm_active_enums::PaymentMethod,
+  },
   my_errors::{unexpected_error::UnexpectedError, MyErrors},
+  retrocession_statements,
+  revenue_share_rates::{self, RateSchedule, RetrocessionTerms},
   users,
 };
 
@@ -13,23 +20,206 @@ pub struct MedicalAppointmentExtractor<'user> {
   user: &'user users::Model,
 }
 
-type MedicalAppointmentDetail = (
+pub type MedicalAppointmentDetail = (
   medical_appointments::Model,
   patients::Model,
   practitioner_offices::Model,
-  f64, // revenue_share_percentage
+  RetrocessionTerms,
 );
 
+/// Appointments extracted over a period, along with what each office is owed
+/// for every calendar month of that period
+#[derive(Debug, Default)]
+pub struct AppointmentsExtraction {
+  pub appointments: Vec<MedicalAppointmentDetail>,
+  pub months: Vec<MonthlyTotals>,
+}
+
+/// Fees of a month of work in an office, split between the practitioner and
+/// the office holder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonthlyTotals {
+  pub office_id: i32,
+  pub office_name: String,
+  pub month: NaiveDate,
+  /// Last day of the month covered by the period, before the end of the month
+  /// when the period stops in the middle of it
+  pub period_end: NaiveDate,
+  pub fees_in_cents: i64,
+  pub revenue_in_cents: i64,
+  pub retrocession_in_cents: i64,
+}
+
+/// Totals of each office for every calendar month between `start_date` and
+/// `end_date`, including the months without appointments for which a fixed
+/// fee is owed. Each appointment is charged under the terms in force on its
+/// day, while the monthly fee or cap follows the terms in force at the end of
+/// the period and is prorated when the period only covers part of the month,
+/// so that two periods splitting a month never charge it twice.
+pub fn monthly_totals(
+  appointments: &[MedicalAppointmentDetail],
+  offices: &[practitioner_offices::Model],
+  rate_schedule: &RateSchedule,
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Vec<MonthlyTotals> {
+  let mut totals = Vec::new();
+  for office in offices {
+    let mut month = start_date.with_day(1).unwrap_or(start_date);
+    while month <= end_date {
+      let month_end = retrocession_statements::month_end(month);
+      let period_start = month.max(start_date);
+      let period_end = month_end.min(end_date);
+
+      let month_appointments: Vec<_> = appointments
+        .iter()
+        .filter(|(appointment, _patient, appointment_office, ..)| {
+          appointment_office.id == office.id
+            && (period_start..=period_end).contains(&appointment.date)
+        })
+        .collect();
+
+      if let Some(terms) = rate_schedule.terms_on(office.id, period_end) {
+        let appointment_shares: Decimal = month_appointments
+          .iter()
+          .map(|(appointment, _patient, _office, terms)| {
+            terms.appointment_share(appointment.price_in_cents)
+          })
+          .sum();
+        let month_fraction = Decimal::from((period_end - period_start).num_days() + 1)
+          / Decimal::from(month_end.day());
+        let retrocession_in_cents = terms.prorated_amount(appointment_shares, month_fraction);
+        let fees_in_cents: i64 = month_appointments
+          .iter()
+          .map(|(appointment, ..)| i64::from(appointment.price_in_cents))
+          .sum();
+
+        if !month_appointments.is_empty() || retrocession_in_cents != 0 {
+          totals.push(MonthlyTotals {
+            office_id: office.id,
+            office_name: office.name.clone(),
+            month,
+            period_end,
+            fees_in_cents,
+            revenue_in_cents: fees_in_cents - retrocession_in_cents,
+            retrocession_in_cents,
+          });
+        }
+      }
+
+      let Some(next_month) = month.checked_add_months(Months::new(1)) else {
+        break;
+      };
+      month = next_month;
+    }
+  }
+  totals
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentMethodTotals {
+  /// `None` for appointments whose payment method was not recorded
+  pub method: Option<PaymentMethod>,
+  pub appointments_count: usize,
+  pub fees_in_cents: i64,
+}
+
+/// Fees of `appointments` by payment method, in the order of `PaymentMethod`
+pub fn payment_method_totals(
+  appointments: &[MedicalAppointmentDetail],
+) -> Vec<PaymentMethodTotals> {
+  PaymentMethod::iter()
+    .map(Some)
+    .chain([None])
+    .filter_map(|method| {
+      let paid_with: Vec<_> = appointments
+        .iter()
+        .filter(|(appointment, ..)| appointment.payment_method == method)
+        .collect();
+      if paid_with.is_empty() {
+        return None;
+      }
+      Some(PaymentMethodTotals {
+        method,
+        appointments_count: paid_with.len(),
+        fees_in_cents: paid_with
+          .iter()
+          .map(|(appointment, ..)| i64::from(appointment.price_in_cents))
+          .sum(),
+      })
+    })
+    .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActTotals {
+  /// Label and code of the act, `None` for appointments without act
+  pub act: Option<String>,
+  pub appointments_count: usize,
+  pub fees_in_cents: i64,
+}
+
+/// Fees of `appointments` by act of the catalogue, sorted by label, the
+/// appointments without act coming last
+pub fn act_totals(appointments: &[MedicalAppointmentDetail]) -> Vec<ActTotals> {
+  let mut totals: Vec<ActTotals> = Vec::new();
+  for (appointment, ..) in appointments {
+    let act = appointment.act_full_label();
+    let fees_in_cents = i64::from(appointment.price_in_cents);
+    match totals.iter_mut().find(|totals| totals.act == act) {
+      Some(totals) => {
+        totals.appointments_count += 1;
+        totals.fees_in_cents += fees_in_cents;
+      }
+      None => totals.push(ActTotals {
+        act,
+        appointments_count: 1,
+        fees_in_cents,
+      }),
+    }
+  }
+
+  totals.sort_by(|a, b| match (&a.act, &b.act) {
+    (Some(a), Some(b)) => a.cmp(b),
+    _ => b.act.is_some().cmp(&a.act.is_some()),
+  });
+  totals
+}
+
+fn euros(amount_in_cents: i64) -> f64 {
+  amount_in_cents as f64 / 100.0
+}
+
+const SUMMARY_SHEET_NAME: &str = "Synthèse";
+
+/// Write in `row` the sum of the cells of `col` from `first_row` up to the
+/// row above
+fn write_sum(
+  worksheet: &mut Worksheet,
+  row: u32,
+  col: u16,
+  first_row: u32,
+  format: &Format,
+) -> Result<(), MyErrors> {
+  if row <= first_row {
+    worksheet.write_with_format(row, col, 0.0, format)?;
+  } else {
+    let range = utility::cell_range(first_row, col, row - 1, col);
+    worksheet.write_formula_with_format(row, col, format!("=SUM({})", range).as_str(), format)?;
+  }
+  Ok(())
+}
+
 pub trait ToExcel {
   fn to_excel(&self) -> Result<Workbook, MyErrors>;
 }
 
-impl ToExcel for Vec<MedicalAppointmentDetail> {
+impl ToExcel for AppointmentsExtraction {
   fn to_excel(&self) -> Result<Workbook, MyErrors> {
     let mut appointments_by_office: HashMap<String, Vec<&MedicalAppointmentDetail>> =
       HashMap::new();
 
-    for appointment in self {
+    for appointment in &self.appointments {
       let office_name = appointment.2.name.clone();
       appointments_by_office
         .entry(office_name)
@@ -40,6 +230,8 @@ impl ToExcel for Vec<MedicalAppointmentDetail> {
     let mut workbook = Workbook::new();
     let date_format = Format::new().set_num_format("dd/mm/yyyy");
     let revenue_format = Format::new().set_num_format("0.00");
+    let bold_format = Format::new().set_bold();
+    let total_format = Format::new().set_bold().set_num_format("0.00");
     let header_format = Format::new()
       .set_bold()
       .set_background_color(Color::Green)
@@ -48,6 +240,117 @@ impl ToExcel for Vec<MedicalAppointmentDetail> {
     let mut sorted_offices: Vec<_> = appointments_by_office.iter().collect();
     sorted_offices.sort_by_key(|(name, _)| name.as_str());
 
+    let months = &self.months;
+    let payment_methods = payment_method_totals(&self.appointments);
+    let acts = act_totals(&self.appointments);
+
+    // The summary comes first, before the detail of each office
+    let summary = workbook.add_worksheet();
+    summary.set_name(SUMMARY_SHEET_NAME)?;
+
+    let month_headers = [
+      "Cabinet",
+      "Mois",
+      "Honoraires (€)",
+      "Votre CA (€)",
+      "Rétrocession (€)",
+    ];
+    for (col, header) in month_headers.into_iter().enumerate() {
+      summary.write_with_format(0, col as u16, header, &header_format)?;
+      summary.set_column_width(col as u16, 20)?;
+    }
+    for (i, totals) in months.iter().enumerate() {
+      let row = i as u32 + 1;
+      summary.write(row, 0, &totals.office_name)?;
+      summary.write(row, 1, totals.month.format("%m/%Y").to_string())?;
+      summary.write_with_format(row, 2, euros(totals.fees_in_cents), &revenue_format)?;
+      summary.write_with_format(row, 3, euros(totals.revenue_in_cents), &revenue_format)?;
+      summary.write_with_format(row, 4, euros(totals.retrocession_in_cents), &revenue_format)?;
+    }
+    let months_total_row = months.len() as u32 + 1;
+    summary.write_with_format(months_total_row, 0, "Total", &bold_format)?;
+    for col in 2..=4 {
+      write_sum(summary, months_total_row, col, 1, &total_format)?;
+    }
+
+    let methods_header_row = months_total_row + 2;
+    let method_headers = ["Mode de paiement", "Consultations", "Honoraires (€)"];
+    for (col, header) in method_headers.into_iter().enumerate() {
+      summary.write_with_format(methods_header_row, col as u16, header, &header_format)?;
+    }
+    for (i, totals) in payment_methods.iter().enumerate() {
+      let row = methods_header_row + i as u32 + 1;
+      let label = totals
+        .method
+        .as_ref()
+        .map_or("Non renseigné", |method| method.to_french());
+      summary.write(row, 0, label)?;
+      summary.write(row, 1, totals.appointments_count as u32)?;
+      summary.write_with_format(row, 2, euros(totals.fees_in_cents), &revenue_format)?;
+    }
+    let methods_total_row = methods_header_row + payment_methods.len() as u32 + 1;
+    summary.write_with_format(methods_total_row, 0, "Total", &bold_format)?;
+    for col in 1..=2 {
+      write_sum(
+        summary,
+        methods_total_row,
+        col,
+        methods_header_row + 1,
+        &total_format,
+      )?;
+    }
+
+    let acts_header_row = methods_total_row + 2;
+    let act_headers = ["Acte", "Consultations", "Honoraires (€)"];
+    for (col, header) in act_headers.into_iter().enumerate() {
+      summary.write_with_format(acts_header_row, col as u16, header, &header_format)?;
+    }
+    for (i, totals) in acts.iter().enumerate() {
+      let row = acts_header_row + i as u32 + 1;
+      summary.write(row, 0, totals.act.as_deref().unwrap_or("Hors catalogue"))?;
+      summary.write(row, 1, totals.appointments_count as u32)?;
+      summary.write_with_format(row, 2, euros(totals.fees_in_cents), &revenue_format)?;
+    }
+    let acts_total_row = acts_header_row + acts.len() as u32 + 1;
+    summary.write_with_format(acts_total_row, 0, "Total", &bold_format)?;
+    for col in 1..=2 {
+      write_sum(
+        summary,
+        acts_total_row,
+        col,
+        acts_header_row + 1,
+        &total_format,
+      )?;
+    }
+
+    // Charts need at least one row of data to point to
+    if !months.is_empty() {
+      let last_month_row = months.len() as u32;
+      let mut chart = Chart::new(ChartType::ColumnStacked);
+      chart.title().set_name("Honoraires par mois");
+      for (col, name) in [(3, "Votre CA"), (4, "Rétrocession")] {
+        chart
+          .add_series()
+          .set_name(name)
+          .set_categories((SUMMARY_SHEET_NAME, 1, 0, last_month_row, 1))
+          .set_values((SUMMARY_SHEET_NAME, 1, col, last_month_row, col));
+      }
+      summary.insert_chart(0, 6, &chart)?;
+    }
+
+    // A month may only hold fixed retrocession fees, without any appointment
+    if !payment_methods.is_empty() {
+      let first_method_row = methods_header_row + 1;
+      let last_method_row = methods_total_row - 1;
+      let mut chart = Chart::new(ChartType::Pie);
+      chart.title().set_name("Honoraires par mode de paiement");
+      chart
+        .add_series()
+        .set_categories((SUMMARY_SHEET_NAME, first_method_row, 0, last_method_row, 0))
+        .set_values((SUMMARY_SHEET_NAME, first_method_row, 2, last_method_row, 2));
+      summary.insert_chart(16, 6, &chart)?;
+    }
+
     // Create a worksheet for each office
     for (office_name, office_appointments) in sorted_offices {
       let worksheet = workbook.add_worksheet();
@@ -74,12 +377,15 @@ impl ToExcel for Vec<MedicalAppointmentDetail> {
       worksheet.write_with_format(0, 6, "Rétrocession", &header_format)?;
       worksheet.set_column_width(6, 20)?;
 
-      for (i, (appointment, patient, _office, revenue_share_percentage)) in
-        office_appointments.iter().enumerate()
-      {
+      worksheet.write_with_format(0, 7, "Acte", &header_format)?;
+      worksheet.set_column_width(7, 30)?;
+
+      for (i, (appointment, patient, _office, terms)) in office_appointments.iter().enumerate() {
         let excel_date = ExcelDateTime::parse_from_str(&appointment.date.to_string())?;
         let price = appointment.price_in_cents as f64 / 100.0;
-        let hand_back = price * revenue_share_percentage / 100.0;
+        let hand_back = f64::try_from(terms.appointment_share(appointment.price_in_cents))
+          .unwrap_or_default()
+          / 100.0;
 
         worksheet.write_with_format(i as u32 + 1, 0, &excel_date, &date_format)?;
 
@@ -94,6 +400,36 @@ impl ToExcel for Vec<MedicalAppointmentDetail> {
         worksheet.write(i as u32 + 1, 4, price)?;
         worksheet.write_with_format(i as u32 + 1, 5, price - hand_back, &revenue_format)?;
         worksheet.write_with_format(i as u32 + 1, 6, hand_back, &revenue_format)?;
+        worksheet.write(i as u32 + 1, 7, appointment.act_full_label())?;
+      }
+
+      let total_row = office_appointments.len() as u32 + 1;
+      worksheet.write_with_format(total_row, 0, "Total", &bold_format)?;
+      for col in 4..=6 {
+        write_sum(worksheet, total_row, col, 1, &total_format)?;
+      }
+
+      // Monthly fees and caps are owed per month rather than per appointment
+      let monthly_row = total_row + 2;
+      let office_id = office_appointments[0].2.id;
+      for (i, totals) in months
+        .iter()
+        .filter(|totals| totals.office_id == office_id)
+        .enumerate()
+      {
+        let row = monthly_row + i as u32;
+        worksheet.write_with_format(
+          row,
+          0,
+          format!("Rétrocession {}", totals.month.format("%m/%Y")),
+          &bold_format,
+        )?;
+        worksheet.write_with_format(
+          row,
+          6,
+          totals.retrocession_in_cents as f64 / 100.0,
+          &revenue_format,
+        )?;
       }
     }
 
@@ -106,6 +442,41 @@ impl<'user> MedicalAppointmentExtractor<'user> {
     MedicalAppointmentExtractor { user }
   }
 
+  /// Appointments of the period along with the monthly totals of each office
+  /// the practitioner works in
+  pub async fn extract_with_monthly_totals(
+    &self,
+    db: &DatabaseConnection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+  ) -> Result<AppointmentsExtraction, MyErrors> {
+    let appointments = self.extract(db, start_date, end_date).await?;
+
+    let rate_schedule = RateSchedule::new(
+      revenue_share_rates::Entity::find_for_user(self.user.id)
+        .all(db)
+        .await?,
+    );
+    let mut office_ids = rate_schedule.office_ids();
+    office_ids.extend(appointments.iter().map(|(_, _, office, ..)| office.id));
+    let offices = practitioner_offices::Entity::find()
+      .filter(practitioner_offices::Column::Id.is_in(office_ids))
+      .order_by_asc(practitioner_offices::Column::Name)
+      .all(db)
+      .await?;
+
+    Ok(AppointmentsExtraction {
+      months: monthly_totals(
+        &appointments,
+        &offices,
+        &rate_schedule,
+        start_date,
+        end_date,
+      ),
+      appointments,
+    })
+  }
+
   pub async fn extract(
     &self,
     db: &DatabaseConnection,
@@ -125,28 +496,21 @@ impl<'user> MedicalAppointmentExtractor<'user> {
       .all(db)
       .await?;
 
-    let user_offices = user_practitioner_offices::Entity::find()
-      .filter(user_practitioner_offices::Column::UserId.eq(self.user.id))
-      .all(db)
-      .await?;
-
-    let revenue_share_by_office: HashMap<i32, f64> = user_offices
-      .into_iter()
-      .map(|uo| {
-        let pct = uo.revenue_share_percentage.try_into().unwrap_or(0.0);
-        (uo.practitioner_office_id, pct)
-      })
-      .collect();
+    let rate_schedule = RateSchedule::new(
+      revenue_share_rates::Entity::find_for_user(self.user.id)
+        .all(db)
+        .await?,
+    );
 
     let results = appointments
       .into_iter()
       .map(|(appointment, patient, office)| -> Result<_, MyErrors> {
         let office = office.ok_or(UnexpectedError::new("office_should_be_defined".to_string()))?;
-        let revenue_share_percentage =
-          *revenue_share_by_office
-            .get(&office.id)
+        let terms =
+          rate_schedule
+            .terms_on(office.id, appointment.date)
             .ok_or(UnexpectedError::new(
-              "revenue_share_percentage_should_be_defined".to_string(),
+              "retrocession_terms_should_be_defined".to_string(),
             ))?;
         Ok((
           appointment,
@@ -154,7 +518,7 @@ impl<'user> MedicalAppointmentExtractor<'user> {
             "patient_should_be_defined".to_string(),
           ))?,
           office,
-          revenue_share_percentage,
+          terms,
         ))
       })
       .collect::<Result<Vec<_>, MyErrors>>()?;
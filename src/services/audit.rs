@@ -0,0 +1,56 @@
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
+
+use crate::{
+  auth::resource::Resource,
+  initializers::get_services,
+  models::{
+    _entities::sea_orm_active_enums::AuditAction,
+    audit_logs::{self, CreateAuditLogParams},
+    my_errors::MyErrors,
+    users,
+  },
+};
+
+const AUDIT_LOGS_PER_PAGE: u64 = 50;
+
+/// Append an entry to the audit trail for an access attempt on a resource
+pub async fn record_access<T: Resource>(
+  user_id: i32,
+  resource: &T,
+  action: AuditAction,
+  granted: bool,
+  ip_address: Option<String>,
+) -> Result<audit_logs::Model, MyErrors> {
+  let services = get_services();
+
+  audit_logs::ActiveModel::create(
+    &services.db,
+    &CreateAuditLogParams {
+      user_id,
+      action,
+      resource_type: resource.resource_name(),
+      resource_id: resource.resource_id(),
+      granted,
+      ip_address,
+    },
+  )
+  .await
+}
+
+pub async fn paginated_for_user(
+  user: &users::Model,
+  page: u64,
+) -> Result<(Vec<audit_logs::Model>, u64), MyErrors> {
+  let db = &get_services().db;
+
+  let paginator = audit_logs::Entity::find()
+    .filter(audit_logs::Column::UserId.eq(user.id))
+    .order_by_desc(audit_logs::Column::CreatedAt)
+    .order_by_desc(audit_logs::Column::Id)
+    .paginate(db, AUDIT_LOGS_PER_PAGE);
+
+  let total_pages = paginator.num_pages().await?;
+  let audit_logs = paginator.fetch_page(page - 1).await?;
+
+  Ok((audit_logs, total_pages))
+}
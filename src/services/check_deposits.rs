@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use sea_orm::{
+  sea_query::Expr, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{
+      check_deposits, medical_appointments, patients, payments,
+      sea_orm_active_enums::PaymentMethod, user_business_informations,
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+    users,
+  },
+  workers::deposit_slip_generator::{self, DepositedCheck},
+};
+
+pub struct DepositSlip {
+  pub data: Vec<u8>,
+  pub filename: String,
+}
+
+/// A deposit along with the number and total amount of the checks it holds
+#[derive(Debug)]
+pub struct DepositSummary {
+  pub deposit: check_deposits::Model,
+  pub checks_count: i64,
+  pub total_in_cents: i64,
+}
+
+/// Checks received by `user` that have not been deposited at the bank yet
+pub async fn pending_checks(user: &users::Model) -> Result<Vec<DepositedCheck>, MyErrors> {
+  let db = &get_services().db;
+
+  let pending_payments = payments::Entity::find()
+    .filter(payments::Column::Method.eq(PaymentMethod::Check))
+    .filter(payments::Column::CheckDepositId.is_null())
+    .find_also_related(medical_appointments::Entity)
+    .filter(medical_appointments::Column::UserId.eq(user.id))
+    .order_by_asc(payments::Column::PaidOn)
+    .order_by_asc(payments::Column::Id)
+    .all(db)
+    .await?;
+
+  deposited_checks(pending_payments).await
+}
+
+async fn deposited_checks(
+  payments: Vec<(payments::Model, Option<medical_appointments::Model>)>,
+) -> Result<Vec<DepositedCheck>, MyErrors> {
+  let db = &get_services().db;
+
+  let patient_ids: Vec<i32> = payments
+    .iter()
+    .filter_map(|(_, appointment)| appointment.as_ref().map(|a| a.patient_id))
+    .collect();
+  let patients_by_id: HashMap<i32, patients::Model> = patients::Entity::find()
+    .filter(patients::Column::Id.is_in(patient_ids))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|patient| (patient.id, patient))
+    .collect();
+
+  Ok(
+    payments
+      .into_iter()
+      .map(|(payment, appointment)| DepositedCheck {
+        paid_on: payment.paid_on,
+        drawer: appointment
+          .and_then(|appointment| patients_by_id.get(&appointment.patient_id))
+          .map(|patient| format!("{} {}", patient.last_name, patient.first_name))
+          .unwrap_or_default(),
+        check_number: payment.reference,
+        amount_in_cents: payment.amount_in_cents,
+        payment_id: payment.id,
+      })
+      .collect(),
+  )
+}
+
+/// Gather every pending check of `user` into a new deposit
+pub async fn deposit(
+  user: &users::Model,
+  deposited_on: NaiveDate,
+) -> Result<DepositSummary, MyErrors> {
+  let checks = pending_checks(user).await?;
+  if checks.is_empty() {
+    return Err(ApplicationError::new("no_check_to_deposit").into());
+  }
+
+  let txn = get_services().db.begin().await?;
+
+  let deposit = check_deposits::ActiveModel::create(&txn, user.id, deposited_on).await?;
+
+  payments::Entity::update_many()
+    .col_expr(payments::Column::CheckDepositId, Expr::value(deposit.id))
+    .filter(payments::Column::Id.is_in(checks.iter().map(|check| check.payment_id)))
+    .filter(payments::Column::CheckDepositId.is_null())
+    .exec(&txn)
+    .await?;
+
+  txn.commit().await?;
+
+  Ok(DepositSummary {
+    deposit,
+    checks_count: checks.len() as i64,
+    total_in_cents: checks
+      .iter()
+      .map(|check| i64::from(check.amount_in_cents))
+      .sum(),
+  })
+}
+
+/// Deposits of `user`, most recent first
+pub async fn deposits_for_user(user: &users::Model) -> Result<Vec<DepositSummary>, MyErrors> {
+  let db = &get_services().db;
+
+  let deposits = check_deposits::Entity::find_for_user(user.id)
+    .all(db)
+    .await?;
+
+  let totals: HashMap<i32, (i64, i64)> = payments::Entity::find()
+    .select_only()
+    .column(payments::Column::CheckDepositId)
+    .column_as(payments::Column::Id.count(), "checks_count")
+    .column_as(payments::Column::AmountInCents.sum(), "total_in_cents")
+    .filter(payments::Column::CheckDepositId.is_in(deposits.iter().map(|deposit| deposit.id)))
+    .group_by(payments::Column::CheckDepositId)
+    .into_tuple::<(i32, i64, Option<i64>)>()
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|(deposit_id, count, total)| (deposit_id, (count, total.unwrap_or(0))))
+    .collect();
+
+  Ok(
+    deposits
+      .into_iter()
+      .map(|deposit| {
+        let (checks_count, total_in_cents) = totals.get(&deposit.id).copied().unwrap_or((0, 0));
+        DepositSummary {
+          deposit,
+          checks_count,
+          total_in_cents,
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Render the slip of a deposit, as many times as needed
+pub async fn slip(
+  deposit: &check_deposits::Model,
+  user: &users::Model,
+) -> Result<DepositSlip, MyErrors> {
+  let db = &get_services().db;
+
+  let deposited_payments = payments::Entity::find()
+    .filter(payments::Column::CheckDepositId.eq(deposit.id))
+    .find_also_related(medical_appointments::Entity)
+    .order_by_asc(payments::Column::PaidOn)
+    .order_by_asc(payments::Column::Id)
+    .all(db)
+    .await?;
+  let checks = deposited_checks(deposited_payments).await?;
+
+  let business_info = user_business_informations::Entity::find()
+    .filter(user_business_informations::Column::UserId.eq(user.id))
+    .one(db)
+    .await?;
+
+  let data = deposit_slip_generator::generate_deposit_slip_pdf(
+    user,
+    business_info.as_ref(),
+    deposit,
+    &checks,
+  )?;
+
+  Ok(DepositSlip {
+    data,
+    filename: format!("bordereau_{}.pdf", deposit.reference.to_lowercase()),
+  })
+}
@@ -0,0 +1,358 @@
+use chrono::NaiveDate;
+use sea_orm::{ActiveEnum, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+use crate::{
+  models::{
+    _entities::{
+      sea_orm_active_enums::{ExportFormat, PaymentMethod},
+      user_business_informations,
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+    users::users,
+  },
+  services::appointments::{
+    AppointmentsExtraction, MedicalAppointmentDetail, MedicalAppointmentExtractor, ToExcel,
+  },
+};
+
+impl ExportFormat {
+  /// `Fec` is the Fichier des Écritures Comptables, as expected by the French
+  /// tax administration and most accounting software
+  pub fn exporter(&self) -> Box<dyn AppointmentExporter> {
+    match self {
+      ExportFormat::Xlsx => Box::new(ExcelExporter),
+      ExportFormat::Csv => Box::new(CsvExporter),
+      ExportFormat::Fec => Box::new(FecExporter),
+    }
+  }
+}
+
+pub trait AppointmentExporter: Send + Sync {
+  fn export(&self, extraction: &AppointmentsExtraction) -> Result<Vec<u8>, MyErrors>;
+  fn content_type(&self) -> &'static str;
+  fn extension(&self) -> &'static str;
+
+  /// Name of the export file of the period
+  fn filename(
+    &self,
+    _business_information: Option<&user_business_informations::Model>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+  ) -> Result<String, MyErrors> {
+    Ok(format!(
+      "appointments_from_{}_to_{}.{}",
+      start_date.format("%d-%m-%Y"),
+      end_date.format("%d-%m-%Y"),
+      self.extension()
+    ))
+  }
+}
+
+/// Export file of the appointments of a period
+pub struct AppointmentsExport {
+  pub data: Vec<u8>,
+  pub filename: String,
+  pub content_type: &'static str,
+}
+
+pub async fn export_appointments(
+  db: &DatabaseConnection,
+  user: &users::Model,
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+  format: &ExportFormat,
+) -> Result<AppointmentsExport, MyErrors> {
+  let extraction = MedicalAppointmentExtractor::for_user(user)
+    .extract_with_monthly_totals(db, start_date, end_date)
+    .await?;
+  let business_information = user_business_informations::Entity::find()
+    .filter(user_business_informations::Column::UserId.eq(user.id))
+    .one(db)
+    .await?;
+  let exporter = format.exporter();
+
+  Ok(AppointmentsExport {
+    data: exporter.export(&extraction)?,
+    filename: exporter.filename(business_information.as_ref(), start_date, end_date)?,
+    content_type: exporter.content_type(),
+  })
+}
+
+/// e.g. `1234,50` or `-0,50`, the decimal separator French software expects
+fn decimal_comma(amount_in_cents: i64) -> String {
+  let sign = if amount_in_cents < 0 { "-" } else { "" };
+  let amount_in_cents = amount_in_cents.unsigned_abs();
+  format!(
+    "{}{},{:02}",
+    sign,
+    amount_in_cents / 100,
+    amount_in_cents % 100
+  )
+}
+
+/// Price of the appointment, split between the practitioner and the office
+/// holder
+fn split_price(detail: &MedicalAppointmentDetail) -> (i64, i64, i64) {
+  let (appointment, _patient, _office, terms) = detail;
+  let price = i64::from(appointment.price_in_cents);
+  let retrocession = terms
+    .appointment_share(appointment.price_in_cents)
+    .round()
+    .try_into()
+    .unwrap_or_default();
+  (price, price - retrocession, retrocession)
+}
+
+pub struct ExcelExporter;
+
+impl AppointmentExporter for ExcelExporter {
+  fn export(&self, extraction: &AppointmentsExtraction) -> Result<Vec<u8>, MyErrors> {
+    Ok(extraction.to_excel()?.save_to_buffer()?)
+  }
+
+  fn content_type(&self) -> &'static str {
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+  }
+
+  fn extension(&self) -> &'static str {
+    "xlsx"
+  }
+}
+
+/// One line per appointment, with the columns of the Excel export
+pub struct CsvExporter;
+
+impl AppointmentExporter for CsvExporter {
+  fn export(&self, extraction: &AppointmentsExtraction) -> Result<Vec<u8>, MyErrors> {
+    let mut writer = csv::WriterBuilder::new()
+      .delimiter(b';')
+      .from_writer(vec![]);
+
+    writer.write_record([
+      "Date",
+      "Cabinet",
+      "Acte",
+      "Nom",
+      "Prénom",
+      "Mode de paiement",
+      "Prix consultation",
+      "Votre CA",
+      "Rétrocession",
+    ])?;
+
+    for detail in &extraction.appointments {
+      let (appointment, patient, office, _terms) = detail;
+      let (price, revenue, retrocession) = split_price(detail);
+      writer.write_record([
+        appointment.date.format("%Y-%m-%d").to_string(),
+        office.name.clone(),
+        appointment.act_full_label().unwrap_or_default(),
+        patient.last_name.clone(),
+        patient.first_name.clone(),
+        appointment
+          .payment_method
+          .as_ref()
+          .map(|method| method.to_value())
+          .unwrap_or_default(),
+        decimal_comma(price),
+        decimal_comma(revenue),
+        decimal_comma(retrocession),
+      ])?;
+    }
+
+    Ok(writer.into_inner().map_err(|e| e.into_error())?)
+  }
+
+  fn content_type(&self) -> &'static str {
+    "text/csv"
+  }
+
+  fn extension(&self) -> &'static str {
+    "csv"
+  }
+}
+
+const FEC_HEADER: [&str; 18] = [
+  "JournalCode",
+  "JournalLib",
+  "EcritureNum",
+  "EcritureDate",
+  "CompteNum",
+  "CompteLib",
+  "CompAuxNum",
+  "CompAuxLib",
+  "PieceRef",
+  "PieceDate",
+  "EcritureLib",
+  "Debit",
+  "Credit",
+  "EcritureLet",
+  "DateLet",
+  "ValidDate",
+  "Montantdevise",
+  "Idevise",
+];
+
+/// Account, and its label, receiving the fee of an appointment
+fn receipt_account(payment_method: Option<&PaymentMethod>) -> (&'static str, &'static str) {
+  match payment_method {
+    Some(PaymentMethod::Cash) => ("530000", "Caisse"),
+    Some(_) => ("512000", "Banque"),
+    None => ("411000", "Patients"),
+  }
+}
+
+struct FecLine<'a> {
+  journal: (&'static str, &'static str),
+  number: usize,
+  date: NaiveDate,
+  account: (&'static str, &'static str),
+  auxiliary: Option<(String, &'a str)>,
+  piece_ref: String,
+  label: &'a str,
+  debit_in_cents: i64,
+  credit_in_cents: i64,
+}
+
+impl FecLine<'_> {
+  fn record(&self) -> [String; 18] {
+    let date = self.date.format("%Y%m%d").to_string();
+    let (auxiliary_number, auxiliary_label) = self
+      .auxiliary
+      .as_ref()
+      .map(|(number, label)| (number.clone(), label.to_string()))
+      .unwrap_or_default();
+    [
+      self.journal.0.to_string(),
+      self.journal.1.to_string(),
+      self.number.to_string(),
+      date.clone(),
+      self.account.0.to_string(),
+      self.account.1.to_string(),
+      auxiliary_number,
+      auxiliary_label,
+      self.piece_ref.clone(),
+      date.clone(),
+      self.label.to_string(),
+      decimal_comma(self.debit_in_cents),
+      decimal_comma(self.credit_in_cents),
+      String::new(),
+      String::new(),
+      date,
+      String::new(),
+      String::new(),
+    ]
+  }
+}
+
+/// Journal entries of the fees collected, one per appointment, and of the
+/// retrocession owed to each office, one per month
+pub struct FecExporter;
+
+const FEES_JOURNAL: (&str, &str) = ("HO", "Honoraires");
+const RETROCESSIONS_JOURNAL: (&str, &str) = ("RT", "Rétrocessions");
+const FEES_ACCOUNT: (&str, &str) = ("706000", "Honoraires");
+const RETROCESSIONS_ACCOUNT: (&str, &str) = ("622600", "Rétrocessions d'honoraires");
+const OFFICES_ACCOUNT: (&str, &str) = ("401000", "Cabinets");
+
+impl AppointmentExporter for FecExporter {
+  fn export(&self, extraction: &AppointmentsExtraction) -> Result<Vec<u8>, MyErrors> {
+    let mut writer = csv::WriterBuilder::new()
+      .delimiter(b'\t')
+      .from_writer(vec![]);
+    writer.write_record(FEC_HEADER)?;
+
+    let mut number = 0;
+    for (appointment, _patient, office, _terms) in &extraction.appointments {
+      number += 1;
+      // Patient names stay out of the accounting records
+      let label = format!(
+        "{} {}",
+        appointment.act_label.as_deref().unwrap_or("Consultation"),
+        office.name
+      );
+      let line = FecLine {
+        journal: FEES_JOURNAL,
+        number,
+        date: appointment.date,
+        account: receipt_account(appointment.payment_method.as_ref()),
+        auxiliary: None,
+        piece_ref: appointment.id.to_string(),
+        label: &label,
+        debit_in_cents: i64::from(appointment.price_in_cents),
+        credit_in_cents: 0,
+      };
+      writer.write_record(line.record())?;
+      writer.write_record(
+        FecLine {
+          account: FEES_ACCOUNT,
+          debit_in_cents: 0,
+          credit_in_cents: i64::from(appointment.price_in_cents),
+          ..line
+        }
+        .record(),
+      )?;
+    }
+
+    for totals in &extraction.months {
+      if totals.retrocession_in_cents == 0 {
+        continue;
+      }
+      number += 1;
+      let label = format!("Rétrocession {}", totals.office_name);
+      let line = FecLine {
+        journal: RETROCESSIONS_JOURNAL,
+        number,
+        date: totals.period_end,
+        account: RETROCESSIONS_ACCOUNT,
+        auxiliary: None,
+        piece_ref: totals.month.format("%Y%m").to_string(),
+        label: &label,
+        debit_in_cents: totals.retrocession_in_cents,
+        credit_in_cents: 0,
+      };
+      writer.write_record(line.record())?;
+      writer.write_record(
+        FecLine {
+          account: OFFICES_ACCOUNT,
+          auxiliary: Some((
+            format!("CAB{}", totals.office_id),
+            totals.office_name.as_str(),
+          )),
+          debit_in_cents: 0,
+          credit_in_cents: totals.retrocession_in_cents,
+          ..line
+        }
+        .record(),
+      )?;
+    }
+
+    Ok(writer.into_inner().map_err(|e| e.into_error())?)
+  }
+
+  fn content_type(&self) -> &'static str {
+    "text/plain"
+  }
+
+  fn extension(&self) -> &'static str {
+    "txt"
+  }
+
+  /// `{SIREN}FEC{YYYYMMDD}.txt`, dated on the closing of the period, as the
+  /// tax administration requires
+  fn filename(
+    &self,
+    business_information: Option<&user_business_informations::Model>,
+    _start_date: NaiveDate,
+    end_date: NaiveDate,
+  ) -> Result<String, MyErrors> {
+    let business_information = business_information.ok_or(ApplicationError::UnprocessableEntity)?;
+
+    Ok(format!(
+      "{}FEC{}.{}",
+      business_information.siren(),
+      end_date.format("%Y%m%d"),
+      self.extension()
+    ))
+  }
+}
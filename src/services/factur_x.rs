@@ -0,0 +1,465 @@
+//! Factur-X electronic invoices: a PDF/A-3 document carrying the invoice data
+//! as an embedded UN/CEFACT Cross Industry Invoice (CII) XML file, readable
+//! by accounting software.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use quick_xml::escape::escape;
+use regex::bytes::Regex;
+
+/// Name the CII XML must be attached under
+pub const ATTACHMENT_NAME: &str = "factur-x.xml";
+/// BASIC WL: document level data only, which is all a note d'honoraires has
+pub const PROFILE_ID: &str = "urn:factur-x.eu:1p0:basicwl";
+const CONFORMANCE_LEVEL: &str = "BASIC WL";
+
+/// Practitioner or patient named on the invoice
+#[derive(Debug, Clone)]
+pub struct Party<'a> {
+  pub name: String,
+  /// Only known for the seller, also given as its tax registration since
+  /// practitioners exempt from VAT have no VAT number
+  pub siret: Option<&'a str>,
+  pub address_line: &'a str,
+  pub zip_code: &'a str,
+  pub city: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct FacturXInvoice<'a> {
+  pub number: String,
+  pub issue_date: NaiveDate,
+  pub seller: Party<'a>,
+  pub buyer: Party<'a>,
+  pub amount_in_cents: i64,
+  /// Reason practitioners are exempt from VAT
+  pub vat_exemption_reason: &'a str,
+  /// Mentions printed on the invoice, e.g. the template's legal mentions
+  pub note: Option<&'a str>,
+}
+
+/// e.g. `1234.50`
+fn amount(amount_in_cents: i64) -> String {
+  format!("{}.{:02}", amount_in_cents / 100, amount_in_cents % 100)
+}
+
+fn date_time_string(date: NaiveDate) -> String {
+  format!(
+    r#"<udt:DateTimeString format="102">{}</udt:DateTimeString>"#,
+    date.format("%Y%m%d")
+  )
+}
+
+impl Party<'_> {
+  fn to_xml(&self, element: &str) -> String {
+    let legal_organization = self
+      .siret
+      .map(|siret| {
+        format!(
+          r#"
+        <ram:SpecifiedLegalOrganization>
+          <ram:ID schemeID="0002">{}</ram:ID>
+        </ram:SpecifiedLegalOrganization>"#,
+          escape(siret)
+        )
+      })
+      .unwrap_or_default();
+
+    // Invoices exempt from VAT (category E) need a VAT or tax registration
+    // identifier of the seller (BR-E-02)
+    let tax_registration = self
+      .siret
+      .map(|siret| {
+        format!(
+          r#"
+        <ram:SpecifiedTaxRegistration>
+          <ram:ID schemeID="FC">{}</ram:ID>
+        </ram:SpecifiedTaxRegistration>"#,
+          escape(siret)
+        )
+      })
+      .unwrap_or_default();
+
+    format!(
+      r#"
+      <ram:{element}>
+        <ram:Name>{}</ram:Name>{legal_organization}
+        <ram:PostalTradeAddress>
+          <ram:PostcodeCode>{}</ram:PostcodeCode>
+          <ram:LineOne>{}</ram:LineOne>
+          <ram:CityName>{}</ram:CityName>
+          <ram:CountryID>FR</ram:CountryID>
+        </ram:PostalTradeAddress>{tax_registration}
+      </ram:{element}>"#,
+      escape(&self.name),
+      escape(self.zip_code),
+      escape(self.address_line),
+      escape(self.city),
+    )
+  }
+}
+
+impl FacturXInvoice<'_> {
+  /// Cross Industry Invoice of the BASIC WL profile. Notes d'honoraires are
+  /// handed out once paid, so nothing is left due.
+  pub fn to_xml(&self) -> String {
+    let note = self
+      .note
+      .map(|note| {
+        format!(
+          r#"
+    <ram:IncludedNote>
+      <ram:Content>{}</ram:Content>
+    </ram:IncludedNote>"#,
+          escape(note)
+        )
+      })
+      .unwrap_or_default();
+    let total = amount(self.amount_in_cents);
+
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<rsm:CrossIndustryInvoice xmlns:rsm="urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100" xmlns:ram="urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100" xmlns:udt="urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100">
+  <rsm:ExchangedDocumentContext>
+    <ram:GuidelineSpecifiedDocumentContextParameter>
+      <ram:ID>{PROFILE_ID}</ram:ID>
+    </ram:GuidelineSpecifiedDocumentContextParameter>
+  </rsm:ExchangedDocumentContext>
+  <rsm:ExchangedDocument>
+    <ram:ID>{number}</ram:ID>
+    <ram:TypeCode>380</ram:TypeCode>
+    <ram:IssueDateTime>{issue_date}</ram:IssueDateTime>{note}
+  </rsm:ExchangedDocument>
+  <rsm:SupplyChainTradeTransaction>
+    <ram:ApplicableHeaderTradeAgreement>{seller}{buyer}
+    </ram:ApplicableHeaderTradeAgreement>
+    <ram:ApplicableHeaderTradeDelivery>
+      <ram:ActualDeliverySupplyChainEvent>
+        <ram:OccurrenceDateTime>{issue_date}</ram:OccurrenceDateTime>
+      </ram:ActualDeliverySupplyChainEvent>
+    </ram:ApplicableHeaderTradeDelivery>
+    <ram:ApplicableHeaderTradeSettlement>
+      <ram:InvoiceCurrencyCode>EUR</ram:InvoiceCurrencyCode>
+      <ram:ApplicableTradeTax>
+        <ram:CalculatedAmount>0.00</ram:CalculatedAmount>
+        <ram:TypeCode>VAT</ram:TypeCode>
+        <ram:ExemptionReason>{exemption_reason}</ram:ExemptionReason>
+        <ram:BasisAmount>{total}</ram:BasisAmount>
+        <ram:CategoryCode>E</ram:CategoryCode>
+        <ram:RateApplicablePercent>0</ram:RateApplicablePercent>
+      </ram:ApplicableTradeTax>
+      <ram:SpecifiedTradeSettlementHeaderMonetarySummation>
+        <ram:LineTotalAmount>{total}</ram:LineTotalAmount>
+        <ram:TaxBasisTotalAmount>{total}</ram:TaxBasisTotalAmount>
+        <ram:TaxTotalAmount currencyID="EUR">0.00</ram:TaxTotalAmount>
+        <ram:GrandTotalAmount>{total}</ram:GrandTotalAmount>
+        <ram:TotalPrepaidAmount>{total}</ram:TotalPrepaidAmount>
+        <ram:DuePayableAmount>0.00</ram:DuePayableAmount>
+      </ram:SpecifiedTradeSettlementHeaderMonetarySummation>
+    </ram:ApplicableHeaderTradeSettlement>
+  </rsm:SupplyChainTradeTransaction>
+</rsm:CrossIndustryInvoice>
+"#,
+      number = escape(&self.number),
+      issue_date = date_time_string(self.issue_date),
+      seller = self.seller.to_xml("SellerTradeParty"),
+      buyer = self.buyer.to_xml("BuyerTradeParty"),
+      exemption_reason = escape(self.vat_exemption_reason),
+    )
+  }
+}
+
+/// XMP metadata identifying the document as PDF/A-3B carrying a Factur-X
+/// attachment, with the extension schema PDF/A requires for the `fx` namespace
+fn xmp_metadata(title: &str, date: &str) -> String {
+  format!(
+    r#"<?xpacket begin="{bom}" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:pdfaid="http://www.aiim.org/pdfa/ns/id/">
+      <pdfaid:part>3</pdfaid:part>
+      <pdfaid:conformance>B</pdfaid:conformance>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+      <dc:title><rdf:Alt><rdf:li xml:lang="x-default">{title}</rdf:li></rdf:Alt></dc:title>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:pdf="http://ns.adobe.com/pdf/1.3/">
+      <pdf:Producer>opencab</pdf:Producer>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+      <xmp:CreateDate>{date}</xmp:CreateDate>
+      <xmp:ModifyDate>{date}</xmp:ModifyDate>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:fx="urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#">
+      <fx:DocumentType>INVOICE</fx:DocumentType>
+      <fx:DocumentFileName>{ATTACHMENT_NAME}</fx:DocumentFileName>
+      <fx:Version>1.0</fx:Version>
+      <fx:ConformanceLevel>{CONFORMANCE_LEVEL}</fx:ConformanceLevel>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:pdfaExtension="http://www.aiim.org/pdfa/ns/extension/" xmlns:pdfaSchema="http://www.aiim.org/pdfa/ns/schema#" xmlns:pdfaProperty="http://www.aiim.org/pdfa/ns/property#">
+      <pdfaExtension:schemas>
+        <rdf:Bag>
+          <rdf:li rdf:parseType="Resource">
+            <pdfaSchema:schema>Factur-X PDFA Extension Schema</pdfaSchema:schema>
+            <pdfaSchema:namespaceURI>urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#</pdfaSchema:namespaceURI>
+            <pdfaSchema:prefix>fx</pdfaSchema:prefix>
+            <pdfaSchema:property>
+              <rdf:Seq>
+                <rdf:li rdf:parseType="Resource">
+                  <pdfaProperty:name>DocumentFileName</pdfaProperty:name>
+                  <pdfaProperty:valueType>Text</pdfaProperty:valueType>
+                  <pdfaProperty:category>external</pdfaProperty:category>
+                  <pdfaProperty:description>The name of the embedded XML document</pdfaProperty:description>
+                </rdf:li>
+                <rdf:li rdf:parseType="Resource">
+                  <pdfaProperty:name>DocumentType</pdfaProperty:name>
+                  <pdfaProperty:valueType>Text</pdfaProperty:valueType>
+                  <pdfaProperty:category>external</pdfaProperty:category>
+                  <pdfaProperty:description>The type of the hybrid document in capital letters, e.g. INVOICE or ORDER</pdfaProperty:description>
+                </rdf:li>
+                <rdf:li rdf:parseType="Resource">
+                  <pdfaProperty:name>Version</pdfaProperty:name>
+                  <pdfaProperty:valueType>Text</pdfaProperty:valueType>
+                  <pdfaProperty:category>external</pdfaProperty:category>
+                  <pdfaProperty:description>The actual version of the standard applying to the embedded XML document</pdfaProperty:description>
+                </rdf:li>
+                <rdf:li rdf:parseType="Resource">
+                  <pdfaProperty:name>ConformanceLevel</pdfaProperty:name>
+                  <pdfaProperty:valueType>Text</pdfaProperty:valueType>
+                  <pdfaProperty:category>external</pdfaProperty:category>
+                  <pdfaProperty:description>The conformance level of the embedded XML document</pdfaProperty:description>
+                </rdf:li>
+              </rdf:Seq>
+            </pdfaSchema:property>
+          </rdf:li>
+        </rdf:Bag>
+      </pdfaExtension:schemas>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#,
+    bom = '\u{feff}',
+    title = escape(title),
+  )
+}
+
+/// Text string in UTF-16BE, the only encoding PDF readers agree on for
+/// accented characters
+fn pdf_text_string(text: &str) -> String {
+  let hex: String = text
+    .encode_utf16()
+    .map(|unit| format!("{:04X}", unit))
+    .collect();
+  format!("<FEFF{}>", hex)
+}
+
+/// Minimal ICC v2 display profile of the sRGB colour space, the output intent
+/// PDF/A requires for documents drawn in RGB
+fn srgb_icc_profile() -> Vec<u8> {
+  fn s15_fixed16(value: f64) -> [u8; 4] {
+    ((value * 65536.0).round() as i32).to_be_bytes()
+  }
+  fn xyz(x: f64, y: f64, z: f64) -> Vec<u8> {
+    [
+      b"XYZ \0\0\0\0".as_slice(),
+      &s15_fixed16(x),
+      &s15_fixed16(y),
+      &s15_fixed16(z),
+    ]
+    .concat()
+  }
+
+  let description = b"sRGB IEC61966-2.1\0";
+  let mut desc = b"desc\0\0\0\0".to_vec();
+  desc.extend((description.len() as u32).to_be_bytes());
+  desc.extend(description);
+  // No Unicode nor ScriptCode description
+  desc.extend([0u8; 8 + 2 + 1 + 67]);
+
+  // A 2.2 gamma, close enough to the sRGB tone curve for an output intent
+  let curve = [
+    b"curv\0\0\0\0".as_slice(),
+    &1u32.to_be_bytes(),
+    &[0x02, 0x33],
+  ]
+  .concat();
+
+  let tags: Vec<(&[u8; 4], Vec<u8>)> = vec![
+    (b"desc", desc),
+    (b"cprt", b"text\0\0\0\0No copyright, use freely\0".to_vec()),
+    (b"wtpt", xyz(0.9642, 1.0, 0.8249)),
+    (b"rXYZ", xyz(0.4361, 0.2225, 0.0139)),
+    (b"gXYZ", xyz(0.3851, 0.7169, 0.0971)),
+    (b"bXYZ", xyz(0.1431, 0.0606, 0.7141)),
+    (b"rTRC", curve.clone()),
+    (b"gTRC", curve.clone()),
+    (b"bTRC", curve),
+  ];
+
+  let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+  let mut data = Vec::new();
+  let data_start = 128 + 4 + 12 * tags.len();
+  for (signature, tag) in &tags {
+    table.extend(*signature);
+    table.extend(((data_start + data.len()) as u32).to_be_bytes());
+    table.extend((tag.len() as u32).to_be_bytes());
+    data.extend(tag);
+    // Tag data is 4 bytes aligned
+    data.resize(data.len().next_multiple_of(4), 0);
+  }
+
+  let size = (data_start + data.len()) as u32;
+  let mut header = Vec::with_capacity(128);
+  header.extend(size.to_be_bytes());
+  header.extend([0u8; 4]);
+  header.extend([0x02, 0x10, 0x00, 0x00]);
+  header.extend(b"mntrRGB XYZ ");
+  header.extend([0u8; 12]);
+  header.extend(b"acsp");
+  header.extend([0u8; 24]);
+  header.extend([0u8; 4]);
+  header.extend(&xyz(0.9642, 1.0, 0.8249)[8..]);
+  header.resize(128, 0);
+
+  [header, table, data].concat()
+}
+
+/// Last match of `pattern`, parsed as a number
+fn last_number(pdf: &[u8], pattern: &str) -> Option<usize> {
+  let captures = Regex::new(pattern).ok()?.captures_iter(pdf).last()?;
+  std::str::from_utf8(&captures[1]).ok()?.parse().ok()
+}
+
+/// Turn `pdf` into a Factur-X invoice: the CII `xml` is attached, and the
+/// metadata and output intent of PDF/A-3 are added.
+///
+/// The objects are appended as an incremental update, leaving the document
+/// written by oxidize-pdf untouched. The generator draws with embedded fonts
+/// only, as PDF/A requires: the standard fonts oxidize-pdf lists in the
+/// resources of every page are never used.
+pub fn attach_to_pdf(
+  mut pdf: Vec<u8>,
+  xml: &str,
+  title: &str,
+  now: DateTime<Utc>,
+) -> Result<Vec<u8>, String> {
+  let previous_xref = last_number(&pdf, r"startxref\s+(\d+)").ok_or("startxref not found")?;
+  let size = last_number(&pdf, r"/Size (\d+)").ok_or("trailer size not found")?;
+  let root = last_number(&pdf, r"/Root (\d+) 0 R").ok_or("trailer root not found")?;
+
+  let catalog_pattern = format!(r"(?s)\n{} 0 obj\s*<<(.*?)>>\s*endobj", root);
+  let catalog = Regex::new(&catalog_pattern)
+    .map_err(|e| e.to_string())?
+    .captures_iter(&pdf)
+    .last()
+    .map(|captures| String::from_utf8_lossy(&captures[1]).into_owned())
+    .ok_or("catalog not found")?;
+  // The metadata written by oxidize-pdf is replaced by the PDF/A one
+  let catalog = Regex::new(r"/Metadata \d+ 0 R")
+    .map_err(|e| e.to_string())?
+    .replace_all(catalog.as_bytes(), b"".as_slice())
+    .into_owned();
+  let catalog = String::from_utf8_lossy(&catalog).trim().to_string();
+
+  let (file, file_spec, metadata, profile, output_intent, info) =
+    (size, size + 1, size + 2, size + 3, size + 4, size + 5);
+  let pdf_date = now.format("D:%Y%m%d%H%M%S+00'00'").to_string();
+  let xmp = xmp_metadata(title, &now.format("%Y-%m-%dT%H:%M:%S+00:00").to_string());
+  let icc_profile = srgb_icc_profile();
+
+  let objects: Vec<(usize, Vec<u8>)> = vec![
+    (
+      file,
+      [
+        format!(
+          "<< /Type /EmbeddedFile /Subtype /text#2Fxml /Params << /Size {len} /ModDate ({pdf_date}) >> /Length {len} >>\nstream\n",
+          len = xml.len()
+        )
+        .as_bytes(),
+        xml.as_bytes(),
+        b"\nendstream",
+      ]
+      .concat(),
+    ),
+    (
+      file_spec,
+      format!(
+        "<< /Type /Filespec /F ({ATTACHMENT_NAME}) /UF ({ATTACHMENT_NAME}) /Desc (Factur-X) /AFRelationship /Data /EF << /F {file} 0 R /UF {file} 0 R >> >>"
+      )
+      .into_bytes(),
+    ),
+    (
+      metadata,
+      [
+        format!(
+          "<< /Type /Metadata /Subtype /XML /Length {} >>\nstream\n",
+          xmp.len()
+        )
+        .as_bytes(),
+        xmp.as_bytes(),
+        b"\nendstream",
+      ]
+      .concat(),
+    ),
+    (
+      profile,
+      [
+        format!("<< /N 3 /Length {} >>\nstream\n", icc_profile.len()).as_bytes(),
+        &icc_profile,
+        b"\nendstream",
+      ]
+      .concat(),
+    ),
+    (
+      output_intent,
+      format!(
+        "<< /Type /OutputIntent /S /GTS_PDFA1 /OutputConditionIdentifier (sRGB IEC61966-2.1) /Info (sRGB IEC61966-2.1) /DestOutputProfile {profile} 0 R >>"
+      )
+      .into_bytes(),
+    ),
+    (
+      info,
+      format!(
+        "<< /Title {} /Producer (opencab) /CreationDate ({pdf_date}) /ModDate ({pdf_date}) >>",
+        pdf_text_string(title)
+      )
+      .into_bytes(),
+    ),
+    (
+      root,
+      format!(
+        "<< {catalog}\n/Metadata {metadata} 0 R /OutputIntents [{output_intent} 0 R] /AF [{file_spec} 0 R] /Names << /EmbeddedFiles << /Names [({ATTACHMENT_NAME}) {file_spec} 0 R] >> >> >>"
+      )
+      .into_bytes(),
+    ),
+  ];
+
+  if !pdf.ends_with(b"\n") {
+    pdf.push(b'\n');
+  }
+  let mut offsets = Vec::with_capacity(objects.len());
+  for (number, object) in &objects {
+    offsets.push((*number, pdf.len()));
+    pdf.extend(format!("{} 0 obj\n", number).as_bytes());
+    pdf.extend(object);
+    pdf.extend(b"\nendobj\n");
+  }
+
+  let xref = pdf.len();
+  offsets.sort();
+  let mut xref_table = "xref\n0 1\n0000000000 65535 f \n".to_string();
+  // One subsection per run of consecutive object numbers
+  for run in offsets.chunk_by(|a, b| a.0 + 1 == b.0) {
+    xref_table.push_str(&format!("{} {}\n", run[0].0, run.len()));
+    for (_, offset) in run {
+      xref_table.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+  }
+  let id = uuid::Uuid::new_v4().simple().to_string();
+  xref_table.push_str(&format!(
+    "trailer\n<< /Size {} /Root {root} 0 R /Info {info} 0 R /Prev {previous_xref} /ID [<{id}> <{id}>] >>\nstartxref\n{xref}\n%%EOF\n",
+    info + 1
+  ));
+  pdf.extend(xref_table.as_bytes());
+
+  Ok(pdf)
+}
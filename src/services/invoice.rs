@@ -1,27 +1,40 @@
 use crate::{
   app_state::{AppState, WorkerJob},
   initializers::get_services,
+  locales::fill,
   models::{
     _entities::{
-      patients, practitioner_offices::Entity as PractitionerOffices,
-      sea_orm_active_enums::PaymentMethod, user_business_informations, users,
+      email_logs, patients,
+      practitioner_offices::Entity as PractitionerOffices,
+      sea_orm_active_enums::{Locale, PaymentMethod},
+      user_business_informations, users,
+    },
+    invoice_templates::{self, InvoiceTemplate},
+    medical_appointments::{
+      ActiveModel as MedicalAppointments, CreateMedicalAppointmentParams, PayerShares,
     },
-    medical_appointments::{ActiveModel as MedicalAppointments, CreateMedicalAppointmentParams},
     my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
     patients as PatientModel,
   },
+  services::{acts, payments},
   workers::{
     self,
     invoice_generator::InvoiceGeneratorArgs,
-    mailer::{args::EmailArgs, attachment::EmailAttachment},
+    mailer::{
+      args::EmailArgs,
+      attachment::EmailAttachment,
+      template::{EmailTemplate, Signature},
+    },
   },
 };
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::{EntityTrait, TransactionTrait};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GenerateInvoiceParams {
-  pub amount: f32,
+  /// Left out to invoice the price of the act in the catalogue
+  pub amount: Option<f32>,
+  pub act_id: Option<i32>,
   pub invoice_date: String,
   pub should_be_sent_by_email: bool,
   pub practitioner_office_id: i32,
@@ -33,6 +46,9 @@ pub struct GenerateInvoiceResponse {
   pub filename: String,
   patient_email: String,
   invoice_date: chrono::NaiveDate,
+  locale: Locale,
+  patient_id: i32,
+  appointment_id: i32,
 }
 
 pub async fn send_invoice(
@@ -41,6 +57,48 @@ pub async fn send_invoice(
   current_user: &users::Model,
   user_business_informations: &user_business_informations::Model,
 ) -> Result<(), MyErrors> {
+  let template = invoice_templates::Entity::find_for_user(current_user.id)
+    .one(&state.db)
+    .await?
+    .as_ref()
+    .map(invoice_templates::Model::template)
+    .unwrap_or_default();
+  let args = invoice_email(
+    generated_invoice,
+    current_user,
+    user_business_informations,
+    &template,
+  )?;
+
+  // Enqueue email job via worker channel
+  state
+    .worker_transmitter
+    .send(WorkerJob::Email(args))
+    .await
+    .map_err(|_| UnexpectedError::ShouldNotHappen)?;
+
+  Ok(())
+}
+
+/// The invoice email of `email_log`, exactly as it was first sent. The invoice
+/// is not printed again: the same PDF goes to the same address, whatever the
+/// template, language or email address of the patient became since.
+pub fn resent_invoice_email(email_log: &email_logs::Model) -> Result<EmailArgs, MyErrors> {
+  let sent_email = email_log
+    .sent_email
+    .clone()
+    .ok_or(ApplicationError::UnprocessableEntity)?;
+
+  Ok(serde_json::from_value(sent_email).map_err(|e| UnexpectedError::new(e.to_string()))?)
+}
+
+/// Build the email delivering an invoice to the patient, in the patient's language
+pub fn invoice_email(
+  generated_invoice: &GenerateInvoiceResponse,
+  current_user: &users::Model,
+  user_business_informations: &user_business_informations::Model,
+  template: &InvoiceTemplate,
+) -> Result<EmailArgs, MyErrors> {
   if generated_invoice.patient_email == PatientModel::DEFAULT_EMAIL {
     return Err(ApplicationError::UnprocessableEntity.into());
   }
@@ -51,75 +109,64 @@ pub async fn send_invoice(
     &generated_invoice.pdf_data,
   );
 
-  let invoice_date = generated_invoice
-    .invoice_date
-    .format("%d/%m/%Y")
-    .to_string();
-
-  let args = EmailArgs::new_text(
-    generated_invoice.patient_email.clone(),
-    format!("Note d'honoraires {}", invoice_date),
-    format!(
-      "Vous trouverez ci-joint votre facture pour la consultation du {}\n\n{} {}\n{}\n{}",
-      invoice_date,
-      current_user.last_name,
-      current_user.first_name,
-      user_business_informations.profession.to_french(),
-      current_user.phone_number
-    ),
-  )
-  .set_from_name(format!(
-    "{} {}",
-    current_user.first_name, current_user.last_name
-  ))
-  .with_attachment(attachment)
-  .with_reply_to(current_user.email.to_string());
-
-  // Enqueue email job via worker channel
-  state
-    .worker_transmitter
-    .send(WorkerJob::Email(args))
-    .await
-    .map_err(|_| UnexpectedError::ShouldNotHappen)?;
-
-  Ok(())
+  let locale = &generated_invoice.locale;
+  let signature =
+    Signature::practitioner(current_user, user_business_informations, template, locale);
+  let email = EmailTemplate::Invoice {
+    invoice_date: generated_invoice.invoice_date,
+  }
+  .render(locale, Some(&signature));
+
+  let args = EmailArgs::from_template(generated_invoice.patient_email.clone(), email)
+    .set_from_name(format!(
+      "{} {}",
+      current_user.first_name, current_user.last_name
+    ))
+    .with_attachment(attachment)
+    .with_reply_to(current_user.email.to_string())
+    .for_user(current_user.id)
+    .for_patient(generated_invoice.patient_id)
+    .for_medical_appointment(generated_invoice.appointment_id);
+
+  Ok(args)
 }
 
+/// Record an appointment of `current_user` with `patient`, who must be
+/// allowed to write to the patient's record, and print its invoice
 pub async fn generate_patient_invoice(
-  patient_id: &i32,
+  patient: patients::Model,
   params: &GenerateInvoiceParams,
   current_user: &users::Model,
 ) -> Result<GenerateInvoiceResponse, MyErrors> {
   let services = get_services();
 
-  let patient = patients::Entity::find_by_id(*patient_id)
-    .filter(patients::Column::UserId.eq(current_user.id))
-    .one(&services.db)
-    .await?
-    .ok_or(ApplicationError::NotFound)?;
-
   let invoice_date = chrono::NaiveDate::parse_from_str(&params.invoice_date, "%Y-%m-%d")?;
 
-  let filename = format!(
-    "{} {} Note d'honoraires - {} {} {}.pdf",
-    current_user.first_name,
-    current_user.last_name.to_uppercase(),
-    &patient.last_name,
-    &patient.first_name,
-    invoice_date.format("%d_%m_%Y")
-  );
+  let (act, price_in_cents) = acts::appointment_act(
+    &services.db,
+    current_user.id,
+    params.act_id,
+    params.practitioner_office_id,
+    params.amount.map(|amount| (amount * 100.0).round() as i32),
+  )
+  .await?;
 
   let medical_appointment_params = CreateMedicalAppointmentParams {
     user_id: current_user.id,
-    patient_id: *patient_id,
+    patient_id: patient.id,
     practitioner_office_id: params.practitioner_office_id,
     payment_method: params.payment_method.clone(),
     date: invoice_date,
-    price_in_cents: (params.amount * 100.0).round() as i32,
+    act,
+    price_in_cents,
+    payer_shares: PayerShares::default(),
   };
 
+  let txn = services.db.begin().await?;
   let created_medical_appointment =
-    MedicalAppointments::create(&services.db, &medical_appointment_params).await?;
+    MedicalAppointments::create(&txn, &medical_appointment_params).await?;
+  payments::settle_on_the_spot(&txn, &created_medical_appointment).await?;
+  txn.commit().await?;
 
   let practitioner_office =
     PractitionerOffices::find_by_id(created_medical_appointment.practitioner_office_id)
@@ -127,20 +174,44 @@ pub async fn generate_patient_invoice(
       .await?
       .ok_or(UnexpectedError::ShouldNotHappen)?;
 
-  let args = InvoiceGeneratorArgs {
-    patient: patient.clone(),
+  print_invoice(InvoiceGeneratorArgs {
+    appointment_id: created_medical_appointment.id,
+    patient,
     user: current_user.clone(),
-    amount: params.amount,
+    amount: price_in_cents as f32 / 100.0,
+    act_label: created_medical_appointment.act_full_label(),
     invoice_date,
     practitioner_office,
-  };
+  })
+  .await
+}
 
-  let pdf_data = workers::invoice_generator::generate_invoice_pdf(&services.db, &args).await?;
+/// Print the invoice described by `args`, named in the language of the patient
+async fn print_invoice(args: InvoiceGeneratorArgs) -> Result<GenerateInvoiceResponse, MyErrors> {
+  let locale = args.patient.document_locale(&args.user);
+  let filename = fill(
+    locale.messages().invoice_filename,
+    &[
+      &format!(
+        "{} {}",
+        args.user.first_name,
+        args.user.last_name.to_uppercase()
+      ),
+      &format!("{} {}", args.patient.last_name, args.patient.first_name),
+      &args.invoice_date.format("%d_%m_%Y").to_string(),
+    ],
+  );
+
+  let pdf_data =
+    workers::invoice_generator::generate_invoice_pdf(&get_services().db, &args).await?;
 
   Ok(GenerateInvoiceResponse {
     pdf_data,
     filename,
-    patient_email: patient.email,
-    invoice_date,
+    patient_email: args.patient.email,
+    invoice_date: args.invoice_date,
+    locale,
+    patient_id: args.patient.id,
+    appointment_id: args.appointment_id,
   })
 }
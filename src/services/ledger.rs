@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use sea_orm::{ActiveEnum, ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{
+      expenses, medical_appointments, patients, payments,
+      sea_orm_active_enums::{ExpenseCategory, PaymentMethod},
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+    users,
+  },
+  services::appointments::ToExcel,
+};
+
+/// A line of the "livre des recettes": money actually received on a given day
+#[derive(Debug)]
+pub struct ReceiptEntry {
+  pub date: NaiveDate,
+  pub payer: String,
+  pub method: PaymentMethod,
+  pub amount_in_cents: i64,
+}
+
+#[derive(Debug)]
+pub struct CategoryTotal {
+  pub category: ExpenseCategory,
+  pub amount_in_cents: i64,
+}
+
+/// Cash-basis summary of a fiscal year, laid out like the 2035 declaration
+#[derive(Debug)]
+pub struct FiscalYearReport {
+  pub year: i32,
+  pub receipts: Vec<ReceiptEntry>,
+  pub total_receipts_in_cents: i64,
+  pub retroceded_fees_in_cents: i64,
+  pub net_receipts_in_cents: i64,
+  pub expenses_by_category: Vec<CategoryTotal>,
+  pub total_expenses_in_cents: i64,
+  pub result_in_cents: i64,
+}
+
+/// First and last day of the calendar year used as fiscal year
+pub fn fiscal_year_bounds(year: i32) -> Result<(NaiveDate, NaiveDate), MyErrors> {
+  let start = NaiveDate::from_ymd_opt(year, 1, 1).ok_or(ApplicationError::UnprocessableEntity)?;
+  let end = NaiveDate::from_ymd_opt(year, 12, 31).ok_or(ApplicationError::UnprocessableEntity)?;
+  Ok((start, end))
+}
+
+fn patient_name(patients_by_id: &HashMap<i32, patients::Model>, patient_id: i32) -> String {
+  patients_by_id
+    .get(&patient_id)
+    .map(|patient| format!("{} {}", patient.last_name, patient.first_name))
+    .unwrap_or_default()
+}
+
+/// Receipts of `user` collected during the period: patient payments, and the
+/// shares reimbursed by insurers on the day they were received.
+async fn receipts(
+  user: &users::Model,
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Result<Vec<ReceiptEntry>, MyErrors> {
+  let db = &get_services().db;
+
+  let patient_payments = payments::Entity::find()
+    .filter(payments::Column::PaidOn.between(start_date, end_date))
+    .find_also_related(medical_appointments::Entity)
+    .filter(medical_appointments::Column::UserId.eq(user.id))
+    .all(db)
+    .await?;
+
+  let reimbursed_appointments = medical_appointments::Entity::find()
+    .filter(medical_appointments::Column::UserId.eq(user.id))
+    .filter(
+      medical_appointments::Column::AmoPaidAt
+        .between(start_date, end_date)
+        .or(medical_appointments::Column::MutuellePaidAt.between(start_date, end_date)),
+    )
+    .all(db)
+    .await?;
+
+  let patient_ids = patient_payments
+    .iter()
+    .filter_map(|(_, appointment)| appointment.as_ref().map(|a| a.patient_id))
+    .chain(reimbursed_appointments.iter().map(|a| a.patient_id));
+  let patients_by_id: HashMap<i32, patients::Model> = patients::Entity::find()
+    .filter(patients::Column::Id.is_in(patient_ids))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|patient| (patient.id, patient))
+    .collect();
+
+  let mut entries: Vec<ReceiptEntry> = patient_payments
+    .into_iter()
+    .filter_map(|(payment, appointment)| {
+      Some(ReceiptEntry {
+        date: payment.paid_on,
+        payer: patient_name(&patients_by_id, appointment?.patient_id),
+        method: payment.method,
+        amount_in_cents: i64::from(payment.amount_in_cents),
+      })
+    })
+    .collect();
+
+  for appointment in &reimbursed_appointments {
+    let name = patient_name(&patients_by_id, appointment.patient_id);
+    let insurer_shares = [
+      (
+        "AMO",
+        appointment.amo_paid_at,
+        appointment.amo_share_in_cents,
+      ),
+      (
+        "Mutuelle",
+        appointment.mutuelle_paid_at,
+        appointment.mutuelle_share_in_cents,
+      ),
+    ];
+
+    for (insurer, paid_at, share) in insurer_shares {
+      if let Some(paid_at) = paid_at.filter(|date| (start_date..=end_date).contains(date)) {
+        if share > 0 {
+          entries.push(ReceiptEntry {
+            date: paid_at,
+            payer: format!("{} – {}", insurer, name),
+            method: PaymentMethod::Transfer,
+            amount_in_cents: i64::from(share),
+          });
+        }
+      }
+    }
+  }
+
+  entries.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.payer.cmp(&b.payer)));
+
+  Ok(entries)
+}
+
+/// Build the yearly ledger of `user`: every receipt of the year, and the
+/// expenses aggregated on the lines of the 2035 declaration. Retroceded fees
+/// are deducted from the receipts rather than counted as an expense.
+pub async fn fiscal_year_report(
+  user: &users::Model,
+  year: i32,
+) -> Result<FiscalYearReport, MyErrors> {
+  let db = &get_services().db;
+  let (start_date, end_date) = fiscal_year_bounds(year)?;
+
+  let receipts = receipts(user, start_date, end_date).await?;
+  let total_receipts_in_cents: i64 = receipts.iter().map(|entry| entry.amount_in_cents).sum();
+
+  let expenses = expenses::Entity::find_for_period(user.id, start_date, end_date)
+    .all(db)
+    .await?;
+
+  let mut totals_by_category: BTreeMap<String, (ExpenseCategory, i64)> = BTreeMap::new();
+  for expense in &expenses {
+    totals_by_category
+      .entry(expense.category.to_value())
+      .or_insert((expense.category.clone(), 0))
+      .1 += i64::from(expense.amount_in_cents);
+  }
+
+  let retroceded_fees_in_cents = totals_by_category
+    .remove(&ExpenseCategory::Retrocession.to_value())
+    .map(|(_, amount)| amount)
+    .unwrap_or(0);
+
+  let expenses_by_category: Vec<CategoryTotal> = totals_by_category
+    .into_values()
+    .map(|(category, amount_in_cents)| CategoryTotal {
+      category,
+      amount_in_cents,
+    })
+    .collect();
+  let total_expenses_in_cents: i64 = expenses_by_category
+    .iter()
+    .map(|total| total.amount_in_cents)
+    .sum();
+
+  let net_receipts_in_cents = total_receipts_in_cents - retroceded_fees_in_cents;
+
+  Ok(FiscalYearReport {
+    year,
+    receipts,
+    total_receipts_in_cents,
+    retroceded_fees_in_cents,
+    net_receipts_in_cents,
+    expenses_by_category,
+    total_expenses_in_cents,
+    result_in_cents: net_receipts_in_cents - total_expenses_in_cents,
+  })
+}
+
+fn euros(amount_in_cents: i64) -> f64 {
+  amount_in_cents as f64 / 100.0
+}
+
+impl ToExcel for FiscalYearReport {
+  fn to_excel(&self) -> Result<Workbook, MyErrors> {
+    let mut workbook = Workbook::new();
+    let date_format = Format::new().set_num_format("dd/mm/yyyy");
+    let amount_format = Format::new().set_num_format("0.00");
+    let bold_format = Format::new().set_bold();
+    let header_format = Format::new()
+      .set_bold()
+      .set_background_color(rust_xlsxwriter::Color::Green)
+      .set_font_color(rust_xlsxwriter::Color::White);
+
+    // Livre des recettes
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Livre des recettes")?;
+
+    for (column, (title, width)) in [
+      ("Date", 15),
+      ("Payeur", 35),
+      ("Mode de paiement", 18),
+      ("Montant (€)", 15),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      worksheet.write_with_format(0, column as u16, title, &header_format)?;
+      worksheet.set_column_width(column as u16, width)?;
+    }
+
+    for (i, entry) in self.receipts.iter().enumerate() {
+      let row = i as u32 + 1;
+      let excel_date = ExcelDateTime::parse_from_str(&entry.date.to_string())?;
+      worksheet.write_with_format(row, 0, &excel_date, &date_format)?;
+      worksheet.write(row, 1, &entry.payer)?;
+      worksheet.write(row, 2, entry.method.to_value())?;
+      worksheet.write_with_format(row, 3, euros(entry.amount_in_cents), &amount_format)?;
+    }
+
+    let total_row = self.receipts.len() as u32 + 1;
+    worksheet.write_with_format(total_row, 1, "Total", &bold_format)?;
+    worksheet.write_with_format(
+      total_row,
+      3,
+      euros(self.total_receipts_in_cents),
+      &amount_format,
+    )?;
+
+    // Synthèse 2035
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(format!("Synthèse 2035 - {}", self.year))?;
+    worksheet.set_column_width(0, 55)?;
+    worksheet.set_column_width(1, 15)?;
+
+    worksheet.write_with_format(0, 0, "Poste", &header_format)?;
+    worksheet.write_with_format(0, 1, "Montant (€)", &header_format)?;
+
+    let mut lines = vec![
+      ("Recettes encaissées", self.total_receipts_in_cents, true),
+      (
+        ExpenseCategory::Retrocession.to_french(),
+        self.retroceded_fees_in_cents,
+        false,
+      ),
+      ("Montant net des recettes", self.net_receipts_in_cents, true),
+    ];
+    lines.extend(
+      self
+        .expenses_by_category
+        .iter()
+        .map(|total| (total.category.to_french(), total.amount_in_cents, false)),
+    );
+    lines.push(("Total des dépenses", self.total_expenses_in_cents, true));
+    lines.push(("Résultat", self.result_in_cents, true));
+
+    for (i, (label, amount_in_cents, bold)) in lines.into_iter().enumerate() {
+      let row = i as u32 + 1;
+      if bold {
+        worksheet.write_with_format(row, 0, label, &bold_format)?;
+      } else {
+        worksheet.write(row, 0, label)?;
+      }
+      worksheet.write_with_format(row, 1, euros(amount_in_cents), &amount_format)?;
+    }
+
+    Ok(workbook)
+  }
+}
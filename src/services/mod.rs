@@ -1,7 +1,23 @@
+pub mod acts;
+pub mod admin;
 pub mod appointments;
+pub mod audit;
+pub mod check_deposits;
 pub mod crypto;
+pub mod exporters;
+pub mod factur_x;
 pub mod invoice;
+pub mod ledger;
+pub mod patient_dedup;
+pub mod patient_export;
+pub mod patient_retention;
+pub mod patient_sharing;
 pub mod patients;
+pub mod payments;
 pub mod practitioner_office;
+pub mod receivables;
+pub mod retrocession;
+pub mod scheduled_exports;
+pub mod stats;
 pub mod storage;
 pub mod user;
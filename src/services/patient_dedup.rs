@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use sea_orm::{
+  sea_query::Expr, ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, ModelTrait,
+  QueryFilter, QueryOrder, TransactionTrait,
+};
+use serde::Serialize;
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{
+      email_logs, medical_appointments, patient_accesses, patient_insurances, patients,
+      sea_orm_active_enums::PatientAccessStatus,
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+    users,
+  },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+  SameSsn,
+  SimilarName,
+}
+
+#[derive(Debug)]
+pub struct DuplicateGroup {
+  pub reason: DuplicateReason,
+  pub patients: Vec<patients::Model>,
+}
+
+/// Lowercase a name and strip its accents, spaces and dashes so that
+/// "Jean-Émile  Dupré" and "jean emile dupre" are considered the same.
+fn normalize_name(name: &str) -> String {
+  name
+    .to_lowercase()
+    .chars()
+    .filter_map(|c| match c {
+      'à' | 'á' | 'â' | 'ä' | 'ã' => Some('a'),
+      'ç' => Some('c'),
+      'è' | 'é' | 'ê' | 'ë' => Some('e'),
+      'ì' | 'í' | 'î' | 'ï' => Some('i'),
+      'ñ' => Some('n'),
+      'ò' | 'ó' | 'ô' | 'ö' | 'õ' => Some('o'),
+      'ù' | 'ú' | 'û' | 'ü' => Some('u'),
+      'ÿ' => Some('y'),
+      c if c.is_alphanumeric() => Some(c),
+      _ => None,
+    })
+    .collect()
+}
+
+fn group_by<K: Ord>(
+  patients: &[patients::Model],
+  key: impl Fn(&patients::Model) -> K,
+) -> impl Iterator<Item = Vec<patients::Model>> {
+  let mut groups: BTreeMap<K, Vec<patients::Model>> = BTreeMap::new();
+  for patient in patients {
+    groups
+      .entry(key(patient))
+      .or_default()
+      .push(patient.clone());
+  }
+
+  groups.into_values()
+}
+
+/// Split namesakes by birth date: patients born on different days are homonyms,
+/// while a patient whose birth date is unknown may be any of them.
+fn split_by_birth_date(namesakes: Vec<patients::Model>) -> Vec<Vec<patients::Model>> {
+  let (unknown, known): (Vec<_>, Vec<_>) = namesakes
+    .into_iter()
+    .partition(|patient| patient.birth_date.is_none());
+
+  if known.is_empty() {
+    return vec![unknown];
+  }
+
+  group_by(&known, |p| p.birth_date)
+    .map(|mut group| {
+      group.extend(unknown.iter().cloned());
+      group
+    })
+    .collect()
+}
+
+/// List the groups of patients of `user` that are likely the same person:
+/// either they share the same social security number, or their names only
+/// differ by case, accents or punctuation and their birth dates do not conflict.
+pub async fn find_duplicates(user: &users::Model) -> Result<Vec<DuplicateGroup>, MyErrors> {
+  let db = &get_services().db;
+
+  let patients = patients::Entity::find()
+    .filter(patients::Column::UserId.eq(user.id))
+    .filter(patients::Column::AnonymizedAt.is_null())
+    .order_by_asc(patients::Column::CreatedAt)
+    .all(db)
+    .await?;
+
+  let mut duplicates: Vec<DuplicateGroup> = group_by(&patients, |p| p.hashed_ssn.clone())
+    .filter(|group| group.len() > 1)
+    .map(|patients| DuplicateGroup {
+      reason: DuplicateReason::SameSsn,
+      patients,
+    })
+    .collect();
+
+  let same_ssn_ids: Vec<HashSet<i32>> = duplicates
+    .iter()
+    .map(|group| group.patients.iter().map(|p| p.id).collect())
+    .collect();
+
+  let similar_names = group_by(&patients, |p| {
+    (normalize_name(&p.last_name), normalize_name(&p.first_name))
+  })
+  .flat_map(split_by_birth_date)
+  .filter(|group| group.len() > 1)
+  // Skip groups that are already reported because of their social security number
+  .filter(|group| {
+    let ids: HashSet<i32> = group.iter().map(|p| p.id).collect();
+    !same_ssn_ids.iter().any(|ssn_ids| ids.is_subset(ssn_ids))
+  })
+  .map(|patients| DuplicateGroup {
+    reason: DuplicateReason::SimilarName,
+    patients,
+  });
+
+  duplicates.extend(similar_names);
+
+  Ok(duplicates)
+}
+
+/// Move every medical appointment, insurance, email and access of `duplicate`
+/// onto `survivor`, then delete `duplicate`. Both records must belong to the
+/// same practitioner.
+pub async fn merge(
+  survivor: &patients::Model,
+  duplicate: patients::Model,
+) -> Result<patients::Model, MyErrors> {
+  if survivor.id == duplicate.id || survivor.user_id != duplicate.user_id {
+    return Err(ApplicationError::UnprocessableEntity.into());
+  }
+
+  let txn = get_services().db.begin().await?;
+
+  medical_appointments::Entity::update_many()
+    .col_expr(
+      medical_appointments::Column::PatientId,
+      Expr::value(survivor.id),
+    )
+    .filter(medical_appointments::Column::PatientId.eq(duplicate.id))
+    .exec(&txn)
+    .await?;
+
+  patient_insurances::Entity::update_many()
+    .col_expr(
+      patient_insurances::Column::PatientId,
+      Expr::value(survivor.id),
+    )
+    .filter(patient_insurances::Column::PatientId.eq(duplicate.id))
+    .exec(&txn)
+    .await?;
+
+  email_logs::Entity::update_many()
+    .col_expr(email_logs::Column::PatientId, Expr::value(survivor.id))
+    .filter(email_logs::Column::PatientId.eq(duplicate.id))
+    .exec(&txn)
+    .await?;
+
+  // A colleague keeps a single access per patient: the one of the survivor,
+  // unless only the duplicate's has been granted
+  let survivor_accesses: HashMap<i32, patient_accesses::Model> = patient_accesses::Entity::find()
+    .filter(patient_accesses::Column::PatientId.eq(survivor.id))
+    .all(&txn)
+    .await?
+    .into_iter()
+    .map(|access| (access.user_id, access))
+    .collect();
+  let duplicate_accesses = patient_accesses::Entity::find()
+    .filter(patient_accesses::Column::PatientId.eq(duplicate.id))
+    .all(&txn)
+    .await?;
+
+  for access in duplicate_accesses {
+    match survivor_accesses.get(&access.user_id) {
+      Some(existing)
+        if existing.status == PatientAccessStatus::Pending
+          && access.status == PatientAccessStatus::Granted =>
+      {
+        existing.clone().delete(&txn).await?;
+      }
+      Some(_) => continue,
+      None => {}
+    }
+
+    let mut access: patient_accesses::ActiveModel = access.into();
+    access.patient_id = ActiveValue::Set(survivor.id);
+    access.update(&txn).await?;
+  }
+
+  duplicate.delete(&txn).await?;
+
+  txn.commit().await?;
+
+  Ok(survivor.clone())
+}
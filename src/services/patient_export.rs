@@ -0,0 +1,155 @@
+use std::io::{Cursor, Write};
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{
+      medical_appointments, patient_insurances, practitioner_offices, user_business_informations,
+    },
+    my_errors::{unexpected_error::UnexpectedError, MyErrors},
+    patients, payments, users,
+  },
+  views::{
+    patient_export::{ExportedMedicalAppointment, PatientExport},
+    patient_insurance::PatientInsuranceResponse,
+  },
+  workers::invoice_generator::{self, InvoiceGeneratorArgs},
+};
+
+pub struct PatientArchive {
+  pub data: Vec<u8>,
+  pub filename: String,
+}
+
+/// Lowercase ASCII version of `name`, safe to quote in a Content-Disposition
+/// header, e.g. `d_artagnan` for `D'Artagnan`
+fn file_name_part(name: &str) -> String {
+  let mut part = String::new();
+  for c in name.to_lowercase().chars() {
+    let folded = match c {
+      'à' | 'á' | 'â' | 'ä' | 'ã' | 'å' => "a",
+      'æ' => "ae",
+      'ç' => "c",
+      'è' | 'é' | 'ê' | 'ë' => "e",
+      'ì' | 'í' | 'î' | 'ï' => "i",
+      'ñ' => "n",
+      'ò' | 'ó' | 'ô' | 'ö' | 'õ' | 'ø' => "o",
+      'œ' => "oe",
+      'ù' | 'ú' | 'û' | 'ü' => "u",
+      'ý' | 'ÿ' => "y",
+      _ => "",
+    };
+    if !folded.is_empty() {
+      part.push_str(folded);
+    } else if c.is_ascii_alphanumeric() {
+      part.push(c);
+    } else if !part.is_empty() && !part.ends_with('_') {
+      part.push('_');
+    }
+  }
+  part.trim_end_matches('_').to_string()
+}
+
+/// Build the GDPR "right of access" archive of a patient: a `patient.json`
+/// document with the identity and appointments, along with the invoice PDFs
+/// regenerated for each appointment.
+pub async fn export_archive(
+  patient: &patients::Model,
+  practitioner: &users::Model,
+) -> Result<PatientArchive, MyErrors> {
+  let db = &get_services().db;
+
+  let appointments = medical_appointments::Entity::find()
+    .filter(medical_appointments::Column::PatientId.eq(patient.id))
+    .filter(medical_appointments::Column::UserId.eq(practitioner.id))
+    .order_by_asc(medical_appointments::Column::Date)
+    .find_also_related(practitioner_offices::Entity)
+    .all(db)
+    .await?;
+
+  // Invoices can only be produced once the practitioner filled in their business information
+  let can_generate_invoices = user_business_informations::Entity::find()
+    .filter(user_business_informations::Column::UserId.eq(practitioner.id))
+    .one(db)
+    .await?
+    .is_some();
+
+  let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+  let options = SimpleFileOptions::default();
+  let mut exported_appointments = Vec::with_capacity(appointments.len());
+
+  for (appointment, office) in appointments {
+    let office = office.ok_or(UnexpectedError::ShouldNotHappen)?;
+
+    let invoice_file = if can_generate_invoices {
+      let pdf_data = invoice_generator::generate_invoice_pdf(
+        db,
+        &InvoiceGeneratorArgs {
+          appointment_id: appointment.id,
+          patient: patient.clone(),
+          user: practitioner.clone(),
+          amount: appointment.price_in_cents as f32 / 100.0,
+          act_label: appointment.act_full_label(),
+          invoice_date: appointment.date,
+          practitioner_office: office.clone(),
+        },
+      )
+      .await?;
+
+      let invoice_file = format!(
+        "invoices/{}_{}.pdf",
+        appointment.date.format("%Y-%m-%d"),
+        appointment.id
+      );
+      archive.start_file(invoice_file.as_str(), options)?;
+      archive.write_all(&pdf_data)?;
+
+      Some(invoice_file)
+    } else {
+      None
+    };
+
+    let payments = payments::Entity::find_for_appointment(appointment.id)
+      .all(db)
+      .await?;
+
+    exported_appointments.push(ExportedMedicalAppointment::new(
+      &appointment,
+      &office,
+      &payments,
+      invoice_file,
+    ));
+  }
+
+  let insurances = patient_insurances::Entity::find_for_patient(patient.id)
+    .all(db)
+    .await?
+    .iter()
+    .map(PatientInsuranceResponse::new)
+    .collect();
+
+  let export = PatientExport::new(
+    patient,
+    patient.decrypt_ssn()?,
+    insurances,
+    exported_appointments,
+  );
+
+  archive.start_file("patient.json", options)?;
+  archive.write_all(&serde_json::to_vec_pretty(&export)?)?;
+
+  let data = archive.finish()?.into_inner();
+
+  Ok(PatientArchive {
+    data,
+    filename: format!(
+      "export_{}_{}_{}.zip",
+      file_name_part(&patient.last_name),
+      file_name_part(&patient.first_name),
+      chrono::Utc::now().format("%Y%m%d")
+    ),
+  })
+}
@@ -0,0 +1,51 @@
+use chrono::{Duration, Utc};
+use sea_orm::{
+  sea_query::Query, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, TransactionTrait,
+};
+
+use crate::models::{
+  _entities::{medical_appointments, patients},
+  my_errors::MyErrors,
+};
+
+/// Permanently delete the anonymized patients whose retention period is over,
+/// along with their medical appointments. A patient is kept as long as one of
+/// their appointments is more recent than the retention period.
+pub async fn purge_expired<C: ConnectionTrait + TransactionTrait>(
+  db: &C,
+  retention_days: u32,
+) -> Result<u64, MyErrors> {
+  let cutoff = Utc::now() - Duration::days(retention_days.into());
+
+  let recent_appointments = Query::select()
+    .column(medical_appointments::Column::PatientId)
+    .from(medical_appointments::Entity)
+    .and_where(medical_appointments::Column::Date.gte(cutoff.date_naive()))
+    .to_owned();
+
+  let txn = db.begin().await?;
+
+  let expired_patient_ids: Vec<i32> = patients::Entity::find()
+    .filter(patients::Column::AnonymizedAt.lt(cutoff))
+    .filter(patients::Column::Id.not_in_subquery(recent_appointments))
+    .all(&txn)
+    .await?
+    .into_iter()
+    .map(|patient| patient.id)
+    .collect();
+
+  medical_appointments::Entity::delete_many()
+    .filter(medical_appointments::Column::PatientId.is_in(expired_patient_ids.clone()))
+    .exec(&txn)
+    .await?;
+
+  let purged = patients::Entity::delete_many()
+    .filter(patients::Column::Id.is_in(expired_patient_ids))
+    .exec(&txn)
+    .await?
+    .rows_affected;
+
+  txn.commit().await?;
+
+  Ok(purged)
+}
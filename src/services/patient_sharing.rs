@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, QueryFilter, QueryOrder};
+
+use crate::{
+  auth::resource::Resource,
+  initializers::get_services,
+  models::{
+    _entities::{patient_accesses, patients, sea_orm_active_enums::PatientAccessLevel, users},
+    my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
+    patient_accesses::{CreatePatientAccessParams, CONSENT_TOKEN_VALIDITY_DAYS},
+    patients::DEFAULT_EMAIL,
+  },
+  workers::mailer::{args::EmailArgs, template::EmailTemplate},
+};
+
+/// An access request on a patient, along with the practitioner owning the record
+#[derive(Debug)]
+pub struct AccessRequest {
+  pub access: patient_accesses::Model,
+  pub patient: patients::Model,
+  pub owner: users::Model,
+}
+
+/// Request an access to the patients matching `ssn` that `requester` cannot see yet.
+/// Records already shared with the requester, or already requested, are skipped.
+pub async fn request_access(
+  requester: &users::Model,
+  ssn: &str,
+  level: PatientAccessLevel,
+) -> Result<Vec<AccessRequest>, MyErrors> {
+  let db = &get_services().db;
+
+  let mut requests = Vec::new();
+  for patient in patients::Model::search_by_ssn(db, ssn).await? {
+    if patient.anonymized_at.is_some() || patient.user_id == requester.id {
+      continue;
+    }
+
+    let already_requested = patient_accesses::Entity::find()
+      .filter(patient_accesses::Column::PatientId.eq(patient.id))
+      .filter(patient_accesses::Column::UserId.eq(requester.id))
+      .one(db)
+      .await?
+      .is_some();
+    if already_requested {
+      continue;
+    }
+
+    let owner = patient
+      .find_related(users::Entity)
+      .one(db)
+      .await?
+      .ok_or(UnexpectedError::ShouldNotHappen)?;
+
+    let access = patient_accesses::ActiveModel::create(
+      db,
+      &CreatePatientAccessParams {
+        patient_id: patient.id,
+        user_id: requester.id,
+        level: level.clone(),
+      },
+    )
+    .await?;
+
+    requests.push(AccessRequest {
+      access,
+      patient,
+      owner,
+    });
+  }
+
+  Ok(requests)
+}
+
+/// e.g. `Marie CURIE`
+fn display_name(first_name: &str, last_name: &str) -> String {
+  format!("{} {}", first_name, last_name.to_uppercase())
+}
+
+/// Build the emails notifying the owner of the record, in their language, and
+/// the patient when their email is known, in the language of their documents,
+/// that `requester` asked for an access
+pub fn access_request_emails(
+  request: &AccessRequest,
+  requester: &users::Model,
+  base_url: &str,
+) -> Vec<EmailArgs> {
+  let requester_name = display_name(&requester.first_name, &requester.last_name);
+  let owner_email = EmailTemplate::AccessRequest {
+    requester_name: requester_name.clone(),
+    level: request.access.level.clone(),
+    patient_name: display_name(&request.patient.first_name, &request.patient.last_name),
+    requests_url: format!("{}/patient_accesses", base_url),
+  }
+  .render(&request.owner.locale, None);
+  let mut emails = vec![
+    EmailArgs::from_template(request.owner.email.clone(), owner_email)
+      .for_user(request.owner.id)
+      .for_patient(request.patient.id),
+  ];
+
+  // The patient can only consent when we know how to reach them
+  let consent_token = (request.patient.email != DEFAULT_EMAIL)
+    .then_some(request.access.consent_token.as_ref())
+    .flatten();
+  if let Some(token) = consent_token {
+    let patient_email = EmailTemplate::PatientConsent {
+      requester_name,
+      level: request.access.level.clone(),
+      owner_name: display_name(&request.owner.first_name, &request.owner.last_name),
+      validity_days: CONSENT_TOKEN_VALIDITY_DAYS,
+      consent_url: format!("{}/consent/{}", base_url, token),
+    }
+    .render(&request.patient.document_locale(&request.owner), None);
+    emails.push(
+      EmailArgs::from_template(request.patient.email.clone(), patient_email)
+        .for_user(requester.id)
+        .for_patient(request.patient.id),
+    );
+  }
+
+  emails
+}
+
+/// List the accesses, pending or granted, on the patients owned by `owner`,
+/// along with the patient and the practitioner who requested each of them
+pub async fn accesses_for_owner(
+  owner: &users::Model,
+) -> Result<Vec<(patient_accesses::Model, patients::Model, users::Model)>, MyErrors> {
+  let db = &get_services().db;
+
+  let accesses = patient_accesses::Entity::find()
+    .find_also_related(patients::Entity)
+    .filter(patients::Column::UserId.eq(owner.id))
+    .order_by_desc(patient_accesses::Column::CreatedAt)
+    .all(db)
+    .await?;
+
+  let requester_ids: Vec<i32> = accesses.iter().map(|(access, _)| access.user_id).collect();
+  let requesters: HashMap<i32, users::Model> = users::Entity::find()
+    .filter(users::Column::Id.is_in(requester_ids))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|user| (user.id, user))
+    .collect();
+
+  accesses
+    .into_iter()
+    .map(|(access, patient)| {
+      let patient = patient.ok_or(UnexpectedError::ShouldNotHappen)?;
+      let requester = requesters
+        .get(&access.user_id)
+        .cloned()
+        .ok_or(UnexpectedError::ShouldNotHappen)?;
+      Ok((access, patient, requester))
+    })
+    .collect()
+}
+
+pub async fn grant(
+  access: patient_accesses::Model,
+  level: PatientAccessLevel,
+) -> Result<patient_accesses::Model, MyErrors> {
+  let db = &get_services().db;
+
+  patient_accesses::ActiveModel::from(access)
+    .grant(db, level)
+    .await
+}
+
+pub async fn revoke(access: patient_accesses::Model) -> Result<(), MyErrors> {
+  let db = &get_services().db;
+
+  access.delete(db).await?;
+
+  Ok(())
+}
+
+/// Grant a pending request from the link sent to the patient
+pub async fn consent(token: &str) -> Result<patient_accesses::Model, MyErrors> {
+  let db = &get_services().db;
+
+  let access = patient_accesses::Entity::find_by_consent_token(token)
+    .one(db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  let level = access.level.clone();
+  grant(access, level).await
+}
+
+/// Whether `user` is allowed to see `patient`, either as its owner or through a granted access
+pub async fn can_access(patient: &patients::Model, user: &users::Model) -> bool {
+  patient.permission_for_user(user.id).await.is_some()
+}
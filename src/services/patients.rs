@@ -1,5 +1,8 @@
 use crate::initializers::get_services;
-use crate::models::_entities::patients;
+use crate::models::_entities::{
+  email_logs, patient_accesses, patient_insurances, patients,
+  sea_orm_active_enums::PatientAccessStatus,
+};
 use crate::models::my_errors::application_error::ApplicationError;
 use crate::models::my_errors::unexpected_error::UnexpectedError;
 use crate::models::{
@@ -7,8 +10,9 @@ use crate::models::{
   patients::{CreatePatientParams, Model as PatientModel},
   users,
 };
-use sea_orm::{ColumnTrait, Condition, QueryFilter};
-use sea_orm::{EntityTrait, PaginatorTrait, QueryOrder};
+use crate::services;
+use sea_orm::{sea_query::Query, ColumnTrait, Condition, QueryFilter};
+use sea_orm::{EntityTrait, PaginatorTrait, QueryOrder, TransactionTrait};
 use uuid::Uuid;
 
 pub async fn create(
@@ -20,9 +24,16 @@ pub async fn create(
   let created_patient = match &patient_params.pid {
     Some(pid) => {
       let pid = Uuid::parse_str(pid).map_err(|err| UnexpectedError::new(err.to_string()))?;
-      PatientModel::search_by_pid(&services.db, pid)
+      let patient = PatientModel::search_by_pid(&services.db, pid)
         .await?
-        .ok_or(ApplicationError::NotFound)?
+        .ok_or(ApplicationError::NotFound)?;
+
+      // A record owned by another practitioner must have been shared first
+      if !services::patient_sharing::can_access(&patient, linked_to_user).await {
+        return Err(ApplicationError::NotFound.into());
+      }
+
+      patient
     }
     None => patients::ActiveModel::create(&services.db, patient_params, linked_to_user.id).await?,
   };
@@ -41,6 +52,28 @@ pub async fn update(
   Ok(())
 }
 
+pub async fn anonymize(patient: patients::Model) -> Result<PatientModel, MyErrors> {
+  let txn = get_services().db.begin().await?;
+
+  // Insurer records hold member numbers, they go away with the identity
+  patient_insurances::Entity::delete_many()
+    .filter(patient_insurances::Column::PatientId.eq(patient.id))
+    .exec(&txn)
+    .await?;
+
+  // So do the emails, sent to their address with their name in the attachments
+  email_logs::Entity::delete_many()
+    .filter(email_logs::Column::PatientId.eq(patient.id))
+    .exec(&txn)
+    .await?;
+
+  let anonymized_patient = patients::ActiveModel::from(patient).anonymize(&txn).await?;
+
+  txn.commit().await?;
+
+  Ok(anonymized_patient)
+}
+
 pub async fn search_paginated(
   query: &str,
   page: u64,
@@ -59,9 +92,21 @@ pub async fn search_paginated(
       [format!("%{}%", query)],
     ));
 
-  // Query patients that belong to the current user and match the search
+  let shared_patient_ids = Query::select()
+    .column(patient_accesses::Column::PatientId)
+    .from(patient_accesses::Entity)
+    .and_where(patient_accesses::Column::UserId.eq(user.id))
+    .and_where(patient_accesses::Column::Status.eq(PatientAccessStatus::Granted))
+    .to_owned();
+
+  // Query patients that belong to, or are shared with, the current user and match the search
   let paginator = patients::Entity::find()
-    .filter(patients::Column::UserId.eq(user.id))
+    .filter(
+      Condition::any()
+        .add(patients::Column::UserId.eq(user.id))
+        .add(patients::Column::Id.in_subquery(shared_patient_ids)),
+    )
+    .filter(patients::Column::AnonymizedAt.is_null())
     .filter(search_condition)
     .order_by_desc(patients::Column::UpdatedAt)
     .paginate(db, 10);
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use sea_orm::{
+  ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+  TransactionTrait,
+};
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{medical_appointments, patients},
+    my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
+    payments::{self, CreatePaymentParams, PaymentStatus},
+    users,
+  },
+};
+
+/// An appointment along with the amount the patient already paid for it
+#[derive(Debug)]
+pub struct AppointmentSettlement {
+  pub appointment: medical_appointments::Model,
+  pub paid_in_cents: i64,
+}
+
+impl AppointmentSettlement {
+  pub fn status(&self) -> PaymentStatus {
+    PaymentStatus::of(&self.appointment, self.paid_in_cents)
+  }
+
+  pub fn unpaid_in_cents(&self) -> i64 {
+    (i64::from(self.appointment.patient_share_in_cents()) - self.paid_in_cents).max(0)
+  }
+}
+
+#[derive(Debug)]
+pub struct UnpaidBalance {
+  pub patient: patients::Model,
+  pub unpaid_in_cents: i64,
+  pub appointments_count: usize,
+}
+
+/// Attach the amount paid so far to each appointment, keeping only the ones
+/// matching `status` when given.
+pub async fn settlements<T: ConnectionTrait>(
+  db: &T,
+  appointments: Vec<medical_appointments::Model>,
+  status: Option<PaymentStatus>,
+) -> Result<Vec<AppointmentSettlement>, MyErrors> {
+  let paid_by_appointment = payments::Entity::paid_by_appointment(
+    db,
+    appointments
+      .iter()
+      .map(|appointment| appointment.id)
+      .collect(),
+  )
+  .await?;
+
+  Ok(
+    appointments
+      .into_iter()
+      .map(|appointment| AppointmentSettlement {
+        paid_in_cents: paid_by_appointment
+          .get(&appointment.id)
+          .copied()
+          .unwrap_or(0),
+        appointment,
+      })
+      .filter(|settlement| status.is_none_or(|status| settlement.status() == status))
+      .collect(),
+  )
+}
+
+/// Record a payment of the patient for an appointment, refusing to collect
+/// more than the patient share. The appointment stays locked until the payment
+/// is saved, so that two payments entered at once cannot both pass the check.
+pub async fn record(
+  appointment: &medical_appointments::Model,
+  params: &CreatePaymentParams,
+) -> Result<payments::Model, MyErrors> {
+  let txn = get_services().db.begin().await?;
+
+  let appointment = medical_appointments::Entity::find_by_id(appointment.id)
+    .lock_exclusive()
+    .one(&txn)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  let paid_in_cents = payments::Entity::paid_by_appointment(&txn, vec![appointment.id])
+    .await?
+    .get(&appointment.id)
+    .copied()
+    .unwrap_or(0);
+  if paid_in_cents + i64::from(params.amount_in_cents)
+    > i64::from(appointment.patient_share_in_cents())
+  {
+    return Err(ApplicationError::new("payment_exceeds_patient_share").into());
+  }
+
+  let payment = payments::ActiveModel::create(&txn, params, appointment.id).await?;
+  txn.commit().await?;
+
+  Ok(payment)
+}
+
+/// Appointments created with a payment method were settled at the end of the
+/// consultation: record the matching payment unless one was already entered.
+pub async fn settle_on_the_spot<T: ConnectionTrait>(
+  db: &T,
+  appointment: &medical_appointments::Model,
+) -> Result<(), MyErrors> {
+  let Some(method) = appointment.payment_method.clone() else {
+    return Ok(());
+  };
+
+  let amount_in_cents = appointment.patient_share_in_cents();
+  let already_paid = payments::Entity::find_for_appointment(appointment.id)
+    .count(db)
+    .await?
+    > 0;
+  if already_paid || amount_in_cents <= 0 {
+    return Ok(());
+  }
+
+  payments::ActiveModel::create(
+    db,
+    &CreatePaymentParams {
+      amount_in_cents,
+      method,
+      paid_on: appointment.date.format("%Y-%m-%d").to_string(),
+      reference: None,
+    },
+    appointment.id,
+  )
+  .await?;
+
+  Ok(())
+}
+
+/// Appointments of `user` in the given period, optionally filtered on their payment status
+pub async fn appointments_between(
+  user: &users::Model,
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+  status: Option<PaymentStatus>,
+) -> Result<Vec<(AppointmentSettlement, patients::Model)>, MyErrors> {
+  let db = &get_services().db;
+
+  let appointments = medical_appointments::Entity::find()
+    .filter(medical_appointments::Column::UserId.eq(user.id))
+    .filter(medical_appointments::Column::Date.between(start_date, end_date))
+    .order_by_asc(medical_appointments::Column::Date)
+    .find_also_related(patients::Entity)
+    .all(db)
+    .await?;
+
+  let (appointments, patients): (Vec<_>, Vec<_>) = appointments.into_iter().unzip();
+  let patients_by_id: BTreeMap<i32, patients::Model> = patients
+    .into_iter()
+    .flatten()
+    .map(|patient| (patient.id, patient))
+    .collect();
+
+  settlements(db, appointments, status)
+    .await?
+    .into_iter()
+    .map(|settlement| {
+      let patient = patients_by_id
+        .get(&settlement.appointment.patient_id)
+        .cloned()
+        .ok_or(UnexpectedError::ShouldNotHappen)?;
+      Ok((settlement, patient))
+    })
+    .collect()
+}
+
+/// What each patient of `user` still owes, largest balances first
+pub async fn unpaid_balances(user: &users::Model) -> Result<Vec<UnpaidBalance>, MyErrors> {
+  let db = &get_services().db;
+
+  let appointments = medical_appointments::Entity::find()
+    .filter(medical_appointments::Column::UserId.eq(user.id))
+    .all(db)
+    .await?;
+
+  let mut unpaid_by_patient: BTreeMap<i32, (i64, usize)> = BTreeMap::new();
+  for settlement in settlements(db, appointments, None).await? {
+    let unpaid_in_cents = settlement.unpaid_in_cents();
+    if unpaid_in_cents > 0 {
+      let entry = unpaid_by_patient
+        .entry(settlement.appointment.patient_id)
+        .or_default();
+      entry.0 += unpaid_in_cents;
+      entry.1 += 1;
+    }
+  }
+
+  let patients = patients::Entity::find()
+    .filter(patients::Column::Id.is_in(unpaid_by_patient.keys().copied()))
+    .all(db)
+    .await?;
+
+  let mut balances: Vec<UnpaidBalance> = patients
+    .into_iter()
+    .filter_map(|patient| {
+      let (unpaid_in_cents, appointments_count) = *unpaid_by_patient.get(&patient.id)?;
+      Some(UnpaidBalance {
+        patient,
+        unpaid_in_cents,
+        appointments_count,
+      })
+    })
+    .collect();
+  balances.sort_by_key(|balance| std::cmp::Reverse(balance.unpaid_in_cents));
+
+  Ok(balances)
+}
@@ -1,14 +1,18 @@
+use chrono::NaiveDate;
 use sea_orm::{
-  prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel,
-  QueryFilter, TransactionTrait,
+  ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel,
+  PaginatorTrait, QueryFilter, TransactionTrait,
 };
 
 use crate::{
   initializers::get_services,
   models::{
     _entities::{practitioner_offices, user_practitioner_offices},
+    accounting_exports,
     my_errors::{application_error::ApplicationError, unexpected_error::UnexpectedError, MyErrors},
     practitioner_offices::PractitionerOfficeParams,
+    retrocession_statements,
+    revenue_share_rates::{self, RateSchedule, RetrocessionTerms},
     user_practitioner_offices::CreateLinkParams,
     users::users,
   },
@@ -18,7 +22,8 @@ pub async fn update(
   mut office: practitioner_offices::ActiveModel,
   params: &PractitionerOfficeParams,
   linked_practitioner: &users::Model,
-  revenue_share_percentage: Decimal,
+  terms: &RetrocessionTerms,
+  terms_valid_from: NaiveDate,
 ) -> Result<(), MyErrors> {
   let services = get_services();
 
@@ -28,35 +33,107 @@ pub async fn update(
     .take()
     .ok_or(UnexpectedError::ShouldNotHappen)?;
 
-  let mut user_practitioner_office = user_practitioner_offices::Entity::find()
+  let user_practitioner_office = user_practitioner_offices::Entity::find()
     .filter(user_practitioner_offices::Column::PractitionerOfficeId.eq(office_id))
     .filter(user_practitioner_offices::Column::UserId.eq(linked_practitioner.id))
     .one(&services.db)
     .await?
-    .ok_or(ApplicationError::NotFound)?
-    .into_active_model();
+    .ok_or(ApplicationError::NotFound)?;
 
   office.name = Set(params.name.trim().to_string());
   office.address_line_1 = Set(params.address_line_1.trim().to_string());
   office.address_zip_code = Set(params.address_zip_code.trim().to_string());
   office.address_city = Set(params.address_city.trim().to_string());
-
-  user_practitioner_office.revenue_share_percentage = Set(revenue_share_percentage);
+  office.contact_email = Set(params.contact_email()?);
 
   let db_transaction = services.db.begin().await?;
 
   office.update(&db_transaction).await?;
-  user_practitioner_office.update(&db_transaction).await?;
+  set_retrocession_terms(
+    &db_transaction,
+    user_practitioner_office,
+    terms,
+    terms_valid_from,
+  )
+  .await?;
 
   db_transaction.commit().await?;
 
   Ok(())
 }
 
+/// Apply `terms` from `valid_from` on. Days already exported, either in an
+/// accounting export or in a sent retrocession statement, keep their terms.
+pub async fn set_retrocession_terms<T: ConnectionTrait>(
+  db: &T,
+  link: user_practitioner_offices::Model,
+  terms: &RetrocessionTerms,
+  valid_from: NaiveDate,
+) -> Result<(), MyErrors> {
+  let schedule = RateSchedule::new(
+    revenue_share_rates::Entity::find_for_office(link.user_id, link.practitioner_office_id)
+      .all(db)
+      .await?,
+  );
+
+  if schedule
+    .terms_on(link.practitioner_office_id, valid_from)
+    .as_ref()
+    == Some(terms)
+  {
+    return Ok(());
+  }
+
+  // The new terms apply until the next change already planned, if any
+  let end = schedule.next_change_after(link.practitioner_office_id, valid_from);
+  let exports = accounting_exports::Entity::find_overlapping(link.user_id, valid_from, end)
+    .count(db)
+    .await?;
+  let sent_statements = retrocession_statements::Entity::find_sent_overlapping(
+    link.user_id,
+    link.practitioner_office_id,
+    valid_from,
+    end,
+  )
+  .count(db)
+  .await?;
+  if exports > 0 || sent_statements > 0 {
+    return Err(ApplicationError::new("revenue_share_period_already_exported").into());
+  }
+
+  revenue_share_rates::ActiveModel::set(
+    db,
+    link.user_id,
+    link.practitioner_office_id,
+    terms,
+    valid_from,
+  )
+  .await?;
+
+  // Keep the terms shown with the office in line with today's ones
+  let schedule = RateSchedule::new(
+    revenue_share_rates::Entity::find_for_office(link.user_id, link.practitioner_office_id)
+      .all(db)
+      .await?,
+  );
+  let today = chrono::Local::now().date_naive();
+  let current_terms = schedule
+    .terms_on(link.practitioner_office_id, today)
+    .unwrap_or_else(|| terms.clone());
+
+  let mut link = link.into_active_model();
+  link.revenue_share_percentage = Set(current_terms.percentage);
+  link.retrocession_kind = Set(current_terms.kind);
+  link.retrocession_amount_in_cents = Set(current_terms.amount_in_cents);
+  link.update(db).await?;
+
+  Ok(())
+}
+
 pub async fn create(
   params: &PractitionerOfficeParams,
   linked_practitioner: &users::Model,
-  revenue_share_percentage: Decimal,
+  terms: RetrocessionTerms,
 ) -> Result<(), MyErrors> {
   let services = get_services();
 
@@ -70,7 +147,7 @@ pub async fn create(
     &CreateLinkParams {
       user_id: linked_practitioner.id,
       practitioner_office_id: created_practitioner_office.id,
-      revenue_share_percentage,
+      terms,
     },
   )
   .await?;
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::Serialize;
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{medical_appointments, patient_insurances, sea_orm_active_enums::InsuranceKind},
+    my_errors::MyErrors,
+    users,
+  },
+  services::{
+    appointments::MedicalAppointmentExtractor,
+    payments::{settlements, AppointmentSettlement},
+  },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Payer {
+  Patient,
+  Amo,
+  Mutuelle,
+}
+
+/// Amount still expected from a payer over a period
+#[derive(Debug)]
+pub struct PayerReceivable {
+  pub payer: Payer,
+  pub insurer_name: Option<String>,
+  pub outstanding_in_cents: i64,
+  pub appointments_count: usize,
+}
+
+/// Shares of an appointment that have not been received yet, with their payer
+fn outstanding_shares(settlement: &AppointmentSettlement) -> Vec<(Payer, i64)> {
+  let appointment = &settlement.appointment;
+  let mut shares = vec![(Payer::Patient, settlement.unpaid_in_cents())];
+
+  if appointment.amo_paid_at.is_none() {
+    shares.push((Payer::Amo, i64::from(appointment.amo_share_in_cents)));
+  }
+  if appointment.mutuelle_paid_at.is_none() {
+    shares.push((
+      Payer::Mutuelle,
+      i64::from(appointment.mutuelle_share_in_cents),
+    ));
+  }
+
+  shares.into_iter().filter(|(_, share)| *share > 0).collect()
+}
+
+/// Outstanding receivables of `user` over a period, per payer. The patient
+/// share is reduced by the payments already received, and insurers are told
+/// apart using the coverage the patient had on the day of the appointment.
+pub async fn outstanding_by_payer(
+  user: &users::Model,
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Result<Vec<PayerReceivable>, MyErrors> {
+  let db = &get_services().db;
+
+  let appointments: Vec<medical_appointments::Model> = MedicalAppointmentExtractor::for_user(user)
+    .extract(db, start_date, end_date)
+    .await?
+    .into_iter()
+    .map(|(appointment, ..)| appointment)
+    .collect();
+
+  let patient_ids: Vec<i32> = appointments
+    .iter()
+    .map(|appointment| appointment.patient_id)
+    .collect();
+  let mut insurances_by_patient: HashMap<i32, Vec<patient_insurances::Model>> = HashMap::new();
+  for insurance in patient_insurances::Entity::find()
+    .filter(patient_insurances::Column::PatientId.is_in(patient_ids))
+    .all(db)
+    .await?
+  {
+    insurances_by_patient
+      .entry(insurance.patient_id)
+      .or_default()
+      .push(insurance);
+  }
+
+  let insurer_name = |patient_id: i32, kind: InsuranceKind, date: NaiveDate| {
+    insurances_by_patient
+      .get(&patient_id)?
+      .iter()
+      .find(|insurance| insurance.kind == kind && insurance.is_valid_on(date))
+      .map(|insurance| insurance.insurer_name.clone())
+  };
+
+  let mut receivables: BTreeMap<(Payer, Option<String>), (i64, usize)> = BTreeMap::new();
+  for settlement in settlements(db, appointments, None).await? {
+    let appointment = &settlement.appointment;
+    for (payer, share) in outstanding_shares(&settlement) {
+      let insurer = match payer {
+        Payer::Patient => None,
+        Payer::Amo => insurer_name(appointment.patient_id, InsuranceKind::Amo, appointment.date),
+        Payer::Mutuelle => insurer_name(
+          appointment.patient_id,
+          InsuranceKind::Mutuelle,
+          appointment.date,
+        ),
+      };
+
+      let entry = receivables.entry((payer, insurer)).or_default();
+      entry.0 += share;
+      entry.1 += 1;
+    }
+  }
+
+  Ok(
+    receivables
+      .into_iter()
+      .map(
+        |((payer, insurer_name), (outstanding_in_cents, appointments_count))| PayerReceivable {
+          payer,
+          insurer_name,
+          outstanding_in_cents,
+          appointments_count,
+        },
+      )
+      .collect(),
+  )
+}
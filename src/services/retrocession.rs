@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use sea_orm::{
+  prelude::{Date, Decimal},
+  ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+};
+
+use crate::{
+  initializers::get_services,
+  locales::fill,
+  models::{
+    _entities::{
+      medical_appointments, practitioner_offices, sea_orm_active_enums::Locale,
+      user_business_informations,
+    },
+    my_errors::{application_error::ApplicationError, MyErrors},
+    retrocession_statements::{self, StatementFigures},
+    revenue_share_rates::{self, RateSchedule},
+    users,
+  },
+  services::appointments::ToExcel,
+  workers::{
+    mailer::{
+      args::EmailArgs,
+      attachment::EmailAttachment,
+      template::{EmailTemplate, Signature},
+    },
+    retrocession_statement_generator::{self, StatementDay},
+  },
+};
+
+const EXCEL_CONTENT_TYPE: &str =
+  "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+pub struct StatementDocument {
+  pub data: Vec<u8>,
+  pub filename: String,
+}
+
+/// A statement along with the office it is addressed to and the daily detail
+/// of the appointments it sums up
+#[derive(Debug)]
+pub struct StatementDetail {
+  pub statement: retrocession_statements::Model,
+  pub office: practitioner_offices::Model,
+  pub days: Vec<StatementDay>,
+  /// Language of the practitioner, which the documents are written in
+  pub locale: Locale,
+}
+
+async fn appointments_of_month(
+  user_id: i32,
+  office_id: i32,
+  month: Date,
+  month_end: Date,
+) -> Result<Vec<medical_appointments::Model>, MyErrors> {
+  Ok(
+    medical_appointments::Entity::find()
+      .filter(medical_appointments::Column::UserId.eq(user_id))
+      .filter(medical_appointments::Column::PractitionerOfficeId.eq(office_id))
+      .filter(medical_appointments::Column::Date.between(month, month_end))
+      .all(&get_services().db)
+      .await?,
+  )
+}
+
+fn days_of(appointments: &[medical_appointments::Model]) -> Vec<StatementDay> {
+  let mut days: BTreeMap<Date, StatementDay> = BTreeMap::new();
+  for appointment in appointments {
+    let day = days.entry(appointment.date).or_insert(StatementDay {
+      date: appointment.date,
+      appointments_count: 0,
+      fees_in_cents: 0,
+    });
+    day.appointments_count += 1;
+    day.fees_in_cents += i64::from(appointment.price_in_cents);
+  }
+  days.into_values().collect()
+}
+
+/// Compute the statement of `month` (`YYYY-MM`) for an office `user` works in,
+/// replacing the one previously generated for the same month unless it was
+/// already sent
+pub async fn generate(
+  user: &users::Model,
+  office_id: i32,
+  month: &str,
+) -> Result<retrocession_statements::Model, MyErrors> {
+  let db = &get_services().db;
+  let month = retrocession_statements::parse_month(month)?;
+
+  let rate_schedule = RateSchedule::new(
+    revenue_share_rates::Entity::find_for_office(user.id, office_id)
+      .all(db)
+      .await?,
+  );
+  let month_end = retrocession_statements::month_end(month);
+  let terms = rate_schedule
+    .terms_on(office_id, month_end)
+    .ok_or(ApplicationError::NotFound)?;
+
+  let appointments = appointments_of_month(user.id, office_id, month, month_end).await?;
+
+  // Each appointment is charged under the terms in force on its day, while
+  // the monthly fee or cap follows the terms in force at the end of the month
+  let appointment_shares: Decimal = appointments
+    .iter()
+    .map(|appointment| {
+      rate_schedule
+        .terms_on(office_id, appointment.date)
+        .unwrap_or_else(|| terms.clone())
+        .appointment_share(appointment.price_in_cents)
+    })
+    .sum();
+
+  let figures = StatementFigures {
+    appointments_count: appointments.len() as i32,
+    fees_in_cents: appointments
+      .iter()
+      .map(|appointment| i64::from(appointment.price_in_cents))
+      .sum(),
+    retrocession_in_cents: terms.monthly_amount(appointment_shares),
+    terms,
+  };
+
+  retrocession_statements::ActiveModel::upsert(db, user.id, office_id, month, &figures).await
+}
+
+/// Statements already generated for an office, most recent month first
+pub async fn statements_for_office(
+  user: &users::Model,
+  office_id: i32,
+) -> Result<Vec<retrocession_statements::Model>, MyErrors> {
+  Ok(
+    retrocession_statements::Entity::find_for_office(user.id, office_id)
+      .all(&get_services().db)
+      .await?,
+  )
+}
+
+/// Detail of a statement of `user`, to be written in their language
+pub async fn detail(
+  statement: retrocession_statements::Model,
+  user: &users::Model,
+) -> Result<StatementDetail, MyErrors> {
+  let office = practitioner_offices::Entity::find_by_id(statement.practitioner_office_id)
+    .one(&get_services().db)
+    .await?
+    .ok_or(ApplicationError::NotFound)?;
+
+  let appointments = appointments_of_month(
+    statement.user_id,
+    statement.practitioner_office_id,
+    statement.month,
+    statement.month_end(),
+  )
+  .await?;
+
+  Ok(StatementDetail {
+    days: days_of(&appointments),
+    statement,
+    office,
+    locale: user.locale.clone(),
+  })
+}
+
+fn filename(detail: &StatementDetail, extension: &str) -> String {
+  format!(
+    "retrocession_{}_{}.{}",
+    detail.office.name.to_lowercase().replace(' ', "_"),
+    detail.statement.month.format("%Y_%m"),
+    extension
+  )
+}
+
+pub async fn pdf(
+  detail: &StatementDetail,
+  user: &users::Model,
+) -> Result<StatementDocument, MyErrors> {
+  let business_info = user_business_informations::Entity::find()
+    .filter(user_business_informations::Column::UserId.eq(user.id))
+    .one(&get_services().db)
+    .await?;
+
+  let data = retrocession_statement_generator::generate_retrocession_statement_pdf(
+    user,
+    business_info.as_ref(),
+    &detail.office,
+    &detail.statement,
+    &detail.days,
+    &detail.locale,
+  )?;
+
+  Ok(StatementDocument {
+    data,
+    filename: filename(detail, "pdf"),
+  })
+}
+
+pub fn spreadsheet(detail: &StatementDetail) -> Result<StatementDocument, MyErrors> {
+  Ok(StatementDocument {
+    data: detail.to_excel()?.save_to_buffer()?,
+    filename: filename(detail, "xlsx"),
+  })
+}
+
+/// Build the email carrying the statement to the office contact
+pub async fn statement_email(
+  detail: &StatementDetail,
+  user: &users::Model,
+) -> Result<EmailArgs, MyErrors> {
+  let contact_email = detail
+    .office
+    .contact_email
+    .clone()
+    .ok_or(ApplicationError::new("office_contact_email_missing"))?;
+
+  let pdf = pdf(detail, user).await?;
+  let spreadsheet = spreadsheet(detail)?;
+
+  let email = EmailTemplate::RetrocessionStatement {
+    office_name: detail.office.name.clone(),
+    month_label: detail.statement.month_label(),
+    amount_due_in_cents: detail.statement.retrocession_in_cents,
+  }
+  .render(&detail.locale, Some(&Signature::name(user)));
+
+  Ok(
+    EmailArgs::from_template(contact_email, email)
+      .set_from_name(user.full_name())
+      .with_attachment(EmailAttachment::from_bytes(
+        pdf.filename,
+        "application/pdf".to_string(),
+        &pdf.data,
+      ))
+      .with_attachment(EmailAttachment::from_bytes(
+        spreadsheet.filename,
+        EXCEL_CONTENT_TYPE.to_string(),
+        &spreadsheet.data,
+      ))
+      .with_reply_to(user.email.clone())
+      .for_user(user.id),
+  )
+}
+
+/// Keep track of who the statement was sent to, and when
+pub async fn mark_as_sent(
+  statement: retrocession_statements::Model,
+  sent_to: String,
+) -> Result<retrocession_statements::Model, MyErrors> {
+  statement
+    .into_active_model()
+    .mark_as_sent(&get_services().db, sent_to)
+    .await
+}
+
+fn euros(amount_in_cents: i64) -> f64 {
+  amount_in_cents as f64 / 100.0
+}
+
+impl ToExcel for StatementDetail {
+  fn to_excel(&self) -> Result<Workbook, MyErrors> {
+    let mut workbook = Workbook::new();
+    let date_format = Format::new().set_num_format("dd/mm/yyyy");
+    let amount_format = Format::new().set_num_format("0.00");
+    let bold_format = Format::new().set_bold();
+    let header_format = Format::new()
+      .set_bold()
+      .set_background_color(rust_xlsxwriter::Color::Green)
+      .set_font_color(rust_xlsxwriter::Color::White);
+
+    let messages = self.locale.messages();
+    // Amounts are plain numbers in the cells, their currency is in the labels
+    let in_euros = |label: &str| format!("{} (€)", label);
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(fill(
+      messages.statement_sheet_name,
+      &[&self.statement.month.format("%m-%Y").to_string()],
+    ))?;
+
+    for (column, (title, width)) in [
+      (messages.statement_date.to_string(), 15),
+      (messages.statement_appointments.to_string(), 15),
+      (in_euros(messages.statement_fees), 18),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      worksheet.write_with_format(0, column as u16, &title, &header_format)?;
+      worksheet.set_column_width(column as u16, width)?;
+    }
+
+    for (i, day) in self.days.iter().enumerate() {
+      let row = i as u32 + 1;
+      let excel_date = ExcelDateTime::parse_from_str(&day.date.to_string())?;
+      worksheet.write_with_format(row, 0, &excel_date, &date_format)?;
+      worksheet.write(row, 1, day.appointments_count)?;
+      worksheet.write_with_format(row, 2, euros(day.fees_in_cents), &amount_format)?;
+    }
+
+    let summary_row = self.days.len() as u32 + 2;
+    let summary: [(String, f64); 3] = [
+      (
+        messages.statement_appointments_count.to_string(),
+        f64::from(self.statement.appointments_count),
+      ),
+      (
+        in_euros(messages.statement_total_fees),
+        euros(self.statement.fees_in_cents),
+      ),
+      (
+        in_euros(messages.statement_amount_due),
+        euros(self.statement.retrocession_in_cents),
+      ),
+    ];
+    let summary_len = summary.len() as u32;
+
+    for (i, (label, value)) in summary.into_iter().enumerate() {
+      let row = summary_row + i as u32;
+      worksheet.write_with_format(row, 0, &label, &bold_format)?;
+      worksheet.write_with_format(row, 2, value, &amount_format)?;
+    }
+
+    let terms_row = summary_row + summary_len;
+    worksheet.write_with_format(terms_row, 0, messages.statement_terms, &bold_format)?;
+    worksheet.write(terms_row, 2, self.statement.terms().label(&self.locale))?;
+
+    Ok(workbook)
+  }
+}
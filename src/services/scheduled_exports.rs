@@ -0,0 +1,171 @@
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone, sea_query::Expr, ColumnTrait, Condition, ConnectionTrait,
+  DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+use crate::{
+  models::{
+    _entities::{scheduled_exports, sea_orm_active_enums::ExportFrequency},
+    my_errors::MyErrors,
+    scheduled_exports::ScheduledExportParams,
+    users::users,
+  },
+  workers::appointments_export,
+};
+
+fn months_in_period(frequency: &ExportFrequency) -> u32 {
+  match frequency {
+    ExportFrequency::Monthly => 1,
+    ExportFrequency::Quarterly => 3,
+    ExportFrequency::Yearly => 12,
+  }
+}
+
+/// First day of the period containing `date`
+fn period_start(frequency: &ExportFrequency, date: NaiveDate) -> NaiveDate {
+  let months = months_in_period(frequency);
+  let month = (date.month0() / months) * months + 1;
+  NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+}
+
+/// Last complete period before `today`, both bounds included
+pub fn previous_period(frequency: &ExportFrequency, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+  let current_start = period_start(frequency, today);
+  let start = current_start
+    .checked_sub_months(Months::new(months_in_period(frequency)))
+    .unwrap_or(current_start);
+  let end = current_start.pred_opt().unwrap_or(current_start);
+  (start, end)
+}
+
+pub async fn save(
+  db: &DatabaseConnection,
+  user: &users::Model,
+  params: &ScheduledExportParams,
+  today: NaiveDate,
+) -> Result<scheduled_exports::Model, MyErrors> {
+  let (last_period_start, _) = previous_period(&params.frequency, today);
+  scheduled_exports::ActiveModel::save_for_user(db, user.id, params, last_period_start).await
+}
+
+/// How long an instance of the application has to send a claimed period
+/// before another one may try again
+const CLAIM_DURATION_MINUTES: i64 = 60;
+
+/// Period following the one starting on `period_start`, both bounds included
+fn next_period(frequency: &ExportFrequency, period_start: NaiveDate) -> (NaiveDate, NaiveDate) {
+  let months = Months::new(months_in_period(frequency));
+  let start = period_start
+    .checked_add_months(months)
+    .unwrap_or(period_start);
+  let end = start
+    .checked_add_months(months)
+    .and_then(|next_start| next_start.pred_opt())
+    .unwrap_or(start);
+  (start, end)
+}
+
+/// Oldest complete period of each export that has not been sent yet. Each of
+/// them is claimed for a while before being returned, so that it is handed out
+/// only once even when several instances of the application check at the same
+/// time. The period only counts as sent once `complete_period` is called: a
+/// failed export is handed out again at a later check, and periods missed
+/// while the application was stopped are handed out one after the other.
+pub async fn take_due_exports(
+  db: &DatabaseConnection,
+  today: NaiveDate,
+) -> Result<Vec<appointments_export::Args>, MyErrors> {
+  let now = chrono::Utc::now();
+  let scheduled_exports = scheduled_exports::Entity::find()
+    .find_also_related(users::Entity)
+    .filter(users::Column::SuspendedAt.is_null())
+    .all(db)
+    .await?;
+
+  let mut due_exports = vec![];
+  for (scheduled_export, user) in scheduled_exports {
+    let Some(user) = user else { continue };
+    let frequency = &scheduled_export.frequency;
+    let (last_complete_start, _) = previous_period(frequency, today);
+    let (start_date, end_date) = match scheduled_export.last_period_start {
+      Some(sent_period_start) => next_period(frequency, sent_period_start),
+      None => previous_period(frequency, today),
+    };
+    if start_date > last_complete_start {
+      continue;
+    }
+
+    let claimed = scheduled_exports::Entity::update_many()
+      .col_expr(
+        scheduled_exports::Column::ClaimedUntil,
+        Expr::value(now + Duration::minutes(CLAIM_DURATION_MINUTES)),
+      )
+      .filter(scheduled_exports::Column::Id.eq(scheduled_export.id))
+      .filter(match scheduled_export.last_period_start {
+        Some(sent_period_start) => scheduled_exports::Column::LastPeriodStart.eq(sent_period_start),
+        None => scheduled_exports::Column::LastPeriodStart.is_null(),
+      })
+      .filter(
+        Condition::any()
+          .add(scheduled_exports::Column::ClaimedUntil.is_null())
+          .add(scheduled_exports::Column::ClaimedUntil.lt(now)),
+      )
+      .exec(db)
+      .await?;
+    if claimed.rows_affected == 0 {
+      continue;
+    }
+
+    due_exports.push(appointments_export::Args {
+      user,
+      start_date,
+      end_date,
+      format: scheduled_export.format,
+      recipient: scheduled_export.recipient_email,
+      scheduled_export_id: Some(scheduled_export.id),
+    });
+  }
+
+  Ok(due_exports)
+}
+
+/// Record the period starting on `period_start` as sent, and release its claim
+pub async fn complete_period<T: ConnectionTrait>(
+  db: &T,
+  scheduled_export_id: i32,
+  period_start: NaiveDate,
+) -> Result<(), MyErrors> {
+  scheduled_exports::Entity::update_many()
+    .col_expr(
+      scheduled_exports::Column::LastPeriodStart,
+      Expr::value(period_start),
+    )
+    .col_expr(
+      scheduled_exports::Column::ClaimedUntil,
+      Expr::value(Option::<DateTimeWithTimeZone>::None),
+    )
+    .filter(scheduled_exports::Column::Id.eq(scheduled_export_id))
+    .exec(db)
+    .await?;
+
+  Ok(())
+}
+
+/// Release the claim on a period that could not be sent, so that it is handed
+/// out again at the next check
+pub async fn release_period<T: ConnectionTrait>(
+  db: &T,
+  scheduled_export_id: i32,
+) -> Result<(), MyErrors> {
+  scheduled_exports::Entity::update_many()
+    .col_expr(
+      scheduled_exports::Column::ClaimedUntil,
+      Expr::value(Option::<DateTimeWithTimeZone>::None),
+    )
+    .filter(scheduled_exports::Column::Id.eq(scheduled_export_id))
+    .exec(db)
+    .await?;
+
+  Ok(())
+}
@@ -0,0 +1,168 @@
+use chrono::NaiveDate;
+use sea_orm::{
+  sea_query::Expr, ColumnTrait, ConnectionTrait, EntityTrait, FromQueryResult, QueryFilter,
+  QueryOrder, QuerySelect, Statement,
+};
+
+use crate::{
+  initializers::get_services,
+  models::{
+    _entities::{medical_appointments, practitioner_offices},
+    my_errors::MyErrors,
+    users::users,
+  },
+};
+
+/// Activity of a practitioner over a period. Every figure is aggregated by the
+/// database, appointments are never loaded one by one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityStats {
+  pub appointments_count: i64,
+  pub revenue_in_cents: i64,
+  pub average_fee_in_cents: i64,
+  /// Patients seen for the first time during the period
+  pub new_patients_count: i64,
+  /// Patients seen during the period who had already been seen before
+  pub returning_patients_count: i64,
+  /// Busiest weekday first
+  pub weekdays: Vec<WeekdayActivity>,
+  pub offices: Vec<OfficeActivity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekdayActivity {
+  /// ISO 8601 number of the weekday, from 1 for Monday to 7 for Sunday
+  pub weekday: i32,
+  pub appointments_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfficeActivity {
+  pub office_id: i32,
+  pub office_name: String,
+  pub appointments_count: i64,
+  pub revenue_in_cents: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct PatientCounts {
+  new_patients_count: i64,
+  returning_patients_count: i64,
+}
+
+const PATIENT_COUNTS_SQL: &str = r#"
+SELECT
+  COUNT(*) FILTER (WHERE first_date >= $2) AS new_patients_count,
+  COUNT(*) FILTER (WHERE first_date < $2) AS returning_patients_count
+FROM (
+  SELECT MIN(date) AS first_date
+  FROM medical_appointments
+  WHERE user_id = $1 AND date <= $3
+  GROUP BY patient_id
+  HAVING MAX(date) >= $2
+) AS seen_patients
+"#;
+
+const WEEKDAY_SQL: &str = "CAST(EXTRACT(ISODOW FROM date) AS INTEGER)";
+
+pub async fn activity(
+  user: &users::Model,
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Result<ActivityStats, MyErrors> {
+  let db = &get_services().db;
+
+  let in_period = || {
+    medical_appointments::Entity::find()
+      .filter(medical_appointments::Column::UserId.eq(user.id))
+      .filter(medical_appointments::Column::Date.between(start_date, end_date))
+  };
+
+  let (appointments_count, revenue_in_cents) = in_period()
+    .select_only()
+    .column_as(
+      medical_appointments::Column::Id.count(),
+      "appointments_count",
+    )
+    .column_as(
+      medical_appointments::Column::PriceInCents.sum(),
+      "revenue_in_cents",
+    )
+    .into_tuple::<(i64, Option<i64>)>()
+    .one(db)
+    .await?
+    .unwrap_or_default();
+  let revenue_in_cents = revenue_in_cents.unwrap_or(0);
+
+  let patients = PatientCounts::find_by_statement(Statement::from_sql_and_values(
+    db.get_database_backend(),
+    PATIENT_COUNTS_SQL,
+    [user.id.into(), start_date.into(), end_date.into()],
+  ))
+  .one(db)
+  .await?;
+
+  let weekdays = in_period()
+    .select_only()
+    .column_as(Expr::cust(WEEKDAY_SQL), "weekday")
+    .column_as(
+      medical_appointments::Column::Id.count(),
+      "appointments_count",
+    )
+    .group_by(Expr::cust(WEEKDAY_SQL))
+    .order_by_desc(medical_appointments::Column::Id.count())
+    .order_by_asc(Expr::cust(WEEKDAY_SQL))
+    .into_tuple::<(i32, i64)>()
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|(weekday, appointments_count)| WeekdayActivity {
+      weekday,
+      appointments_count,
+    })
+    .collect();
+
+  let offices = in_period()
+    .inner_join(practitioner_offices::Entity)
+    .select_only()
+    .column(practitioner_offices::Column::Id)
+    .column(practitioner_offices::Column::Name)
+    .column_as(
+      medical_appointments::Column::Id.count(),
+      "appointments_count",
+    )
+    .column_as(
+      medical_appointments::Column::PriceInCents.sum(),
+      "revenue_in_cents",
+    )
+    .group_by(practitioner_offices::Column::Id)
+    .group_by(practitioner_offices::Column::Name)
+    .order_by_asc(practitioner_offices::Column::Name)
+    .into_tuple::<(i32, String, i64, Option<i64>)>()
+    .all(db)
+    .await?
+    .into_iter()
+    .map(
+      |(office_id, office_name, appointments_count, revenue_in_cents)| OfficeActivity {
+        office_id,
+        office_name,
+        appointments_count,
+        revenue_in_cents: revenue_in_cents.unwrap_or(0),
+      },
+    )
+    .collect();
+
+  Ok(ActivityStats {
+    appointments_count,
+    revenue_in_cents,
+    average_fee_in_cents: if appointments_count > 0 {
+      revenue_in_cents / appointments_count
+    } else {
+      0
+    },
+    new_patients_count: patients.as_ref().map_or(0, |p| p.new_patients_count),
+    returning_patients_count: patients.as_ref().map_or(0, |p| p.returning_patients_count),
+    weekdays,
+    offices,
+  })
+}
@@ -38,24 +38,24 @@ impl StorageService {
     })
   }
 
-  pub fn signature_url(&self, signature_filename: &str) -> String {
+  pub fn file_url(&self, file_name: &str) -> String {
     format!(
       "{}/storage/v1/object/public/{}/{}",
-      self.supabase_url, self.bucket_name, signature_filename
+      self.supabase_url, self.bucket_name, file_name
     )
   }
 
-  /// Fetch a signature image from Supabase storage
+  /// Fetch an image, a signature or a logo, from Supabase storage
   ///
   /// # Arguments
-  /// * `user_id` - The user ID to fetch the signature for
+  /// * `file_name` - The name the file was uploaded with
   ///
   /// # Returns
   /// * `Result<Vec<u8>, MyErrors>` - The image bytes or an error
-  pub async fn fetch_signature(&self, signature_file_name: &str) -> Result<Vec<u8>, MyErrors> {
-    let url = self.signature_url(signature_file_name);
+  pub async fn fetch_file(&self, file_name: &str) -> Result<Vec<u8>, MyErrors> {
+    let url = self.file_url(file_name);
 
-    info!("Fetching signature from: {}", url);
+    info!("Fetching file from: {}", url);
 
     let response = self
       .client
@@ -64,10 +64,10 @@ impl StorageService {
       .send()
       .await
       .map_err(|e| {
-        error!("Failed to fetch signature: {}", e);
+        error!("Failed to fetch file: {}", e);
         MyErrors {
           code: StatusCode::INTERNAL_SERVER_ERROR,
-          msg: format!("Failed to fetch signature from storage: {}", e),
+          msg: format!("Failed to fetch file from storage: {}", e),
         }
       })?;
 
@@ -78,7 +78,7 @@ impl StorageService {
       return match status {
         reqwest::StatusCode::NOT_FOUND => Err(MyErrors {
           code: StatusCode::NOT_FOUND,
-          msg: format!("Signature not found for user {}", signature_file_name),
+          msg: format!("File not found: {}", file_name),
         }),
         _ => {
           error!("Supabase storage error {}: {}", status, error_text);
@@ -91,32 +91,29 @@ impl StorageService {
     }
 
     let image_bytes = response.bytes().await.map_err(|e| {
-      error!("Failed to read signature bytes: {}", e);
+      error!("Failed to read file bytes: {}", e);
       MyErrors {
         code: StatusCode::INTERNAL_SERVER_ERROR,
-        msg: format!("Failed to read signature data: {}", e),
+        msg: format!("Failed to read file data: {}", e),
       }
     })?;
 
-    info!(
-      "Successfully fetched signature: {} bytes",
-      image_bytes.len()
-    );
+    info!("Successfully fetched file: {} bytes", image_bytes.len());
     Ok(image_bytes.to_vec())
   }
 
-  /// Upload a signature image to Supabase storage
+  /// Upload an image, a signature or a logo, to Supabase storage
   ///
   /// # Arguments
-  /// * `signature_data` - The image bytes to upload
-  /// * `filename` - The filename to use for the signature
+  /// * `file_data` - The image bytes to upload
+  /// * `filename` - The filename to use for the file
   /// * `content_type` - The MIME type of the file (e.g., "image/png", "image/jpeg")
   ///
   /// # Returns
   /// * `Result<(), MyErrors>` - Success or an error
-  pub async fn upload_signature(
+  pub async fn upload_file(
     &self,
-    signature_data: &[u8],
+    file_data: &[u8],
     filename: &str,
     content_type: &str,
   ) -> Result<(), MyErrors> {
@@ -126,10 +123,10 @@ impl StorageService {
     );
 
     info!(
-      "Uploading signature to: /storage/v1/object/{}/{}, size: {} bytes, content_type: {}",
+      "Uploading file to: /storage/v1/object/{}/{}, size: {} bytes, content_type: {}",
       self.bucket_name,
       filename,
-      signature_data.len(),
+      file_data.len(),
       content_type
     );
 
@@ -138,16 +135,16 @@ impl StorageService {
       .post(&url)
       .header("Authorization", format!("Bearer {}", self.supabase_key))
       .header("Content-Type", content_type)
-      .body(signature_data.to_vec())
+      .body(file_data.to_vec())
       .send()
       .await
       .map_err(|e| {
         error!("Failed to send upload request: {}", e);
-        UnexpectedError::new("failed_to_upload_signature".to_string())
+        UnexpectedError::new("failed_to_upload_file".to_string())
       })?;
 
     if response.status().is_success() {
-      info!("Successfully uploaded signature: {}", filename);
+      info!("Successfully uploaded file: {}", filename);
       Ok(())
     } else {
       let status = response.status();
@@ -3,9 +3,12 @@ use crate::{
 };
 use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbErr, IntoActiveModel, ModelTrait};
 
-use crate::models::{
-  _entities::user_business_informations, user_business_informations::CreateBusinessInformation,
-  users,
+use crate::{
+  models::{
+    _entities::user_business_informations, user_business_informations::CreateBusinessInformation,
+    users,
+  },
+  workers::mailer::{args::EmailArgs, template::EmailTemplate},
 };
 
 pub async fn save_business_information(
@@ -45,6 +48,19 @@ pub fn check_access_key(concerned_user: &users::Model, access_key: String) -> bo
   }
 }
 
+/// Build the email delivering the access key to a user, if one is registered
+pub fn access_key_email(user: &users::Model, base_url: &str) -> Option<EmailArgs> {
+  let access_key = user.access_key.as_ref()?;
+
+  let email = EmailTemplate::AccessKey {
+    access_key: access_key.clone(),
+    login_url: format!("{}/login", base_url),
+  }
+  .render(&user.locale, None);
+
+  Some(EmailArgs::from_template(user.email.clone(), email).for_user(user.id))
+}
+
 /// Generate a random access key in the format XXX-XXX-XXX-XXX
 pub fn generate_access_key() -> String {
   use rand::Rng;
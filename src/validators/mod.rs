@@ -1,2 +1,3 @@
 pub mod address;
 pub mod business_information;
+pub mod patient;
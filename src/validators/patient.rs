@@ -0,0 +1,20 @@
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// French phone number validation regex, accepting the national and international forms
+static FR_PHONE_NUMBER_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^(?:\+33 ?|0)[1-9](?:[ .-]?\d{2}){4}$").unwrap());
+
+pub fn is_phone_number_valid(phone_number: &str) -> bool {
+  FR_PHONE_NUMBER_REGEX.is_match(phone_number)
+}
+
+pub fn is_birth_date_valid(birth_date: NaiveDate) -> bool {
+  let earliest = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+  birth_date >= earliest && birth_date <= chrono::Utc::now().date_naive()
+}
+
+pub fn is_referring_doctor_valid(referring_doctor: &str) -> bool {
+  referring_doctor.len() < 100
+}
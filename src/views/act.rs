@@ -0,0 +1,37 @@
+use serde::Serialize;
+
+use crate::models::_entities::{act_office_prices, acts};
+
+#[derive(Debug, Serialize)]
+pub struct ActOfficePriceResponse {
+  practitioner_office_id: i32,
+  price_in_cents: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActResponse {
+  id: i32,
+  label: String,
+  code: Option<String>,
+  default_price_in_cents: i32,
+  office_prices: Vec<ActOfficePriceResponse>,
+}
+
+impl ActResponse {
+  #[must_use]
+  pub fn new(act: &acts::Model, office_prices: &[act_office_prices::Model]) -> Self {
+    Self {
+      id: act.id,
+      label: act.label.clone(),
+      code: act.code.clone(),
+      default_price_in_cents: act.default_price_in_cents,
+      office_prices: office_prices
+        .iter()
+        .map(|office_price| ActOfficePriceResponse {
+          practitioner_office_id: office_price.practitioner_office_id,
+          price_in_cents: office_price.price_in_cents,
+        })
+        .collect(),
+    }
+  }
+}
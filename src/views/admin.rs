@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  models::_entities::{sea_orm_active_enums::UserRole, users},
+  services::admin::{UsageStats, UserUsage},
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminUserResponse {
+  id: i32,
+  pid: String,
+  email: String,
+  first_name: String,
+  last_name: String,
+  phone_number: String,
+  role: UserRole,
+  is_access_key_verified: bool,
+  suspended_at: Option<String>,
+  created_at: String,
+  patients_count: i64,
+  medical_appointments_count: i64,
+}
+
+impl AdminUserResponse {
+  #[must_use]
+  pub fn new(user: &users::Model, usage: &UserUsage) -> Self {
+    Self {
+      id: user.id,
+      pid: user.pid.to_string(),
+      email: user.email.clone(),
+      first_name: user.first_name.clone(),
+      last_name: user.last_name.clone(),
+      phone_number: user.phone_number.clone(),
+      role: user.role.clone(),
+      is_access_key_verified: user.is_access_key_verified,
+      suspended_at: user.suspended_at.map(|date| date.to_rfc3339()),
+      created_at: user.created_at.to_rfc3339(),
+      patients_count: usage.patients_count,
+      medical_appointments_count: usage.medical_appointments_count,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UsageStatsResponse {
+  users_count: u64,
+  pending_users_count: u64,
+  suspended_users_count: u64,
+  patients_count: u64,
+  medical_appointments_count: u64,
+  practitioner_offices_count: u64,
+}
+
+impl UsageStatsResponse {
+  #[must_use]
+  pub fn new(stats: &UsageStats) -> Self {
+    Self {
+      users_count: stats.users_count,
+      pending_users_count: stats.pending_users_count,
+      suspended_users_count: stats.suspended_users_count,
+      patients_count: stats.patients_count,
+      medical_appointments_count: stats.medical_appointments_count,
+      practitioner_offices_count: stats.practitioner_offices_count,
+    }
+  }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::{audit_logs, sea_orm_active_enums::AuditAction};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuditLogResponse {
+  id: i32,
+  action: AuditAction,
+  resource_type: String,
+  resource_id: i32,
+  granted: bool,
+  ip_address: Option<String>,
+  created_at: String,
+}
+
+impl AuditLogResponse {
+  #[must_use]
+  pub fn new(audit_log: &audit_logs::Model) -> Self {
+    Self {
+      id: audit_log.id,
+      action: audit_log.action.clone(),
+      resource_type: audit_log.resource_type.clone(),
+      resource_id: audit_log.resource_id,
+      granted: audit_log.granted,
+      ip_address: audit_log.ip_address.clone(),
+      created_at: audit_log.created_at.to_rfc3339(),
+    }
+  }
+}
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  models::_entities::{user_business_informations, users},
+  models::_entities::{
+    sea_orm_active_enums::{Locale, UserRole},
+    user_business_informations, users,
+  },
   views::user::BusinessInformation,
 };
 
@@ -31,6 +34,8 @@ pub struct CurrentResponse {
   pub first_name: String,
   pub last_name: String,
   pub email: String,
+  pub role: UserRole,
+  pub locale: Locale,
   pub business_information: Option<BusinessInformation>,
 }
 
@@ -42,6 +47,8 @@ impl CurrentResponse {
       first_name: user.0.first_name.clone(),
       last_name: user.0.last_name.clone(),
       email: user.0.email.clone(),
+      role: user.0.role.clone(),
+      locale: user.0.locale.clone(),
       business_information: user.1.as_ref().map(BusinessInformation::new),
     }
   }
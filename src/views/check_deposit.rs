@@ -0,0 +1,49 @@
+use serde::Serialize;
+
+use crate::{
+  services::check_deposits::DepositSummary, workers::deposit_slip_generator::DepositedCheck,
+};
+
+#[derive(Debug, Serialize)]
+pub struct PendingCheckResponse {
+  payment_id: i32,
+  paid_on: String,
+  drawer: String,
+  check_number: Option<String>,
+  amount_in_cents: i32,
+}
+
+impl PendingCheckResponse {
+  #[must_use]
+  pub fn new(check: &DepositedCheck) -> Self {
+    Self {
+      payment_id: check.payment_id,
+      paid_on: check.paid_on.format("%Y-%m-%d").to_string(),
+      drawer: check.drawer.clone(),
+      check_number: check.check_number.clone(),
+      amount_in_cents: check.amount_in_cents,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckDepositResponse {
+  id: i32,
+  reference: String,
+  deposited_on: String,
+  checks_count: i64,
+  total_in_cents: i64,
+}
+
+impl CheckDepositResponse {
+  #[must_use]
+  pub fn new(summary: &DepositSummary) -> Self {
+    Self {
+      id: summary.deposit.id,
+      reference: summary.deposit.reference.clone(),
+      deposited_on: summary.deposit.deposited_on.format("%Y-%m-%d").to_string(),
+      checks_count: summary.checks_count,
+      total_in_cents: summary.total_in_cents,
+    }
+  }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{_entities::sea_orm_active_enums::EmailStatus, email_logs};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EmailLogResponse {
+  id: i32,
+  medical_appointment_id: Option<i32>,
+  recipient: String,
+  subject: String,
+  template: Option<String>,
+  attachment_names: Vec<String>,
+  status: EmailStatus,
+  error: Option<String>,
+  created_at: String,
+  sent_at: Option<String>,
+}
+
+impl EmailLogResponse {
+  #[must_use]
+  pub fn new(email_log: &email_logs::Model) -> Self {
+    Self {
+      id: email_log.id,
+      medical_appointment_id: email_log.medical_appointment_id,
+      recipient: email_log.recipient.clone(),
+      subject: email_log.subject.clone(),
+      template: email_log.template.clone(),
+      attachment_names: email_log.attachment_names(),
+      status: email_log.status.clone(),
+      error: email_log.error.clone(),
+      created_at: email_log.created_at.to_rfc3339(),
+      sent_at: email_log.sent_at.map(|sent_at| sent_at.to_rfc3339()),
+    }
+  }
+}
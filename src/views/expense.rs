@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::models::{
+  _entities::sea_orm_active_enums::{ExpenseCategory, PaymentMethod},
+  expenses,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ExpenseResponse {
+  id: i32,
+  spent_on: String,
+  category: ExpenseCategory,
+  label: String,
+  amount_in_cents: i32,
+  payment_method: Option<PaymentMethod>,
+}
+
+impl ExpenseResponse {
+  #[must_use]
+  pub fn new(expense: &expenses::Model) -> Self {
+    Self {
+      id: expense.id,
+      spent_on: expense.spent_on.format("%Y-%m-%d").to_string(),
+      category: expense.category.clone(),
+      label: expense.label.clone(),
+      amount_in_cents: expense.amount_in_cents,
+      payment_method: expense.payment_method.clone(),
+    }
+  }
+}
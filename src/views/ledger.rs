@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use crate::{
+  models::_entities::sea_orm_active_enums::{ExpenseCategory, PaymentMethod},
+  services::ledger::{CategoryTotal, FiscalYearReport, ReceiptEntry},
+};
+
+#[derive(Debug, Serialize)]
+pub struct ReceiptEntryResponse {
+  date: String,
+  payer: String,
+  method: PaymentMethod,
+  amount_in_cents: i64,
+}
+
+impl ReceiptEntryResponse {
+  #[must_use]
+  pub fn new(entry: &ReceiptEntry) -> Self {
+    Self {
+      date: entry.date.format("%Y-%m-%d").to_string(),
+      payer: entry.payer.clone(),
+      method: entry.method.clone(),
+      amount_in_cents: entry.amount_in_cents,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryTotalResponse {
+  category: ExpenseCategory,
+  label: String,
+  amount_in_cents: i64,
+}
+
+impl CategoryTotalResponse {
+  #[must_use]
+  pub fn new(total: &CategoryTotal) -> Self {
+    Self {
+      category: total.category.clone(),
+      label: total.category.to_french().to_string(),
+      amount_in_cents: total.amount_in_cents,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FiscalYearReportResponse {
+  year: i32,
+  receipts: Vec<ReceiptEntryResponse>,
+  total_receipts_in_cents: i64,
+  retroceded_fees_in_cents: i64,
+  net_receipts_in_cents: i64,
+  expenses_by_category: Vec<CategoryTotalResponse>,
+  total_expenses_in_cents: i64,
+  result_in_cents: i64,
+}
+
+impl FiscalYearReportResponse {
+  #[must_use]
+  pub fn new(report: &FiscalYearReport) -> Self {
+    Self {
+      year: report.year,
+      receipts: report
+        .receipts
+        .iter()
+        .map(ReceiptEntryResponse::new)
+        .collect(),
+      total_receipts_in_cents: report.total_receipts_in_cents,
+      retroceded_fees_in_cents: report.retroceded_fees_in_cents,
+      net_receipts_in_cents: report.net_receipts_in_cents,
+      expenses_by_category: report
+        .expenses_by_category
+        .iter()
+        .map(CategoryTotalResponse::new)
+        .collect(),
+      total_expenses_in_cents: report.total_expenses_in_cents,
+      result_in_cents: report.result_in_cents,
+    }
+  }
+}
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  models::_entities::{
-    medical_appointments, practitioner_offices, sea_orm_active_enums::PaymentMethod,
+  models::{
+    _entities::{patients, practitioner_offices, sea_orm_active_enums::PaymentMethod},
+    payments::PaymentStatus,
   },
+  services::payments::AppointmentSettlement,
   views::practitioner_office::PractitionerOffice,
 };
 
@@ -11,22 +13,75 @@ use crate::{
 pub struct MedicalAppointmentResponse {
   id: i32,
   date: String,
+  act_id: Option<i32>,
   price_in_cents: i32,
   payment_method: Option<PaymentMethod>,
+  amo_share_in_cents: i32,
+  mutuelle_share_in_cents: i32,
+  patient_share_in_cents: i32,
+  amo_paid_at: Option<String>,
+  mutuelle_paid_at: Option<String>,
+  paid_in_cents: i64,
+  payment_status: PaymentStatus,
   office: PractitionerOffice,
 }
 
 impl MedicalAppointmentResponse {
-  pub fn new(
-    medical_appointment: &medical_appointments::Model,
-    office: &practitioner_offices::Model,
-  ) -> Self {
+  pub fn new(settlement: &AppointmentSettlement, office: &practitioner_offices::Model) -> Self {
+    let medical_appointment = &settlement.appointment;
+
     Self {
       id: medical_appointment.id,
       date: medical_appointment.date.format("%Y-%m-%d").to_string(),
+      act_id: medical_appointment.act_id,
       price_in_cents: medical_appointment.price_in_cents,
       payment_method: medical_appointment.payment_method.clone(),
+      amo_share_in_cents: medical_appointment.amo_share_in_cents,
+      mutuelle_share_in_cents: medical_appointment.mutuelle_share_in_cents,
+      patient_share_in_cents: medical_appointment.patient_share_in_cents(),
+      amo_paid_at: medical_appointment
+        .amo_paid_at
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+      mutuelle_paid_at: medical_appointment
+        .mutuelle_paid_at
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+      paid_in_cents: settlement.paid_in_cents,
+      payment_status: settlement.status(),
       office: PractitionerOffice::new(office),
     }
   }
 }
+
+#[derive(Debug, Serialize)]
+pub struct CalendarAppointmentResponse {
+  id: i32,
+  date: String,
+  practitioner_office_id: i32,
+  price_in_cents: i32,
+  patient_share_in_cents: i32,
+  paid_in_cents: i64,
+  payment_status: PaymentStatus,
+  patient_id: i32,
+  patient_first_name: String,
+  patient_last_name: String,
+}
+
+impl CalendarAppointmentResponse {
+  #[must_use]
+  pub fn new(settlement: &AppointmentSettlement, patient: &patients::Model) -> Self {
+    let medical_appointment = &settlement.appointment;
+
+    Self {
+      id: medical_appointment.id,
+      date: medical_appointment.date.format("%Y-%m-%d").to_string(),
+      practitioner_office_id: medical_appointment.practitioner_office_id,
+      price_in_cents: medical_appointment.price_in_cents,
+      patient_share_in_cents: medical_appointment.patient_share_in_cents(),
+      paid_in_cents: settlement.paid_in_cents,
+      payment_status: settlement.status(),
+      patient_id: patient.id,
+      patient_first_name: patient.first_name.clone(),
+      patient_last_name: patient.last_name.clone(),
+    }
+  }
+}
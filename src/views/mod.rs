@@ -1,5 +1,19 @@
+pub mod act;
+pub mod admin;
+pub mod audit_log;
 pub mod auth;
+pub mod check_deposit;
+pub mod email_log;
+pub mod expense;
+pub mod ledger;
 pub mod medical_appointments;
 pub mod patient;
+pub mod patient_access;
+pub mod patient_export;
+pub mod patient_insurance;
+pub mod payment;
 pub mod practitioner_office;
+pub mod receivables;
+pub mod retrocession_statement;
+pub mod stats;
 pub mod user;
@@ -1,4 +1,10 @@
-use crate::models::patients;
+use crate::{
+  models::{
+    _entities::sea_orm_active_enums::{Locale, PatientSex},
+    patients,
+  },
+  services::patient_dedup::{DuplicateGroup, DuplicateReason},
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -14,6 +20,11 @@ pub struct PatientResponse {
   pub address_zip_code: String,
   pub address_city: String,
   pub address_country: String,
+  pub birth_date: Option<String>,
+  pub phone_number: Option<String>,
+  pub sex: Option<PatientSex>,
+  pub referring_doctor: Option<String>,
+  pub locale: Option<Locale>,
 }
 
 impl PatientResponse {
@@ -32,6 +43,13 @@ impl PatientResponse {
       address_zip_code: patient.address_zip_code.clone(),
       address_city: patient.address_city.clone(),
       address_country: patient.address_country.clone(),
+      birth_date: patient
+        .birth_date
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+      phone_number: patient.phone_number.clone(),
+      sex: patient.sex.clone(),
+      referring_doctor: patient.referring_doctor.clone(),
+      locale: patient.locale.clone(),
     }
   }
 
@@ -50,6 +68,29 @@ impl PatientResponse {
       address_zip_code: patient.address_zip_code.clone(),
       address_city: patient.address_city.clone(),
       address_country: patient.address_country.clone(),
+      birth_date: patient
+        .birth_date
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+      phone_number: patient.phone_number.clone(),
+      sex: patient.sex.clone(),
+      referring_doctor: patient.referring_doctor.clone(),
+      locale: patient.locale.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateGroupResponse {
+  reason: DuplicateReason,
+  patients: Vec<PatientResponse>,
+}
+
+impl DuplicateGroupResponse {
+  #[must_use]
+  pub fn new(group: &DuplicateGroup) -> Self {
+    Self {
+      reason: group.reason,
+      patients: group.patients.iter().map(PatientResponse::new).collect(),
     }
   }
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+  _entities::{
+    patient_accesses,
+    sea_orm_active_enums::{PatientAccessLevel, PatientAccessStatus},
+  },
+  patients, users,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PatientAccessResponse {
+  id: i32,
+  patient_id: i32,
+  patient_first_name: String,
+  patient_last_name: String,
+  requester_id: i32,
+  requester_first_name: String,
+  requester_last_name: String,
+  level: PatientAccessLevel,
+  status: PatientAccessStatus,
+  created_at: String,
+}
+
+impl PatientAccessResponse {
+  #[must_use]
+  pub fn new(
+    access: &patient_accesses::Model,
+    patient: &patients::Model,
+    requester: &users::Model,
+  ) -> Self {
+    Self {
+      id: access.id,
+      patient_id: patient.id,
+      patient_first_name: patient.first_name.clone(),
+      patient_last_name: patient.last_name.clone(),
+      requester_id: requester.id,
+      requester_first_name: requester.first_name.clone(),
+      requester_last_name: requester.last_name.clone(),
+      level: access.level.clone(),
+      status: access.status.clone(),
+      created_at: access.created_at.to_rfc3339(),
+    }
+  }
+}
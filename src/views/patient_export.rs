@@ -0,0 +1,114 @@
+use serde::Serialize;
+
+use crate::models::{
+  _entities::{
+    medical_appointments, practitioner_offices,
+    sea_orm_active_enums::{PatientSex, PaymentMethod},
+  },
+  patients, payments,
+};
+use crate::views::{patient_insurance::PatientInsuranceResponse, payment::PaymentResponse};
+
+/// Machine-readable document listing everything we hold about a patient,
+/// delivered as `patient.json` inside the GDPR export archive.
+#[derive(Debug, Serialize)]
+pub struct PatientExport {
+  generated_at: String,
+  patient: ExportedIdentity,
+  insurances: Vec<PatientInsuranceResponse>,
+  medical_appointments: Vec<ExportedMedicalAppointment>,
+}
+
+impl PatientExport {
+  #[must_use]
+  pub fn new(
+    patient: &patients::Model,
+    ssn: String,
+    insurances: Vec<PatientInsuranceResponse>,
+    medical_appointments: Vec<ExportedMedicalAppointment>,
+  ) -> Self {
+    Self {
+      generated_at: chrono::Utc::now().to_rfc3339(),
+      patient: ExportedIdentity {
+        pid: patient.pid.to_string(),
+        first_name: patient.first_name.clone(),
+        last_name: patient.last_name.clone(),
+        ssn,
+        email: (patient.email != patients::DEFAULT_EMAIL).then(|| patient.email.clone()),
+        address_line_1: patient.address_line_1.clone(),
+        address_zip_code: patient.address_zip_code.clone(),
+        address_city: patient.address_city.clone(),
+        address_country: patient.address_country.clone(),
+        birth_date: patient
+          .birth_date
+          .map(|date| date.format("%Y-%m-%d").to_string()),
+        phone_number: patient.phone_number.clone(),
+        sex: patient.sex.clone(),
+        referring_doctor: patient.referring_doctor.clone(),
+        created_at: patient.created_at.to_rfc3339(),
+        updated_at: patient.updated_at.to_rfc3339(),
+      },
+      insurances,
+      medical_appointments,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct ExportedIdentity {
+  pid: String,
+  first_name: String,
+  last_name: String,
+  ssn: String,
+  email: Option<String>,
+  address_line_1: String,
+  address_zip_code: String,
+  address_city: String,
+  address_country: String,
+  birth_date: Option<String>,
+  phone_number: Option<String>,
+  sex: Option<PatientSex>,
+  referring_doctor: Option<String>,
+  created_at: String,
+  updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedMedicalAppointment {
+  date: String,
+  price_in_cents: i32,
+  payment_method: Option<PaymentMethod>,
+  amo_share_in_cents: i32,
+  mutuelle_share_in_cents: i32,
+  office_name: String,
+  office_address: String,
+  payments: Vec<PaymentResponse>,
+  invoice_file: Option<String>,
+  created_at: String,
+}
+
+impl ExportedMedicalAppointment {
+  #[must_use]
+  pub fn new(
+    medical_appointment: &medical_appointments::Model,
+    office: &practitioner_offices::Model,
+    payments: &[payments::Model],
+    invoice_file: Option<String>,
+  ) -> Self {
+    Self {
+      date: medical_appointment.date.format("%Y-%m-%d").to_string(),
+      price_in_cents: medical_appointment.price_in_cents,
+      payment_method: medical_appointment.payment_method.clone(),
+      amo_share_in_cents: medical_appointment.amo_share_in_cents,
+      mutuelle_share_in_cents: medical_appointment.mutuelle_share_in_cents,
+      office_name: office.name.clone(),
+      office_address: format!(
+        "{}, {} {}",
+        office.address_line_1, office.address_zip_code, office.address_city
+      ),
+      payments: payments.iter().map(PaymentResponse::new).collect(),
+      invoice_file,
+      created_at: medical_appointment.created_at.to_rfc3339(),
+    }
+  }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::{patient_insurances, sea_orm_active_enums::InsuranceKind};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PatientInsuranceResponse {
+  id: i32,
+  kind: InsuranceKind,
+  insurer_name: String,
+  member_number: Option<String>,
+  valid_until: Option<String>,
+}
+
+impl PatientInsuranceResponse {
+  #[must_use]
+  pub fn new(insurance: &patient_insurances::Model) -> Self {
+    Self {
+      id: insurance.id,
+      kind: insurance.kind.clone(),
+      insurer_name: insurance.insurer_name.clone(),
+      member_number: insurance.member_number.clone(),
+      valid_until: insurance
+        .valid_until
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+    }
+  }
+}
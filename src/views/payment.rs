@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+use crate::models::{_entities::sea_orm_active_enums::PaymentMethod, payments};
+
+#[derive(Debug, Serialize)]
+pub struct PaymentResponse {
+  id: i32,
+  amount_in_cents: i32,
+  method: PaymentMethod,
+  paid_on: String,
+  reference: Option<String>,
+  check_deposit_id: Option<i32>,
+}
+
+impl PaymentResponse {
+  #[must_use]
+  pub fn new(payment: &payments::Model) -> Self {
+    Self {
+      id: payment.id,
+      amount_in_cents: payment.amount_in_cents,
+      method: payment.method.clone(),
+      paid_on: payment.paid_on.format("%Y-%m-%d").to_string(),
+      reference: payment.reference.clone(),
+      check_deposit_id: payment.check_deposit_id,
+    }
+  }
+}
@@ -1,7 +1,10 @@
 use sea_orm::prelude::Decimal;
 use serde::{Deserialize, Serialize, Serializer};
 
-use crate::models::_entities::{practitioner_offices, user_practitioner_offices};
+use crate::models::{
+  _entities::{practitioner_offices, revenue_share_rates, sea_orm_active_enums::RetrocessionKind},
+  user_practitioner_offices,
+};
 
 fn serialize_decimal_as_f64<S: Serializer>(
   value: &Option<Decimal>,
@@ -25,11 +28,16 @@ pub struct PractitionerOffice {
   pub address_line_1: String,
   pub address_zip_code: String,
   pub address_city: String,
+  pub contact_email: Option<String>,
   #[serde(
     skip_serializing_if = "Option::is_none",
     serialize_with = "serialize_decimal_as_f64"
   )]
   pub revenue_share_percentage: Option<Decimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub retrocession_kind: Option<RetrocessionKind>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub retrocession_amount_in_cents: Option<i32>,
 }
 
 impl PractitionerOffice {
@@ -40,7 +48,10 @@ impl PractitionerOffice {
       address_line_1: office.address_line_1.clone(),
       address_zip_code: office.address_zip_code.clone(),
       address_city: office.address_city.clone(),
+      contact_email: office.contact_email.clone(),
       revenue_share_percentage: None,
+      retrocession_kind: None,
+      retrocession_amount_in_cents: None,
     }
   }
 
@@ -48,9 +59,32 @@ impl PractitionerOffice {
     office: &practitioner_offices::Model,
     upo: &user_practitioner_offices::Model,
   ) -> Self {
+    let terms = upo.terms();
     Self {
-      revenue_share_percentage: Some(upo.revenue_share_percentage),
+      revenue_share_percentage: Some(terms.percentage),
+      retrocession_kind: Some(terms.kind),
+      retrocession_amount_in_cents: terms.amount_in_cents,
       ..Self::new(office)
     }
   }
 }
+
+#[derive(Debug, Serialize)]
+pub struct RevenueShareRateResponse {
+  kind: RetrocessionKind,
+  percentage: f64,
+  amount_in_cents: Option<i32>,
+  valid_from: String,
+}
+
+impl RevenueShareRateResponse {
+  #[must_use]
+  pub fn new(rate: &revenue_share_rates::Model) -> Self {
+    Self {
+      kind: rate.kind.clone(),
+      percentage: rate.percentage.try_into().unwrap_or_default(),
+      amount_in_cents: rate.amount_in_cents,
+      valid_from: rate.valid_from.format("%Y-%m-%d").to_string(),
+    }
+  }
+}
@@ -0,0 +1,48 @@
+use serde::Serialize;
+
+use crate::services::{
+  payments::UnpaidBalance,
+  receivables::{Payer, PayerReceivable},
+};
+
+#[derive(Debug, Serialize)]
+pub struct PayerReceivableResponse {
+  payer: Payer,
+  insurer_name: Option<String>,
+  outstanding_in_cents: i64,
+  appointments_count: usize,
+}
+
+impl PayerReceivableResponse {
+  #[must_use]
+  pub fn new(receivable: &PayerReceivable) -> Self {
+    Self {
+      payer: receivable.payer,
+      insurer_name: receivable.insurer_name.clone(),
+      outstanding_in_cents: receivable.outstanding_in_cents,
+      appointments_count: receivable.appointments_count,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnpaidBalanceResponse {
+  patient_id: i32,
+  first_name: String,
+  last_name: String,
+  unpaid_in_cents: i64,
+  appointments_count: usize,
+}
+
+impl UnpaidBalanceResponse {
+  #[must_use]
+  pub fn new(balance: &UnpaidBalance) -> Self {
+    Self {
+      patient_id: balance.patient.id,
+      first_name: balance.patient.first_name.clone(),
+      last_name: balance.patient.last_name.clone(),
+      unpaid_in_cents: balance.unpaid_in_cents,
+      appointments_count: balance.appointments_count,
+    }
+  }
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+use crate::models::{_entities::sea_orm_active_enums::RetrocessionKind, retrocession_statements};
+
+#[derive(Debug, Serialize)]
+pub struct RetrocessionStatementResponse {
+  id: i32,
+  practitioner_office_id: i32,
+  month: String,
+  appointments_count: i32,
+  fees_in_cents: i64,
+  retrocession_kind: RetrocessionKind,
+  revenue_share_percentage: f64,
+  retrocession_amount_in_cents: Option<i32>,
+  retrocession_in_cents: i64,
+  sent_to: Option<String>,
+  sent_at: Option<String>,
+}
+
+impl RetrocessionStatementResponse {
+  #[must_use]
+  pub fn new(statement: &retrocession_statements::Model) -> Self {
+    Self {
+      id: statement.id,
+      practitioner_office_id: statement.practitioner_office_id,
+      month: statement.month.format("%Y-%m").to_string(),
+      appointments_count: statement.appointments_count,
+      fees_in_cents: statement.fees_in_cents,
+      retrocession_kind: statement.retrocession_kind.clone(),
+      revenue_share_percentage: statement
+        .revenue_share_percentage
+        .try_into()
+        .unwrap_or_default(),
+      retrocession_amount_in_cents: statement.retrocession_amount_in_cents,
+      retrocession_in_cents: statement.retrocession_in_cents,
+      sent_to: statement.sent_to.clone(),
+      sent_at: statement.sent_at.map(|sent_at| sent_at.to_rfc3339()),
+    }
+  }
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+use crate::services::stats::{ActivityStats, OfficeActivity, WeekdayActivity};
+
+#[derive(Debug, Serialize)]
+pub struct WeekdayActivityResponse {
+  weekday: i32,
+  label: &'static str,
+  appointments_count: i64,
+}
+
+impl WeekdayActivityResponse {
+  #[must_use]
+  pub fn new(activity: &WeekdayActivity) -> Self {
+    let label = match activity.weekday {
+      1 => "Lundi",
+      2 => "Mardi",
+      3 => "Mercredi",
+      4 => "Jeudi",
+      5 => "Vendredi",
+      6 => "Samedi",
+      _ => "Dimanche",
+    };
+    Self {
+      weekday: activity.weekday,
+      label,
+      appointments_count: activity.appointments_count,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfficeActivityResponse {
+  office_id: i32,
+  office_name: String,
+  appointments_count: i64,
+  revenue_in_cents: i64,
+}
+
+impl OfficeActivityResponse {
+  #[must_use]
+  pub fn new(activity: &OfficeActivity) -> Self {
+    Self {
+      office_id: activity.office_id,
+      office_name: activity.office_name.clone(),
+      appointments_count: activity.appointments_count,
+      revenue_in_cents: activity.revenue_in_cents,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityStatsResponse {
+  start_date: String,
+  end_date: String,
+  appointments_count: i64,
+  revenue_in_cents: i64,
+  average_fee_in_cents: i64,
+  new_patients_count: i64,
+  returning_patients_count: i64,
+  weekdays: Vec<WeekdayActivityResponse>,
+  offices: Vec<OfficeActivityResponse>,
+}
+
+impl ActivityStatsResponse {
+  #[must_use]
+  pub fn new(start_date: String, end_date: String, stats: &ActivityStats) -> Self {
+    Self {
+      start_date,
+      end_date,
+      appointments_count: stats.appointments_count,
+      revenue_in_cents: stats.revenue_in_cents,
+      average_fee_in_cents: stats.average_fee_in_cents,
+      new_patients_count: stats.new_patients_count,
+      returning_patients_count: stats.returning_patients_count,
+      weekdays: stats
+        .weekdays
+        .iter()
+        .map(WeekdayActivityResponse::new)
+        .collect(),
+      offices: stats
+        .offices
+        .iter()
+        .map(OfficeActivityResponse::new)
+        .collect(),
+    }
+  }
+}
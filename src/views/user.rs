@@ -1,4 +1,11 @@
-use crate::models::_entities::user_business_informations;
+use crate::models::{
+  _entities::{
+    scheduled_exports,
+    sea_orm_active_enums::{ExportFormat, ExportFrequency},
+    user_business_informations,
+  },
+  invoice_templates::InvoiceTemplate,
+};
 use sea_orm::ActiveEnum;
 use serde::{Deserialize, Serialize};
 
@@ -27,3 +34,39 @@ impl BusinessInformation {
     }
   }
 }
+
+#[derive(Debug, Serialize)]
+pub struct ScheduledExportResponse {
+  pub frequency: ExportFrequency,
+  pub format: ExportFormat,
+  pub recipient_email: String,
+  pub last_period_start: Option<String>,
+}
+
+impl ScheduledExportResponse {
+  #[must_use]
+  pub fn new(scheduled_export: &scheduled_exports::Model) -> Self {
+    Self {
+      frequency: scheduled_export.frequency.clone(),
+      format: scheduled_export.format.clone(),
+      recipient_email: scheduled_export.recipient_email.clone(),
+      last_period_start: scheduled_export
+        .last_period_start
+        .map(|date| date.format("%Y-%m-%d").to_string()),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceTemplateResponse {
+  #[serde(flatten)]
+  pub template: InvoiceTemplate,
+  pub logo_url: Option<String>,
+}
+
+impl InvoiceTemplateResponse {
+  #[must_use]
+  pub fn new(template: InvoiceTemplate, logo_url: Option<String>) -> Self {
+    Self { template, logo_url }
+  }
+}
@@ -1,79 +1,95 @@
 use crate::{
-  app_state::{AppState, WorkerJob},
+  app_state::AppState,
   models::{
-    my_errors::{unexpected_error::UnexpectedError, MyErrors},
+    _entities::sea_orm_active_enums::ExportFormat, accounting_exports, my_errors::MyErrors,
     users::users,
   },
-  services::appointments::{MedicalAppointmentExtractor, ToExcel},
-  workers::mailer::{args::EmailArgs, attachment::EmailAttachment},
+  services::{
+    exporters::{self, AppointmentsExport},
+    scheduled_exports,
+  },
+  workers::mailer::{self, args::EmailArgs, attachment::EmailAttachment, template::EmailTemplate},
 };
 use chrono::NaiveDate;
-use rust_xlsxwriter::Workbook;
 
 #[derive(Debug, Clone)]
 pub struct Args {
   pub user: users::Model,
   pub start_date: NaiveDate,
   pub end_date: NaiveDate,
+  pub format: ExportFormat,
+  /// Address the export is sent to, the user's own or their accountant's
+  pub recipient: String,
+  /// Scheduled export the period was claimed for, none when asked by hand
+  pub scheduled_export_id: Option<i32>,
 }
 
-const EXCEL_CONTENT_TYPE: &str =
-  "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
-
 pub async fn process_appointment_extraction(args: Args, state: AppState) -> Result<(), MyErrors> {
-  let workbook = MedicalAppointmentExtractor::for_user(&args.user)
-    .extract(&state.db, args.start_date, args.end_date)
-    .await?
-    .to_excel()?;
+  let result = export_and_send(&args, &state).await;
+
+  // A scheduled period only counts as sent once the email went out
+  if let Some(scheduled_export_id) = args.scheduled_export_id {
+    match &result {
+      Ok(()) => {
+        scheduled_exports::complete_period(&state.db, scheduled_export_id, args.start_date).await?
+      }
+      Err(_) => scheduled_exports::release_period(&state.db, scheduled_export_id).await?,
+    }
+  }
+
+  result
+}
 
-  send_excel_by_mail(
-    workbook,
-    args.user.email,
+async fn export_and_send(args: &Args, state: &AppState) -> Result<(), MyErrors> {
+  let export = exporters::export_appointments(
+    &state.db,
+    &args.user,
+    args.start_date,
+    args.end_date,
+    &args.format,
+  )
+  .await?;
+
+  send_export_by_mail(
+    export,
+    args.recipient.clone(),
+    &args.user,
     args.start_date,
     args.end_date,
     state,
   )
   .await?;
 
+  // Revenue share rates of the exported days can no longer change
+  accounting_exports::ActiveModel::create(&state.db, args.user.id, args.start_date, args.end_date)
+    .await?;
+
   Ok(())
 }
 
-async fn send_excel_by_mail(
-  mut workbook: Workbook,
+async fn send_export_by_mail(
+  export: AppointmentsExport,
   to: String,
+  user: &users::Model,
   start_date: NaiveDate,
   end_date: NaiveDate,
-  state: AppState,
+  state: &AppState,
 ) -> Result<(), MyErrors> {
-  let wb_buffer = workbook.save_to_buffer()?;
-
-  let workbook_attachment = EmailAttachment::from_bytes(
-    format!(
-      "appointments_from_{}_to_{}.xlsx",
-      start_date.format("%d-%m-%Y"),
-      end_date.format("%d-%m-%Y")
-    ),
-    EXCEL_CONTENT_TYPE.to_string(),
-    &wb_buffer,
+  let export_attachment = EmailAttachment::from_bytes(
+    export.filename,
+    export.content_type.to_string(),
+    &export.data,
   );
 
-  let email_args = EmailArgs::new_text(
-    to,
-    format!(
-      "Vos RDV du {} au {}",
-      start_date.format("%d/%m/%Y"),
-      end_date.format("%d/%m/%Y")
-    ),
-    "Bonjour,\n\nVous trouverez tous vos rendez-vous de la période sélectionnée en pièce jointe"
-      .to_string(),
-  )
-  .with_attachment(workbook_attachment);
-
-  state
-    .worker_transmitter
-    .send(WorkerJob::Email(email_args))
-    .await
-    .map_err(|_| UnexpectedError::ShouldNotHappen)?;
+  let email = EmailTemplate::AppointmentsExport {
+    start_date,
+    end_date,
+  }
+  .render(&user.locale, None);
+  let email_args = EmailArgs::from_template(to, email)
+    .with_attachment(export_attachment)
+    .for_user(user.id);
 
-  Ok(())
+  // Sent right away rather than queued, so that a failure is known here
+  mailer::worker::process_email(email_args, &state.db, &state.config).await
 }
@@ -0,0 +1,215 @@
+use axum::http::StatusCode;
+use oxidize_pdf::graphics::Color;
+use oxidize_pdf::text::Font;
+use oxidize_pdf::{Document, Page};
+use sea_orm::prelude::Date;
+
+use crate::models::{
+  _entities::{check_deposits, user_business_informations, users},
+  my_errors::MyErrors,
+};
+
+/// Conversion constant: millimeters to points
+const MM_TO_POINTS: f64 = 2.834645669; // 72 / 25.4
+
+/// Number of check lines printed on each page of the slip
+const CHECKS_PER_PAGE: usize = 25;
+
+fn mm(value: f64) -> f64 {
+  value * MM_TO_POINTS
+}
+
+/// A check listed on the deposit slip
+#[derive(Debug, Clone)]
+pub struct DepositedCheck {
+  pub payment_id: i32,
+  pub paid_on: Date,
+  pub drawer: String,
+  pub check_number: Option<String>,
+  pub amount_in_cents: i32,
+}
+
+/// Generate the deposit slip ("bordereau de remise de chèques") handed to the bank
+pub fn generate_deposit_slip_pdf(
+  user: &users::Model,
+  business_info: Option<&user_business_informations::Model>,
+  deposit: &check_deposits::Model,
+  checks: &[DepositedCheck],
+) -> std::result::Result<Vec<u8>, MyErrors> {
+  create_deposit_slip_pdf(user, business_info, deposit, checks).map_err(|e| MyErrors {
+    code: StatusCode::INTERNAL_SERVER_ERROR,
+    msg: format!("PDF creation failed: {}", e),
+  })
+}
+
+fn format_amount(amount_in_cents: i64) -> String {
+  format!("{:.2}€", amount_in_cents as f64 / 100.0)
+}
+
+fn create_deposit_slip_pdf(
+  user: &users::Model,
+  business_info: Option<&user_business_informations::Model>,
+  deposit: &check_deposits::Model,
+  checks: &[DepositedCheck],
+) -> std::result::Result<Vec<u8>, String> {
+  let mut doc = Document::new();
+  doc.set_title(format!(
+    "Bordereau de remise de chèques {}",
+    deposit.reference
+  ));
+
+  let page_height = mm(297.0);
+  let margin = mm(20.0);
+  let columns = [
+    ("N°", 0.0),
+    ("Date", 12.0),
+    ("Tireur", 40.0),
+    ("N° de chèque", 105.0),
+    ("Montant", 145.0),
+  ];
+
+  let pages: Vec<&[DepositedCheck]> = if checks.is_empty() {
+    vec![&[]]
+  } else {
+    checks.chunks(CHECKS_PER_PAGE).collect()
+  };
+  let pages_count = pages.len();
+
+  for (page_index, page_checks) in pages.into_iter().enumerate() {
+    let mut page = Page::a4();
+    let mut y_position = page_height - margin - mm(5.0);
+
+    // === HEADER SECTION ===
+    page
+      .text()
+      .set_font(Font::HelveticaBold, 16.0)
+      .at(margin, y_position)
+      .write("Bordereau de remise de chèques")
+      .map_err(|e| format!("Failed to write title: {}", e))?;
+    y_position -= mm(10.0);
+
+    page
+      .text()
+      .set_font(Font::Helvetica, 11.0)
+      .at(margin, y_position)
+      .write(&format!("Remettant : {}", user.full_name()))
+      .map_err(|e| format!("Failed to write practitioner name: {}", e))?;
+    y_position -= mm(6.0);
+
+    if let Some(business_info) = business_info {
+      page
+        .text()
+        .set_font(Font::Helvetica, 10.0)
+        .at(margin, y_position)
+        .write(&format!("N°SIRET : {}", business_info.siret_number))
+        .map_err(|e| format!("Failed to write SIRET number: {}", e))?;
+      y_position -= mm(6.0);
+    }
+
+    page
+      .text()
+      .set_font(Font::Helvetica, 11.0)
+      .at(margin, y_position)
+      .write(&format!(
+        "Remise n° {} du {}",
+        deposit.reference,
+        deposit.deposited_on.format("%d/%m/%Y")
+      ))
+      .map_err(|e| format!("Failed to write deposit reference: {}", e))?;
+
+    page
+      .text()
+      .set_font(Font::Helvetica, 9.0)
+      .at(mm(170.0), y_position)
+      .write(&format!("Page {}/{}", page_index + 1, pages_count))
+      .map_err(|e| format!("Failed to write page number: {}", e))?;
+    y_position -= mm(14.0);
+
+    // === CHECKS TABLE ===
+    for (label, offset) in columns {
+      page
+        .text()
+        .set_font(Font::HelveticaBold, 10.0)
+        .at(margin + mm(offset), y_position)
+        .write(label)
+        .map_err(|e| format!("Failed to write column header: {}", e))?;
+    }
+
+    let header_line_y = y_position - mm(2.0);
+    page
+      .graphics()
+      .set_stroke_color(Color::black())
+      .set_line_width(mm(0.3))
+      .move_to(margin, header_line_y)
+      .line_to(mm(190.0), header_line_y)
+      .stroke();
+    y_position -= mm(8.0);
+
+    for (index, check) in page_checks.iter().enumerate() {
+      let line_number = page_index * CHECKS_PER_PAGE + index + 1;
+      let cells = [
+        line_number.to_string(),
+        check.paid_on.format("%d/%m/%Y").to_string(),
+        check.drawer.clone(),
+        check.check_number.clone().unwrap_or_default(),
+        format_amount(i64::from(check.amount_in_cents)),
+      ];
+
+      for (cell, (_, offset)) in cells.iter().zip(columns) {
+        page
+          .text()
+          .set_font(Font::Helvetica, 10.0)
+          .at(margin + mm(offset), y_position)
+          .write(cell)
+          .map_err(|e| format!("Failed to write check line: {}", e))?;
+      }
+      y_position -= mm(7.0);
+    }
+
+    // === TOTALS, on the last page only ===
+    if page_index + 1 == pages_count {
+      let total_in_cents: i64 = checks
+        .iter()
+        .map(|check| i64::from(check.amount_in_cents))
+        .sum();
+
+      let total_line_y = y_position + mm(4.0);
+      page
+        .graphics()
+        .set_stroke_color(Color::black())
+        .set_line_width(mm(0.3))
+        .move_to(margin, total_line_y)
+        .line_to(mm(190.0), total_line_y)
+        .stroke();
+      y_position -= mm(4.0);
+
+      page
+        .text()
+        .set_font(Font::HelveticaBold, 11.0)
+        .at(margin, y_position)
+        .write(&format!("Nombre de chèques : {}", checks.len()))
+        .map_err(|e| format!("Failed to write checks count: {}", e))?;
+
+      page
+        .text()
+        .set_font(Font::HelveticaBold, 11.0)
+        .at(margin + mm(105.0), y_position)
+        .write(&format!("Total : {}", format_amount(total_in_cents)))
+        .map_err(|e| format!("Failed to write total: {}", e))?;
+      y_position -= mm(20.0);
+
+      page
+        .text()
+        .set_font(Font::Helvetica, 11.0)
+        .at(margin + mm(105.0), y_position)
+        .write("Signature du remettant")
+        .map_err(|e| format!("Failed to write signature label: {}", e))?;
+    }
+
+    doc.add_page(page);
+  }
+
+  doc
+    .to_bytes()
+    .map_err(|e| format!("Failed to generate PDF: {}", e))
+}
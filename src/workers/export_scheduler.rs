@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use crate::{
+  app_state::{AppState, WorkerJob},
+  services::scheduled_exports,
+};
+
+/// Periodically enqueue the scheduled appointment exports whose period is over
+pub async fn start_scheduler(state: AppState) {
+  let mut interval = tokio::time::interval(Duration::from_secs(
+    state.config.scheduled_exports.check_interval_minutes * 60,
+  ));
+
+  loop {
+    interval.tick().await;
+
+    let today = chrono::Local::now().date_naive();
+    let due_exports = match scheduled_exports::take_due_exports(&state.db, today).await {
+      Ok(due_exports) => due_exports,
+      Err(e) => {
+        tracing::error!("Scheduled exports check failed: {:?}", e);
+        continue;
+      }
+    };
+
+    for args in due_exports {
+      let user_id = args.user.id;
+      if let Err(e) = state
+        .worker_transmitter
+        .send(WorkerJob::AccountingReport(args, state.clone()))
+        .await
+      {
+        tracing::error!(
+          "Failed to enqueue the scheduled export of user {}: {:?}",
+          user_id,
+          e
+        );
+      }
+    }
+  }
+}
@@ -4,11 +4,18 @@ use oxidize_pdf::text::Font;
 use oxidize_pdf::{Document, Page};
 use serde::Serialize;
 
+use crate::locales::fill;
 use crate::models::{
-  _entities::{patients, practitioner_offices, user_business_informations, users},
+  _entities::{
+    patients, practitioner_offices, sea_orm_active_enums::Locale, user_business_informations, users,
+  },
+  invoice_templates::{self, InvoiceTemplate},
   my_errors::{application_error::ApplicationError, MyErrors},
 };
-use crate::services::storage::StorageService;
+use crate::services::{
+  factur_x::{self, FacturXInvoice, Party},
+  storage::StorageService,
+};
 use sea_orm::{prelude::Date, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 
 /// Conversion constant: millimeters to points
@@ -20,21 +27,120 @@ fn mm(value: f64) -> f64 {
   value * MM_TO_POINTS
 }
 
+/// DejaVu Sans, embedded in the invoices as PDF/A requires, unlike the
+/// standard Helvetica which readers substitute
+const REGULAR_FONT: &str = "DejaVuSans";
+const BOLD_FONT: &str = "DejaVuSans-Bold";
+const REGULAR_FONT_DATA: &[u8] = include_bytes!("fonts/DejaVuSans.ttf");
+const BOLD_FONT_DATA: &[u8] = include_bytes!("fonts/DejaVuSans-Bold.ttf");
+
+fn regular_font() -> Font {
+  Font::custom(REGULAR_FONT)
+}
+
+fn bold_font() -> Font {
+  Font::custom(BOLD_FONT)
+}
+
+/// Characters and lines of the free text printed in the right column of the
+/// header, at 9pt, so that it stays clear of the patient's details
+const HEADER_MAX_CHARS: usize = 32;
+const HEADER_MAX_LINES: usize = 8;
+
+/// Characters and lines of the footer, at 8pt, between the margins and below
+/// the signature
+const FOOTER_MAX_CHARS: usize = 90;
+const FOOTER_MAX_LINES: usize = 6;
+
+/// `text` wrapped on words into lines of at most `max_chars` characters, cut
+/// with `...` past `max_lines` lines
+fn wrap_text(text: &str, max_chars: usize, max_lines: usize) -> Vec<String> {
+  let mut lines = Vec::new();
+  for paragraph in text.lines() {
+    let mut line = String::new();
+    for word in paragraph.split_whitespace() {
+      let mut word: Vec<char> = word.chars().collect();
+      // Words longer than a line are split
+      while word.len() > max_chars {
+        if !line.is_empty() {
+          lines.push(std::mem::take(&mut line));
+        }
+        lines.push(word.drain(..max_chars).collect());
+      }
+      let word: String = word.into_iter().collect();
+      if word.is_empty() {
+        continue;
+      }
+      if line.is_empty() {
+        line = word;
+      } else if line.chars().count() + 1 + word.chars().count() <= max_chars {
+        line.push(' ');
+        line.push_str(&word);
+      } else {
+        lines.push(std::mem::replace(&mut line, word));
+      }
+    }
+    lines.push(line);
+  }
+
+  if lines.len() > max_lines {
+    lines.truncate(max_lines);
+    if let Some(last) = lines.last_mut() {
+      *last = last.chars().take(max_chars.saturating_sub(3)).collect();
+      last.push_str("...");
+    }
+  }
+  lines
+}
+
+/// Lines of the free text of the header, as printed on the invoice
+pub fn header_lines(template: &InvoiceTemplate) -> Vec<String> {
+  template
+    .header_text
+    .as_deref()
+    .map(|text| wrap_text(text, HEADER_MAX_CHARS, HEADER_MAX_LINES))
+    .unwrap_or_default()
+}
+
+/// Lines of the footer, as printed on the invoice: the footer text and the
+/// additional legal mentions, then the VAT exemption mention which is always
+/// printed
+pub fn footer_lines(template: &InvoiceTemplate) -> Vec<String> {
+  let free_text = [&template.footer_text, &template.legal_mentions]
+    .into_iter()
+    .flatten()
+    .map(String::as_str)
+    .collect::<Vec<_>>()
+    .join("\n");
+  let mut lines = wrap_text(&free_text, FOOTER_MAX_CHARS, FOOTER_MAX_LINES - 1);
+  lines.push(invoice_templates::VAT_EXEMPTION_MENTION.to_string());
+  lines
+}
+
+/// Rough estimate of the width of a label at 11pt, to underline it
+fn label_width_mm(label: &str) -> f64 {
+  label.chars().count() as f64 * 2.2
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct InvoiceGeneratorArgs {
+  /// The appointment invoiced, whose id numbers the invoice
+  pub appointment_id: i32,
   pub patient: patients::Model,
   pub user: users::Model,
   pub amount: f32,
+  /// Label of the act invoiced, as recorded on the appointment
+  pub act_label: Option<String>,
   pub invoice_date: Date,
   pub practitioner_office: practitioner_offices::Model,
 }
 
-/// Generate an invoice PDF based on the French invoice template
+/// Generate a Factur-X invoice PDF based on the practitioner's template
 pub async fn generate_invoice_pdf(
   db: &DatabaseConnection,
   args: &InvoiceGeneratorArgs,
 ) -> std::result::Result<Vec<u8>, MyErrors> {
-  // Initialize storage service for signature fetching
+  // Initialize storage service for signature and logo fetching
   let storage_service = match StorageService::new() {
     Ok(service) => Some(service),
     Err(e) => {
@@ -59,7 +165,7 @@ pub async fn generate_invoice_pdf(
   // Try to fetch signature if storage service is available
   let signature_data = match &storage_service {
     Some(service) => match service
-      .fetch_signature(
+      .fetch_file(
         business_info
           .signature_file_name
           .as_ref()
@@ -83,6 +189,41 @@ pub async fn generate_invoice_pdf(
     None => None,
   };
 
+  // Practitioners who never customised their notes get the default template
+  let stored_template = invoice_templates::Entity::find_for_user(args.user.id)
+    .one(db)
+    .await?;
+  let mut template = stored_template
+    .as_ref()
+    .map(invoice_templates::Model::template)
+    .unwrap_or_default();
+
+  // The default title is translated, a customised one is printed as written
+  let locale = args.patient.document_locale(&args.user);
+  if template.title == InvoiceTemplate::default().title {
+    template.title = locale.messages().invoice_title.to_string();
+  }
+
+  let logo_data = match (
+    &storage_service,
+    stored_template
+      .as_ref()
+      .and_then(|stored| stored.logo_file_name.as_ref()),
+  ) {
+    (Some(service), Some(logo_file_name)) => match service.fetch_file(logo_file_name).await {
+      Ok(data) => Some(data),
+      Err(e) => {
+        tracing::warn!(
+          "Failed to fetch logo for user {}: {}. Continuing without logo.",
+          args.user.id,
+          e
+        );
+        None
+      }
+    },
+    _ => None,
+  };
+
   // Decrypt patient SSN
   let patient_ssn = args.patient.decrypt_ssn()?;
 
@@ -93,33 +234,72 @@ pub async fn generate_invoice_pdf(
     &args.patient,
     &patient_ssn,
     &args.amount,
+    args.act_label.as_deref(),
     &args.invoice_date,
     &args.practitioner_office,
+    &template,
+    &locale,
     signature_data.as_deref(),
+    logo_data.as_deref(),
   )
   .map_err(|e| MyErrors {
     code: StatusCode::INTERNAL_SERVER_ERROR,
     msg: format!("PDF creation failed: {}", e),
   })?;
 
-  Ok(pdf_data)
+  let factur_x_invoice = FacturXInvoice {
+    number: args.appointment_id.to_string(),
+    issue_date: args.invoice_date,
+    seller: Party {
+      name: args.user.full_name(),
+      siret: Some(&business_info.siret_number),
+      address_line: &args.practitioner_office.address_line_1,
+      zip_code: &args.practitioner_office.address_zip_code,
+      city: &args.practitioner_office.address_city,
+    },
+    buyer: Party {
+      name: format!("{} {}", args.patient.last_name, args.patient.first_name),
+      siret: None,
+      address_line: &args.patient.address_line_1,
+      zip_code: &args.patient.address_zip_code,
+      city: &args.patient.address_city,
+    },
+    amount_in_cents: (f64::from(args.amount) * 100.0).round() as i64,
+    vat_exemption_reason: invoice_templates::VAT_EXEMPTION_MENTION,
+    note: template.legal_mentions.as_deref(),
+  };
+
+  factur_x::attach_to_pdf(
+    pdf_data,
+    &factur_x_invoice.to_xml(),
+    &template.title,
+    chrono::Utc::now(),
+  )
+  .map_err(|e| MyErrors {
+    code: StatusCode::INTERNAL_SERVER_ERROR,
+    msg: format!("Factur-X embedding failed: {}", e),
+  })
 }
 
-/// Embed a signature image into the PDF page
+/// Embed an image, the signature or the logo, into the PDF page
 ///
 /// # Arguments
 /// * `page` - Mutable reference to the PDF page
+/// * `image_name` - Name of the image in the page resources
 /// * `image_bytes` - The image bytes (JPG/PNG)
 /// * `x_mm` - X position in millimeters
-/// * `y_mm` - Y position in millimeters (from bottom)
+/// * `y_mm` - Y position of the top of the image in millimeters (from bottom)
+/// * `max_size_mm` - Box the image is scaled to fit in, keeping its aspect ratio
 ///
 /// # Returns
 /// * `Result<(), String>` - Success or error message
-fn embed_signature_image(
+fn embed_image(
   page: &mut Page,
+  image_name: &str,
   image_bytes: &[u8],
   x_mm: f64,
   y_mm: f64,
+  max_size_mm: (f64, f64),
 ) -> std::result::Result<(), String> {
   use oxidize_pdf::graphics::Image;
 
@@ -131,11 +311,10 @@ fn embed_signature_image(
   let rgb_img = img.to_rgb8();
   let (width, height) = rgb_img.dimensions();
 
-  tracing::info!("Loaded signature image: {}x{} pixels", width, height);
+  tracing::info!("Loaded {} image: {}x{} pixels", image_name, width, height);
 
   // Calculate aspect ratio and target dimensions
-  let max_width_mm = 60.0; // Maximum width for signature
-  let max_height_mm = 30.0; // Maximum height for signature
+  let (max_width_mm, max_height_mm) = max_size_mm;
 
   let aspect_ratio = width as f64 / height as f64;
   let (target_width_mm, target_height_mm) = if aspect_ratio > max_width_mm / max_height_mm {
@@ -147,7 +326,8 @@ fn embed_signature_image(
   };
 
   tracing::info!(
-    "Target signature dimensions: {:.2}x{:.2} mm",
+    "Target {} dimensions: {:.2}x{:.2} mm",
+    image_name,
     target_width_mm,
     target_height_mm
   );
@@ -173,7 +353,6 @@ fn embed_signature_image(
     Image::from_jpeg_data(jpg_data).map_err(|e| format!("Failed to create JPEG image: {}", e))?;
 
   // Add image to page resources
-  let image_name = "signature";
   page.add_image(image_name, image_obj);
 
   // Calculate final position (adjust Y for image height)
@@ -191,7 +370,8 @@ fn embed_signature_image(
     .map_err(|e| format!("Failed to draw image: {}", e))?;
 
   tracing::info!(
-    "Successfully embedded signature image at ({:.2}, {:.2}) mm with size {:.2}x{:.2} mm",
+    "Successfully embedded {} image at ({:.2}, {:.2}) mm with size {:.2}x{:.2} mm",
+    image_name,
     x_mm,
     y_mm,
     target_width_mm,
@@ -201,7 +381,7 @@ fn embed_signature_image(
   Ok(())
 }
 
-/// Create a simple invoice PDF matching the provided template
+/// Create a simple invoice PDF laid out from the practitioner's template
 #[allow(clippy::too_many_arguments)]
 fn create_modern_invoice_pdf(
   user: &users::Model,
@@ -209,13 +389,24 @@ fn create_modern_invoice_pdf(
   patient: &patients::Model,
   patient_ssn: &str,
   amount: &f32,
+  act_label: Option<&str>,
   invoice_date: &Date,
   practitioner_office: &practitioner_offices::Model,
+  template: &InvoiceTemplate,
+  locale: &Locale,
   signature_data: Option<&[u8]>,
+  logo_data: Option<&[u8]>,
 ) -> std::result::Result<Vec<u8>, String> {
+  let messages = locale.messages();
+
   // Create PDF document
   let mut doc = Document::new();
-  doc.set_title("Note d'honoraires acquittée");
+  doc.set_title(&template.title);
+  doc
+    .add_font_from_bytes(REGULAR_FONT, REGULAR_FONT_DATA.to_vec())
+    .and_then(|_| doc.add_font_from_bytes(BOLD_FONT, BOLD_FONT_DATA.to_vec()))
+    .map_err(|e| format!("Failed to embed fonts: {}", e))?;
+  let accent_color = Color::hex(&template.accent_color);
 
   // Create A4 page (210mm x 297mm = 595 x 842 points)
   let mut page = Page::a4();
@@ -226,15 +417,37 @@ fn create_modern_invoice_pdf(
   let mut y_position = page_height - margin - mm(10.0);
 
   // === HEADER SECTION ===
+  // Logo in the top right corner
+  if let Some(logo_bytes) = logo_data {
+    if let Err(e) = embed_image(&mut page, "logo", logo_bytes, 145.0, 272.0, (40.0, 25.0)) {
+      tracing::warn!(
+        "Failed to embed logo image: {}. Continuing without logo.",
+        e
+      );
+    }
+  }
+
+  // Free text of the practitioner, in the right column below the logo
+  let mut header_y = page_height - margin - mm(32.0);
+  for line in header_lines(template) {
+    page
+      .text()
+      .set_font(regular_font(), 9.0)
+      .at(mm(120.0), header_y)
+      .write(&line)
+      .map_err(|e| format!("Failed to write header text: {}", e))?;
+    header_y -= mm(4.5);
+  }
+
   // Practitioner name with title on same line, separated by dash
   let full_name = format!(
     "{} – {}",
     &user.full_name(),
-    business_info.profession.to_french()
+    business_info.profession.label(locale)
   );
   page
     .text()
-    .set_font(Font::HelveticaBold, 14.0)
+    .set_font(bold_font(), 14.0)
     .at(margin, y_position)
     .write(&full_name)
     .map_err(|e| format!("Failed to write practitioner name: {}", e))?;
@@ -244,33 +457,39 @@ fn create_modern_invoice_pdf(
   if let Some(ref adeli) = business_info.adeli_number {
     page
       .text()
-      .set_font(Font::Helvetica, 10.0)
+      .set_font(regular_font(), 10.0)
       .at(margin, y_position)
-      .write(&format!("N° Adeli : {}", adeli))
+      .write(&format!("{} {}", messages.adeli_number, adeli))
       .map_err(|e| format!("Failed to write Adeli number: {}", e))?;
     y_position -= mm(5.0);
   }
 
   page
     .text()
-    .set_font(Font::Helvetica, 10.0)
+    .set_font(regular_font(), 10.0)
     .at(margin, y_position)
-    .write(&format!("N°RPPS : {}", business_info.rpps_number))
+    .write(&format!(
+      "{} {}",
+      messages.rpps_number, business_info.rpps_number
+    ))
     .map_err(|e| format!("Failed to write RPPS number: {}", e))?;
   y_position -= mm(5.0);
 
   page
     .text()
-    .set_font(Font::Helvetica, 10.0)
+    .set_font(regular_font(), 10.0)
     .at(margin, y_position)
-    .write(&format!("N°SIRET : {}", business_info.siret_number))
+    .write(&format!(
+      "{} {}",
+      messages.siret_number, business_info.siret_number
+    ))
     .map_err(|e| format!("Failed to write SIRET number: {}", e))?;
   y_position -= mm(12.0);
 
   // Address
   page
     .text()
-    .set_font(Font::Helvetica, 10.0)
+    .set_font(regular_font(), 10.0)
     .at(margin, y_position)
     .write(&practitioner_office.address_line_1)
     .map_err(|e| format!("Failed to write address line 1: {}", e))?;
@@ -278,7 +497,7 @@ fn create_modern_invoice_pdf(
 
   page
     .text()
-    .set_font(Font::Helvetica, 10.0)
+    .set_font(regular_font(), 10.0)
     .at(margin, y_position)
     .write(&format!(
       "{} {}",
@@ -290,118 +509,156 @@ fn create_modern_invoice_pdf(
   // Contact info
   page
     .text()
-    .set_font(Font::Helvetica, 10.0)
+    .set_font(regular_font(), 10.0)
     .at(margin, y_position)
-    .write(&format!("Tel : {}", &user.phone_number))
+    .write(&format!("{} {}", messages.phone_number, &user.phone_number))
     .map_err(|e| format!("Failed to write phone number: {}", e))?;
   y_position -= mm(8.0);
 
   page
     .text()
-    .set_font(Font::Helvetica, 10.0)
+    .set_font(regular_font(), 10.0)
     .at(margin, y_position)
     .write(&user.email)
     .map_err(|e| format!("Failed to write email: {}", e))?;
   y_position -= mm(30.0);
 
   // === INVOICE TITLE - CENTERED ===
-  let title = "Note d'honoraires acquittée";
+  // Rough estimate: 3.5mm per character at 20pt
+  let title_width = template.title.chars().count() as f64 * 3.9;
   page
     .text()
-    .set_font(Font::HelveticaBold, 20.0)
-    .at(mm(60.0), y_position)
-    .write(title)
-    .map_err(|e| format!("Failed to write title: {}", e))?;
+    .set_font(bold_font(), 20.0)
+    .set_fill_color(accent_color)
+    .at(mm(((210.0 - title_width) / 2.0).max(25.0)), y_position)
+    .write(&template.title)
+    .map_err(|e| format!("Failed to write title: {}", e))?
+    .set_fill_color(Color::black());
   y_position -= mm(30.0);
 
   // === PATIENT INFORMATION ===
   // Patient name
   let patient_full_name = format!("{} {}", patient.last_name, patient.first_name);
-  let full_text = format!("Reçu de : {}", patient_full_name);
+  let full_text = format!("{} {}", messages.received_from, patient_full_name);
 
   page
     .text()
-    .set_font(Font::Helvetica, 11.0)
+    .set_font(regular_font(), 11.0)
     .at(margin, y_position)
     .write(&full_text)
     .map_err(|e| format!("Failed to write patient name: {}", e))?;
 
-  // Draw underline only for "Reçu de :"
+  // Draw underline only for the label
   let underline_y = y_position - mm(1.0);
   page
     .graphics()
     .set_stroke_color(Color::black())
     .set_line_width(mm(0.3))
     .move_to(margin, underline_y)
-    .line_to(margin + mm(17.0), underline_y)
+    .line_to(
+      margin + mm(label_width_mm(messages.received_from)),
+      underline_y,
+    )
     .stroke();
 
+  // Birth date, printed on care sheets to tell homonyms apart
+  if let Some(birth_date) = patient.birth_date.filter(|_| template.show_birth_date) {
+    y_position -= mm(7.0);
+    page
+      .text()
+      .set_font(regular_font(), 11.0)
+      .at(margin, y_position)
+      .write(&format!(
+        "{} {}",
+        messages.birth_date,
+        birth_date.format(messages.date_format)
+      ))
+      .map_err(|e| format!("Failed to write patient birth date: {}", e))?;
+  }
+
   y_position -= mm(12.0);
 
   // Social security number with box
-  let ssn_y = y_position;
-  page
-    .text()
-    .set_font(Font::Helvetica, 11.0)
-    .at(margin, y_position)
-    .write(&format!("Numéro de sécurité sociale : {}", patient_ssn))
-    .map_err(|e| format!("Failed to write SSN: {}", e))?;
+  if template.show_ssn {
+    let ssn_y = y_position;
+    page
+      .text()
+      .set_font(regular_font(), 11.0)
+      .at(margin, y_position)
+      .write(&format!("{} {}", messages.ssn, patient_ssn))
+      .map_err(|e| format!("Failed to write SSN: {}", e))?;
 
-  // Draw box around SSN field
-  let box_x = margin - mm(2.0);
-  let box_y = ssn_y - mm(3.0);
-  let box_width = mm(185.0) - box_x;
-  let box_height = mm(8.0);
+    // Draw box around SSN field
+    let box_x = margin - mm(2.0);
+    let box_y = ssn_y - mm(3.0);
+    let box_width = mm(185.0) - box_x;
+    let box_height = mm(8.0);
 
-  page
-    .graphics()
-    .set_stroke_color(Color::black())
-    .set_line_width(mm(0.5))
-    .rect(box_x, box_y, box_width, box_height)
-    .stroke();
+    page
+      .graphics()
+      .set_stroke_color(accent_color)
+      .set_line_width(mm(0.5))
+      .rect(box_x, box_y, box_width, box_height)
+      .stroke();
 
-  y_position -= mm(18.0);
+    y_position -= mm(18.0);
+  }
 
   // Address with box
-  let addr_y = y_position;
-  let address_text = format!(
-    "Adresse : {} – {} {}",
-    patient.address_line_1, patient.address_zip_code, patient.address_city
-  );
-  page
-    .text()
-    .set_font(Font::Helvetica, 11.0)
-    .at(margin, y_position)
-    .write(&address_text)
-    .map_err(|e| format!("Failed to write patient address: {}", e))?;
+  if template.show_patient_address {
+    let addr_y = y_position;
+    let address_text = format!(
+      "{} {} – {} {}",
+      messages.address, patient.address_line_1, patient.address_zip_code, patient.address_city
+    );
+    page
+      .text()
+      .set_font(regular_font(), 11.0)
+      .at(margin, y_position)
+      .write(&address_text)
+      .map_err(|e| format!("Failed to write patient address: {}", e))?;
 
-  // Draw box around address field
-  let addr_box_x = margin - mm(2.0);
-  let addr_box_y = addr_y - mm(3.0);
-  let addr_box_width = mm(185.0) - addr_box_x;
-  let addr_box_height = mm(8.0);
+    // Draw box around address field
+    let addr_box_x = margin - mm(2.0);
+    let addr_box_y = addr_y - mm(3.0);
+    let addr_box_width = mm(185.0) - addr_box_x;
+    let addr_box_height = mm(8.0);
 
-  page
-    .graphics()
-    .set_stroke_color(Color::black())
-    .set_line_width(mm(0.5))
-    .rect(addr_box_x, addr_box_y, addr_box_width, addr_box_height)
-    .stroke();
+    page
+      .graphics()
+      .set_stroke_color(accent_color)
+      .set_line_width(mm(0.5))
+      .rect(addr_box_x, addr_box_y, addr_box_width, addr_box_height)
+      .stroke();
+
+    y_position -= mm(18.0);
+  }
 
-  y_position -= mm(18.0);
+  if let Some(act_label) = act_label {
+    page
+      .text()
+      .set_font(regular_font(), 11.0)
+      .at(margin, y_position)
+      .write(&format!("{} {}", messages.act, act_label))
+      .map_err(|e| format!("Failed to write act: {}", e))?;
+    y_position -= mm(10.0);
+  }
 
-  let full_text = format!("Honoraire : {:.2}€", amount);
+  let full_text = format!(
+    "{} {}",
+    messages.fee,
+    fill(messages.amount, &[&format!("{:.2}", amount)])
+  );
 
   page
     .text()
-    .set_font(Font::Helvetica, 11.0)
+    .set_font(regular_font(), 11.0)
     .at(margin, y_position)
     .write(&full_text)
     .map_err(|e| format!("Failed to write amount: {}", e))?;
 
-  // Draw underline only for "Honoraire :"
-  let underline_text = "Honoraire :";
-  let text_width = underline_text.len() as f64 * 2.5; // Rough estimate: 2.5mm per character at 11pt
+  // Draw underline only for the label
+  let text_width = label_width_mm(messages.fee);
   let underline_y = y_position - mm(1.0);
 
   page
@@ -411,20 +668,20 @@ fn create_modern_invoice_pdf(
     .move_to(margin, underline_y)
     .line_to(margin + mm(text_width), underline_y)
     .stroke();
-  y_position -= mm(35.0);
+  y_position -= mm(25.0);
 
   // === DATE AND SIGNATURE ===
-  let invoice_date_str = invoice_date.format("%d/%m/%Y").to_string();
-  let date_location = format!(
-    "Fait à {}, le {}",
-    practitioner_office.address_city, invoice_date_str
+  let invoice_date_str = invoice_date.format(messages.date_format).to_string();
+  let date_location = fill(
+    messages.made_at,
+    &[&practitioner_office.address_city, &invoice_date_str],
   );
 
   // Right align date
   let date_x = mm(230.0) - margin - mm(85.0);
   page
     .text()
-    .set_font(Font::Helvetica, 11.0)
+    .set_font(regular_font(), 11.0)
     .at(date_x, y_position)
     .write(&date_location)
     .map_err(|e| format!("Failed to write date: {}", e))?;
@@ -434,7 +691,14 @@ fn create_modern_invoice_pdf(
 
   // Try to embed signature image if available
   if let Some(sig_bytes) = signature_data {
-    match embed_signature_image(&mut page, sig_bytes, sig_x_mm, y_position / MM_TO_POINTS) {
+    match embed_image(
+      &mut page,
+      "signature",
+      sig_bytes,
+      sig_x_mm,
+      y_position / MM_TO_POINTS,
+      (60.0, 30.0),
+    ) {
       Ok(_) => {
         tracing::info!("Successfully embedded signature image");
       }
@@ -452,11 +716,25 @@ fn create_modern_invoice_pdf(
   // Practitioner name below signature
   page
     .text()
-    .set_font(Font::Helvetica, 11.0)
+    .set_font(regular_font(), 11.0)
     .at(date_x + mm(20.0), y_position)
     .write(&user.full_name())
     .map_err(|e| format!("Failed to write practitioner name at bottom: {}", e))?;
 
+  // === FOOTER ===
+  // Footer text, then the legal mentions, stacked up from the bottom margin
+  let footer_lines = footer_lines(template);
+  let mut footer_y = mm(10.0 + 4.0 * footer_lines.len().saturating_sub(1) as f64);
+  for line in footer_lines {
+    page
+      .text()
+      .set_font(regular_font(), 8.0)
+      .at(margin, footer_y)
+      .write(&line)
+      .map_err(|e| format!("Failed to write footer: {}", e))?;
+    footer_y -= mm(4.0);
+  }
+
   // Add the page to the document
   doc.add_page(page);
 
@@ -1,4 +1,4 @@
-use crate::workers::mailer::attachment::EmailAttachment;
+use crate::workers::mailer::{attachment::EmailAttachment, template::RenderedEmail};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,9 +8,15 @@ pub struct EmailArgs {
   pub to_name: Option<String>,
   pub subject: String,
   pub text_body: String,
-  // pub html_body: Option<String>,
+  pub html_body: Option<String>,
   pub attachments: Vec<EmailAttachment>,
   pub reply_to: Option<String>,
+  /// Name of the template the email was rendered from
+  pub template: Option<String>,
+  /// What the email relates to, recorded in the email log
+  pub user_id: Option<i32>,
+  pub patient_id: Option<i32>,
+  pub medical_appointment_id: Option<i32>,
 }
 
 impl EmailArgs {
@@ -21,8 +27,22 @@ impl EmailArgs {
       to_name: None,
       subject,
       text_body,
+      html_body: None,
       attachments: Vec::new(),
       reply_to: None,
+      template: None,
+      user_id: None,
+      patient_id: None,
+      medical_appointment_id: None,
+    }
+  }
+
+  /// HTML email with its plain text fallback
+  pub fn from_template(to: String, email: RenderedEmail) -> Self {
+    Self {
+      html_body: Some(email.html_body),
+      template: Some(email.template.to_string()),
+      ..Self::new_text(to, email.subject, email.text_body)
     }
   }
 
@@ -40,4 +60,20 @@ impl EmailArgs {
     self.reply_to = Some(reply_to);
     self
   }
+
+  /// User on whose behalf, or to whom, the email is sent
+  pub fn for_user(mut self, user_id: i32) -> Self {
+    self.user_id = Some(user_id);
+    self
+  }
+
+  pub fn for_patient(mut self, patient_id: i32) -> Self {
+    self.patient_id = Some(patient_id);
+    self
+  }
+
+  pub fn for_medical_appointment(mut self, medical_appointment_id: i32) -> Self {
+    self.medical_appointment_id = Some(medical_appointment_id);
+    self
+  }
 }
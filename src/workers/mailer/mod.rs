@@ -1,3 +1,4 @@
 pub mod args;
 pub mod attachment;
+pub mod template;
 pub mod worker;
@@ -0,0 +1,332 @@
+//! Named email templates, rendered from the message catalogue as an HTML body
+//! and its plain text fallback.
+
+use chrono::NaiveDate;
+
+use crate::{
+  locales::fill,
+  models::{
+    _entities::{
+      sea_orm_active_enums::{Locale, PatientAccessLevel},
+      user_business_informations, users,
+    },
+    invoice_templates::InvoiceTemplate,
+  },
+};
+
+const LAYOUT: &str = include_str!("templates/layout.html");
+
+/// Colour of the emails not sent on behalf of a practitioner
+const DEFAULT_ACCENT_COLOR: &str = "#000000";
+
+/// Emails sent by OpenCab, with the variables they are filled with
+#[derive(Debug, Clone)]
+pub enum EmailTemplate {
+  Invoice {
+    invoice_date: NaiveDate,
+  },
+  PasswordReset {
+    reset_url: String,
+  },
+  AccessKey {
+    access_key: String,
+    login_url: String,
+  },
+  AppointmentsExport {
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+  },
+  /// Sent to the office holder, with the statement attached
+  RetrocessionStatement {
+    office_name: String,
+    /// e.g. `03/2026`
+    month_label: String,
+    amount_due_in_cents: i64,
+  },
+  /// Sent to the practitioner owning the record
+  AccessRequest {
+    requester_name: String,
+    level: PatientAccessLevel,
+    patient_name: String,
+    requests_url: String,
+  },
+  /// Sent to the patient, whose consent also grants the access
+  PatientConsent {
+    requester_name: String,
+    level: PatientAccessLevel,
+    owner_name: String,
+    validity_days: i64,
+    consent_url: String,
+  },
+}
+
+/// Closing block of the emails sent on behalf of a practitioner
+#[derive(Debug, Clone)]
+pub struct Signature {
+  pub lines: Vec<String>,
+  /// `#RRGGBB` colour of the email frame, the one of the practitioner's notes
+  pub accent_color: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedEmail {
+  pub template: &'static str,
+  pub subject: String,
+  pub text_body: String,
+  pub html_body: String,
+}
+
+enum Variable {
+  Text(String),
+  Link(String),
+}
+
+impl Variable {
+  fn text(&self) -> &str {
+    match self {
+      Self::Text(value) | Self::Link(value) => value,
+    }
+  }
+
+  fn html(&self) -> String {
+    match self {
+      Self::Text(value) => escape_html(value),
+      Self::Link(url) => {
+        let url = escape_html(url);
+        format!("<a href=\"{}\">{}</a>", url, url)
+      }
+    }
+  }
+}
+
+impl Signature {
+  /// Just the practitioner's name, for the emails sent to their colleagues
+  pub fn name(user: &users::Model) -> Self {
+    Self {
+      lines: vec![user.full_name()],
+      accent_color: DEFAULT_ACCENT_COLOR.to_string(),
+    }
+  }
+
+  /// The practitioner's custom signature, or their name, profession and phone number
+  pub fn practitioner(
+    user: &users::Model,
+    business_information: &user_business_informations::Model,
+    template: &InvoiceTemplate,
+    locale: &Locale,
+  ) -> Self {
+    let lines = match &template.email_signature {
+      Some(signature) => signature.lines().map(str::to_string).collect(),
+      None => vec![
+        format!("{} {}", user.last_name, user.first_name),
+        business_information.profession.label(locale).to_string(),
+        user.phone_number.clone(),
+      ],
+    };
+
+    Self {
+      lines,
+      accent_color: template.accent_color.clone(),
+    }
+  }
+}
+
+impl EmailTemplate {
+  /// Identifies the template in the email log
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Invoice { .. } => "invoice",
+      Self::PasswordReset { .. } => "password_reset",
+      Self::AccessKey { .. } => "access_key",
+      Self::AppointmentsExport { .. } => "appointments_export",
+      Self::RetrocessionStatement { .. } => "retrocession_statement",
+      Self::AccessRequest { .. } => "access_request",
+      Self::PatientConsent { .. } => "patient_consent",
+    }
+  }
+
+  pub fn render(&self, locale: &Locale, signature: Option<&Signature>) -> RenderedEmail {
+    let messages = locale.messages();
+    let date = |date: &NaiveDate| date.format(messages.date_format).to_string();
+    let amount = |amount_in_cents: &i64| {
+      fill(
+        messages.amount,
+        &[&format!("{:.2}", *amount_in_cents as f64 / 100.0)],
+      )
+    };
+    let level = |level: &PatientAccessLevel| match level {
+      PatientAccessLevel::Read => messages.read_access.to_string(),
+      PatientAccessLevel::Write => messages.write_access.to_string(),
+    };
+
+    let (subject, subject_values, body, body_values) = match self {
+      Self::Invoice { invoice_date } => (
+        messages.invoice_email_subject,
+        vec![date(invoice_date)],
+        messages.invoice_email_body,
+        vec![Variable::Text(date(invoice_date))],
+      ),
+      Self::PasswordReset { reset_url } => (
+        messages.password_reset_subject,
+        vec![],
+        messages.password_reset_body,
+        vec![Variable::Link(reset_url.clone())],
+      ),
+      Self::AccessKey {
+        access_key,
+        login_url,
+      } => (
+        messages.access_key_subject,
+        vec![],
+        messages.access_key_body,
+        vec![
+          Variable::Text(access_key.clone()),
+          Variable::Link(login_url.clone()),
+        ],
+      ),
+      Self::AppointmentsExport {
+        start_date,
+        end_date,
+      } => (
+        messages.export_email_subject,
+        vec![date(start_date), date(end_date)],
+        messages.export_email_body,
+        vec![],
+      ),
+      Self::RetrocessionStatement {
+        office_name,
+        month_label,
+        amount_due_in_cents,
+      } => (
+        messages.statement_subject,
+        vec![office_name.clone(), month_label.clone()],
+        messages.statement_email_body,
+        vec![
+          Variable::Text(month_label.clone()),
+          Variable::Text(office_name.clone()),
+          Variable::Text(amount(amount_due_in_cents)),
+        ],
+      ),
+      Self::AccessRequest {
+        requester_name,
+        level: access_level,
+        patient_name,
+        requests_url,
+      } => (
+        messages.access_request_subject,
+        vec![],
+        messages.access_request_body,
+        vec![
+          Variable::Text(requester_name.clone()),
+          Variable::Text(level(access_level)),
+          Variable::Text(patient_name.clone()),
+          Variable::Link(requests_url.clone()),
+        ],
+      ),
+      Self::PatientConsent {
+        requester_name,
+        level: access_level,
+        owner_name,
+        validity_days,
+        consent_url,
+      } => (
+        messages.patient_consent_subject,
+        vec![],
+        messages.patient_consent_body,
+        vec![
+          Variable::Text(requester_name.clone()),
+          Variable::Text(level(access_level)),
+          Variable::Text(owner_name.clone()),
+          Variable::Text(validity_days.to_string()),
+          Variable::Link(consent_url.clone()),
+        ],
+      ),
+    };
+
+    let subject = fill(
+      subject,
+      &subject_values
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>(),
+    );
+
+    let mut text_body = fill(
+      body,
+      &body_values.iter().map(Variable::text).collect::<Vec<_>>(),
+    );
+    let html_values = body_values.iter().map(Variable::html).collect::<Vec<_>>();
+    let mut html_paragraphs = paragraphs(&fill(
+      &escape_html(body),
+      &html_values.iter().map(String::as_str).collect::<Vec<_>>(),
+    ));
+
+    if let Some(signature) = signature {
+      text_body = format!("{}\n\n{}", text_body, signature.lines.join("\n"));
+      let lines = signature
+        .lines
+        .iter()
+        .map(|line| escape_html(line))
+        .collect::<Vec<_>>();
+      html_paragraphs.push(format!(
+        "      <p style=\"margin: 24px 0 0; color: #52525b;\">{}</p>",
+        lines.join("<br>")
+      ));
+    }
+
+    let html_body = render_layout(&[
+      ("lang", lang(locale)),
+      ("subject", &escape_html(&subject)),
+      (
+        "accent_color",
+        signature.map_or(DEFAULT_ACCENT_COLOR, |signature| &signature.accent_color),
+      ),
+      ("content", &html_paragraphs.join("\n")),
+    ]);
+
+    RenderedEmail {
+      template: self.name(),
+      subject,
+      text_body,
+      html_body,
+    }
+  }
+}
+
+fn lang(locale: &Locale) -> &'static str {
+  match locale {
+    Locale::En => "en",
+    Locale::Fr => "fr",
+  }
+}
+
+/// One `<p>` per block of `text` separated by a blank line
+fn paragraphs(text: &str) -> Vec<String> {
+  text
+    .split("\n\n")
+    .map(|paragraph| {
+      format!(
+        "      <p style=\"margin: 0 0 12px;\">{}</p>",
+        paragraph.replace('\n', "<br>")
+      )
+    })
+    .collect()
+}
+
+/// Replace the `{{name}}` placeholders of the layout by their value
+fn render_layout(variables: &[(&str, &str)]) -> String {
+  variables
+    .iter()
+    .fold(LAYOUT.to_string(), |html, (name, value)| {
+      html.replace(&format!("{{{{{}}}}}", name), value)
+    })
+}
+
+fn escape_html(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&#39;")
+}
@@ -1,21 +1,70 @@
-use crate::{config::Config, models::my_errors::MyErrors, workers::mailer::args::EmailArgs};
+use crate::{
+  config::Config,
+  models::{
+    email_logs::{self, CreateEmailLogParams},
+    my_errors::MyErrors,
+  },
+  workers::mailer::args::EmailArgs,
+};
 use lettre::{
   message::{header::ContentType, Attachment, Mailbox, MultiPart, SinglePart},
   transport::smtp::authentication::Credentials,
   AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
 };
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
-pub async fn process_email(args: EmailArgs, config: &Arc<Config>) -> Result<(), MyErrors> {
+pub async fn process_email(
+  args: EmailArgs,
+  db: &DatabaseConnection,
+  config: &Arc<Config>,
+) -> Result<(), MyErrors> {
   tracing::info!("Start sending email to: {}", args.to);
 
-  let email = build_email(&args, config)?;
-  send_email(email, config).await?;
+  // A failure to log the email must not prevent it from being sent
+  let email_log = email_logs::ActiveModel::create(db, &email_log_params(&args))
+    .await
+    .inspect_err(|e| tracing::warn!("Failed to log email to {}: {}", args.to, e))
+    .ok();
+
+  let result = deliver(&args, config).await;
+
+  if let Some(email_log) = email_log {
+    if let Err(e) = email_logs::ActiveModel::record_result(db, email_log, &result).await {
+      tracing::warn!("Failed to record the result of email to {}: {}", args.to, e);
+    }
+  }
+
+  result?;
 
   tracing::info!("Email sent successfully");
   Ok(())
 }
 
+fn email_log_params(args: &EmailArgs) -> CreateEmailLogParams {
+  CreateEmailLogParams {
+    user_id: args.user_id,
+    patient_id: args.patient_id,
+    medical_appointment_id: args.medical_appointment_id,
+    recipient: args.to.clone(),
+    subject: args.subject.clone(),
+    template: args.template.clone(),
+    attachment_names: args
+      .attachments
+      .iter()
+      .map(|attachment| attachment.filename.clone())
+      .collect(),
+    // Only invoices, tied to their appointment, can be sent again. Other
+    // emails may hold an access key or a reset link, which must not be kept.
+    sent_email: args.medical_appointment_id.map(|_| serde_json::json!(args)),
+  }
+}
+
+async fn deliver(args: &EmailArgs, config: &Arc<Config>) -> Result<(), MyErrors> {
+  let email = build_email(args, config)?;
+  send_email(email, config).await
+}
+
 fn build_email(args: &EmailArgs, _config: &Arc<Config>) -> Result<Message, MyErrors> {
   let to: Mailbox = if let Some(name) = &args.to_name {
     format!("{} <{}>", name, args.to).parse()?
@@ -48,14 +97,26 @@ fn build_simple_body(
   builder: lettre::message::MessageBuilder,
   args: &EmailArgs,
 ) -> Result<Message, MyErrors> {
-  Ok(builder.body(args.text_body.clone())?)
+  match &args.html_body {
+    Some(html_body) => Ok(builder.multipart(MultiPart::alternative_plain_html(
+      args.text_body.clone(),
+      html_body.clone(),
+    ))?),
+    None => Ok(builder.body(args.text_body.clone())?),
+  }
 }
 
 fn build_multipart_body(
   builder: lettre::message::MessageBuilder,
   args: &EmailArgs,
 ) -> Result<Message, MyErrors> {
-  let mut multipart = MultiPart::mixed().singlepart(SinglePart::plain(args.text_body.clone()));
+  let mut multipart = match &args.html_body {
+    Some(html_body) => MultiPart::mixed().multipart(MultiPart::alternative_plain_html(
+      args.text_body.clone(),
+      html_body.clone(),
+    )),
+    None => MultiPart::mixed().singlepart(SinglePart::plain(args.text_body.clone())),
+  };
 
   for attachment in &args.attachments {
     let data = attachment.decode_data()?;
@@ -1,11 +1,16 @@
 use crate::{app_state::WorkerJob, config::Config};
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
 pub mod appointments_export;
+pub mod deposit_slip_generator;
 pub mod downloader;
+pub mod export_scheduler;
 pub mod invoice_generator;
 pub mod mailer;
+pub mod retention_purge;
+pub mod retrocession_statement_generator;
 
 const WORKER_CHANNEL_SIZE: usize = 100;
 
@@ -16,9 +21,14 @@ pub fn create_worker_channel() -> (mpsc::Sender<WorkerJob>, mpsc::Receiver<Worke
 
 /// Start the worker pool with the specified number of workers
 /// Each worker will continuously process jobs from the channel
-pub async fn start_worker_pool(mut rx: mpsc::Receiver<WorkerJob>, config: Arc<Config>) {
+pub async fn start_worker_pool(
+  mut rx: mpsc::Receiver<WorkerJob>,
+  db: DatabaseConnection,
+  config: Arc<Config>,
+) {
   tokio::spawn(async move {
     while let Some(job) = rx.recv().await {
+      let db_clone = db.clone();
       let config_clone = config.clone();
 
       // Spawn a task for each job
@@ -26,7 +36,9 @@ pub async fn start_worker_pool(mut rx: mpsc::Receiver<WorkerJob>, config: Arc<Co
         tracing::debug!("Processing job");
 
         let result = match job {
-          WorkerJob::Email(args) => mailer::worker::process_email(args, &config_clone).await,
+          WorkerJob::Email(args) => {
+            mailer::worker::process_email(args, &db_clone, &config_clone).await
+          }
           WorkerJob::AccountingReport(args, state) => {
             appointments_export::process_appointment_extraction(args, state).await
           }
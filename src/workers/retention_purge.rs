@@ -0,0 +1,21 @@
+use std::{sync::Arc, time::Duration};
+
+use sea_orm::DatabaseConnection;
+
+use crate::{config::Config, services::patient_retention};
+
+/// Periodically purge the anonymized patients whose retention period is over
+pub async fn start_scheduler(db: DatabaseConnection, config: Arc<Config>) {
+  let retention = &config.retention;
+  let mut interval =
+    tokio::time::interval(Duration::from_secs(retention.purge_interval_hours * 3600));
+
+  loop {
+    interval.tick().await;
+
+    match patient_retention::purge_expired(&db, retention.patient_retention_days).await {
+      Ok(purged) => tracing::info!("Retention purge removed {} patient(s)", purged),
+      Err(e) => tracing::error!("Retention purge failed: {:?}", e),
+    }
+  }
+}
@@ -0,0 +1,236 @@
+use axum::http::StatusCode;
+use oxidize_pdf::graphics::Color;
+use oxidize_pdf::text::Font;
+use oxidize_pdf::{Document, Page};
+use sea_orm::prelude::Date;
+
+use crate::{
+  locales::{fill, Messages},
+  models::{
+    _entities::{
+      practitioner_offices, retrocession_statements, sea_orm_active_enums::Locale,
+      user_business_informations, users,
+    },
+    my_errors::MyErrors,
+  },
+};
+
+/// Conversion constant: millimeters to points
+const MM_TO_POINTS: f64 = 2.834645669; // 72 / 25.4
+
+fn mm(value: f64) -> f64 {
+  value * MM_TO_POINTS
+}
+
+/// Appointments of a single day in the office. Patients are deliberately
+/// left out: the office holder only needs the amounts.
+#[derive(Debug, Clone)]
+pub struct StatementDay {
+  pub date: Date,
+  pub appointments_count: i32,
+  pub fees_in_cents: i64,
+}
+
+/// Generate the monthly retrocession statement sent to the office holder, in
+/// the practitioner's language
+pub fn generate_retrocession_statement_pdf(
+  user: &users::Model,
+  business_info: Option<&user_business_informations::Model>,
+  office: &practitioner_offices::Model,
+  statement: &retrocession_statements::Model,
+  days: &[StatementDay],
+  locale: &Locale,
+) -> std::result::Result<Vec<u8>, MyErrors> {
+  create_retrocession_statement_pdf(user, business_info, office, statement, days, locale).map_err(
+    |e| MyErrors {
+      code: StatusCode::INTERNAL_SERVER_ERROR,
+      msg: format!("PDF creation failed: {}", e),
+    },
+  )
+}
+
+fn format_amount(messages: &Messages, amount_in_cents: i64) -> String {
+  fill(
+    messages.amount,
+    &[&format!("{:.2}", amount_in_cents as f64 / 100.0)],
+  )
+}
+
+fn create_retrocession_statement_pdf(
+  user: &users::Model,
+  business_info: Option<&user_business_informations::Model>,
+  office: &practitioner_offices::Model,
+  statement: &retrocession_statements::Model,
+  days: &[StatementDay],
+  locale: &Locale,
+) -> std::result::Result<Vec<u8>, String> {
+  let messages = locale.messages();
+  let month_label = statement.month_label();
+
+  let mut doc = Document::new();
+  doc.set_title(fill(
+    messages.statement_subject,
+    &[&office.name, &month_label],
+  ));
+
+  let page_height = mm(297.0);
+  let margin = mm(20.0);
+  let columns = [
+    (messages.statement_date, 0.0),
+    (messages.statement_appointments, 50.0),
+    (messages.statement_fees, 110.0),
+  ];
+
+  let mut page = Page::a4();
+  let mut y_position = page_height - margin - mm(5.0);
+
+  // === HEADER SECTION ===
+  page
+    .text()
+    .set_font(Font::HelveticaBold, 16.0)
+    .at(margin, y_position)
+    .write(&fill(messages.statement_title, &[&month_label]))
+    .map_err(|e| format!("Failed to write title: {}", e))?;
+  y_position -= mm(10.0);
+
+  page
+    .text()
+    .set_font(Font::Helvetica, 11.0)
+    .at(margin, y_position)
+    .write(&fill(messages.statement_practitioner, &[&user.full_name()]))
+    .map_err(|e| format!("Failed to write practitioner name: {}", e))?;
+  y_position -= mm(6.0);
+
+  if let Some(business_info) = business_info {
+    page
+      .text()
+      .set_font(Font::Helvetica, 10.0)
+      .at(margin, y_position)
+      .write(&format!(
+        "{} {}",
+        messages.siret_number, business_info.siret_number
+      ))
+      .map_err(|e| format!("Failed to write SIRET number: {}", e))?;
+    y_position -= mm(6.0);
+  }
+
+  page
+    .text()
+    .set_font(Font::Helvetica, 11.0)
+    .at(margin, y_position)
+    .write(&fill(
+      messages.statement_office,
+      &[&format!(
+        "{}, {} {} {}",
+        office.name, office.address_line_1, office.address_zip_code, office.address_city
+      )],
+    ))
+    .map_err(|e| format!("Failed to write office address: {}", e))?;
+  y_position -= mm(6.0);
+
+  page
+    .text()
+    .set_font(Font::Helvetica, 11.0)
+    .at(margin, y_position)
+    .write(&fill(
+      messages.statement_period,
+      &[
+        &statement.month.format(messages.date_format).to_string(),
+        &statement
+          .month_end()
+          .format(messages.date_format)
+          .to_string(),
+      ],
+    ))
+    .map_err(|e| format!("Failed to write period: {}", e))?;
+  y_position -= mm(14.0);
+
+  // === DAYS TABLE ===
+  for (label, offset) in columns {
+    page
+      .text()
+      .set_font(Font::HelveticaBold, 10.0)
+      .at(margin + mm(offset), y_position)
+      .write(label)
+      .map_err(|e| format!("Failed to write column header: {}", e))?;
+  }
+
+  let header_line_y = y_position - mm(2.0);
+  page
+    .graphics()
+    .set_stroke_color(Color::black())
+    .set_line_width(mm(0.3))
+    .move_to(margin, header_line_y)
+    .line_to(mm(190.0), header_line_y)
+    .stroke();
+  y_position -= mm(7.0);
+
+  // A month has at most 31 days, which fits on a single page
+  for day in days {
+    let cells = [
+      day.date.format(messages.date_format).to_string(),
+      day.appointments_count.to_string(),
+      format_amount(messages, day.fees_in_cents),
+    ];
+
+    for (cell, (_, offset)) in cells.iter().zip(columns) {
+      page
+        .text()
+        .set_font(Font::Helvetica, 10.0)
+        .at(margin + mm(offset), y_position)
+        .write(cell)
+        .map_err(|e| format!("Failed to write day line: {}", e))?;
+    }
+    y_position -= mm(6.0);
+  }
+
+  // === TOTALS ===
+  let total_line_y = y_position + mm(3.0);
+  page
+    .graphics()
+    .set_stroke_color(Color::black())
+    .set_line_width(mm(0.3))
+    .move_to(margin, total_line_y)
+    .line_to(mm(190.0), total_line_y)
+    .stroke();
+  y_position -= mm(4.0);
+
+  let totals = [
+    (
+      messages.statement_appointments_count,
+      statement.appointments_count.to_string(),
+    ),
+    (
+      messages.statement_total_fees,
+      format_amount(messages, statement.fees_in_cents),
+    ),
+    (messages.statement_terms, statement.terms().label(locale)),
+    (
+      messages.statement_amount_due,
+      format_amount(messages, statement.retrocession_in_cents),
+    ),
+  ];
+
+  for (label, value) in totals {
+    page
+      .text()
+      .set_font(Font::HelveticaBold, 11.0)
+      .at(margin, y_position)
+      .write(label)
+      .map_err(|e| format!("Failed to write total label: {}", e))?;
+
+    page
+      .text()
+      .set_font(Font::HelveticaBold, 11.0)
+      .at(margin + mm(110.0), y_position)
+      .write(&value)
+      .map_err(|e| format!("Failed to write total: {}", e))?;
+    y_position -= mm(7.0);
+  }
+
+  doc.add_page(page);
+
+  doc
+    .to_bytes()
+    .map_err(|e| format!("Failed to generate PDF: {}", e))
+}
@@ -3,10 +3,19 @@ mod steps;
 
 use cucumber::World;
 use migration::{Migrator, MigratorTrait};
-use opencab::models::{
-  medical_appointments::Model as AppointmentModel, my_errors::MyErrors,
-  patients::Model as PatientModel, practitioner_offices::Model as OfficeModel,
-  users::Model as UserModel,
+use opencab::{
+  models::{
+    invoice_templates::InvoiceTemplate, medical_appointments::Model as AppointmentModel,
+    my_errors::MyErrors, patients::Model as PatientModel,
+    practitioner_offices::Model as OfficeModel,
+    retrocession_statements::Model as RetrocessionStatementModel, users::Model as UserModel,
+  },
+  services::{
+    acts::CatalogueAct, admin::UsageStats, appointments::AppointmentsExtraction,
+    check_deposits::DepositSummary, ledger::FiscalYearReport, patient_dedup::DuplicateGroup,
+    payments::UnpaidBalance, receivables::PayerReceivable, stats::ActivityStats,
+  },
+  workers::{appointments_export, mailer::args::EmailArgs},
 };
 use sea_orm::{ConnectionTrait, Database, DatabaseConnection};
 
@@ -19,6 +28,25 @@ pub struct AppWorld {
   pub crypto: CryptoState,
   pub appointments: AppointmentsState,
   pub practitioner_office: PractitionerOfficeState,
+  pub admin: AdminState,
+  pub audit: AuditState,
+  pub patient_export: PatientExportState,
+  pub patient_retention: PatientRetentionState,
+  pub patient_dedup: PatientDedupState,
+  pub patient_sharing: PatientSharingState,
+  pub patient_identity: PatientIdentityState,
+  pub receivables: ReceivablesState,
+  pub payments: PaymentsState,
+  pub check_deposits: CheckDepositsState,
+  pub ledger: LedgerState,
+  pub retrocession_statements: RetrocessionStatementsState,
+  pub stats: StatsState,
+  pub scheduled_exports: ScheduledExportsState,
+  pub acts: ActsState,
+  pub invoice_templates: InvoiceTemplatesState,
+  pub factur_x: FacturXState,
+  pub locales: LocalesState,
+  pub email_logs: EmailLogsState,
 }
 
 impl AppWorld {
@@ -30,8 +58,9 @@ impl AppWorld {
 
     // Reset all data between scenarios — equivalent of RSpec's database_cleaner
     db.execute_unprepared(
-      "TRUNCATE TABLE medical_appointments, user_practitioner_offices,
-             user_business_informations, patients, practitioner_offices, users
+      "TRUNCATE TABLE audit_logs, patient_accesses, patient_insurances, payments, check_deposits,
+             accounting_exports, expenses, retrocession_statements, medical_appointments, user_practitioner_offices,
+             scheduled_exports, act_office_prices, acts, invoice_templates, user_business_informations, patients, practitioner_offices, users
              RESTART IDENTITY CASCADE",
     )
     .await
@@ -42,6 +71,25 @@ impl AppWorld {
       crypto: CryptoState::default(),
       appointments: AppointmentsState::default(),
       practitioner_office: PractitionerOfficeState::default(),
+      admin: AdminState::default(),
+      audit: AuditState::default(),
+      patient_export: PatientExportState::default(),
+      patient_retention: PatientRetentionState::default(),
+      patient_dedup: PatientDedupState::default(),
+      patient_sharing: PatientSharingState::default(),
+      patient_identity: PatientIdentityState::default(),
+      receivables: ReceivablesState::default(),
+      payments: PaymentsState::default(),
+      check_deposits: CheckDepositsState::default(),
+      ledger: LedgerState::default(),
+      retrocession_statements: RetrocessionStatementsState::default(),
+      stats: StatsState::default(),
+      scheduled_exports: ScheduledExportsState::default(),
+      acts: ActsState::default(),
+      invoice_templates: InvoiceTemplatesState::default(),
+      factur_x: FacturXState::default(),
+      locales: LocalesState::default(),
+      email_logs: EmailLogsState::default(),
     }
   }
 }
@@ -62,6 +110,156 @@ pub struct PractitionerOfficeState {
   pub last_error: Option<MyErrors>,
 }
 
+#[derive(Debug, Default)]
+pub struct AdminState {
+  pub user: Option<UserModel>,
+  pub stats: Option<UsageStats>,
+}
+
+#[derive(Debug, Default)]
+pub struct AuditState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub change_rejected: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PatientExportState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub office: Option<OfficeModel>,
+  pub archive: Vec<u8>,
+  pub archive_name: String,
+}
+
+#[derive(Debug, Default)]
+pub struct PatientRetentionState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub purged: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct PatientDedupState {
+  pub user: Option<UserModel>,
+  pub colleague: Option<UserModel>,
+  pub patients: Vec<PatientModel>,
+  pub groups: Vec<DuplicateGroup>,
+  pub merge_failed: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PatientSharingState {
+  pub owner: Option<UserModel>,
+  pub colleague: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub emails: Vec<EmailArgs>,
+  pub consent_rejected: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PatientIdentityState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub namesakes: usize,
+  pub creation_failed: bool,
+  pub duplicate_groups: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct ReceivablesState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub office: Option<OfficeModel>,
+  pub appointment: Option<AppointmentModel>,
+  pub receivables: Vec<PayerReceivable>,
+  pub creation_failed: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PaymentsState {
+  pub user: Option<UserModel>,
+  pub office: Option<OfficeModel>,
+  pub patients: Vec<PatientModel>,
+  pub appointment: Option<AppointmentModel>,
+  pub reference: Option<String>,
+  pub payment_failed: bool,
+  pub listed: usize,
+  pub balances: Vec<UnpaidBalance>,
+}
+
+#[derive(Debug, Default)]
+pub struct CheckDepositsState {
+  pub pending: usize,
+  pub deposit: Option<DepositSummary>,
+  pub deposit_failed: bool,
+  pub slip: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+pub struct LedgerState {
+  pub expense_failed: bool,
+  pub report: Option<FiscalYearReport>,
+  pub spreadsheet: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+pub struct RetrocessionStatementsState {
+  pub statement: Option<RetrocessionStatementModel>,
+  pub pdf: Vec<u8>,
+  pub spreadsheet: Vec<u8>,
+  pub email: Option<EmailArgs>,
+}
+
+#[derive(Debug, Default)]
+pub struct StatsState {
+  pub stats: Option<ActivityStats>,
+}
+
+#[derive(Debug, Default)]
+pub struct ActsState {
+  pub catalogue: Vec<CatalogueAct>,
+  pub rejected: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ScheduledExportsState {
+  pub due: Vec<appointments_export::Args>,
+  pub rejected: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct InvoiceTemplatesState {
+  pub template: Option<InvoiceTemplate>,
+  pub logo_file_name: Option<String>,
+  pub rejected: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct FacturXState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub office: Option<OfficeModel>,
+  pub pdf: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+pub struct LocalesState {
+  pub user: Option<UserModel>,
+  pub patient: Option<PatientModel>,
+  pub office: Option<OfficeModel>,
+  pub filename: Option<String>,
+  pub email: Option<EmailArgs>,
+}
+
+#[derive(Debug, Default)]
+pub struct EmailLogsState {
+  pub delivery_failed: bool,
+  /// Invoice email as first sent, to compare with its resend
+  pub first_email: Option<EmailArgs>,
+  pub resend_rejected: bool,
+}
+
 #[derive(Debug, Default)]
 pub struct AppointmentsState {
   pub user: Option<UserModel>,
@@ -69,7 +267,10 @@ pub struct AppointmentsState {
   pub office: Option<OfficeModel>,
   pub second_office: Option<OfficeModel>,
   pub appointment: Option<AppointmentModel>,
-  pub extracted: Vec<(AppointmentModel, PatientModel, OfficeModel, f64)>,
+  pub extracted: AppointmentsExtraction,
+  pub revenue_share_change_failed: bool,
+  pub export: String,
+  pub download: Option<(String, Vec<u8>)>,
 }
 
 impl AppointmentsState {
@@ -87,6 +288,7 @@ async fn main() {
     std::env::var("TEST_DATABASE_URL").unwrap_or_else(|_| DEFAULT_TEST_DATABASE_URL.to_string());
   let db = Database::connect(&db_url).await.unwrap();
   Migrator::up(&db, None).await.unwrap();
+  opencab::initializers::app_services::init_services(&db);
 
   AppWorld::cucumber()
     .max_concurrent_scenarios(1)
@@ -1,25 +1,30 @@
 use chrono::NaiveDate;
 use opencab::models::{
-  _entities::sea_orm_active_enums::PaymentMethod,
+  _entities::{acts, sea_orm_active_enums::PaymentMethod},
   medical_appointments::{
     ActiveModel as AppointmentActiveModel, CreateMedicalAppointmentParams,
-    Model as AppointmentModel,
+    Model as AppointmentModel, PayerShares,
   },
+  my_errors::MyErrors,
 };
 use sea_orm::DatabaseConnection;
 
 pub struct AppointmentFactory {
   date: NaiveDate,
+  act: Option<acts::Model>,
   price_in_cents: i32,
   payment_method: Option<PaymentMethod>,
+  payer_shares: PayerShares,
 }
 
 impl Default for AppointmentFactory {
   fn default() -> Self {
     Self {
       date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+      act: None,
       price_in_cents: 5000,
       payment_method: None,
+      payer_shares: PayerShares::default(),
     }
   }
 }
@@ -34,6 +39,11 @@ impl AppointmentFactory {
     self
   }
 
+  pub fn act(mut self, act: acts::Model) -> Self {
+    self.act = Some(act);
+    self
+  }
+
   pub fn price(mut self, price_in_cents: i32) -> Self {
     self.price_in_cents = price_in_cents;
     self
@@ -44,6 +54,16 @@ impl AppointmentFactory {
     self
   }
 
+  pub fn amo_share(mut self, amo_share_in_cents: i32) -> Self {
+    self.payer_shares.amo_share_in_cents = amo_share_in_cents;
+    self
+  }
+
+  pub fn mutuelle_share(mut self, mutuelle_share_in_cents: i32) -> Self {
+    self.payer_shares.mutuelle_share_in_cents = mutuelle_share_in_cents;
+    self
+  }
+
   pub async fn create(
     self,
     db: &DatabaseConnection,
@@ -51,6 +71,19 @@ impl AppointmentFactory {
     patient_id: i32,
     office_id: i32,
   ) -> AppointmentModel {
+    self
+      .try_create(db, user_id, patient_id, office_id)
+      .await
+      .unwrap()
+  }
+
+  pub async fn try_create(
+    self,
+    db: &DatabaseConnection,
+    user_id: i32,
+    patient_id: i32,
+    office_id: i32,
+  ) -> Result<AppointmentModel, MyErrors> {
     AppointmentActiveModel::create(
       db,
       &CreateMedicalAppointmentParams {
@@ -58,11 +91,12 @@ impl AppointmentFactory {
         patient_id,
         practitioner_office_id: office_id,
         date: self.date,
+        act: self.act,
         price_in_cents: self.price_in_cents,
         payment_method: self.payment_method,
+        payer_shares: self.payer_shares,
       },
     )
     .await
-    .unwrap()
   }
 }
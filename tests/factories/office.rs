@@ -39,6 +39,7 @@ impl OfficeFactory {
         address_line_1: self.address_line_1,
         address_zip_code: self.address_zip_code,
         address_city: self.address_city,
+        contact_email: None,
       },
     )
     .await
@@ -1,5 +1,7 @@
-use opencab::models::patients::{
-  ActiveModel as PatientActiveModel, CreatePatientParams, Model as PatientModel,
+use opencab::models::{
+  _entities::sea_orm_active_enums::{Locale, PatientSex},
+  my_errors::MyErrors,
+  patients::{ActiveModel as PatientActiveModel, CreatePatientParams, Model as PatientModel},
 };
 use sea_orm::DatabaseConnection;
 
@@ -11,6 +13,11 @@ pub struct PatientFactory {
   address_line_1: String,
   address_zip_code: String,
   address_city: String,
+  birth_date: Option<String>,
+  phone_number: Option<String>,
+  sex: Option<PatientSex>,
+  referring_doctor: Option<String>,
+  locale: Option<Locale>,
 }
 
 impl Default for PatientFactory {
@@ -23,6 +30,11 @@ impl Default for PatientFactory {
       address_line_1: "2 avenue des Champs".to_string(),
       address_zip_code: "75008".to_string(),
       address_city: "Paris".to_string(),
+      birth_date: None,
+      phone_number: None,
+      sex: None,
+      referring_doctor: None,
+      locale: None,
     }
   }
 }
@@ -42,7 +54,45 @@ impl PatientFactory {
     self
   }
 
+  pub fn ssn(mut self, ssn: &str) -> Self {
+    self.ssn = ssn.to_string();
+    self
+  }
+
+  pub fn birth_date(mut self, birth_date: &str) -> Self {
+    self.birth_date = Some(birth_date.to_string());
+    self
+  }
+
+  pub fn phone_number(mut self, phone_number: &str) -> Self {
+    self.phone_number = Some(phone_number.to_string());
+    self
+  }
+
+  pub fn sex(mut self, sex: PatientSex) -> Self {
+    self.sex = Some(sex);
+    self
+  }
+
+  pub fn referring_doctor(mut self, referring_doctor: &str) -> Self {
+    self.referring_doctor = Some(referring_doctor.to_string());
+    self
+  }
+
+  pub fn locale(mut self, locale: Locale) -> Self {
+    self.locale = Some(locale);
+    self
+  }
+
   pub async fn create(self, db: &DatabaseConnection, user_id: i32) -> PatientModel {
+    self.try_create(db, user_id).await.unwrap()
+  }
+
+  pub async fn try_create(
+    self,
+    db: &DatabaseConnection,
+    user_id: i32,
+  ) -> Result<PatientModel, MyErrors> {
     let params = CreatePatientParams {
       first_name: self.first_name,
       last_name: self.last_name,
@@ -51,11 +101,14 @@ impl PatientFactory {
       address_zip_code: self.address_zip_code,
       address_city: self.address_city,
       email: self.email,
+      birth_date: self.birth_date,
+      phone_number: self.phone_number,
+      sex: self.sex,
+      referring_doctor: self.referring_doctor,
+      locale: self.locale,
       pid: None,
     };
 
-    PatientActiveModel::create(db, &params, user_id)
-      .await
-      .unwrap()
+    PatientActiveModel::create(db, &params, user_id).await
   }
 }
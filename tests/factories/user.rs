@@ -26,6 +26,11 @@ impl UserFactory {
     Self::default()
   }
 
+  pub fn email(mut self, email: &str) -> Self {
+    self.email = email.to_string();
+    self
+  }
+
   pub async fn create(self, db: &DatabaseConnection) -> UserModel {
     UserModel::create_with_password(
       db,
@@ -0,0 +1,80 @@
+Feature: Act catalogue
+  As a practitioner
+  I want a catalogue of the acts I perform with their prices
+  In order to stop typing the price of each appointment
+
+  Background:
+    Given a practitioner exists
+    And a practitioner office "Cabinet Central" exists with revenue share 70
+    And a second office "Cabinet Sud" exists with revenue share 50
+    And a patient "Alice" "Dupont" exists
+    And an act "Consultation" coded "gs" at 2500 cents
+
+  Rule: Appointments take the price of their act
+
+    Scenario: An appointment of an act costs its default price
+      When I create an appointment on "2026-03-10" for act "Consultation"
+      Then the appointment costs 2500 cents for act "Consultation (GS)"
+
+    Scenario: An office can have its own price for an act
+      Given an act "Séance" coded "amk 8" at 4000 cents, and 5000 cents at office "Cabinet Sud"
+      When I create an appointment on "2026-03-10" for act "Séance" at office "Cabinet Sud"
+      Then the appointment costs 5000 cents for act "Séance (AMK 8)"
+      When I create an appointment on "2026-03-11" for act "Séance" at office "Cabinet Central"
+      Then the appointment costs 4000 cents for act "Séance (AMK 8)"
+
+    Scenario: A price typed in wins over the catalogue
+      When I create an appointment on "2026-03-10" for act "Consultation" at price 3000
+      Then the appointment costs 3000 cents for act "Consultation (GS)"
+
+    Scenario: An appointment needs either a price or an act
+      When I try to create an appointment on "2026-03-10" without price nor act
+      Then the appointment without price is rejected
+
+    Scenario: An appointment can only be created in an office I work in
+      When I try to create an appointment on "2026-03-10" for act "Consultation" in an office I do not work in
+      Then the appointment in another office is rejected
+
+    Scenario: Deleting an act keeps the price of its appointments
+      Given I create an appointment on "2026-03-10" for act "Consultation"
+      When I delete the act "Consultation"
+      Then the appointment costs 2500 cents without act
+      And the appointment is still labelled "Consultation (GS)"
+
+    Scenario: Renaming an act leaves its past appointments as they were
+      Given I create an appointment on "2026-03-10" for act "Consultation"
+      When I rename the act "Consultation" to "Consultation longue" coded "g"
+      Then the appointment costs 2500 cents for act "Consultation (GS)"
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      And I export the extracted appointments as "csv"
+      Then the export line 1 reads "2026-03-10;Cabinet Central;Consultation (GS);Dupont;Alice;;25,00;7,50;17,50"
+
+  Rule: The catalogue is checked
+
+    Scenario: An act cannot be priced twice in the same office
+      When I try to add an act "Bilan" at 6000 cents priced twice at office "Cabinet Sud"
+      Then the act is rejected
+
+    Scenario: An act needs a positive price
+      When I try to add an act "Bilan" at 0 cents
+      Then the act is rejected
+
+  Rule: Exports are grouped by act
+
+    Scenario: Extracted fees are summed up by act
+      Given an act "Séance" coded "amk 8" at 4000 cents, and 5000 cents at office "Cabinet Sud"
+      And I create an appointment on "2026-03-10" for act "Consultation"
+      And I create an appointment on "2026-03-11" for act "Consultation"
+      And I create an appointment on "2026-03-12" for act "Séance" at office "Cabinet Sud"
+      And an appointment on "2026-03-13" at price 6000
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      Then the extracted fees of act "Consultation (GS)" are 5000 cents over 2 appointments
+      And the extracted fees of act "Séance (AMK 8)" are 5000 cents over 1 appointment
+      And the extracted fees without act are 6000 cents over 1 appointment
+      And the acts come in the order "Consultation (GS)", "Séance (AMK 8)" and then without act
+
+    Scenario: The CSV export names the act of each appointment
+      Given I create an appointment on "2026-03-10" for act "Consultation"
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      And I export the extracted appointments as "csv"
+      Then the export line 1 reads "2026-03-10;Cabinet Central;Consultation (GS);Dupont;Alice;;25,00;7,50;17,50"
@@ -0,0 +1,27 @@
+Feature: Back-office administration
+  As an administrator
+  I want to manage practitioner accounts
+  In order to operate the platform without touching the database
+
+  Background:
+    Given a practitioner account "doctor@test.com" exists
+
+  Rule: Accounts can be suspended and approved
+
+    Scenario: Suspending an account
+      When an administrator suspends the account
+      Then the account is suspended
+
+    Scenario: Approving a suspended account restores access
+      Given the account is suspended by an administrator
+      When an administrator approves the account
+      Then the account is not suspended
+      And the account access key is verified
+
+  Rule: Usage statistics only expose counts
+
+    Scenario: Usage statistics count users and patients
+      Given a practitioner account "other@test.com" exists
+      And the practitioner has 2 patients
+      When an administrator fetches the usage statistics
+      Then the statistics report 2 users, 2 pending users and 2 patients
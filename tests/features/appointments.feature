@@ -51,3 +51,118 @@ Feature: Medical appointment management
       Then 2 appointments are returned
       And the extracted appointment for office "Cabinet Central" has a revenue share of 70.0
       And the extracted appointment for office "Cabinet Sud" has a revenue share of 50.0
+
+  Rule: Extracted appointments use the revenue share in force on their date
+
+    Scenario: A new revenue share only applies from its start date
+      Given an appointment on "2026-03-10" at price 10000
+      And an appointment on "2026-05-10" at price 10000
+      And the revenue share becomes 60 from "2026-04-01"
+      When I extract appointments between "2026-03-01" and "2026-05-31"
+      Then the extracted appointment on "2026-03-10" has a revenue share of 70.0
+      And the extracted appointment on "2026-05-10" has a revenue share of 60.0
+
+    Scenario: The revenue share of an exported period cannot change
+      Given an appointment on "2026-03-10" at price 10000
+      And the appointments between "2026-03-01" and "2026-03-31" have been exported
+      When I try to change the revenue share to 60 from "2026-03-15"
+      Then the revenue share change is rejected
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      Then the first extracted appointment has a revenue share of 70.0
+
+    Scenario: A revenue share starting after the exported period can be set
+      Given the appointments between "2026-03-01" and "2026-03-31" have been exported
+      When I try to change the revenue share to 60 from "2026-04-01"
+      Then the revenue share change is accepted
+
+  Rule: Extracted appointments sum up the retrocession owed each month
+
+    Scenario: A capped revenue share never owes more than its cap
+      Given the revenue share becomes 20 capped at 3000 cents from "2026-03-01"
+      And an appointment on "2026-03-10" at price 10000
+      And an appointment on "2026-03-20" at price 10000
+      And an appointment on "2026-04-10" at price 10000
+      When I extract appointments between "2026-03-01" and "2026-04-30"
+      Then the extracted retrocession of "2026-03" is 3000 cents
+      And the extracted retrocession of "2026-04" is 2000 cents
+
+    Scenario: A fixed fee is owed for months without appointments
+      Given the retrocession becomes a fixed fee of 50000 cents from "2026-03-01"
+      And an appointment on "2026-03-10" at price 10000
+      When I extract appointments between "2026-03-01" and "2026-04-30"
+      Then the extracted fees of "2026-03" are 10000 cents, of which 50000 cents of retrocession
+      And the extracted fees of "2026-04" are 0 cents, of which 50000 cents of retrocession
+
+    Scenario: A month split between two extractions is only charged once
+      Given the retrocession becomes a fixed fee of 30000 cents from "2026-03-01"
+      When I extract appointments between "2026-04-01" and "2026-04-12"
+      Then the extracted retrocession of "2026-04" is 12000 cents
+      When I extract appointments between "2026-04-13" and "2026-04-30"
+      Then the extracted retrocession of "2026-04" is 18000 cents
+
+    Scenario: The cap of a partial month is prorated
+      Given the revenue share becomes 20 capped at 3000 cents from "2026-03-01"
+      And an appointment on "2026-04-10" at price 50000
+      When I extract appointments between "2026-04-01" and "2026-04-15"
+      Then the extracted retrocession of "2026-04" is 1500 cents
+
+    Scenario: Extracted appointments are summed up by month and payment method
+      Given an appointment on "2026-03-10" at price 10000 with payment "card"
+      And an appointment on "2026-03-20" at price 5000 with payment "cash"
+      And an appointment on "2026-04-02" at price 4000 with payment "card"
+      When I extract appointments between "2026-03-01" and "2026-04-30"
+      Then the extracted fees of "2026-03" are 15000 cents, of which 10500 cents of retrocession
+      And the extracted fees of "2026-04" are 4000 cents, of which 2800 cents of retrocession
+      And the extracted fees paid by "card" are 14000 cents over 2 appointments
+      And the extracted fees paid by "cash" are 5000 cents over 1 appointment
+
+  Rule: Extracted appointments can be exported for accounting software
+
+    Scenario: The spreadsheet can be downloaded right away
+      Given an appointment on "2026-03-10" at price 10000
+      When I download the appointments between "2026-03-01" and "2026-03-31"
+      Then the downloaded spreadsheet is named "appointments_from_01-03-2026_to_31-03-2026.xlsx"
+
+    Scenario: A month with only a fixed fee can be downloaded as a spreadsheet
+      Given the retrocession becomes a fixed fee of 50000 cents from "2026-03-01"
+      When I download the appointments between "2026-04-01" and "2026-04-30"
+      Then the downloaded spreadsheet is named "appointments_from_01-04-2026_to_30-04-2026.xlsx"
+
+    Scenario: The CSV export has a line per appointment
+      Given an appointment on "2026-03-10" at price 10000
+      And an appointment on "2026-03-20" at price 4500
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      And I export the extracted appointments as "csv"
+      Then the export has 3 lines
+      And the export line 1 reads "2026-03-10;Cabinet Central;;Dupont;Alice;;100,00;30,00;70,00"
+
+    Scenario: A retrocession above the price is exported with its sign
+      Given the retrocession becomes 4550 cents per appointment from "2026-03-01"
+      And an appointment on "2026-03-10" at price 4500
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      And I export the extracted appointments as "csv"
+      Then the export line 1 reads "2026-03-10;Cabinet Central;;Dupont;Alice;;45,00;-0,50;45,50"
+
+    Scenario: The FEC export records the fees and the retrocession owed
+      Given an appointment on "2026-03-10" at price 10000
+      When I extract appointments between "2026-03-01" and "2026-03-31"
+      And I export the extracted appointments as "fec"
+      Then the export has 5 lines
+      And the export line 1 debits "100,00" on account "411000"
+      And the export line 2 credits "100,00" on account "706000"
+      And the export line 3 debits "70,00" on account "622600"
+      And the export line 4 credits "70,00" on account "401000"
+
+    Scenario: The FEC export is named after the SIREN and the closing date
+      Given the practitioner's SIRET is "12345678900012"
+      And an appointment on "2026-03-10" at price 10000
+      When I download the "fec" export between "2026-03-01" and "2026-03-31"
+      Then the downloaded export is named "123456789FEC20260331.txt"
+
+    Scenario: The FEC export books the fixed fee of months without appointments
+      Given the retrocession becomes a fixed fee of 50000 cents from "2026-03-01"
+      When I extract appointments between "2026-04-01" and "2026-04-30"
+      And I export the extracted appointments as "fec"
+      Then the export has 3 lines
+      And the export line 1 debits "500,00" on account "622600"
+      And the export line 2 credits "500,00" on account "401000"
@@ -0,0 +1,35 @@
+Feature: Audit trail of patient data access
+  As a practitioner
+  I want every access to patient records to be traced
+  In order to comply with GDPR and HDS requirements
+
+  Background:
+    Given a practitioner with a patient for audit tests
+
+  Rule: Accesses are appended to the audit trail
+
+    Scenario: Reading a patient is recorded
+      When the practitioner reads the patient record
+      Then the audit trail contains 1 granted "read" entry for "patient"
+
+    Scenario: A denied access is recorded as such
+      Given another practitioner exists for audit tests
+      When the other practitioner tries to read the patient record
+      Then the audit trail of the other practitioner contains 1 denied "read" entry for "patient"
+
+    Scenario: Audit entries cannot be modified
+      Given the practitioner reads the patient record
+      When I try to modify the audit entry
+      Then the modification is rejected
+
+    Scenario: Audit entries cannot be deleted
+      Given the practitioner reads the patient record
+      When I try to delete the audit entry
+      Then the deletion is rejected
+      And the audit trail still contains 1 entry
+
+    Scenario: Deleting a practitioner does not wipe their audit trail
+      Given the practitioner reads the patient record
+      When I try to delete the practitioner
+      Then the deletion is rejected
+      And the audit trail still contains 1 entry
@@ -0,0 +1,55 @@
+Feature: Check deposit slips
+  As a practitioner
+  I want to print the deposit slip of the checks I hand to my bank
+  In order to stop copying each check by hand on the bank's form
+
+  Background:
+    Given a practitioner with an office for payments
+    And a patient "Claire" "Dubois" for payments
+    And a patient "Paul" "Martin" for payments
+
+  Rule: Only checks not yet deposited are gathered on a slip
+
+    Scenario: Pending checks are listed
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 6000 cents by check "0001234" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-04"
+      And the patient pays 5000 cents by transfer on "2026-03-04"
+      When I list the checks to deposit
+      Then 1 check is waiting to be deposited
+
+    Scenario: A check taken when generating an invoice is listed
+      Given I invoice 6000 cents to "Dubois" on "2026-03-02", paid by check
+      When I list the checks to deposit
+      Then 1 check is waiting to be deposited
+
+    Scenario: Depositing the checks prints a numbered slip
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 6000 cents by check "0001234" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-04"
+      And the patient pays 2000 cents by check "0009876" on "2026-03-04"
+      When I deposit the checks on "2026-03-09"
+      Then the deposit "REM-2026-001" holds 2 checks for 8000 cents
+      And the deposit slip is a PDF document
+      When I list the checks to deposit
+      Then 0 check is waiting to be deposited
+
+    Scenario: Slips are numbered per year
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 6000 cents by check "0001234" on "2026-03-02"
+      And I deposit the checks on "2026-03-09"
+      And an appointment of 5000 cents for "Martin" on "2026-03-11"
+      And the patient pays 5000 cents by check "0009876" on "2026-03-11"
+      When I deposit the checks on "2026-03-16"
+      Then the deposit "REM-2026-002" holds 1 checks for 5000 cents
+
+    Scenario: There is nothing to deposit
+      When I deposit the checks on "2026-03-09"
+      Then the deposit is rejected
+
+    Scenario: A slip can be printed again
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 6000 cents by check "0001234" on "2026-03-02"
+      And I deposit the checks on "2026-03-09"
+      When I print the slip of the deposit again
+      Then the deposit slip is a PDF document
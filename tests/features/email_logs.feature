@@ -0,0 +1,42 @@
+Feature: Outbound email log
+  As a practitioner
+  I want to know which emails were sent to my patients and whether they left
+  In order to answer a patient who never got their invoice
+
+  Background:
+    Given a general practitioner with an office
+    And a patient speaking "fr"
+    And the mail server is unreachable
+    And I invoice the patient 5000 cents on "2026-03-15"
+
+  Scenario: Every email sent about a patient is kept in their history
+    When the invoice email is processed
+    Then the patient has 1 email in their history
+    And the last email of the patient is a "invoice" email titled "Note d'honoraires 15/03/2026"
+    And the last email of the patient was sent to "patient@test.com"
+    And the last email of the patient has the attachment "John DOE Note d'honoraires - Dupont Alice 15_03_2026.pdf"
+    And the last email of the patient is tied to its appointment
+
+  Scenario: A failed delivery is kept with the SMTP error
+    When the invoice email is processed
+    Then the last email of the patient failed with an error
+
+  Scenario: An invoice is resent as it was first sent
+    Given the invoice email is processed
+    And the patient's email becomes "alice@new.test"
+    And the patient now speaks "en"
+    When I resend the last email of the patient
+    Then the invoice email is sent to "patient@test.com"
+    And the resent invoice is the one first sent
+    And the invoice file is named "John DOE Note d'honoraires - Dupont Alice 15_03_2026.pdf"
+    And the invoice email subject is "Note d'honoraires 15/03/2026"
+
+  Scenario: Other practitioners cannot resend an invoice
+    Given the invoice email is processed
+    When another practitioner resends the last email of the patient
+    Then the resend is rejected
+
+  Scenario: The history goes away with the patient's identity
+    Given the invoice email is processed
+    When the patient is anonymized
+    Then the patient has 0 email in their history
@@ -0,0 +1,30 @@
+Feature: HTML email templates
+  As a practitioner
+  I want the emails sent on my behalf to be formatted and signed
+  In order to look professional while staying readable in any mail client
+
+  Background:
+    Given a general practitioner with an office
+    And a patient speaking "fr"
+
+  Scenario: Invoice emails have an HTML body and a plain text fallback
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice email HTML contains "Vous trouverez ci-joint votre facture pour la consultation du 15/03/2026</p>"
+    And the invoice email HTML contains "Doe John<br>MÉDECIN GÉNÉRALISTE<br>"
+    And the invoice email body reads "Vous trouverez ci-joint votre facture pour la consultation du 15/03/2026"
+    And the invoice email is signed "MÉDECIN GÉNÉRALISTE"
+
+  Scenario: Practitioners sign their emails their own way
+    Given the practitioner signs their emails "Cabinet Doe & associés"
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice email HTML contains "Cabinet Doe &amp; associés</p>"
+    And the invoice email text ends with "Cabinet Doe & associés"
+
+  Scenario: Invoice emails take the colour of the practitioner's notes
+    Given the practitioner's notes are coloured "#1a2b3c"
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice email HTML contains "border-top: 4px solid #1A2B3C"
+
+  Scenario: Account emails link to the platform
+    Then the access code email HTML contains "https://opencab.test/login</a>"
+    And the access code email text contains "https://opencab.test/login"
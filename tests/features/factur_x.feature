@@ -0,0 +1,32 @@
+Feature: Factur-X invoices
+  As a practitioner
+  I want my notes d'honoraires to carry their data in a machine-readable form
+  In order to comply with e-invoicing and spare my accountant the typing
+
+  Background:
+    Given a practitioner with SIRET "12345678900012" and a patient named "Alice" "Dupont"
+
+  Scenario: The invoice is a PDF/A-3 embedding its Factur-X data
+    When I generate the invoice of an appointment on "2026-03-15" for 5000 cents
+    Then the invoice declares PDF/A-3 conformance
+    And the invoice embeds the fonts it is written with
+    And the invoice embeds "factur-x.xml"
+    And the embedded Factur-X XML is valid
+
+  Scenario: The Factur-X data describes the seller, the buyer, the amounts and the dates
+    When I generate the invoice of an appointment on "2026-03-15" for 5050 cents
+    Then the Factur-X "profile" is "urn:factur-x.eu:1p0:basicwl"
+    And the Factur-X "issue date" is "20260315"
+    And the Factur-X "seller" is "John Doe"
+    And the Factur-X "seller SIRET" is "12345678900012"
+    And the Factur-X "seller tax registration" is "12345678900012"
+    And the Factur-X "buyer" is "Dupont Alice"
+    And the Factur-X "grand total" is "50.50"
+    And the Factur-X "amount due" is "0.00"
+    And the Factur-X "VAT exemption reason" is "TVA non applicable, art. 261-4-1° du CGI"
+
+  Scenario: Names are escaped in the XML
+    Given a practitioner with SIRET "12345678900012" and a patient named "Zoé" "Martin & <Fils>"
+    When I generate the invoice of an appointment on "2026-03-15" for 5000 cents
+    Then the embedded Factur-X XML is valid
+    And the Factur-X "buyer" is "Martin & <Fils> Zoé"
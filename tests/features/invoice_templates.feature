@@ -0,0 +1,66 @@
+Feature: Invoice templates
+  As a practitioner
+  I want to customise the look and mentions of my notes d'honoraires
+  In order to hand out invoices carrying my own branding
+
+  Background:
+    Given a practitioner exists
+
+  Scenario: Practitioners start with the default template
+    When I look at my invoice template
+    Then my invoice title is "Note d'honoraires acquittée"
+    And my invoice has no additional legal mentions
+    And my invoice footer ends with "TVA non applicable, art. 261-4-1° du CGI"
+    And the social security number is shown on my invoices
+
+  Scenario: The VAT exemption mention is printed whatever the legal mentions
+    Given I save an invoice template with the legal mentions "  "
+    When I look at my invoice template
+    Then my invoice has no additional legal mentions
+    And my invoice footer ends with "TVA non applicable, art. 261-4-1° du CGI"
+
+  Scenario: Additional legal mentions come on top of the VAT exemption mention
+    Given I save an invoice template with the legal mentions "Membre d'une association agréée"
+    When I look at my invoice template
+    Then my invoice legal mentions are "Membre d'une association agréée"
+    And my invoice footer is printed on 2 lines of at most 90 characters
+    And my invoice footer ends with "TVA non applicable, art. 261-4-1° du CGI"
+
+  Scenario: Long header and footer texts are wrapped to fit the page
+    Given I save an invoice template with a header and a footer of 500 characters
+    When I look at my invoice template
+    Then my invoice header is printed on 8 lines of at most 32 characters
+    And my invoice footer is printed on 6 lines of at most 90 characters
+    And my invoice footer ends with "TVA non applicable, art. 261-4-1° du CGI"
+
+  Scenario: A customised template is kept
+    Given I save an invoice template titled "Facture" in "#1a2b3c" with footer "Règlement à réception" hiding the social security number
+    When I look at my invoice template
+    Then my invoice title is "Facture"
+    And my invoice accent colour is "#1A2B3C"
+    And my invoice footer is "Règlement à réception"
+    And the social security number is hidden on my invoices
+
+  Scenario: Saving the template again updates it
+    Given I save an invoice template titled "Facture" in "#1a2b3c" with footer "Règlement à réception" hiding the social security number
+    And I save an invoice template titled "Note d'honoraires" in "#000000" with footer "" hiding the social security number
+    When I look at my invoice template
+    Then my invoice title is "Note d'honoraires"
+    And my invoice has no footer
+    And I have 1 invoice template
+
+  Scenario: Uploading a logo keeps the rest of the template
+    Given my invoice logo is "logo_1.png"
+    And I save an invoice template titled "Facture" in "#1a2b3c" with footer "" hiding the social security number
+    When I look at my invoice template
+    Then my invoice title is "Facture"
+    And my invoice logo is "logo_1.png"
+
+  Scenario: An invalid accent colour is rejected
+    When I save an invoice template titled "Facture" in "blue" with footer "" hiding the social security number
+    Then the invoice template is rejected
+    And I have 0 invoice template
+
+  Scenario: A blank title is rejected
+    When I save an invoice template titled "  " in "#000000" with footer "" hiding the social security number
+    Then the invoice template is rejected
@@ -0,0 +1,57 @@
+Feature: Receipts and expenses ledger
+  As a practitioner
+  I want my receipts and expenses summed up per fiscal year
+  In order to fill in my 2035 declaration
+
+  Background:
+    Given a practitioner with an office for payments
+    And a patient "Claire" "Dubois" for payments
+
+  Rule: Receipts are counted when the money is received
+
+    Scenario: Payments are listed in the receipts book of their year
+      Given an appointment of 6000 cents for "Dubois" on "2025-12-29"
+      And the patient pays 6000 cents by check "0001234" on "2026-01-05"
+      And an appointment of 5000 cents for "Dubois" on "2026-02-02"
+      And the patient pays 5000 cents by transfer on "2026-02-02"
+      When I build the fiscal year report of 2026
+      Then the receipts book holds 2 receipts for 11000 cents
+
+    Scenario: Unpaid appointments are not receipts
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      When I build the fiscal year report of 2026
+      Then the receipts book holds 0 receipts for 0 cents
+
+  Rule: Expenses are summed up by 2035 category
+
+    Scenario: Expenses of the same category are added up
+      Given an appointment of 10000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 10000 cents by transfer on "2026-03-02"
+      And an expense "Loyer mars" of 3000 cents in "Rent" on "2026-03-01"
+      And an expense "Loyer avril" of 3000 cents in "Rent" on "2026-04-01"
+      And an expense "Cotisation ordre" of 1000 cents in "Memberships" on "2026-01-15"
+      And an expense "Loyer décembre" of 3000 cents in "Rent" on "2025-12-01"
+      When I build the fiscal year report of 2026
+      Then the expenses in "Rent" amount to 6000 cents
+      And the expenses in "Memberships" amount to 1000 cents
+      And the total expenses amount to 7000 cents
+      And the result amounts to 3000 cents
+
+    Scenario: Retroceded fees are deducted from the receipts
+      Given an appointment of 10000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 10000 cents by transfer on "2026-03-02"
+      And an expense "Rétrocession mars" of 2000 cents in "Retrocession" on "2026-03-31"
+      When I build the fiscal year report of 2026
+      Then the net receipts amount to 8000 cents
+      And the total expenses amount to 0 cents
+      And the result amounts to 8000 cents
+
+    Scenario: An expense needs a positive amount
+      When I record an expense "Fournitures" of 0 cents in "Supplies" on "2026-03-01"
+      Then the expense is rejected
+
+    Scenario: The report can be downloaded as a spreadsheet
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And the patient pays 6000 cents by check "0001234" on "2026-03-02"
+      When I download the fiscal year report of 2026
+      Then the report is a spreadsheet
@@ -0,0 +1,39 @@
+Feature: Multilingual invoices and emails
+  As a practitioner
+  I want invoices and emails in the language of the person receiving them
+  In order to serve my foreign patients
+
+  Background:
+    Given a general practitioner with an office
+
+  Scenario: An English-speaking patient receives an invoice in English
+    Given a patient speaking "en"
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice file is named "John DOE Fee note - Dupont Alice 15_03_2026.pdf"
+    And the invoice email subject is "Fee note 15/03/2026"
+    And the invoice email body reads "Please find attached your invoice for the consultation of 15/03/2026"
+    And the invoice email is signed "GENERAL PRACTITIONER"
+
+  Scenario: A patient without a language gets the practitioner's one
+    Given a patient without a language
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice file is named "John DOE Note d'honoraires - Dupont Alice 15_03_2026.pdf"
+    And the invoice email subject is "Note d'honoraires 15/03/2026"
+    And the invoice email body reads "Vous trouverez ci-joint votre facture pour la consultation du 15/03/2026"
+    And the invoice email is signed "MÉDECIN GÉNÉRALISTE"
+
+  Scenario: An English-speaking practitioner invoices their patients in English by default
+    Given the practitioner switches to "en"
+    And a patient without a language
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice email subject is "Fee note 15/03/2026"
+
+  Scenario: The patient's language prevails over the practitioner's
+    Given the practitioner switches to "en"
+    And a patient speaking "fr"
+    When I invoice the patient 5000 cents on "2026-03-15"
+    Then the invoice email subject is "Note d'honoraires 15/03/2026"
+
+  Scenario: Account emails follow the practitioner's language
+    Given the practitioner switches to "en"
+    Then the access code email subject is "Your OpenCab access code"
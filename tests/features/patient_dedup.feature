@@ -0,0 +1,57 @@
+Feature: Patient deduplication and merge
+  As a practitioner
+  I want to find and merge duplicate patient records
+  In order to keep a single record per patient
+
+  Background:
+    Given a practitioner for deduplication tests
+
+  Rule: Duplicates are detected by social security number or similar names
+
+    Scenario: Patients sharing a social security number are duplicates
+      Given a patient "Alice" "Dupont" with social security number "1234567890123"
+      And a patient "Alicia" "Durand" with social security number "1234567890123"
+      When I look for duplicate patients
+      Then I find 1 group of "same_ssn" duplicates with 2 patients
+
+    Scenario: Patients whose names only differ by accents and case are duplicates
+      Given a patient "Hélène" "Lefèvre" with social security number "1111111111111"
+      And a patient "helene" "LEFEVRE" with social security number "2222222222222"
+      When I look for duplicate patients
+      Then I find 1 group of "similar_name" duplicates with 2 patients
+
+    Scenario: Distinct patients are not reported
+      Given a patient "Alice" "Dupont" with social security number "1111111111111"
+      And a patient "Bob" "Martin" with social security number "2222222222222"
+      When I look for duplicate patients
+      Then I find no duplicates
+
+  Rule: Merging moves appointments onto the surviving record
+
+    Scenario: Appointments of the duplicate are moved and the duplicate is removed
+      Given a patient "Alice" "Dupont" with social security number "1234567890123"
+      And a patient "alice" "dupont" with social security number "1234567890123"
+      And each patient has 1 appointment
+      When I merge the second patient into the first one
+      Then the first patient has 2 appointments
+      And the second patient no longer exists
+
+    Scenario: Colleagues keep their access to the merged record
+      Given a patient "Alice" "Dupont" with social security number "1234567890123"
+      And a patient "alice" "dupont" with social security number "1234567890123"
+      And a colleague with a granted access to the second patient
+      When I merge the second patient into the first one
+      Then the colleague still has a granted access to the first patient
+
+    Scenario: An access held on both records is kept once
+      Given a patient "Alice" "Dupont" with social security number "1234567890123"
+      And a patient "alice" "dupont" with social security number "1234567890123"
+      And a colleague with a granted access to the first patient
+      And a colleague with a granted access to the second patient
+      When I merge the second patient into the first one
+      Then the colleague still has a granted access to the first patient
+
+    Scenario: A patient cannot be merged into itself
+      Given a patient "Alice" "Dupont" with social security number "1234567890123"
+      When I merge the first patient into itself
+      Then the merge is rejected
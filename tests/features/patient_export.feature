@@ -0,0 +1,31 @@
+Feature: GDPR export of patient data
+  As a practitioner
+  I want to export everything I hold about a patient
+  In order to answer their right of access request
+
+  Background:
+    Given a practitioner with a patient to export
+
+  Rule: The archive contains a machine-readable document of the patient data
+
+    Scenario: Identity is exported with the decrypted social security number
+      When I export the patient data
+      Then the archive contains "patient.json"
+      And the exported social security number is "1234567890123"
+
+    Scenario: The archive name only keeps plain letters of the patient's name
+      Given the patient is named "Zoé Anne" "D'Artagnan-Lefèvre"
+      When I export the patient data
+      Then the archive is named "export_d_artagnan_lefevre_zoe_anne" followed by the date
+
+    Scenario: Appointments of the practitioner are exported in chronological order
+      Given the patient had an appointment on "2026-02-10" for 6000 cents
+      And the patient had an appointment on "2026-01-05" for 5000 cents
+      When I export the patient data
+      Then the export lists 2 appointments
+      And the first exported appointment is on "2026-01-05"
+
+    Scenario: No invoice is included without business information
+      Given the patient had an appointment on "2026-01-05" for 5000 cents
+      When I export the patient data
+      Then the archive contains no invoice
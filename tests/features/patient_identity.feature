@@ -0,0 +1,44 @@
+Feature: Patient identity details
+  As a practitioner
+  I want to record the birth date, phone number, sex and referring doctor of my patients
+  In order to fill in care sheets and tell homonyms apart
+
+  Background:
+    Given a practitioner for identity tests
+
+  Rule: Identity details are validated and stored
+
+    Scenario: A patient is created with all identity details
+      When I create a female patient born on "1980-02-01" with phone number "06 12 34 56 78" referred by "Dr Martin"
+      Then the patient is female
+      And the patient is born on "1980-02-01"
+      And the patient phone number is "06 12 34 56 78"
+      And the patient is referred by "Dr Martin"
+
+    Scenario: Identity details are optional
+      When I create a patient without identity details
+      Then the patient has no birth date
+
+    Scenario Outline: Invalid identity details are rejected
+      When I try to create a patient born on "<birth_date>" with phone number "<phone_number>"
+      Then the patient creation is rejected
+
+      Examples:
+        | birth_date | phone_number   |
+        | 2999-01-01 | 06 12 34 56 78 |
+        | 1980-13-45 | 06 12 34 56 78 |
+        | 1980-02-01 | 12345          |
+
+  Rule: Namesakes born on different days are not duplicates
+
+    Scenario: Namesakes with different birth dates are homonyms
+      Given a patient "Jean" "Martin" born on "1950-05-10"
+      And a patient "Jean" "Martin" born on "1982-11-03"
+      When I look for duplicates among them
+      Then no duplicate is reported
+
+    Scenario: Namesakes with the same birth date are duplicates
+      Given a patient "Jean" "Martin" born on "1950-05-10"
+      And a patient "jean" "MARTIN" born on "1950-05-10"
+      When I look for duplicates among them
+      Then 1 duplicate group is reported
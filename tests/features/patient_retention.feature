@@ -0,0 +1,41 @@
+Feature: Retention-aware patient deletion
+  As a practitioner
+  I want deleted patients to be anonymized rather than destroyed
+  In order to keep the accounting history I am legally required to retain
+
+  Background:
+    Given a practitioner with a patient seen on "2026-01-05" for 5000 cents
+
+  Rule: Deleting a patient scrubs their identity
+
+    Scenario: Identity fields and social security number are scrubbed
+      When the patient is deleted
+      Then the patient identity is anonymized
+      And the patient social security number is erased
+
+    Scenario: Appointment amounts are kept for accounting
+      When the patient is deleted
+      Then the patient still has 1 appointment for 5000 cents
+
+    Scenario: An anonymized patient no longer shows up in searches
+      When the patient is deleted
+      Then searching for "Dupont" returns no patient
+
+  Rule: Anonymized patients are purged once the retention period is over
+
+    Scenario: A patient past the retention period is purged
+      Given the patient was anonymized 11 years ago
+      And the patient appointment dates back 11 years
+      When the retention purge runs with a retention of 3650 days
+      Then 1 patient is purged
+      And the patient and their appointments no longer exist
+
+    Scenario: A patient with a recent appointment is kept
+      Given the patient was anonymized 11 years ago
+      When the retention purge runs with a retention of 3650 days
+      Then 0 patient is purged
+
+    Scenario: A patient that was not anonymized is never purged
+      Given the patient appointment dates back 11 years
+      When the retention purge runs with a retention of 3650 days
+      Then 0 patient is purged
@@ -0,0 +1,63 @@
+Feature: Consent-based patient sharing
+  As a practitioner
+  I want to access the record of a patient followed by a colleague
+  In order to treat them, with the consent of the owner or of the patient
+
+  Background:
+    Given a practitioner owning a patient with social security number "1234567890123"
+    And a colleague practitioner
+
+  Rule: A record is only shared once the access has been granted
+
+    Scenario: Requesting an access does not share the record yet
+      When the colleague requests a "read" access to the patient "1234567890123"
+      Then the colleague has a pending request
+      And the colleague has no access to the patient
+
+    Scenario: Requesting an access twice only creates one request
+      When the colleague requests a "read" access to the patient "1234567890123"
+      And the colleague requests a "read" access to the patient "1234567890123"
+      Then the owner sees 1 access request
+
+    Scenario: The owner and the patient are notified of the request
+      When the colleague requests a "read" access to the patient "1234567890123"
+      Then 2 notification emails are prepared
+
+    Scenario: The patient is asked for consent in their own language
+      Given the patient speaks "en"
+      When the colleague requests a "read" access to the patient "1234567890123"
+      Then the owner is notified with the subject "Demande d'accès à un dossier patient"
+      And the patient is asked for consent with the subject "Sharing of your patient record"
+
+    Scenario: The owner grants a read access
+      Given the colleague requests a "read" access to the patient "1234567890123"
+      When the owner grants a "read" access
+      Then the colleague can read the patient
+      But the colleague cannot update the patient
+      And the colleague cannot add an appointment to the patient
+      And the colleague cannot export the patient
+
+    Scenario: The patient consents from the emailed link
+      Given the colleague requests a "write" access to the patient "1234567890123"
+      When the patient follows the consent link
+      Then the colleague can update the patient
+      But the colleague cannot delete the patient
+      And the colleague cannot export the patient
+
+    Scenario: An expired consent link no longer shares the record
+      Given the colleague requests a "write" access to the patient "1234567890123"
+      And the consent link has expired
+      When the patient follows the consent link
+      Then the consent is rejected
+      And the colleague has no access to the patient
+
+    Scenario: A shared patient shows up in the colleague searches
+      Given the colleague requests a "read" access to the patient "1234567890123"
+      When the owner grants a "read" access
+      Then the colleague finds the patient when searching for "Dupont"
+
+    Scenario: A revoked access no longer shares the record
+      Given the colleague requests a "read" access to the patient "1234567890123"
+      And the owner grants a "read" access
+      When the owner revokes the access
+      Then the colleague has no access to the patient
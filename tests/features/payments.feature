@@ -0,0 +1,58 @@
+Feature: Appointment payments
+  As a practitioner
+  I want to record each payment received for an appointment
+  In order to know which appointments are paid, partially paid or still unpaid
+
+  Background:
+    Given a practitioner with an office for payments
+    And a patient "Claire" "Dubois" for payments
+
+  Rule: The payment status is derived from the payments received
+
+    Scenario: An appointment without payment is unpaid
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      Then the appointment is "unpaid"
+
+    Scenario: A check covering part of the price
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      When the patient pays 2000 cents by check "0001234" on "2026-03-02"
+      Then the appointment is "partially_paid"
+      And the patient "Dubois" still owes 4000 cents
+
+    Scenario: Several payments settle the appointment
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      When the patient pays 2000 cents by check "0001234" on "2026-03-02"
+      And the patient pays 4000 cents by transfer on "2026-03-20"
+      Then the appointment is "paid"
+
+    Scenario: A payment cannot exceed the patient share
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      When the patient tries to pay 7000 cents by cash
+      Then the payment is rejected
+
+    Scenario: Two payments entered at once cannot exceed the patient share
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      When the patient pays 6000 cents by cash twice at the same time
+      Then the payment is rejected
+      And the appointment has 1 payment
+
+    Scenario: An appointment paid at the end of the consultation
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02" paid by card
+      Then the appointment is "paid"
+
+  Rule: Appointments can be filtered on their payment status
+
+    Scenario: Only unpaid appointments are listed
+      Given a patient "Paul" "Martin" for payments
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02" paid by card
+      And an appointment of 5000 cents for "Martin" on "2026-03-04"
+      When I list the "unpaid" appointments between "2026-03-01" and "2026-03-31"
+      Then 1 appointment is listed
+
+    Scenario: Unpaid balances are reported per patient, largest first
+      Given a patient "Paul" "Martin" for payments
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-04"
+      And an appointment of 5000 cents for "Martin" on "2026-03-11"
+      When I list the unpaid balances
+      Then the first unpaid balance is 10000 cents for "Martin" over 2 appointments
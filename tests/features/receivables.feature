@@ -0,0 +1,41 @@
+Feature: Third-party payment receivables
+  As a practitioner
+  I want to know how much each payer still owes me
+  In order to follow up on insurers that have not reimbursed my appointments
+
+  Background:
+    Given a practitioner with an office for receivables
+    And an insured patient covered by "CPAM Paris" and the mutuelle "Harmonie"
+
+  Rule: Appointment shares are split between the patient and the insurers
+
+    Scenario: Unpaid shares are reported per payer
+      Given an appointment of 5000 cents with an AMO share of 3000 and a mutuelle share of 1500
+      When I compute the outstanding receivables
+      Then the patient owes 500 cents
+      And "CPAM Paris" owes 3000 cents
+      And "Harmonie" owes 1500 cents
+
+    Scenario: Received shares are no longer outstanding
+      Given an appointment of 5000 cents with an AMO share of 3000 and a mutuelle share of 1500
+      And the patient share is paid by card
+      And the AMO share is received on "2026-02-01"
+      When I compute the outstanding receivables
+      Then 1 payer has an outstanding balance
+      And "Harmonie" owes 1500 cents
+
+    Scenario: Shares cannot exceed the appointment price
+      When I try to record an appointment of 5000 cents with an AMO share of 4000 and a mutuelle share of 2000
+      Then the appointment is rejected
+
+    Scenario: Shares too large to be added up are rejected
+      When I try to record an appointment of 5000 cents with an AMO share of 2147483647 and a mutuelle share of 2147483647
+      Then the appointment is rejected
+
+  Rule: Insurers are matched on the coverage in force on the appointment day
+
+    Scenario: An expired mutuelle is not charged
+      Given the mutuelle coverage ended on "2025-12-31"
+      And an appointment of 5000 cents with an AMO share of 3000 and a mutuelle share of 1500
+      When I compute the outstanding receivables
+      Then an unknown mutuelle owes 1500 cents
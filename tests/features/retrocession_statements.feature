@@ -0,0 +1,117 @@
+Feature: Retrocession statements
+  As a practitioner working in someone else's office
+  I want a monthly statement of what I owe the office holder
+  In order to settle the retrocession without redoing the maths by hand
+
+  Background:
+    Given a practitioner working in "Cabinet Central" with a revenue share of 20
+    And a patient "Claire" "Dubois" for payments
+    And a patient "Paul" "Martin" for payments
+
+  Rule: A statement sums up the fees of the month in the office
+
+    Scenario: The amount owed applies the revenue share to the month fees
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-02"
+      And an appointment of 5000 cents for "Dubois" on "2026-03-16"
+      And an appointment of 7000 cents for "Martin" on "2026-04-01"
+      When I generate the retrocession statement of "2026-03"
+      Then the statement counts 3 appointments for 16000 cents
+      And the statement owes 3200 cents to the office holder
+      And the statement details 2 days
+
+    Scenario: Generating a month again replaces its statement
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      And an appointment of 4000 cents for "Martin" on "2026-03-20"
+      When I generate the retrocession statement of "2026-03"
+      Then the statement counts 2 appointments for 10000 cents
+      And the office has 1 statement in its history
+
+    Scenario: Past statements are kept in the history
+      Given an appointment of 6000 cents for "Dubois" on "2026-02-10"
+      And an appointment of 4000 cents for "Martin" on "2026-03-20"
+      And I generate the retrocession statement of "2026-02"
+      And I generate the retrocession statement of "2026-03"
+      Then the office has 2 statements in its history
+
+    Scenario: A statement can be downloaded
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      When I download the statement
+      Then the statement documents are a PDF and a spreadsheet
+
+  Rule: The amount owed follows the retrocession model of the office
+
+    Scenario: A fixed monthly fee does not depend on the fees
+      Given the office charges a fixed monthly fee of 40000 cents from "2026-03-01"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-16"
+      When I generate the retrocession statement of "2026-03"
+      Then the statement counts 2 appointments for 11000 cents
+      And the statement owes 40000 cents to the office holder
+
+    Scenario: A fee per appointment is owed for each appointment
+      Given the office charges 1500 cents per appointment from "2026-03-01"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-16"
+      And an appointment of 5000 cents for "Dubois" on "2026-03-20"
+      When I generate the retrocession statement of "2026-03"
+      Then the statement owes 4500 cents to the office holder
+
+    Scenario: A capped revenue share never owes more than its cap
+      Given the office revenue share of 20 is capped at 2000 cents from "2026-03-01"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And an appointment of 5000 cents for "Martin" on "2026-03-02"
+      And an appointment of 5000 cents for "Dubois" on "2026-03-16"
+      When I generate the retrocession statement of "2026-03"
+      Then the statement owes 2000 cents to the office holder
+
+  Rule: Statements are emailed to the office contact
+
+    Scenario: The statement is emailed with its documents attached
+      Given the office contact is "secretariat@cabinet-central.fr"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      When I send the statement
+      Then an email with 2 attachments is addressed to "secretariat@cabinet-central.fr"
+      And the statement email is signed by the practitioner in HTML and in plain text
+      And the statement is marked as sent to "secretariat@cabinet-central.fr"
+
+    Scenario: A sent statement cannot be generated again
+      Given the office contact is "secretariat@cabinet-central.fr"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      And I send the statement
+      And an appointment of 4000 cents for "Martin" on "2026-03-20"
+      Then generating the retrocession statement of "2026-03" again is rejected
+      And the statement counts 1 appointments for 6000 cents
+      And the statement is marked as sent to "secretariat@cabinet-central.fr"
+
+    Scenario: The statement is written in the practitioner's language
+      Given the practitioner of the office speaks "en"
+      And the office contact is "secretariat@cabinet-central.fr"
+      And the office charges a fixed monthly fee of 40000 cents from "2026-03-01"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      When I send the statement
+      Then the statement email subject is "Retrocession statement Cabinet Central - 03/2026"
+      When I download the statement
+      Then the statement spreadsheet mentions "Amount due to the office holder (€)"
+      And the statement spreadsheet mentions "Flat fee of €400.00 per month"
+
+    Scenario: An office without contact cannot receive statements
+      Given an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      When I send the statement
+      Then the statement is not sent
+
+  Rule: Sent statements keep the revenue share they were computed with
+
+    Scenario: The revenue share of a sent month cannot change
+      Given the office contact is "secretariat@cabinet-central.fr"
+      And an appointment of 6000 cents for "Dubois" on "2026-03-02"
+      And I generate the retrocession statement of "2026-03"
+      And I send the statement
+      Then changing the office revenue share to 30 from "2026-03-15" is rejected
+      And changing the office revenue share to 30 from "2026-04-01" is accepted
@@ -0,0 +1,54 @@
+Feature: Scheduled appointment exports
+  As a practitioner
+  I want my appointments to be exported automatically at the end of each period
+  In order to stop triggering the export for my accountant by hand
+
+  Background:
+    Given a practitioner exists
+
+  Scenario: A monthly export is due once on the first day of the month
+    Given I schedule a "monthly" "fec" export to "comptable@cabinet.fr" on "2026-03-15"
+    When the scheduled exports are checked on "2026-03-31"
+    Then no scheduled export is due
+    When the scheduled exports are checked on "2026-04-01"
+    Then a "fec" export from "2026-03-01" to "2026-03-31" is due for "comptable@cabinet.fr"
+    When the scheduled exports are checked on "2026-04-01"
+    Then no scheduled export is due
+
+  Scenario: A quarterly export covers the previous quarter
+    Given I schedule a "quarterly" "xlsx" export to "comptable@cabinet.fr" on "2026-02-10"
+    When the scheduled exports are checked on "2026-04-02"
+    Then a "xlsx" export from "2026-01-01" to "2026-03-31" is due for "comptable@cabinet.fr"
+
+  Scenario: A yearly export covers the previous year
+    Given I schedule a "yearly" "csv" export to "comptable@cabinet.fr" on "2026-06-01"
+    When the scheduled exports are checked on "2026-12-31"
+    Then no scheduled export is due
+    When the scheduled exports are checked on "2027-01-01"
+    Then a "csv" export from "2026-01-01" to "2026-12-31" is due for "comptable@cabinet.fr"
+
+  Scenario: Periods missed while the application was stopped are sent one after the other
+    Given I schedule a "monthly" "xlsx" export to "comptable@cabinet.fr" on "2026-01-20"
+    When the scheduled exports are checked on "2026-04-10"
+    Then a "xlsx" export from "2026-01-01" to "2026-01-31" is due for "comptable@cabinet.fr"
+    When the due export is sent
+    And the scheduled exports are checked on "2026-04-10"
+    Then a "xlsx" export from "2026-02-01" to "2026-02-28" is due for "comptable@cabinet.fr"
+    When the due export is sent
+    And the scheduled exports are checked on "2026-04-10"
+    Then a "xlsx" export from "2026-03-01" to "2026-03-31" is due for "comptable@cabinet.fr"
+    When the due export is sent
+    And the scheduled exports are checked on "2026-04-10"
+    Then no scheduled export is due
+
+  Scenario: A period whose export failed is sent again
+    Given the mail server is unreachable
+    And I schedule a "monthly" "xlsx" export to "comptable@cabinet.fr" on "2026-03-15"
+    When the scheduled exports are checked on "2026-04-01"
+    And the due export fails to be sent
+    And the scheduled exports are checked on "2026-04-02"
+    Then a "xlsx" export from "2026-03-01" to "2026-03-31" is due for "comptable@cabinet.fr"
+
+  Scenario: An invalid recipient is rejected
+    When I schedule a "monthly" "xlsx" export to "not an email" on "2026-03-15"
+    Then the scheduled export is rejected
@@ -0,0 +1,32 @@
+Feature: Activity statistics
+  As a practitioner
+  I want an overview of my activity over a period
+  In order to follow how my practice evolves
+
+  Background:
+    Given a practitioner exists
+    And a practitioner office "Cabinet Central" exists with revenue share 70
+    And a second office "Cabinet Sud" exists with revenue share 50
+    And a patient "Alice" "Dupont" exists
+
+  Scenario: Activity is summed up over the period
+    Given an appointment on "2026-02-10" at price 5000
+    And an appointment on "2026-03-02" at price 6000
+    And a patient "Paul" "Martin" exists
+    And an appointment on "2026-03-03" at price 4000
+    And an appointment on "2026-03-09" at price 5000
+    And an appointment on "2026-03-12" at price 3000 at office "Cabinet Sud"
+    And an appointment on "2026-04-01" at price 7000
+    When I request my activity between "2026-03-01" and "2026-03-31"
+    Then my activity counts 4 appointments for 18000 cents
+    And my average fee is 4500 cents
+    And I saw 1 new and 1 returning patient
+    And my busiest weekday is 1 with 2 appointments
+    And the office "Cabinet Central" accounts for 3 appointments and 15000 cents
+    And the office "Cabinet Sud" accounts for 1 appointment and 3000 cents
+
+  Scenario: A period without appointments has empty statistics
+    When I request my activity between "2026-03-01" and "2026-03-31"
+    Then my activity counts 0 appointments for 0 cents
+    And my average fee is 0 cents
+    And I saw 0 new and 0 returning patients
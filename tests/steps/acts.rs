@@ -0,0 +1,276 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::{acts, medical_appointments},
+    acts::{ActParams, OfficePriceParams},
+  },
+  services::{self, appointments::act_totals},
+};
+use sea_orm::{EntityTrait, ModelTrait};
+
+use crate::{
+  factories::{medical_appointment::AppointmentFactory, office::OfficeFactory},
+  AppWorld,
+};
+
+fn office_id(world: &AppWorld, office_name: &str) -> i32 {
+  world
+    .appointments
+    .all_offices()
+    .find(|office| office.name == office_name)
+    .unwrap_or_else(|| panic!("office '{}' not found", office_name))
+    .id
+}
+
+fn find_act(world: &AppWorld, label: &str) -> acts::Model {
+  world
+    .acts
+    .catalogue
+    .iter()
+    .map(|(act, _)| act)
+    .find(|act| act.label == label)
+    .unwrap_or_else(|| panic!("act '{}' not found", label))
+    .clone()
+}
+
+async fn add_act(world: &mut AppWorld, params: ActParams) {
+  let user = world.appointments.user.as_ref().unwrap();
+  match services::acts::create(user, &params).await {
+    Ok(act) => world.acts.catalogue.push(act),
+    Err(_) => world.acts.rejected = true,
+  }
+}
+
+#[given(expr = "an act {string} coded {string} at {int} cents")]
+async fn act_exists(world: &mut AppWorld, label: String, code: String, price: i32) {
+  let params = ActParams {
+    label,
+    code: Some(code),
+    default_price_in_cents: price,
+    office_prices: vec![],
+  };
+  add_act(world, params).await;
+}
+
+#[given(expr = "an act {string} coded {string} at {int} cents, and {int} cents at office {string}")]
+async fn act_with_office_price_exists(
+  world: &mut AppWorld,
+  label: String,
+  code: String,
+  price: i32,
+  office_price: i32,
+  office_name: String,
+) {
+  let params = ActParams {
+    label,
+    code: Some(code),
+    default_price_in_cents: price,
+    office_prices: vec![OfficePriceParams {
+      practitioner_office_id: office_id(world, &office_name),
+      price_in_cents: office_price,
+    }],
+  };
+  add_act(world, params).await;
+}
+
+#[when(expr = "I try to add an act {string} at {int} cents priced twice at office {string}")]
+async fn add_act_priced_twice(
+  world: &mut AppWorld,
+  label: String,
+  price: i32,
+  office_name: String,
+) {
+  let office_price = OfficePriceParams {
+    practitioner_office_id: office_id(world, &office_name),
+    price_in_cents: price,
+  };
+  let params = ActParams {
+    label,
+    code: None,
+    default_price_in_cents: price,
+    office_prices: vec![office_price.clone(), office_price],
+  };
+  add_act(world, params).await;
+}
+
+#[when(expr = "I try to add an act {string} at {int} cents")]
+async fn add_act_at_price(world: &mut AppWorld, label: String, price: i32) {
+  let params = ActParams {
+    label,
+    code: None,
+    default_price_in_cents: price,
+    office_prices: vec![],
+  };
+  add_act(world, params).await;
+}
+
+#[then("the act is rejected")]
+fn act_rejected(world: &mut AppWorld) {
+  assert!(world.acts.rejected);
+}
+
+async fn create_appointment(
+  world: &mut AppWorld,
+  date: &str,
+  act: Option<&str>,
+  office_id: i32,
+  price: Option<i32>,
+) -> bool {
+  let user_id = world.appointments.user.as_ref().unwrap().id;
+  let patient_id = world.appointments.patient.as_ref().unwrap().id;
+  let act_id = act.map(|label| find_act(world, label).id);
+
+  let Ok((act, price_in_cents)) =
+    services::acts::appointment_act(&world.db, user_id, act_id, office_id, price).await
+  else {
+    return false;
+  };
+
+  let mut factory = AppointmentFactory::new().date(date).price(price_in_cents);
+  if let Some(act) = act {
+    factory = factory.act(act);
+  }
+  world.appointments.appointment = Some(
+    factory
+      .create(&world.db, user_id, patient_id, office_id)
+      .await,
+  );
+  true
+}
+
+#[given(expr = "I create an appointment on {string} for act {string}")]
+#[when(expr = "I create an appointment on {string} for act {string}")]
+async fn create_appointment_for_act(world: &mut AppWorld, date: String, act: String) {
+  let office_id = world.appointments.office.as_ref().unwrap().id;
+  assert!(create_appointment(world, &date, Some(&act), office_id, None).await);
+}
+
+#[given(expr = "I create an appointment on {string} for act {string} at office {string}")]
+#[when(expr = "I create an appointment on {string} for act {string} at office {string}")]
+async fn create_appointment_for_act_at_office(
+  world: &mut AppWorld,
+  date: String,
+  act: String,
+  office_name: String,
+) {
+  let office_id = office_id(world, &office_name);
+  assert!(create_appointment(world, &date, Some(&act), office_id, None).await);
+}
+
+#[when(expr = "I create an appointment on {string} for act {string} at price {int}")]
+async fn create_appointment_for_act_at_price(
+  world: &mut AppWorld,
+  date: String,
+  act: String,
+  price: i32,
+) {
+  let office_id = world.appointments.office.as_ref().unwrap().id;
+  assert!(create_appointment(world, &date, Some(&act), office_id, Some(price)).await);
+}
+
+#[when(expr = "I try to create an appointment on {string} without price nor act")]
+async fn create_appointment_without_price(world: &mut AppWorld, date: String) {
+  let office_id = world.appointments.office.as_ref().unwrap().id;
+  world.acts.rejected = !create_appointment(world, &date, None, office_id, None).await;
+}
+
+#[when(
+  expr = "I try to create an appointment on {string} for act {string} in an office I do not work in"
+)]
+async fn create_appointment_in_other_office(world: &mut AppWorld, date: String, act: String) {
+  let office = OfficeFactory::new()
+    .name("Cabinet Nord")
+    .create(&world.db)
+    .await;
+  world.acts.rejected = !create_appointment(world, &date, Some(&act), office.id, None).await;
+}
+
+#[then("the appointment in another office is rejected")]
+fn appointment_in_other_office_rejected(world: &mut AppWorld) {
+  assert!(world.acts.rejected);
+  assert!(world.appointments.appointment.is_none());
+}
+
+#[then("the appointment without price is rejected")]
+fn appointment_without_price_rejected(world: &mut AppWorld) {
+  assert!(world.acts.rejected);
+  assert!(world.appointments.appointment.is_none());
+}
+
+#[when(expr = "I rename the act {string} to {string} coded {string}")]
+async fn rename_act(world: &mut AppWorld, label: String, new_label: String, code: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let params = ActParams {
+    label: new_label,
+    code: Some(code),
+    default_price_in_cents: find_act(world, &label).default_price_in_cents,
+    office_prices: vec![],
+  };
+  let renamed = services::acts::update(find_act(world, &label), user, &params)
+    .await
+    .unwrap();
+  world.acts.catalogue.push(renamed);
+}
+
+#[when(expr = "I delete the act {string}")]
+async fn delete_act(world: &mut AppWorld, label: String) {
+  find_act(world, &label).delete(&world.db).await.unwrap();
+}
+
+async fn reload_appointment(world: &AppWorld) -> medical_appointments::Model {
+  let appointment_id = world.appointments.appointment.as_ref().unwrap().id;
+  medical_appointments::Entity::find_by_id(appointment_id)
+    .one(&world.db)
+    .await
+    .unwrap()
+    .unwrap()
+}
+
+#[then(expr = "the appointment costs {int} cents for act {string}")]
+async fn appointment_costs_for_act(world: &mut AppWorld, price: i32, act: String) {
+  let appointment = reload_appointment(world).await;
+  assert_eq!(appointment.price_in_cents, price);
+  assert!(appointment.act_id.is_some());
+  assert_eq!(appointment.act_full_label(), Some(act));
+}
+
+#[then(expr = "the appointment is still labelled {string}")]
+async fn appointment_still_labelled(world: &mut AppWorld, act: String) {
+  let appointment = reload_appointment(world).await;
+  assert_eq!(appointment.act_full_label(), Some(act));
+}
+
+#[then(expr = "the appointment costs {int} cents without act")]
+async fn appointment_costs_without_act(world: &mut AppWorld, price: i32) {
+  let appointment = reload_appointment(world).await;
+  assert_eq!(appointment.price_in_cents, price);
+  assert_eq!(appointment.act_id, None);
+}
+
+fn assert_act_totals(world: &AppWorld, act: Option<&str>, fees: i64, count: usize) {
+  let totals = act_totals(&world.appointments.extracted.appointments)
+    .into_iter()
+    .find(|totals| totals.act.as_deref() == act)
+    .unwrap_or_else(|| panic!("no extracted fees for act {:?}", act));
+  assert_eq!(totals.fees_in_cents, fees);
+  assert_eq!(totals.appointments_count, count);
+}
+
+#[then(expr = "the extracted fees of act {string} are {int} cents over {int} appointment(s)")]
+fn extracted_act_fees(world: &mut AppWorld, act: String, fees: i64, count: usize) {
+  assert_act_totals(world, Some(&act), fees, count);
+}
+
+#[then(expr = "the extracted fees without act are {int} cents over {int} appointment(s)")]
+fn extracted_fees_without_act(world: &mut AppWorld, fees: i64, count: usize) {
+  assert_act_totals(world, None, fees, count);
+}
+
+#[then(expr = "the acts come in the order {string}, {string} and then without act")]
+fn acts_order(world: &mut AppWorld, first: String, second: String) {
+  let acts: Vec<Option<String>> = act_totals(&world.appointments.extracted.appointments)
+    .into_iter()
+    .map(|totals| totals.act)
+    .collect();
+  assert_eq!(acts, vec![Some(first), Some(second), None]);
+}
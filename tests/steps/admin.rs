@@ -0,0 +1,79 @@
+use cucumber::{given, then, when};
+use opencab::{models::_entities::users, services::admin};
+use sea_orm::{EntityTrait, IntoActiveModel};
+
+use crate::{
+  factories::{patient::PatientFactory, user::UserFactory},
+  AppWorld,
+};
+
+async fn reload_user(world: &mut AppWorld) -> users::Model {
+  let user_id = world.admin.user.as_ref().unwrap().id;
+  users::Entity::find_by_id(user_id)
+    .one(&world.db)
+    .await
+    .unwrap()
+    .unwrap()
+}
+
+#[given(expr = "a practitioner account {string} exists")]
+async fn practitioner_account_exists(world: &mut AppWorld, email: String) {
+  let user = UserFactory::new().email(&email).create(&world.db).await;
+  if world.admin.user.is_none() {
+    world.admin.user = Some(user);
+  }
+}
+
+#[given(expr = "the practitioner has {int} patients")]
+async fn practitioner_has_patients(world: &mut AppWorld, count: usize) {
+  let user_id = world.admin.user.as_ref().unwrap().id;
+  for i in 0..count {
+    PatientFactory::new()
+      .last_name(&format!("Patient{}", i))
+      .create(&world.db, user_id)
+      .await;
+  }
+}
+
+#[given("the account is suspended by an administrator")]
+#[when("an administrator suspends the account")]
+async fn suspend_account(world: &mut AppWorld) {
+  let user = world.admin.user.take().unwrap();
+  let suspended = user.into_active_model().suspend(&world.db).await.unwrap();
+  world.admin.user = Some(suspended);
+}
+
+#[when("an administrator approves the account")]
+async fn approve_account(world: &mut AppWorld) {
+  let user = world.admin.user.take().unwrap();
+  let approved = user.into_active_model().approve(&world.db).await.unwrap();
+  world.admin.user = Some(approved);
+}
+
+#[when("an administrator fetches the usage statistics")]
+async fn fetch_usage_stats(world: &mut AppWorld) {
+  world.admin.stats = Some(admin::usage_stats(&world.db).await.unwrap());
+}
+
+#[then("the account is suspended")]
+async fn account_is_suspended(world: &mut AppWorld) {
+  assert!(reload_user(world).await.is_suspended());
+}
+
+#[then("the account is not suspended")]
+async fn account_is_not_suspended(world: &mut AppWorld) {
+  assert!(!reload_user(world).await.is_suspended());
+}
+
+#[then("the account access key is verified")]
+async fn account_access_key_verified(world: &mut AppWorld) {
+  assert!(reload_user(world).await.is_access_key_verified);
+}
+
+#[then(expr = "the statistics report {int} users, {int} pending users and {int} patients")]
+fn statistics_report(world: &mut AppWorld, users: u64, pending: u64, patients: u64) {
+  let stats = world.admin.stats.as_ref().unwrap();
+  assert_eq!(stats.users_count, users);
+  assert_eq!(stats.pending_users_count, pending);
+  assert_eq!(stats.patients_count, patients);
+}
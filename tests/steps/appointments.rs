@@ -2,14 +2,21 @@ use chrono::NaiveDate;
 use cucumber::{given, then, when};
 use opencab::models::{
   _entities::{
-    medical_appointments, practitioner_offices, sea_orm_active_enums::PaymentMethod,
+    acts, medical_appointments, practitioner_offices,
+    sea_orm_active_enums::{ExportFormat, PaymentMethod, RetrocessionKind},
     user_practitioner_offices,
   },
-  medical_appointments::UpdateMedicalAppointmentParams,
+  accounting_exports,
+  medical_appointments::{PayerShares, UpdateMedicalAppointmentParams},
+  revenue_share_rates::RetrocessionTerms,
+  user_business_informations::CreateBusinessInformation,
   user_practitioner_offices::CreateLinkParams,
 };
-use opencab::services::appointments::MedicalAppointmentExtractor;
-use sea_orm::{prelude::Decimal, EntityTrait, IntoActiveModel};
+use opencab::services::{
+  appointments::{payment_method_totals, MedicalAppointmentExtractor, MonthlyTotals},
+  exporters, practitioner_office, user,
+};
+use sea_orm::{prelude::Decimal, ActiveEnum, EntityTrait, IntoActiveModel, ModelTrait};
 
 use crate::{
   factories::{
@@ -36,7 +43,7 @@ async fn create_office_with_revenue_share(
     &CreateLinkParams {
       user_id: user.id,
       practitioner_office_id: office.id,
-      revenue_share_percentage: Decimal::from(revenue_share),
+      terms: RetrocessionTerms::percentage(Decimal::from(revenue_share)),
     },
   )
   .await
@@ -113,6 +120,7 @@ async fn when_create_appointment(world: &mut AppWorld, date_str: String, price:
   do_create_appointment(world, &date_str, price).await;
 }
 
+#[given(expr = "an appointment on {string} at price {int} with payment {string}")]
 #[when(expr = "I create an appointment on {string} at price {int} with payment {string}")]
 async fn create_appointment_with_payment(
   world: &mut AppWorld,
@@ -139,11 +147,18 @@ async fn update_appointment_date(world: &mut AppWorld, date_str: String) {
   let appointment_id = appointment.id;
   let office_id = world.appointments.office.as_ref().unwrap().id;
   let new_date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").unwrap();
+  let act = appointment
+    .find_related(acts::Entity)
+    .one(&world.db)
+    .await
+    .unwrap();
   let params = UpdateMedicalAppointmentParams {
     date: new_date,
+    act,
     price_in_cents: appointment.price_in_cents,
     practitioner_office_id: office_id,
     payment_method: appointment.payment_method.clone(),
+    payer_shares: PayerShares::default(),
   };
   appointment
     .into_active_model()
@@ -165,12 +180,123 @@ async fn extract_appointments(world: &mut AppWorld, start_str: String, end_str:
   let start = NaiveDate::parse_from_str(&start_str, "%Y-%m-%d").unwrap();
   let end = NaiveDate::parse_from_str(&end_str, "%Y-%m-%d").unwrap();
   let results = MedicalAppointmentExtractor::for_user(user)
-    .extract(&world.db, start, end)
+    .extract_with_monthly_totals(&world.db, start, end)
     .await
     .unwrap();
   world.appointments.extracted = results;
 }
 
+async fn change_retrocession_terms(
+  world: &mut AppWorld,
+  terms: RetrocessionTerms,
+  valid_from: &str,
+) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let office = world.appointments.office.as_ref().unwrap();
+  let link = user_practitioner_offices::Entity::find_by_id((user.id, office.id))
+    .one(&world.db)
+    .await
+    .unwrap()
+    .unwrap();
+  let valid_from = NaiveDate::parse_from_str(valid_from, "%Y-%m-%d").unwrap();
+
+  world.appointments.revenue_share_change_failed =
+    practitioner_office::set_retrocession_terms(&world.db, link, &terms, valid_from)
+      .await
+      .is_err();
+}
+
+#[given(expr = "the revenue share becomes {int} from {string}")]
+async fn revenue_share_becomes(world: &mut AppWorld, percentage: i64, valid_from: String) {
+  let terms = RetrocessionTerms::percentage(Decimal::from(percentage));
+  change_retrocession_terms(world, terms, &valid_from).await;
+  assert!(!world.appointments.revenue_share_change_failed);
+}
+
+#[given(expr = "the revenue share becomes {int} capped at {int} cents from {string}")]
+async fn capped_revenue_share_becomes(
+  world: &mut AppWorld,
+  percentage: i64,
+  cap_in_cents: i32,
+  valid_from: String,
+) {
+  let terms = RetrocessionTerms {
+    kind: RetrocessionKind::CappedPercentage,
+    percentage: Decimal::from(percentage),
+    amount_in_cents: Some(cap_in_cents),
+  };
+  change_retrocession_terms(world, terms, &valid_from).await;
+  assert!(!world.appointments.revenue_share_change_failed);
+}
+
+#[given(expr = "the retrocession becomes a fixed fee of {int} cents from {string}")]
+async fn fixed_fee_becomes(world: &mut AppWorld, amount_in_cents: i32, valid_from: String) {
+  let terms = RetrocessionTerms {
+    kind: RetrocessionKind::FixedMonthlyFee,
+    percentage: Decimal::ZERO,
+    amount_in_cents: Some(amount_in_cents),
+  };
+  change_retrocession_terms(world, terms, &valid_from).await;
+  assert!(!world.appointments.revenue_share_change_failed);
+}
+
+#[given(expr = "the retrocession becomes {int} cents per appointment from {string}")]
+async fn per_appointment_fee_becomes(
+  world: &mut AppWorld,
+  amount_in_cents: i32,
+  valid_from: String,
+) {
+  let terms = RetrocessionTerms {
+    kind: RetrocessionKind::PerAppointmentFee,
+    percentage: Decimal::ZERO,
+    amount_in_cents: Some(amount_in_cents),
+  };
+  change_retrocession_terms(world, terms, &valid_from).await;
+  assert!(!world.appointments.revenue_share_change_failed);
+}
+
+#[given(expr = "the practitioner's SIRET is {string}")]
+async fn practitioner_siret(world: &mut AppWorld, siret_number: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  user::save_business_information(
+    &CreateBusinessInformation {
+      rpps_number: "10101010101".to_string(),
+      adeli_number: None,
+      siret_number,
+      profession: "general_practitioner".to_string(),
+    },
+    user,
+  )
+  .await
+  .unwrap();
+}
+
+#[when(expr = "I try to change the revenue share to {int} from {string}")]
+async fn try_change_revenue_share(world: &mut AppWorld, percentage: i64, valid_from: String) {
+  let terms = RetrocessionTerms::percentage(Decimal::from(percentage));
+  change_retrocession_terms(world, terms, &valid_from).await;
+}
+
+#[given(expr = "the appointments between {string} and {string} have been exported")]
+async fn appointments_exported(world: &mut AppWorld, start_str: String, end_str: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let start = NaiveDate::parse_from_str(&start_str, "%Y-%m-%d").unwrap();
+  let end = NaiveDate::parse_from_str(&end_str, "%Y-%m-%d").unwrap();
+  accounting_exports::ActiveModel::create(&world.db, user.id, start, end)
+    .await
+    .unwrap();
+}
+
+#[then("the revenue share change is rejected")]
+fn revenue_share_change_rejected(world: &mut AppWorld) {
+  assert!(world.appointments.revenue_share_change_failed);
+}
+
+#[then("the revenue share change is accepted")]
+fn revenue_share_change_accepted(world: &mut AppWorld) {
+  assert!(!world.appointments.revenue_share_change_failed);
+}
+
 #[then(expr = "the appointment is saved with date {string}")]
 fn appointment_saved(world: &mut AppWorld, date_str: String) {
   let appointment = world.appointments.appointment.as_ref().unwrap();
@@ -196,25 +322,164 @@ fn appointment_date(world: &mut AppWorld, date_str: String) {
 
 #[then(expr = "{int} appointments are returned")]
 fn appointments_count(world: &mut AppWorld, count: usize) {
-  assert_eq!(world.appointments.extracted.len(), count);
+  assert_eq!(world.appointments.extracted.appointments.len(), count);
 }
 
 #[then(expr = "the first extracted appointment has a revenue share of {float}")]
 fn first_appointment_revenue_share(world: &mut AppWorld, expected: f64) {
-  let (_, _, _, revenue_share) = &world.appointments.extracted[0];
-  assert_eq!(*revenue_share, expected);
+  let (_, _, _, terms) = &world.appointments.extracted.appointments[0];
+  assert_eq!(f64::try_from(terms.percentage).unwrap(), expected);
 }
 
 #[then(expr = "the extracted appointment for office {string} has a revenue share of {float}")]
 fn appointment_revenue_share_for_office(world: &mut AppWorld, office_name: String, expected: f64) {
-  let (_, _, office, revenue_share) = world
+  let (_, _, office, terms) = world
     .appointments
     .extracted
+    .appointments
     .iter()
     .find(|(_, _, o, _)| o.name == office_name)
     .unwrap_or_else(|| panic!("no extracted appointment for office '{}'", office_name));
   assert_eq!(office.name, office_name);
-  assert_eq!(*revenue_share, expected);
+  assert_eq!(f64::try_from(terms.percentage).unwrap(), expected);
+}
+
+#[then(expr = "the extracted appointment on {string} has a revenue share of {float}")]
+fn appointment_revenue_share_on(world: &mut AppWorld, date_str: String, expected: f64) {
+  let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").unwrap();
+  let (_, _, _, terms) = world
+    .appointments
+    .extracted
+    .appointments
+    .iter()
+    .find(|(appointment, ..)| appointment.date == date)
+    .unwrap_or_else(|| panic!("no extracted appointment on '{}'", date_str));
+  assert_eq!(f64::try_from(terms.percentage).unwrap(), expected);
+}
+
+fn extracted_month<'a>(world: &'a AppWorld, month: &str) -> &'a MonthlyTotals {
+  let month = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").unwrap();
+  world
+    .appointments
+    .extracted
+    .months
+    .iter()
+    .find(|totals| totals.month == month)
+    .unwrap_or_else(|| panic!("no extracted totals for {}", month))
+}
+
+#[then(expr = "the extracted retrocession of {string} is {int} cents")]
+fn extracted_monthly_retrocession(world: &mut AppWorld, month: String, expected: i64) {
+  assert_eq!(
+    extracted_month(world, &month).retrocession_in_cents,
+    expected
+  );
+}
+
+#[then(
+  expr = "the extracted fees of {string} are {int} cents, of which {int} cents of retrocession"
+)]
+fn extracted_monthly_totals(world: &mut AppWorld, month: String, fees: i64, retrocession: i64) {
+  let totals = extracted_month(world, &month);
+  assert_eq!(totals.fees_in_cents, fees);
+  assert_eq!(totals.retrocession_in_cents, retrocession);
+  assert_eq!(totals.revenue_in_cents, fees - retrocession);
+}
+
+#[then(expr = "the extracted fees paid by {string} are {int} cents over {int} appointment(s)")]
+fn extracted_payment_method_totals(world: &mut AppWorld, payment: String, fees: i64, count: usize) {
+  let method = Some(parse_payment_method(&payment));
+  let totals = payment_method_totals(&world.appointments.extracted.appointments)
+    .into_iter()
+    .find(|totals| totals.method == method)
+    .unwrap_or_else(|| panic!("no extracted fees paid by {}", payment));
+  assert_eq!(totals.fees_in_cents, fees);
+  assert_eq!(totals.appointments_count, count);
+}
+
+#[when(expr = "I export the extracted appointments as {string}")]
+fn export_extracted(world: &mut AppWorld, format: String) {
+  let format = match format.as_str() {
+    "xlsx" => ExportFormat::Xlsx,
+    "csv" => ExportFormat::Csv,
+    "fec" => ExportFormat::Fec,
+    _ => panic!("unknown export format: {}", format),
+  };
+  let data = format
+    .exporter()
+    .export(&world.appointments.extracted)
+    .unwrap();
+  world.appointments.export = String::from_utf8(data).unwrap();
+}
+
+#[when(expr = "I download the appointments between {string} and {string}")]
+async fn download_appointments(world: &mut AppWorld, start_str: String, end_str: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let start = NaiveDate::parse_from_str(&start_str, "%Y-%m-%d").unwrap();
+  let end = NaiveDate::parse_from_str(&end_str, "%Y-%m-%d").unwrap();
+  let export = exporters::export_appointments(&world.db, user, start, end, &ExportFormat::Xlsx)
+    .await
+    .unwrap();
+  world.appointments.download = Some((export.filename, export.data));
+}
+
+#[when(expr = "I download the {string} export between {string} and {string}")]
+async fn download_export(world: &mut AppWorld, format: String, start_str: String, end_str: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let start = NaiveDate::parse_from_str(&start_str, "%Y-%m-%d").unwrap();
+  let end = NaiveDate::parse_from_str(&end_str, "%Y-%m-%d").unwrap();
+  let format = ExportFormat::try_from_value(&format).unwrap();
+  let export = exporters::export_appointments(&world.db, user, start, end, &format)
+    .await
+    .unwrap();
+  world.appointments.download = Some((export.filename, export.data));
+}
+
+#[then(expr = "the downloaded export is named {string}")]
+fn downloaded_export(world: &mut AppWorld, filename: String) {
+  let (downloaded_filename, _data) = world.appointments.download.as_ref().unwrap();
+  assert_eq!(*downloaded_filename, filename);
+}
+
+#[then(expr = "the downloaded spreadsheet is named {string}")]
+fn downloaded_spreadsheet(world: &mut AppWorld, filename: String) {
+  let (downloaded_filename, data) = world.appointments.download.as_ref().unwrap();
+  assert_eq!(*downloaded_filename, filename);
+  // xlsx files are zip archives
+  assert!(data.starts_with(b"PK"));
+}
+
+fn export_line(world: &AppWorld, line: usize) -> &str {
+  world
+    .appointments
+    .export
+    .lines()
+    .nth(line)
+    .unwrap_or_else(|| panic!("no line {} in the export", line))
+}
+
+#[then(expr = "the export has {int} lines")]
+fn export_line_count(world: &mut AppWorld, count: usize) {
+  assert_eq!(world.appointments.export.lines().count(), count);
+}
+
+#[then(expr = "the export line {int} reads {string}")]
+fn export_line_reads(world: &mut AppWorld, line: usize, expected: String) {
+  assert_eq!(export_line(world, line), expected);
+}
+
+#[then(expr = "the export line {int} debits {string} on account {string}")]
+fn export_line_debits(world: &mut AppWorld, line: usize, amount: String, account: String) {
+  let fields: Vec<&str> = export_line(world, line).split('\t').collect();
+  assert_eq!(fields[4], account);
+  assert_eq!(fields[11], amount);
+}
+
+#[then(expr = "the export line {int} credits {string} on account {string}")]
+fn export_line_credits(world: &mut AppWorld, line: usize, amount: String, account: String) {
+  let fields: Vec<&str> = export_line(world, line).split('\t').collect();
+  assert_eq!(fields[4], account);
+  assert_eq!(fields[12], amount);
 }
 
 fn parse_payment_method(s: &str) -> PaymentMethod {
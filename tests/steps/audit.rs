@@ -0,0 +1,138 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::AuditAction, audit_logs, patients::Model as PatientModel,
+  },
+  services::audit,
+};
+use sea_orm::{
+  ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, ModelTrait,
+  QueryFilter,
+};
+
+use crate::{
+  factories::{patient::PatientFactory, user::UserFactory},
+  AppWorld,
+};
+
+async fn read_patient(user_id: i32, patient: &PatientModel) {
+  let granted = patient.user_id == user_id;
+  audit::record_access(user_id, patient, AuditAction::Read, granted, None)
+    .await
+    .unwrap();
+}
+
+async fn entries_for(world: &AppWorld, user_id: i32) -> Vec<audit_logs::Model> {
+  audit_logs::Entity::find()
+    .filter(audit_logs::Column::UserId.eq(user_id))
+    .all(&world.db)
+    .await
+    .unwrap()
+}
+
+fn parse_action(action: &str) -> AuditAction {
+  match action {
+    "read" => AuditAction::Read,
+    "update" => AuditAction::Update,
+    "delete" => AuditAction::Delete,
+    "search" => AuditAction::Search,
+    _ => panic!("unknown audit action: {}", action),
+  }
+}
+
+#[given("a practitioner with a patient for audit tests")]
+async fn practitioner_with_patient(world: &mut AppWorld) {
+  let user = UserFactory::new().create(&world.db).await;
+  world.audit.patient = Some(PatientFactory::new().create(&world.db, user.id).await);
+  world.audit.user = Some(user);
+}
+
+#[given("another practitioner exists for audit tests")]
+async fn another_practitioner(world: &mut AppWorld) {
+  UserFactory::new()
+    .email("other@test.com")
+    .create(&world.db)
+    .await;
+}
+
+#[given("the practitioner reads the patient record")]
+#[when("the practitioner reads the patient record")]
+async fn practitioner_reads_patient(world: &mut AppWorld) {
+  let user_id = world.audit.user.as_ref().unwrap().id;
+  read_patient(user_id, world.audit.patient.as_ref().unwrap()).await;
+}
+
+#[when("the other practitioner tries to read the patient record")]
+async fn other_practitioner_reads_patient(world: &mut AppWorld) {
+  let other_id = world.audit.user.as_ref().unwrap().id + 1;
+  read_patient(other_id, world.audit.patient.as_ref().unwrap()).await;
+}
+
+#[when("I try to modify the audit entry")]
+async fn modify_audit_entry(world: &mut AppWorld) {
+  let user_id = world.audit.user.as_ref().unwrap().id;
+  let entry = entries_for(world, user_id).await.remove(0);
+
+  let mut active = entry.into_active_model();
+  active.granted = Set(false);
+  world.audit.change_rejected = active.update(&world.db).await.is_err();
+}
+
+#[when("I try to delete the audit entry")]
+async fn delete_audit_entry(world: &mut AppWorld) {
+  let user_id = world.audit.user.as_ref().unwrap().id;
+  let entry = entries_for(world, user_id).await.remove(0);
+
+  world.audit.change_rejected = entry.delete(&world.db).await.is_err();
+}
+
+#[when("I try to delete the practitioner")]
+async fn delete_practitioner(world: &mut AppWorld) {
+  let user = world.audit.user.clone().unwrap();
+
+  world.audit.change_rejected = user.delete(&world.db).await.is_err();
+}
+
+#[then(expr = "the audit trail contains {int} granted {string} entry for {string}")]
+async fn audit_trail_contains_granted(
+  world: &mut AppWorld,
+  count: usize,
+  action: String,
+  resource: String,
+) {
+  let user_id = world.audit.user.as_ref().unwrap().id;
+  let entries = entries_for(world, user_id).await;
+  assert_eq!(entries.len(), count);
+  assert!(entries
+    .iter()
+    .all(|e| e.granted && e.action == parse_action(&action) && e.resource_type == resource));
+}
+
+#[then(
+  expr = "the audit trail of the other practitioner contains {int} denied {string} entry for {string}"
+)]
+async fn audit_trail_contains_denied(
+  world: &mut AppWorld,
+  count: usize,
+  action: String,
+  resource: String,
+) {
+  let other_id = world.audit.user.as_ref().unwrap().id + 1;
+  let entries = entries_for(world, other_id).await;
+  assert_eq!(entries.len(), count);
+  assert!(entries
+    .iter()
+    .all(|e| !e.granted && e.action == parse_action(&action) && e.resource_type == resource));
+}
+
+#[then("the modification is rejected")]
+#[then("the deletion is rejected")]
+fn modification_rejected(world: &mut AppWorld) {
+  assert!(world.audit.change_rejected);
+}
+
+#[then(expr = "the audit trail still contains {int} entry")]
+async fn audit_trail_still_contains(world: &mut AppWorld, count: usize) {
+  let user_id = world.audit.user.as_ref().unwrap().id;
+  assert_eq!(entries_for(world, user_id).await.len(), count);
+}
@@ -0,0 +1,111 @@
+use chrono::NaiveDate;
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::PaymentMethod,
+    user_business_informations::CreateBusinessInformation,
+  },
+  services::{
+    check_deposits,
+    invoice::{self, GenerateInvoiceParams},
+    user,
+  },
+};
+
+use crate::AppWorld;
+
+#[given(expr = "I invoice {int} cents to {string} on {string}, paid by check")]
+async fn invoice_paid_by_check(
+  world: &mut AppWorld,
+  price_in_cents: i32,
+  last_name: String,
+  date: String,
+) {
+  let practitioner = world.payments.user.as_ref().unwrap();
+  let patient = world
+    .payments
+    .patients
+    .iter()
+    .find(|patient| patient.last_name == last_name)
+    .unwrap();
+  user::save_business_information(
+    &CreateBusinessInformation {
+      rpps_number: "10101010101".to_string(),
+      adeli_number: None,
+      siret_number: "12345678900012".to_string(),
+      profession: "general_practitioner".to_string(),
+    },
+    practitioner,
+  )
+  .await
+  .unwrap();
+
+  invoice::generate_patient_invoice(
+    patient.clone(),
+    &GenerateInvoiceParams {
+      amount: Some(price_in_cents as f32 / 100.0),
+      act_id: None,
+      invoice_date: date,
+      should_be_sent_by_email: false,
+      practitioner_office_id: world.payments.office.as_ref().unwrap().id,
+      payment_method: Some(PaymentMethod::Check),
+    },
+    practitioner,
+  )
+  .await
+  .unwrap();
+}
+
+#[when("I list the checks to deposit")]
+async fn list_pending_checks(world: &mut AppWorld) {
+  let user = world.payments.user.as_ref().unwrap();
+  world.check_deposits.pending = check_deposits::pending_checks(user).await.unwrap().len();
+}
+
+#[given(expr = "I deposit the checks on {string}")]
+#[when(expr = "I deposit the checks on {string}")]
+async fn deposit_checks(world: &mut AppWorld, deposited_on: String) {
+  let user = world.payments.user.as_ref().unwrap();
+  let deposited_on = NaiveDate::parse_from_str(&deposited_on, "%Y-%m-%d").unwrap();
+
+  match check_deposits::deposit(user, deposited_on).await {
+    Ok(summary) => {
+      world.check_deposits.slip = check_deposits::slip(&summary.deposit, user)
+        .await
+        .unwrap()
+        .data;
+      world.check_deposits.deposit = Some(summary);
+    }
+    Err(_) => world.check_deposits.deposit_failed = true,
+  }
+}
+
+#[when("I print the slip of the deposit again")]
+async fn print_slip_again(world: &mut AppWorld) {
+  let user = world.payments.user.as_ref().unwrap();
+  let deposit = &world.check_deposits.deposit.as_ref().unwrap().deposit;
+  world.check_deposits.slip = check_deposits::slip(deposit, user).await.unwrap().data;
+}
+
+#[then(expr = "{int} check is waiting to be deposited")]
+fn pending_checks(world: &mut AppWorld, expected: usize) {
+  assert_eq!(world.check_deposits.pending, expected);
+}
+
+#[then(expr = "the deposit {string} holds {int} checks for {int} cents")]
+fn deposit_holds(world: &mut AppWorld, reference: String, checks_count: i64, total: i64) {
+  let summary = world.check_deposits.deposit.as_ref().unwrap();
+  assert_eq!(summary.deposit.reference, reference);
+  assert_eq!(summary.checks_count, checks_count);
+  assert_eq!(summary.total_in_cents, total);
+}
+
+#[then("the deposit slip is a PDF document")]
+fn slip_is_pdf(world: &mut AppWorld) {
+  assert!(world.check_deposits.slip.starts_with(b"%PDF"));
+}
+
+#[then("the deposit is rejected")]
+fn deposit_rejected(world: &mut AppWorld) {
+  assert!(world.check_deposits.deposit_failed);
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use cucumber::{given, then, when};
+use opencab::{
+  auth::resource::{Permission, Resource},
+  config::Config,
+  models::{
+    _entities::{
+      email_logs::Column as EmailLogColumn,
+      sea_orm_active_enums::{AuditAction, EmailStatus, Locale},
+    },
+    email_logs, patients,
+  },
+  services::{self, invoice},
+  workers::mailer,
+};
+use sea_orm::{
+  ActiveEnum, ActiveModelTrait, ActiveValue::Set, ColumnTrait, IntoActiveModel, QueryFilter,
+};
+
+use crate::{factories::user::UserFactory, AppWorld};
+
+async fn patient_emails(world: &AppWorld) -> Vec<email_logs::Model> {
+  let practitioner = world.locales.user.as_ref().unwrap();
+  let patient = world.locales.patient.as_ref().unwrap();
+  email_logs::Entity::find_for_patient(practitioner.id, patient.id)
+    .all(&world.db)
+    .await
+    .unwrap()
+}
+
+async fn last_email(world: &AppWorld) -> email_logs::Model {
+  patient_emails(world).await.into_iter().next().unwrap()
+}
+
+#[given("the mail server is unreachable")]
+fn mail_server_unreachable(_world: &mut AppWorld) {
+  std::env::set_var("SMTP_SERVER_HOST", "127.0.0.1");
+  std::env::set_var("SMTP_SERVER_PORT", "1");
+  std::env::set_var("SMTP_AUTH_USER", "opencab@test.com");
+  std::env::set_var("SMTP_AUTH_PASSWORD", "password");
+}
+
+#[given("the invoice email is processed")]
+#[when("the invoice email is processed")]
+async fn invoice_email_processed(world: &mut AppWorld) {
+  let config = Arc::new(Config::load("test").unwrap());
+  let email = world.locales.email.clone().unwrap();
+  world.email_logs.delivery_failed = mailer::worker::process_email(email, &world.db, &config)
+    .await
+    .is_err();
+}
+
+#[given(expr = "the patient's email becomes {string}")]
+async fn patient_email_becomes(world: &mut AppWorld, email: String) {
+  let mut patient: patients::ActiveModel =
+    world.locales.patient.take().unwrap().into_active_model();
+  patient.email = Set(email);
+  world.locales.patient = Some(patient.update(&world.db).await.unwrap());
+}
+
+#[given(expr = "the patient now speaks {string}")]
+async fn patient_now_speaks(world: &mut AppWorld, locale: String) {
+  let mut patient: patients::ActiveModel =
+    world.locales.patient.take().unwrap().into_active_model();
+  patient.locale = Set(Some(Locale::try_from_value(&locale).unwrap()));
+  world.locales.patient = Some(patient.update(&world.db).await.unwrap());
+}
+
+#[when("I resend the last email of the patient")]
+async fn resend_last_email(world: &mut AppWorld) {
+  let email = invoice::resent_invoice_email(&last_email(world).await).unwrap();
+  world.locales.filename = Some(email.attachments[0].filename.clone());
+  world.email_logs.first_email = world.locales.email.take();
+  world.locales.email = Some(email);
+}
+
+#[when("another practitioner resends the last email of the patient")]
+async fn another_practitioner_resends(world: &mut AppWorld) {
+  let email_log_id = last_email(world).await.id;
+  let patient = world.locales.patient.as_ref().unwrap();
+  let other_practitioner = UserFactory::new()
+    .email("other.doctor@test.com")
+    .create(&world.db)
+    .await;
+
+  let allowed = patient
+    .permission_for_user(other_practitioner.id)
+    .await
+    .is_some_and(|permission| permission >= Permission::required_for(&AuditAction::Update));
+  let email_log = email_logs::Entity::find_for_patient(other_practitioner.id, patient.id)
+    .filter(EmailLogColumn::Id.eq(email_log_id))
+    .one(&world.db)
+    .await
+    .unwrap();
+
+  world.email_logs.resend_rejected = !allowed && email_log.is_none();
+}
+
+#[when("the patient is anonymized")]
+async fn patient_anonymized(world: &mut AppWorld) {
+  let patient = world.locales.patient.clone().unwrap();
+  services::patients::anonymize(patient).await.unwrap();
+}
+
+#[then(expr = "the patient has {int} email(s) in their history")]
+async fn patient_has_emails(world: &mut AppWorld, count: usize) {
+  assert_eq!(patient_emails(world).await.len(), count);
+}
+
+#[then(expr = "the last email of the patient is a {string} email titled {string}")]
+async fn last_email_template(world: &mut AppWorld, template: String, subject: String) {
+  let email_log = last_email(world).await;
+  assert_eq!(email_log.template, Some(template));
+  assert_eq!(email_log.subject, subject);
+}
+
+#[then(expr = "the last email of the patient was sent to {string}")]
+async fn last_email_recipient(world: &mut AppWorld, recipient: String) {
+  assert_eq!(last_email(world).await.recipient, recipient);
+}
+
+#[then(expr = "the last email of the patient has the attachment {string}")]
+async fn last_email_attachment(world: &mut AppWorld, filename: String) {
+  assert_eq!(last_email(world).await.attachment_names(), vec![filename]);
+}
+
+#[then("the last email of the patient is tied to its appointment")]
+async fn last_email_tied_to_appointment(world: &mut AppWorld) {
+  assert!(last_email(world).await.medical_appointment_id.is_some());
+}
+
+#[then("the last email of the patient failed with an error")]
+async fn last_email_failed(world: &mut AppWorld) {
+  let email_log = last_email(world).await;
+  assert!(world.email_logs.delivery_failed);
+  assert_eq!(email_log.status, EmailStatus::Failed);
+  assert!(email_log.error.is_some());
+  assert!(email_log.sent_at.is_none());
+}
+
+#[then(expr = "the invoice email is sent to {string}")]
+fn invoice_email_sent_to(world: &mut AppWorld, recipient: String) {
+  assert_eq!(world.locales.email.as_ref().unwrap().to, recipient);
+}
+
+#[then("the resent invoice is the one first sent")]
+fn resent_invoice_unchanged(world: &mut AppWorld) {
+  let first_email = world.email_logs.first_email.as_ref().unwrap();
+  let resent_email = world.locales.email.as_ref().unwrap();
+  assert_eq!(resent_email.to, first_email.to);
+  assert_eq!(resent_email.text_body, first_email.text_body);
+  assert_eq!(
+    resent_email.attachments[0].data,
+    first_email.attachments[0].data
+  );
+}
+
+#[then("the resend is rejected")]
+fn resend_rejected(world: &mut AppWorld) {
+  assert!(world.email_logs.resend_rejected);
+}
@@ -0,0 +1,68 @@
+use cucumber::{given, then};
+use opencab::{
+  models::invoice_templates::{self, InvoiceTemplate},
+  services::user,
+  workers::mailer::args::EmailArgs,
+};
+
+use crate::AppWorld;
+
+/// Save the practitioner's invoice template once changed by `change`
+async fn change_template(world: &mut AppWorld, change: impl FnOnce(&mut InvoiceTemplate)) {
+  let practitioner = world.locales.user.as_ref().unwrap();
+  let mut template = invoice_templates::Entity::find_for_user(practitioner.id)
+    .one(&world.db)
+    .await
+    .unwrap()
+    .as_ref()
+    .map(invoice_templates::Model::template)
+    .unwrap_or_default();
+  change(&mut template);
+
+  invoice_templates::ActiveModel::save_template(&world.db, practitioner.id, &template)
+    .await
+    .unwrap();
+}
+
+#[given(expr = "the practitioner signs their emails {string}")]
+async fn signs_emails(world: &mut AppWorld, signature: String) {
+  change_template(world, |template| template.email_signature = Some(signature)).await;
+}
+
+#[given(expr = "the practitioner's notes are coloured {string}")]
+async fn notes_coloured(world: &mut AppWorld, accent_color: String) {
+  change_template(world, |template| template.accent_color = accent_color).await;
+}
+
+fn invoice_email(world: &AppWorld) -> &EmailArgs {
+  world.locales.email.as_ref().unwrap()
+}
+
+fn access_code_email(world: &AppWorld) -> EmailArgs {
+  let practitioner = world.locales.user.as_ref().unwrap();
+  user::access_key_email(practitioner, "https://opencab.test").unwrap()
+}
+
+#[then(expr = "the invoice email HTML contains {string}")]
+fn invoice_email_html_contains(world: &mut AppWorld, fragment: String) {
+  let html = invoice_email(world).html_body.as_deref().unwrap();
+  assert!(html.contains(&fragment), "{}", html);
+}
+
+#[then(expr = "the invoice email text ends with {string}")]
+fn invoice_email_text_ends_with(world: &mut AppWorld, text: String) {
+  let body = &invoice_email(world).text_body;
+  assert!(body.ends_with(&text), "{}", body);
+}
+
+#[then(expr = "the access code email HTML contains {string}")]
+fn access_code_email_html_contains(world: &mut AppWorld, fragment: String) {
+  let html = access_code_email(world).html_body.unwrap();
+  assert!(html.contains(&fragment), "{}", html);
+}
+
+#[then(expr = "the access code email text contains {string}")]
+fn access_code_email_text_contains(world: &mut AppWorld, text: String) {
+  let body = access_code_email(world).text_body;
+  assert!(body.contains(&text), "{}", body);
+}
@@ -0,0 +1,459 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::user_business_informations::CreateBusinessInformation,
+  services::{factur_x, user},
+  workers::invoice_generator::{self, InvoiceGeneratorArgs},
+};
+use quick_xml::{events::Event, Reader};
+
+use crate::{
+  factories::{
+    medical_appointment::AppointmentFactory, office::OfficeFactory, patient::PatientFactory,
+    user::UserFactory,
+  },
+  AppWorld,
+};
+
+/// Children each CII element may have, in the order of the D16B schema, for
+/// the aggregates of the BASIC WL profile. Required ones are marked with `!`.
+const ELEMENT_ORDER: [(&str, &[&str]); 12] = [
+  (
+    "CrossIndustryInvoice",
+    &[
+      "!ExchangedDocumentContext",
+      "!ExchangedDocument",
+      "!SupplyChainTradeTransaction",
+    ],
+  ),
+  (
+    "ExchangedDocumentContext",
+    &[
+      "BusinessProcessSpecifiedDocumentContextParameter",
+      "!GuidelineSpecifiedDocumentContextParameter",
+    ],
+  ),
+  (
+    "ExchangedDocument",
+    &["!ID", "!TypeCode", "!IssueDateTime", "IncludedNote"],
+  ),
+  ("IssueDateTime", &["!DateTimeString"]),
+  ("IncludedNote", &["!Content", "SubjectCode"]),
+  (
+    "SupplyChainTradeTransaction",
+    &[
+      "!ApplicableHeaderTradeAgreement",
+      "!ApplicableHeaderTradeDelivery",
+      "!ApplicableHeaderTradeSettlement",
+    ],
+  ),
+  (
+    "ApplicableHeaderTradeAgreement",
+    &[
+      "BuyerReference",
+      "!SellerTradeParty",
+      "!BuyerTradeParty",
+      "SellerTaxRepresentativeTradeParty",
+      "BuyerOrderReferencedDocument",
+      "ContractReferencedDocument",
+    ],
+  ),
+  (
+    "SellerTradeParty",
+    &[
+      "ID",
+      "GlobalID",
+      "!Name",
+      "SpecifiedLegalOrganization",
+      "!PostalTradeAddress",
+      "URIUniversalCommunication",
+      "SpecifiedTaxRegistration",
+    ],
+  ),
+  (
+    "BuyerTradeParty",
+    &[
+      "ID",
+      "GlobalID",
+      "!Name",
+      "SpecifiedLegalOrganization",
+      "PostalTradeAddress",
+      "URIUniversalCommunication",
+      "SpecifiedTaxRegistration",
+    ],
+  ),
+  (
+    "PostalTradeAddress",
+    &[
+      "PostcodeCode",
+      "LineOne",
+      "LineTwo",
+      "LineThree",
+      "CityName",
+      "!CountryID",
+      "CountrySubDivisionName",
+    ],
+  ),
+  (
+    "ApplicableTradeTax",
+    &[
+      "!CalculatedAmount",
+      "!TypeCode",
+      "ExemptionReason",
+      "!BasisAmount",
+      "!CategoryCode",
+      "ExemptionReasonCode",
+      "DueDateTypeCode",
+      "RateApplicablePercent",
+    ],
+  ),
+  (
+    "SpecifiedTradeSettlementHeaderMonetarySummation",
+    &[
+      "!LineTotalAmount",
+      "ChargeTotalAmount",
+      "AllowanceTotalAmount",
+      "!TaxBasisTotalAmount",
+      "TaxTotalAmount",
+      "RoundingAmount",
+      "!GrandTotalAmount",
+      "TotalPrepaidAmount",
+      "!DuePayableAmount",
+    ],
+  ),
+];
+
+/// Element of the XML, without namespace prefixes
+#[derive(Debug, Default)]
+struct Element {
+  name: String,
+  attributes: Vec<(String, String)>,
+  text: String,
+  children: Vec<Element>,
+}
+
+impl Element {
+  fn child(&self, name: &str) -> Option<&Element> {
+    self.children.iter().find(|child| child.name == name)
+  }
+
+  /// Descendant at `path`, e.g. `ExchangedDocument/ID`
+  fn at(&self, path: &str) -> Option<&Element> {
+    path
+      .split('/')
+      .try_fold(self, |element, name| element.child(name))
+  }
+
+  fn text_at(&self, path: &str) -> &str {
+    self
+      .at(path)
+      .map(|element| element.text.as_str())
+      .unwrap_or_else(|| panic!("{} is missing", path))
+  }
+
+  fn cents_at(&self, path: &str) -> i64 {
+    let amount = self.text_at(path);
+    let (euros, cents) = amount.split_once('.').unwrap_or((amount, "00"));
+    assert_eq!(cents.len(), 2, "{} has not 2 decimals: {}", path, amount);
+    euros.parse::<i64>().unwrap() * 100 + cents.parse::<i64>().unwrap()
+  }
+
+  /// Text of every element, keyed by its path
+  fn texts(&self, parent: &str) -> Vec<(String, String)> {
+    let path = format!("{}/{}", parent, self.name);
+    let mut texts = vec![(path.clone(), self.text.clone())];
+    for child in &self.children {
+      texts.extend(child.texts(&path));
+    }
+    texts
+  }
+
+  fn elements(&self) -> Box<dyn Iterator<Item = &Element> + '_> {
+    Box::new(std::iter::once(self).chain(self.children.iter().flat_map(Element::elements)))
+  }
+}
+
+/// Children of the aggregates listed in [`ELEMENT_ORDER`] must come in the
+/// order of the schema, the required ones present
+fn check_element_order(root: &Element) {
+  for element in root.elements() {
+    let Some((_, allowed)) = ELEMENT_ORDER.iter().find(|(name, _)| *name == element.name) else {
+      continue;
+    };
+    let positions: Vec<usize> = element
+      .children
+      .iter()
+      .map(|child| {
+        allowed
+          .iter()
+          .position(|name| name.trim_start_matches('!') == child.name)
+          .unwrap_or_else(|| panic!("{} is not allowed in {}", child.name, element.name))
+      })
+      .collect();
+    assert!(
+      positions.is_sorted(),
+      "children of {} are out of order",
+      element.name
+    );
+    for required in allowed.iter().filter_map(|name| name.strip_prefix('!')) {
+      assert!(
+        element.child(required).is_some(),
+        "{} is missing in {}",
+        required,
+        element.name
+      );
+    }
+  }
+}
+
+/// Business rules of EN 16931 the BASIC WL profile checks on the document
+/// level data of an invoice exempt from VAT
+fn check_business_rules(root: &Element) {
+  let transaction = root.child("SupplyChainTradeTransaction").unwrap();
+  let agreement = transaction.child("ApplicableHeaderTradeAgreement").unwrap();
+  let settlement = transaction
+    .child("ApplicableHeaderTradeSettlement")
+    .unwrap();
+  let seller = agreement.child("SellerTradeParty").unwrap();
+
+  // BR-01 to BR-09
+  assert_eq!(
+    root.text_at("ExchangedDocumentContext/GuidelineSpecifiedDocumentContextParameter/ID"),
+    factur_x::PROFILE_ID
+  );
+  assert!(!root.text_at("ExchangedDocument/ID").is_empty());
+  assert!(["380", "381", "384", "389", "751"].contains(&root.text_at("ExchangedDocument/TypeCode")));
+  let issue_date = root
+    .at("ExchangedDocument/IssueDateTime/DateTimeString")
+    .unwrap();
+  assert_eq!(
+    issue_date.attributes,
+    vec![("format".to_string(), "102".to_string())]
+  );
+  assert!(chrono::NaiveDate::parse_from_str(&issue_date.text, "%Y%m%d").is_ok());
+  assert_eq!(settlement.text_at("InvoiceCurrencyCode"), "EUR");
+  assert!(!seller.text_at("Name").is_empty());
+  assert!(!agreement.text_at("BuyerTradeParty/Name").is_empty());
+  assert_eq!(seller.text_at("PostalTradeAddress/CountryID"), "FR");
+
+  // BR-CO-10 to BR-CO-16, without allowances nor charges
+  let totals = settlement
+    .child("SpecifiedTradeSettlementHeaderMonetarySummation")
+    .unwrap();
+  let line_total = totals.cents_at("LineTotalAmount");
+  let tax_basis = totals.cents_at("TaxBasisTotalAmount");
+  let tax_total = totals.cents_at("TaxTotalAmount");
+  let grand_total = totals.cents_at("GrandTotalAmount");
+  let prepaid = totals.cents_at("TotalPrepaidAmount");
+  assert_eq!(tax_basis, line_total);
+  assert_eq!(grand_total, tax_basis + tax_total);
+  assert_eq!(totals.cents_at("DuePayableAmount"), grand_total - prepaid);
+  assert_eq!(
+    totals.at("TaxTotalAmount").unwrap().attributes,
+    vec![("currencyID".to_string(), "EUR".to_string())]
+  );
+
+  // BR-E-01 to BR-E-10: a single breakdown of the exempt amount
+  let taxes: Vec<&Element> = settlement
+    .children
+    .iter()
+    .filter(|child| child.name == "ApplicableTradeTax")
+    .collect();
+  assert_eq!(taxes.len(), 1);
+  let tax = taxes[0];
+  assert_eq!(tax.text_at("TypeCode"), "VAT");
+  assert_eq!(tax.text_at("CategoryCode"), "E");
+  assert_eq!(tax.cents_at("CalculatedAmount"), 0);
+  assert_eq!(tax.cents_at("BasisAmount"), tax_basis);
+  assert_eq!(tax.text_at("RateApplicablePercent"), "0");
+  assert!(!tax.text_at("ExemptionReason").is_empty());
+  assert_eq!(tax_total, 0);
+  assert!(
+    seller
+      .children
+      .iter()
+      .filter(|child| child.name == "SpecifiedTaxRegistration")
+      .any(|registration| !registration.text_at("ID").is_empty()),
+    "BR-E-02: the seller has no VAT nor tax registration identifier"
+  );
+}
+
+fn field_path(field: &str) -> &'static str {
+  match field {
+    "profile" => "GuidelineSpecifiedDocumentContextParameter/ID",
+    "issue date" => "ExchangedDocument/IssueDateTime/DateTimeString",
+    "seller" => "SellerTradeParty/Name",
+    "seller SIRET" => "SellerTradeParty/SpecifiedLegalOrganization/ID",
+    "seller tax registration" => "SellerTradeParty/SpecifiedTaxRegistration/ID",
+    "buyer" => "BuyerTradeParty/Name",
+    "grand total" => "GrandTotalAmount",
+    "amount due" => "DuePayableAmount",
+    "VAT exemption reason" => "ApplicableTradeTax/ExemptionReason",
+    _ => panic!("Unknown Factur-X field {}", field),
+  }
+}
+
+/// The attachment is stored uncompressed, right after its dictionary
+fn embedded_xml(pdf: &[u8]) -> String {
+  let pdf = String::from_utf8_lossy(pdf);
+  let file = pdf.find("/Type /EmbeddedFile").unwrap();
+  let start = file + pdf[file..].find("stream\n").unwrap() + "stream\n".len();
+  let end = start + pdf[start..].find("\nendstream").unwrap();
+  pdf[start..end].to_string()
+}
+
+/// Tree of the XML. Panics on malformed XML.
+fn parse_xml(xml: &str) -> Element {
+  fn element(start: &quick_xml::events::BytesStart) -> Element {
+    Element {
+      name: String::from_utf8(start.local_name().as_ref().to_vec()).unwrap(),
+      attributes: start
+        .attributes()
+        .map(|attribute| {
+          let attribute = attribute.unwrap();
+          (
+            String::from_utf8(attribute.key.local_name().as_ref().to_vec()).unwrap(),
+            attribute.unescape_value().unwrap().into_owned(),
+          )
+        })
+        .collect(),
+      ..Default::default()
+    }
+  }
+
+  let mut reader = Reader::from_str(xml);
+  reader.trim_text(true);
+  let mut stack = vec![Element::default()];
+  loop {
+    match reader.read_event().unwrap() {
+      Event::Start(start) => stack.push(element(&start)),
+      Event::Empty(start) => stack.last_mut().unwrap().children.push(element(&start)),
+      Event::End(_) => {
+        let closed = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(closed);
+      }
+      Event::Text(text) => stack.last_mut().unwrap().text = text.unescape().unwrap().into_owned(),
+      Event::Eof => break,
+      _ => {}
+    }
+  }
+  assert_eq!(stack.len(), 1);
+  let mut document = stack.pop().unwrap();
+  assert_eq!(document.children.len(), 1);
+  document.children.pop().unwrap()
+}
+
+fn factur_x_field(world: &AppWorld, field: &str) -> String {
+  let path = field_path(field);
+  parse_xml(&embedded_xml(&world.factur_x.pdf))
+    .texts("")
+    .into_iter()
+    .find(|(element, _)| element.ends_with(path))
+    .map(|(_, text)| text)
+    .unwrap_or_else(|| panic!("No {} in the Factur-X XML", path))
+}
+
+#[given(expr = "a practitioner with SIRET {string} and a patient named {string} {string}")]
+async fn practitioner_with_siret(
+  world: &mut AppWorld,
+  siret: String,
+  first_name: String,
+  last_name: String,
+) {
+  let practitioner = match world.factur_x.user.take() {
+    Some(practitioner) => practitioner,
+    None => {
+      let practitioner = UserFactory::new().create(&world.db).await;
+      user::save_business_information(
+        &CreateBusinessInformation {
+          rpps_number: "10101010101".to_string(),
+          adeli_number: None,
+          siret_number: siret,
+          profession: "general_practitioner".to_string(),
+        },
+        &practitioner,
+      )
+      .await
+      .unwrap();
+      world.factur_x.office = Some(OfficeFactory::new().create(&world.db).await);
+      practitioner
+    }
+  };
+  world.factur_x.patient = Some(
+    PatientFactory::new()
+      .first_name(&first_name)
+      .last_name(&last_name)
+      .create(&world.db, practitioner.id)
+      .await,
+  );
+  world.factur_x.user = Some(practitioner);
+}
+
+#[when(expr = "I generate the invoice of an appointment on {string} for {int} cents")]
+async fn generate_invoice(world: &mut AppWorld, date: String, price: i32) {
+  let state = &world.factur_x;
+  let (user, patient, office) = (
+    state.user.clone().unwrap(),
+    state.patient.clone().unwrap(),
+    state.office.clone().unwrap(),
+  );
+  let appointment = AppointmentFactory::new()
+    .date(&date)
+    .price(price)
+    .create(&world.db, user.id, patient.id, office.id)
+    .await;
+
+  world.factur_x.pdf = invoice_generator::generate_invoice_pdf(
+    &world.db,
+    &InvoiceGeneratorArgs {
+      appointment_id: appointment.id,
+      patient,
+      user,
+      amount: price as f32 / 100.0,
+      act_label: None,
+      invoice_date: appointment.date,
+      practitioner_office: office,
+    },
+  )
+  .await
+  .unwrap();
+}
+
+#[then("the invoice declares PDF/A-3 conformance")]
+fn declares_pdf_a3(world: &mut AppWorld) {
+  let pdf = String::from_utf8_lossy(&world.factur_x.pdf);
+  assert!(pdf.contains("<pdfaid:part>3</pdfaid:part>"));
+  assert!(pdf.contains("<pdfaid:conformance>B</pdfaid:conformance>"));
+  assert!(pdf.contains("/OutputIntents ["));
+  assert!(pdf.trim_end().ends_with("%%EOF"));
+}
+
+#[then(expr = "the invoice embeds {string}")]
+fn embeds_attachment(world: &mut AppWorld, file_name: String) {
+  assert_eq!(file_name, factur_x::ATTACHMENT_NAME);
+  let pdf = String::from_utf8_lossy(&world.factur_x.pdf);
+  assert!(pdf.contains(&format!("/EmbeddedFiles << /Names [({})", file_name)));
+  assert!(pdf.contains("/AFRelationship /Data"));
+  assert!(pdf.contains("/AF ["));
+}
+
+#[then("the embedded Factur-X XML is valid")]
+fn valid_xml(world: &mut AppWorld) {
+  let root = parse_xml(&embedded_xml(&world.factur_x.pdf));
+  assert_eq!(root.name, "CrossIndustryInvoice");
+  check_element_order(&root);
+  check_business_rules(&root);
+}
+
+#[then("the invoice embeds the fonts it is written with")]
+fn embeds_fonts(world: &mut AppWorld) {
+  let pdf = String::from_utf8_lossy(&world.factur_x.pdf);
+  assert!(pdf.contains("/BaseFont /DejaVuSans"));
+  assert!(pdf.contains("/BaseFont /DejaVuSans-Bold"));
+  // One font program for the regular and one for the bold font
+  assert_eq!(pdf.matches("/FontFile2").count(), 2);
+}
+
+#[then(expr = "the Factur-X {string} is {string}")]
+fn factur_x_value(world: &mut AppWorld, field: String, value: String) {
+  assert_eq!(factur_x_field(world, &field), value);
+}
@@ -0,0 +1,174 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::invoice_templates::{self, InvoiceTemplate},
+  workers::invoice_generator,
+};
+use sea_orm::{EntityTrait, PaginatorTrait};
+
+use crate::AppWorld;
+
+#[given(
+  expr = "I save an invoice template titled {string} in {string} with footer {string} hiding the social security number"
+)]
+#[when(
+  expr = "I save an invoice template titled {string} in {string} with footer {string} hiding the social security number"
+)]
+async fn save_template(world: &mut AppWorld, title: String, accent_color: String, footer: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let template = InvoiceTemplate {
+    title,
+    accent_color,
+    footer_text: Some(footer),
+    show_ssn: false,
+    ..Default::default()
+  };
+  world.invoice_templates.rejected =
+    invoice_templates::ActiveModel::save_template(&world.db, user.id, &template)
+      .await
+      .is_err();
+}
+
+#[given(expr = "I save an invoice template with the legal mentions {string}")]
+async fn save_template_with_legal_mentions(world: &mut AppWorld, legal_mentions: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let template = InvoiceTemplate {
+    legal_mentions: Some(legal_mentions),
+    ..Default::default()
+  };
+  invoice_templates::ActiveModel::save_template(&world.db, user.id, &template)
+    .await
+    .unwrap();
+}
+
+#[given(expr = "I save an invoice template with a header and a footer of {int} characters")]
+async fn save_template_with_long_texts(world: &mut AppWorld, length: usize) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let text: String = "Consultations sur rendez-vous du lundi au samedi "
+    .chars()
+    .cycle()
+    .take(length)
+    .collect();
+  let template = InvoiceTemplate {
+    header_text: Some(text.clone()),
+    footer_text: Some(text),
+    ..Default::default()
+  };
+  invoice_templates::ActiveModel::save_template(&world.db, user.id, &template)
+    .await
+    .unwrap();
+}
+
+#[given(expr = "my invoice logo is {string}")]
+async fn set_logo(world: &mut AppWorld, logo_file_name: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  invoice_templates::ActiveModel::set_logo(&world.db, user.id, Some(logo_file_name))
+    .await
+    .unwrap();
+}
+
+#[when("I look at my invoice template")]
+async fn look_at_template(world: &mut AppWorld) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let stored = invoice_templates::Entity::find_for_user(user.id)
+    .one(&world.db)
+    .await
+    .unwrap();
+  world.invoice_templates.template = Some(
+    stored
+      .as_ref()
+      .map(invoice_templates::Model::template)
+      .unwrap_or_default(),
+  );
+  world.invoice_templates.logo_file_name = stored.and_then(|stored| stored.logo_file_name);
+}
+
+fn template(world: &AppWorld) -> &InvoiceTemplate {
+  world.invoice_templates.template.as_ref().unwrap()
+}
+
+#[then(expr = "my invoice title is {string}")]
+fn invoice_title(world: &mut AppWorld, title: String) {
+  assert_eq!(template(world).title, title);
+}
+
+#[then(expr = "my invoice accent colour is {string}")]
+fn invoice_accent_color(world: &mut AppWorld, accent_color: String) {
+  assert_eq!(template(world).accent_color, accent_color);
+}
+
+#[then(expr = "my invoice legal mentions are {string}")]
+fn invoice_legal_mentions(world: &mut AppWorld, legal_mentions: String) {
+  assert_eq!(template(world).legal_mentions, Some(legal_mentions));
+}
+
+#[then("my invoice has no additional legal mentions")]
+fn invoice_without_legal_mentions(world: &mut AppWorld) {
+  assert_eq!(template(world).legal_mentions, None);
+}
+
+#[then(expr = "my invoice footer ends with {string}")]
+fn invoice_footer_ends_with(world: &mut AppWorld, line: String) {
+  let footer = invoice_generator::footer_lines(template(world));
+  assert_eq!(footer.last(), Some(&line), "{:?}", footer);
+}
+
+#[then(expr = "my invoice footer is printed on {int} lines of at most {int} characters")]
+fn invoice_footer_fits(world: &mut AppWorld, lines: usize, chars: usize) {
+  let footer = invoice_generator::footer_lines(template(world));
+  assert_eq!(footer.len(), lines, "{:?}", footer);
+  assert!(
+    footer.iter().all(|line| line.chars().count() <= chars),
+    "{:?}",
+    footer
+  );
+}
+
+#[then(expr = "my invoice header is printed on {int} lines of at most {int} characters")]
+fn invoice_header_fits(world: &mut AppWorld, lines: usize, chars: usize) {
+  let header = invoice_generator::header_lines(template(world));
+  assert_eq!(header.len(), lines, "{:?}", header);
+  assert!(
+    header.iter().all(|line| line.chars().count() <= chars),
+    "{:?}",
+    header
+  );
+}
+
+#[then(expr = "my invoice footer is {string}")]
+fn invoice_footer(world: &mut AppWorld, footer: String) {
+  assert_eq!(template(world).footer_text, Some(footer));
+}
+
+#[then("my invoice has no footer")]
+fn invoice_without_footer(world: &mut AppWorld) {
+  assert_eq!(template(world).footer_text, None);
+}
+
+#[then(expr = "my invoice logo is {string}")]
+fn invoice_logo(world: &mut AppWorld, logo_file_name: String) {
+  assert_eq!(world.invoice_templates.logo_file_name, Some(logo_file_name));
+}
+
+#[then("the social security number is shown on my invoices")]
+fn ssn_shown(world: &mut AppWorld) {
+  assert!(template(world).show_ssn);
+}
+
+#[then("the social security number is hidden on my invoices")]
+fn ssn_hidden(world: &mut AppWorld) {
+  assert!(!template(world).show_ssn);
+}
+
+#[then(expr = "I have {int} invoice template")]
+async fn templates_count(world: &mut AppWorld, count: u64) {
+  let stored = invoice_templates::Entity::find()
+    .count(&world.db)
+    .await
+    .unwrap();
+  assert_eq!(stored, count);
+}
+
+#[then("the invoice template is rejected")]
+fn template_rejected(world: &mut AppWorld) {
+  assert!(world.invoice_templates.rejected);
+}
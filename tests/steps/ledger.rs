@@ -0,0 +1,121 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::ExpenseCategory,
+    expenses::{self, CreateExpenseParams},
+  },
+  services::{appointments::ToExcel, ledger},
+};
+
+use crate::AppWorld;
+
+fn parse_category(category: &str) -> ExpenseCategory {
+  serde_json::from_value(serde_json::Value::String(category.to_string())).unwrap()
+}
+
+async fn record_expense(
+  world: &mut AppWorld,
+  label: &str,
+  amount_in_cents: i32,
+  category: &str,
+  spent_on: &str,
+) {
+  let user_id = world.payments.user.as_ref().unwrap().id;
+  let result = expenses::ActiveModel::create(
+    &world.db,
+    &CreateExpenseParams {
+      spent_on: spent_on.to_string(),
+      category: parse_category(category),
+      label: label.to_string(),
+      amount_in_cents,
+      payment_method: None,
+    },
+    user_id,
+  )
+  .await;
+  world.ledger.expense_failed = result.is_err();
+}
+
+#[given(expr = "an expense {string} of {int} cents in {string} on {string}")]
+async fn an_expense(
+  world: &mut AppWorld,
+  label: String,
+  amount_in_cents: i32,
+  category: String,
+  spent_on: String,
+) {
+  record_expense(world, &label, amount_in_cents, &category, &spent_on).await;
+  assert!(!world.ledger.expense_failed);
+}
+
+#[when(expr = "I record an expense {string} of {int} cents in {string} on {string}")]
+async fn record_an_expense(
+  world: &mut AppWorld,
+  label: String,
+  amount_in_cents: i32,
+  category: String,
+  spent_on: String,
+) {
+  record_expense(world, &label, amount_in_cents, &category, &spent_on).await;
+}
+
+#[when(expr = "I build the fiscal year report of {int}")]
+async fn build_report(world: &mut AppWorld, year: i32) {
+  let user = world.payments.user.as_ref().unwrap();
+  world.ledger.report = Some(ledger::fiscal_year_report(user, year).await.unwrap());
+}
+
+#[when(expr = "I download the fiscal year report of {int}")]
+async fn download_report(world: &mut AppWorld, year: i32) {
+  let user = world.payments.user.as_ref().unwrap();
+  let report = ledger::fiscal_year_report(user, year).await.unwrap();
+  world.ledger.spreadsheet = report.to_excel().unwrap().save_to_buffer().unwrap();
+}
+
+#[then(expr = "the receipts book holds {int} receipts for {int} cents")]
+fn receipts_book_holds(world: &mut AppWorld, count: usize, total: i64) {
+  let report = world.ledger.report.as_ref().unwrap();
+  assert_eq!(report.receipts.len(), count);
+  assert_eq!(report.total_receipts_in_cents, total);
+}
+
+#[then(expr = "the expenses in {string} amount to {int} cents")]
+fn expenses_in_category(world: &mut AppWorld, category: String, expected: i64) {
+  let category = parse_category(&category);
+  let report = world.ledger.report.as_ref().unwrap();
+  let total = report
+    .expenses_by_category
+    .iter()
+    .find(|total| total.category == category)
+    .map(|total| total.amount_in_cents)
+    .unwrap_or_default();
+  assert_eq!(total, expected);
+}
+
+#[then(expr = "the total expenses amount to {int} cents")]
+fn total_expenses(world: &mut AppWorld, expected: i64) {
+  let report = world.ledger.report.as_ref().unwrap();
+  assert_eq!(report.total_expenses_in_cents, expected);
+}
+
+#[then(expr = "the net receipts amount to {int} cents")]
+fn net_receipts(world: &mut AppWorld, expected: i64) {
+  let report = world.ledger.report.as_ref().unwrap();
+  assert_eq!(report.net_receipts_in_cents, expected);
+}
+
+#[then(expr = "the result amounts to {int} cents")]
+fn result_amount(world: &mut AppWorld, expected: i64) {
+  let report = world.ledger.report.as_ref().unwrap();
+  assert_eq!(report.result_in_cents, expected);
+}
+
+#[then("the expense is rejected")]
+fn expense_rejected(world: &mut AppWorld) {
+  assert!(world.ledger.expense_failed);
+}
+
+#[then("the report is a spreadsheet")]
+fn report_is_spreadsheet(world: &mut AppWorld) {
+  assert!(world.ledger.spreadsheet.starts_with(b"PK"));
+}
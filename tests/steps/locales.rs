@@ -0,0 +1,161 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::Locale,
+    invoice_templates,
+    revenue_share_rates::RetrocessionTerms,
+    user_business_informations::CreateBusinessInformation,
+    user_practitioner_offices::{self, CreateLinkParams},
+    users,
+  },
+  services::{
+    invoice::{self, GenerateInvoiceParams},
+    user,
+  },
+};
+use sea_orm::{prelude::Decimal, ActiveEnum, IntoActiveModel};
+
+use crate::{
+  factories::{office::OfficeFactory, patient::PatientFactory, user::UserFactory},
+  AppWorld,
+};
+
+#[given("a general practitioner with an office")]
+async fn practitioner_with_office(world: &mut AppWorld) {
+  let practitioner = UserFactory::new().create(&world.db).await;
+  user::save_business_information(
+    &CreateBusinessInformation {
+      rpps_number: "10101010101".to_string(),
+      adeli_number: None,
+      siret_number: "12345678900012".to_string(),
+      profession: "general_practitioner".to_string(),
+    },
+    &practitioner,
+  )
+  .await
+  .unwrap();
+  let office = OfficeFactory::new().create(&world.db).await;
+  user_practitioner_offices::ActiveModel::create(
+    &world.db,
+    &CreateLinkParams {
+      user_id: practitioner.id,
+      practitioner_office_id: office.id,
+      terms: RetrocessionTerms::percentage(Decimal::from(100)),
+    },
+  )
+  .await
+  .unwrap();
+  world.locales.office = Some(office);
+  world.locales.user = Some(practitioner);
+}
+
+#[given(expr = "the practitioner switches to {string}")]
+async fn practitioner_switches(world: &mut AppWorld, locale: String) {
+  let practitioner = world.locales.user.take().unwrap();
+  world.locales.user = Some(
+    practitioner
+      .into_active_model()
+      .set_locale(&world.db, Locale::try_from_value(&locale).unwrap())
+      .await
+      .unwrap(),
+  );
+}
+
+#[given(expr = "a patient speaking {string}")]
+async fn patient_speaking(world: &mut AppWorld, locale: String) {
+  let practitioner = world.locales.user.as_ref().unwrap();
+  world.locales.patient = Some(
+    PatientFactory::new()
+      .locale(Locale::try_from_value(&locale).unwrap())
+      .create(&world.db, practitioner.id)
+      .await,
+  );
+}
+
+#[given("a patient without a language")]
+async fn patient_without_language(world: &mut AppWorld) {
+  let practitioner = world.locales.user.as_ref().unwrap();
+  world.locales.patient = Some(
+    PatientFactory::new()
+      .create(&world.db, practitioner.id)
+      .await,
+  );
+}
+
+#[given(expr = "I invoice the patient {int} cents on {string}")]
+#[when(expr = "I invoice the patient {int} cents on {string}")]
+async fn invoice_patient(world: &mut AppWorld, price: i32, date: String) {
+  let state = &world.locales;
+  let practitioner = state.user.as_ref().unwrap();
+  let generated = invoice::generate_patient_invoice(
+    state.patient.clone().unwrap(),
+    &GenerateInvoiceParams {
+      amount: Some(price as f32 / 100.0),
+      act_id: None,
+      invoice_date: date,
+      should_be_sent_by_email: true,
+      practitioner_office_id: state.office.as_ref().unwrap().id,
+      payment_method: None,
+    },
+    practitioner,
+  )
+  .await
+  .unwrap();
+  let (_, business_information) =
+    users::Model::find_by_pid(&world.db, &practitioner.pid.to_string())
+      .await
+      .unwrap();
+  let template = invoice_templates::Entity::find_for_user(practitioner.id)
+    .one(&world.db)
+    .await
+    .unwrap()
+    .as_ref()
+    .map(invoice_templates::Model::template)
+    .unwrap_or_default();
+
+  world.locales.email = Some(
+    invoice::invoice_email(
+      &generated,
+      practitioner,
+      &business_information.unwrap(),
+      &template,
+    )
+    .unwrap(),
+  );
+  world.locales.filename = Some(generated.filename);
+}
+
+#[then(expr = "the invoice file is named {string}")]
+fn invoice_file_named(world: &mut AppWorld, filename: String) {
+  assert_eq!(world.locales.filename.as_deref(), Some(filename.as_str()));
+  let email = world.locales.email.as_ref().unwrap();
+  assert_eq!(email.attachments[0].filename, filename);
+}
+
+#[then(expr = "the invoice email subject is {string}")]
+fn invoice_email_subject(world: &mut AppWorld, subject: String) {
+  assert_eq!(world.locales.email.as_ref().unwrap().subject, subject);
+}
+
+#[then(expr = "the invoice email body reads {string}")]
+fn invoice_email_body(world: &mut AppWorld, text: String) {
+  let body = &world.locales.email.as_ref().unwrap().text_body;
+  assert!(body.starts_with(&text), "{}", body);
+}
+
+#[then(expr = "the invoice email is signed {string}")]
+fn invoice_email_signed(world: &mut AppWorld, profession: String) {
+  let body = &world.locales.email.as_ref().unwrap().text_body;
+  assert!(
+    body.contains(&format!("Doe John\n{}\n", profession)),
+    "{}",
+    body
+  );
+}
+
+#[then(expr = "the access code email subject is {string}")]
+fn access_code_email_subject(world: &mut AppWorld, subject: String) {
+  let practitioner = world.locales.user.as_ref().unwrap();
+  let email = user::access_key_email(practitioner, "https://opencab.test").unwrap();
+  assert_eq!(email.subject, subject);
+}
@@ -1,3 +1,23 @@
+pub mod acts;
+pub mod admin;
 pub mod appointments;
+pub mod audit;
+pub mod check_deposits;
 pub mod crypto;
+pub mod email_logs;
+pub mod email_templates;
+pub mod factur_x;
+pub mod invoice_templates;
+pub mod ledger;
+pub mod locales;
+pub mod patient_dedup;
+pub mod patient_export;
+pub mod patient_identity;
+pub mod patient_retention;
+pub mod patient_sharing;
+pub mod payments;
 pub mod practitioner_office;
+pub mod receivables;
+pub mod retrocession_statements;
+pub mod scheduled_exports;
+pub mod stats;
@@ -0,0 +1,175 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::{
+      medical_appointments, patient_accesses, patients,
+      sea_orm_active_enums::{PatientAccessLevel, PatientAccessStatus},
+    },
+    patient_accesses::CreatePatientAccessParams,
+  },
+  services::patient_dedup::{self, DuplicateReason},
+};
+use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter};
+
+use crate::{
+  factories::{
+    medical_appointment::AppointmentFactory, office::OfficeFactory, patient::PatientFactory,
+    user::UserFactory,
+  },
+  AppWorld,
+};
+
+fn parse_reason(reason: &str) -> DuplicateReason {
+  match reason {
+    "same_ssn" => DuplicateReason::SameSsn,
+    "similar_name" => DuplicateReason::SimilarName,
+    _ => panic!("unknown duplicate reason: {}", reason),
+  }
+}
+
+#[given("a practitioner for deduplication tests")]
+async fn practitioner_for_dedup(world: &mut AppWorld) {
+  world.patient_dedup.user = Some(UserFactory::new().create(&world.db).await);
+}
+
+#[given(expr = "a patient {string} {string} with social security number {string}")]
+async fn patient_with_ssn(
+  world: &mut AppWorld,
+  first_name: String,
+  last_name: String,
+  ssn: String,
+) {
+  let user_id = world.patient_dedup.user.as_ref().unwrap().id;
+  let patient = PatientFactory::new()
+    .first_name(&first_name)
+    .last_name(&last_name)
+    .ssn(&ssn)
+    .create(&world.db, user_id)
+    .await;
+  world.patient_dedup.patients.push(patient);
+}
+
+#[given(expr = "each patient has {int} appointment")]
+async fn each_patient_has_appointments(world: &mut AppWorld, count: usize) {
+  let user_id = world.patient_dedup.user.as_ref().unwrap().id;
+  let office = OfficeFactory::new().create(&world.db).await;
+  for patient in &world.patient_dedup.patients {
+    for _ in 0..count {
+      AppointmentFactory::new()
+        .create(&world.db, user_id, patient.id, office.id)
+        .await;
+    }
+  }
+}
+
+#[given(expr = "a colleague with a granted access to the {word} patient")]
+async fn colleague_with_access(world: &mut AppWorld, which: String) {
+  let colleague = match world.patient_dedup.colleague.clone() {
+    Some(colleague) => colleague,
+    None => {
+      let colleague = UserFactory::new()
+        .email("colleague@test.com")
+        .create(&world.db)
+        .await;
+      world.patient_dedup.colleague = Some(colleague.clone());
+      colleague
+    }
+  };
+  let patient = match which.as_str() {
+    "first" => &world.patient_dedup.patients[0],
+    "second" => &world.patient_dedup.patients[1],
+    _ => panic!("unknown patient: {}", which),
+  };
+
+  patient_accesses::ActiveModel::create(
+    &world.db,
+    &CreatePatientAccessParams {
+      patient_id: patient.id,
+      user_id: colleague.id,
+      level: PatientAccessLevel::Read,
+    },
+  )
+  .await
+  .unwrap()
+  .into_active_model()
+  .grant(&world.db, PatientAccessLevel::Read)
+  .await
+  .unwrap();
+}
+
+#[when("I look for duplicate patients")]
+async fn look_for_duplicates(world: &mut AppWorld) {
+  let user = world.patient_dedup.user.as_ref().unwrap();
+  world.patient_dedup.groups = patient_dedup::find_duplicates(user).await.unwrap();
+}
+
+#[when("I merge the second patient into the first one")]
+async fn merge_second_into_first(world: &mut AppWorld) {
+  let survivor = &world.patient_dedup.patients[0];
+  let duplicate = world.patient_dedup.patients[1].clone();
+  world.patient_dedup.merge_failed = patient_dedup::merge(survivor, duplicate).await.is_err();
+}
+
+#[when("I merge the first patient into itself")]
+async fn merge_into_itself(world: &mut AppWorld) {
+  let survivor = &world.patient_dedup.patients[0];
+  world.patient_dedup.merge_failed = patient_dedup::merge(survivor, survivor.clone())
+    .await
+    .is_err();
+}
+
+#[then(expr = "I find {int} group of {string} duplicates with {int} patients")]
+fn find_duplicate_group(world: &mut AppWorld, count: usize, reason: String, size: usize) {
+  let groups = &world.patient_dedup.groups;
+  assert_eq!(groups.len(), count);
+  assert!(groups
+    .iter()
+    .all(|g| g.reason == parse_reason(&reason) && g.patients.len() == size));
+}
+
+#[then("I find no duplicates")]
+fn find_no_duplicates(world: &mut AppWorld) {
+  assert!(world.patient_dedup.groups.is_empty());
+}
+
+#[then(expr = "the first patient has {int} appointments")]
+async fn first_patient_has_appointments(world: &mut AppWorld, count: u64) {
+  let survivor_id = world.patient_dedup.patients[0].id;
+  let appointments = medical_appointments::Entity::find()
+    .filter(medical_appointments::Column::PatientId.eq(survivor_id))
+    .count(&world.db)
+    .await
+    .unwrap();
+  assert!(!world.patient_dedup.merge_failed);
+  assert_eq!(appointments, count);
+}
+
+#[then("the second patient no longer exists")]
+async fn second_patient_no_longer_exists(world: &mut AppWorld) {
+  let duplicate_id = world.patient_dedup.patients[1].id;
+  let duplicate = patients::Entity::find_by_id(duplicate_id)
+    .one(&world.db)
+    .await
+    .unwrap();
+  assert!(duplicate.is_none());
+}
+
+#[then("the colleague still has a granted access to the first patient")]
+async fn colleague_keeps_access(world: &mut AppWorld) {
+  let survivor_id = world.patient_dedup.patients[0].id;
+  let colleague_id = world.patient_dedup.colleague.as_ref().unwrap().id;
+  let accesses = patient_accesses::Entity::find()
+    .filter(patient_accesses::Column::PatientId.eq(survivor_id))
+    .filter(patient_accesses::Column::UserId.eq(colleague_id))
+    .all(&world.db)
+    .await
+    .unwrap();
+  assert!(!world.patient_dedup.merge_failed);
+  assert_eq!(accesses.len(), 1);
+  assert_eq!(accesses[0].status, PatientAccessStatus::Granted);
+}
+
+#[then("the merge is rejected")]
+fn merge_rejected(world: &mut AppWorld) {
+  assert!(world.patient_dedup.merge_failed);
+}
@@ -0,0 +1,122 @@
+use std::io::{Cursor, Read};
+
+use cucumber::{given, then, when};
+use opencab::services::patient_export;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, IntoActiveModel};
+use zip::ZipArchive;
+
+use crate::{
+  factories::{
+    medical_appointment::AppointmentFactory, office::OfficeFactory, patient::PatientFactory,
+    user::UserFactory,
+  },
+  AppWorld,
+};
+
+fn exported_document(world: &AppWorld) -> serde_json::Value {
+  let mut archive = ZipArchive::new(Cursor::new(world.patient_export.archive.clone())).unwrap();
+  let mut content = String::new();
+  archive
+    .by_name("patient.json")
+    .unwrap()
+    .read_to_string(&mut content)
+    .unwrap();
+  serde_json::from_str(&content).unwrap()
+}
+
+fn archive_file_names(world: &AppWorld) -> Vec<String> {
+  let archive = ZipArchive::new(Cursor::new(world.patient_export.archive.clone())).unwrap();
+  archive.file_names().map(str::to_string).collect()
+}
+
+#[given("a practitioner with a patient to export")]
+async fn practitioner_with_patient(world: &mut AppWorld) {
+  let user = UserFactory::new().create(&world.db).await;
+  world.patient_export.patient = Some(PatientFactory::new().create(&world.db, user.id).await);
+  world.patient_export.office = Some(OfficeFactory::new().create(&world.db).await);
+  world.patient_export.user = Some(user);
+}
+
+#[given(expr = "the patient had an appointment on {string} for {int} cents")]
+async fn patient_had_appointment(world: &mut AppWorld, date: String, price: i32) {
+  let state = &world.patient_export;
+  AppointmentFactory::new()
+    .date(&date)
+    .price(price)
+    .create(
+      &world.db,
+      state.user.as_ref().unwrap().id,
+      state.patient.as_ref().unwrap().id,
+      state.office.as_ref().unwrap().id,
+    )
+    .await;
+}
+
+#[when("I export the patient data")]
+async fn export_patient_data(world: &mut AppWorld) {
+  let state = &world.patient_export;
+  let archive = patient_export::export_archive(
+    state.patient.as_ref().unwrap(),
+    state.user.as_ref().unwrap(),
+  )
+  .await
+  .unwrap();
+  world.patient_export.archive = archive.data;
+  world.patient_export.archive_name = archive.filename;
+}
+
+#[given(expr = "the patient is named {string} {string}")]
+async fn patient_named(world: &mut AppWorld, first_name: String, last_name: String) {
+  let mut patient = world
+    .patient_export
+    .patient
+    .take()
+    .unwrap()
+    .into_active_model();
+  patient.first_name = Set(first_name);
+  patient.last_name = Set(last_name);
+  world.patient_export.patient = Some(patient.update(&world.db).await.unwrap());
+}
+
+#[then(expr = "the archive is named {string} followed by the date")]
+fn archive_named(world: &mut AppWorld, prefix: String) {
+  let date = chrono::Utc::now().format("%Y%m%d");
+  assert_eq!(
+    world.patient_export.archive_name,
+    format!("{}_{}.zip", prefix, date)
+  );
+}
+
+#[then(expr = "the archive contains {string}")]
+fn archive_contains(world: &mut AppWorld, file_name: String) {
+  assert!(archive_file_names(world).contains(&file_name));
+}
+
+#[then("the archive contains no invoice")]
+fn archive_contains_no_invoice(world: &mut AppWorld) {
+  assert!(archive_file_names(world)
+    .iter()
+    .all(|name| !name.starts_with("invoices/")));
+}
+
+#[then(expr = "the exported social security number is {string}")]
+fn exported_ssn(world: &mut AppWorld, ssn: String) {
+  assert_eq!(exported_document(world)["patient"]["ssn"], ssn);
+}
+
+#[then(expr = "the export lists {int} appointments")]
+fn export_lists_appointments(world: &mut AppWorld, count: usize) {
+  let document = exported_document(world);
+  assert_eq!(
+    document["medical_appointments"].as_array().unwrap().len(),
+    count
+  );
+}
+
+#[then(expr = "the first exported appointment is on {string}")]
+fn first_exported_appointment(world: &mut AppWorld, date: String) {
+  assert_eq!(
+    exported_document(world)["medical_appointments"][0]["date"],
+    date
+  );
+}
@@ -0,0 +1,130 @@
+use cucumber::{given, then, when};
+use opencab::{
+  models::{_entities::sea_orm_active_enums::PatientSex, patients::Model as PatientModel},
+  services::patient_dedup,
+};
+
+use crate::{
+  factories::{patient::PatientFactory, user::UserFactory},
+  AppWorld,
+};
+
+fn created_patient(world: &AppWorld) -> &PatientModel {
+  world.patient_identity.patient.as_ref().unwrap()
+}
+
+#[given("a practitioner for identity tests")]
+async fn practitioner_for_identity(world: &mut AppWorld) {
+  world.patient_identity.user = Some(UserFactory::new().create(&world.db).await);
+}
+
+#[given(expr = "a patient {string} {string} born on {string}")]
+async fn patient_born_on(
+  world: &mut AppWorld,
+  first_name: String,
+  last_name: String,
+  birth_date: String,
+) {
+  let user_id = world.patient_identity.user.as_ref().unwrap().id;
+  // Each namesake gets their own social security number
+  world.patient_identity.namesakes += 1;
+  PatientFactory::new()
+    .first_name(&first_name)
+    .last_name(&last_name)
+    .ssn(&format!("{:013}", world.patient_identity.namesakes))
+    .birth_date(&birth_date)
+    .create(&world.db, user_id)
+    .await;
+}
+
+#[when(
+  expr = "I create a female patient born on {string} with phone number {string} referred by {string}"
+)]
+async fn create_patient_with_details(
+  world: &mut AppWorld,
+  birth_date: String,
+  phone_number: String,
+  referring_doctor: String,
+) {
+  let user_id = world.patient_identity.user.as_ref().unwrap().id;
+  world.patient_identity.patient = Some(
+    PatientFactory::new()
+      .sex(PatientSex::Female)
+      .birth_date(&birth_date)
+      .phone_number(&phone_number)
+      .referring_doctor(&referring_doctor)
+      .create(&world.db, user_id)
+      .await,
+  );
+}
+
+#[when("I create a patient without identity details")]
+async fn create_patient_without_details(world: &mut AppWorld) {
+  let user_id = world.patient_identity.user.as_ref().unwrap().id;
+  world.patient_identity.patient = Some(PatientFactory::new().create(&world.db, user_id).await);
+}
+
+#[when(expr = "I try to create a patient born on {string} with phone number {string}")]
+async fn try_create_patient(world: &mut AppWorld, birth_date: String, phone_number: String) {
+  let user_id = world.patient_identity.user.as_ref().unwrap().id;
+  world.patient_identity.creation_failed = PatientFactory::new()
+    .birth_date(&birth_date)
+    .phone_number(&phone_number)
+    .try_create(&world.db, user_id)
+    .await
+    .is_err();
+}
+
+#[when("I look for duplicates among them")]
+async fn look_for_duplicates(world: &mut AppWorld) {
+  let user = world.patient_identity.user.as_ref().unwrap();
+  world.patient_identity.duplicate_groups =
+    patient_dedup::find_duplicates(user).await.unwrap().len();
+}
+
+#[then("the patient is female")]
+fn patient_is_female(world: &mut AppWorld) {
+  assert_eq!(created_patient(world).sex, Some(PatientSex::Female));
+}
+
+#[then(expr = "the patient is born on {string}")]
+fn patient_is_born_on(world: &mut AppWorld, birth_date: String) {
+  let patient = created_patient(world);
+  assert_eq!(
+    patient.birth_date.map(|d| d.format("%Y-%m-%d").to_string()),
+    Some(birth_date)
+  );
+}
+
+#[then(expr = "the patient phone number is {string}")]
+fn patient_phone_number(world: &mut AppWorld, phone_number: String) {
+  assert_eq!(created_patient(world).phone_number, Some(phone_number));
+}
+
+#[then(expr = "the patient is referred by {string}")]
+fn patient_referred_by(world: &mut AppWorld, referring_doctor: String) {
+  assert_eq!(
+    created_patient(world).referring_doctor,
+    Some(referring_doctor)
+  );
+}
+
+#[then("the patient has no birth date")]
+fn patient_has_no_birth_date(world: &mut AppWorld) {
+  assert!(created_patient(world).birth_date.is_none());
+}
+
+#[then("the patient creation is rejected")]
+fn patient_creation_rejected(world: &mut AppWorld) {
+  assert!(world.patient_identity.creation_failed);
+}
+
+#[then("no duplicate is reported")]
+fn no_duplicate_reported(world: &mut AppWorld) {
+  assert_eq!(world.patient_identity.duplicate_groups, 0);
+}
+
+#[then(expr = "{int} duplicate group is reported")]
+fn duplicate_groups_reported(world: &mut AppWorld, count: usize) {
+  assert_eq!(world.patient_identity.duplicate_groups, count);
+}
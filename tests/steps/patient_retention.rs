@@ -0,0 +1,132 @@
+use chrono::{Duration, Utc};
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::{medical_appointments, patients},
+    patients::ANONYMIZED_PLACEHOLDER,
+  },
+  services::{self, patient_retention},
+};
+use sea_orm::{
+  ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+};
+
+use crate::{
+  factories::{
+    medical_appointment::AppointmentFactory, office::OfficeFactory, patient::PatientFactory,
+    user::UserFactory,
+  },
+  AppWorld,
+};
+
+async fn reload_patient(world: &AppWorld) -> Option<patients::Model> {
+  let patient_id = world.patient_retention.patient.as_ref().unwrap().id;
+  patients::Entity::find_by_id(patient_id)
+    .one(&world.db)
+    .await
+    .unwrap()
+}
+
+async fn patient_appointments(world: &AppWorld) -> Vec<medical_appointments::Model> {
+  let patient_id = world.patient_retention.patient.as_ref().unwrap().id;
+  medical_appointments::Entity::find()
+    .filter(medical_appointments::Column::PatientId.eq(patient_id))
+    .all(&world.db)
+    .await
+    .unwrap()
+}
+
+#[given(expr = "a practitioner with a patient seen on {string} for {int} cents")]
+async fn practitioner_with_patient_seen(world: &mut AppWorld, date: String, price: i32) {
+  let user = UserFactory::new().create(&world.db).await;
+  let patient = PatientFactory::new().create(&world.db, user.id).await;
+  let office = OfficeFactory::new().create(&world.db).await;
+  AppointmentFactory::new()
+    .date(&date)
+    .price(price)
+    .create(&world.db, user.id, patient.id, office.id)
+    .await;
+
+  world.patient_retention.user = Some(user);
+  world.patient_retention.patient = Some(patient);
+}
+
+#[given("the patient was anonymized 11 years ago")]
+async fn patient_anonymized_long_ago(world: &mut AppWorld) {
+  let patient = world.patient_retention.patient.clone().unwrap();
+  let mut patient = services::patients::anonymize(patient)
+    .await
+    .unwrap()
+    .into_active_model();
+
+  patient.anonymized_at = Set(Some((Utc::now() - Duration::days(11 * 365)).into()));
+  world.patient_retention.patient = Some(patient.update(&world.db).await.unwrap());
+}
+
+#[given("the patient appointment dates back 11 years")]
+async fn appointment_dates_back(world: &mut AppWorld) {
+  for appointment in patient_appointments(world).await {
+    let mut appointment = appointment.into_active_model();
+    appointment.date = Set((Utc::now() - Duration::days(11 * 365)).date_naive());
+    ActiveModelTrait::update(appointment, &world.db)
+      .await
+      .unwrap();
+  }
+}
+
+#[when("the patient is deleted")]
+async fn patient_is_deleted(world: &mut AppWorld) {
+  let patient = world.patient_retention.patient.clone().unwrap();
+  services::patients::anonymize(patient).await.unwrap();
+}
+
+#[when(expr = "the retention purge runs with a retention of {int} days")]
+async fn retention_purge_runs(world: &mut AppWorld, retention_days: u32) {
+  world.patient_retention.purged = patient_retention::purge_expired(&world.db, retention_days)
+    .await
+    .unwrap();
+}
+
+#[then("the patient identity is anonymized")]
+async fn patient_identity_anonymized(world: &mut AppWorld) {
+  let patient = reload_patient(world).await.unwrap();
+  assert!(patient.anonymized_at.is_some());
+  assert_eq!(patient.first_name, ANONYMIZED_PLACEHOLDER);
+  assert_eq!(patient.last_name, ANONYMIZED_PLACEHOLDER);
+  assert!(patient.address_line_1.is_empty());
+  assert_ne!(patient.email, "patient@test.com");
+}
+
+#[then("the patient social security number is erased")]
+async fn patient_ssn_erased(world: &mut AppWorld) {
+  let patient = reload_patient(world).await.unwrap();
+  assert!(patient.ssn.is_empty());
+  assert!(patient.hashed_ssn.is_empty());
+}
+
+#[then(expr = "the patient still has {int} appointment for {int} cents")]
+async fn patient_still_has_appointment(world: &mut AppWorld, count: usize, price: i32) {
+  let appointments = patient_appointments(world).await;
+  assert_eq!(appointments.len(), count);
+  assert!(appointments.iter().all(|a| a.price_in_cents == price));
+}
+
+#[then(expr = "searching for {string} returns no patient")]
+async fn search_returns_no_patient(world: &mut AppWorld, query: String) {
+  let user = world.patient_retention.user.as_ref().unwrap();
+  let (found, _) = services::patients::search_paginated(&query, 1, user)
+    .await
+    .unwrap();
+  assert!(found.is_empty());
+}
+
+#[then(expr = "{int} patient is purged")]
+fn patients_purged(world: &mut AppWorld, count: u64) {
+  assert_eq!(world.patient_retention.purged, count);
+}
+
+#[then("the patient and their appointments no longer exist")]
+async fn patient_no_longer_exists(world: &mut AppWorld) {
+  assert!(reload_patient(world).await.is_none());
+  assert!(patient_appointments(world).await.is_empty());
+}
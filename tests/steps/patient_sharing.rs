@@ -0,0 +1,216 @@
+use chrono::Duration;
+use cucumber::{given, then, when};
+use opencab::{
+  auth::resource::{Permission, Resource},
+  models::_entities::{
+    patient_accesses,
+    sea_orm_active_enums::{AuditAction, Locale, PatientAccessLevel, PatientAccessStatus},
+  },
+  services::{self, patient_sharing},
+};
+use sea_orm::{
+  ActiveEnum, ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel,
+  QueryFilter,
+};
+
+use crate::{
+  factories::{patient::PatientFactory, user::UserFactory},
+  AppWorld,
+};
+
+fn parse_level(level: &str) -> PatientAccessLevel {
+  match level {
+    "read" => PatientAccessLevel::Read,
+    "write" => PatientAccessLevel::Write,
+    _ => panic!("unknown access level: {}", level),
+  }
+}
+
+async fn colleague_access(world: &AppWorld) -> Option<patient_accesses::Model> {
+  let colleague_id = world.patient_sharing.colleague.as_ref().unwrap().id;
+  patient_accesses::Entity::find()
+    .filter(patient_accesses::Column::UserId.eq(colleague_id))
+    .one(&world.db)
+    .await
+    .unwrap()
+}
+
+async fn colleague_is_allowed_to(world: &AppWorld, action: AuditAction) -> bool {
+  let colleague_id = world.patient_sharing.colleague.as_ref().unwrap().id;
+  let patient = world.patient_sharing.patient.as_ref().unwrap();
+  patient
+    .permission_for_user(colleague_id)
+    .await
+    .is_some_and(|permission| permission >= Permission::required_for(&action))
+}
+
+#[given(expr = "a practitioner owning a patient with social security number {string}")]
+async fn practitioner_owning_patient(world: &mut AppWorld, ssn: String) {
+  let owner = UserFactory::new().create(&world.db).await;
+  world.patient_sharing.patient = Some(
+    PatientFactory::new()
+      .ssn(&ssn)
+      .create(&world.db, owner.id)
+      .await,
+  );
+  world.patient_sharing.owner = Some(owner);
+}
+
+#[given("a colleague practitioner")]
+async fn colleague_practitioner(world: &mut AppWorld) {
+  world.patient_sharing.colleague = Some(
+    UserFactory::new()
+      .email("colleague@test.com")
+      .create(&world.db)
+      .await,
+  );
+}
+
+#[given(expr = "the colleague requests a {string} access to the patient {string}")]
+#[when(expr = "the colleague requests a {string} access to the patient {string}")]
+async fn colleague_requests_access(world: &mut AppWorld, level: String, ssn: String) {
+  let colleague = world.patient_sharing.colleague.as_ref().unwrap();
+  let requests = patient_sharing::request_access(colleague, &ssn, parse_level(&level))
+    .await
+    .unwrap();
+
+  world.patient_sharing.emails.extend(
+    requests.iter().flat_map(|request| {
+      patient_sharing::access_request_emails(request, colleague, "http://test")
+    }),
+  );
+}
+
+#[given(expr = "the patient speaks {string}")]
+async fn patient_speaks(world: &mut AppWorld, locale: String) {
+  let mut patient = world
+    .patient_sharing
+    .patient
+    .take()
+    .unwrap()
+    .into_active_model();
+  patient.locale = Set(Some(Locale::try_from_value(&locale).unwrap()));
+  world.patient_sharing.patient = Some(patient.update(&world.db).await.unwrap());
+}
+
+#[given(expr = "the owner grants a {string} access")]
+#[when(expr = "the owner grants a {string} access")]
+async fn owner_grants_access(world: &mut AppWorld, level: String) {
+  let access = colleague_access(world).await.unwrap();
+  patient_sharing::grant(access, parse_level(&level))
+    .await
+    .unwrap();
+}
+
+#[given("the consent link has expired")]
+async fn consent_link_expired(world: &mut AppWorld) {
+  let mut access = colleague_access(world).await.unwrap().into_active_model();
+  access.consent_token_expires_at = Set(Some((chrono::Utc::now() - Duration::minutes(1)).into()));
+  access.update(&world.db).await.unwrap();
+}
+
+#[when("the patient follows the consent link")]
+async fn patient_consents(world: &mut AppWorld) {
+  let access = colleague_access(world).await.unwrap();
+  world.patient_sharing.consent_rejected =
+    patient_sharing::consent(access.consent_token.as_ref().unwrap())
+      .await
+      .is_err();
+}
+
+#[when("the owner revokes the access")]
+async fn owner_revokes_access(world: &mut AppWorld) {
+  let access = colleague_access(world).await.unwrap();
+  patient_sharing::revoke(access).await.unwrap();
+}
+
+#[then("the colleague has a pending request")]
+async fn colleague_has_pending_request(world: &mut AppWorld) {
+  let access = colleague_access(world).await.unwrap();
+  assert_eq!(access.status, PatientAccessStatus::Pending);
+}
+
+#[then("the colleague has no access to the patient")]
+async fn colleague_has_no_access(world: &mut AppWorld) {
+  assert!(!colleague_is_allowed_to(world, AuditAction::Read).await);
+}
+
+#[then(expr = "the owner sees {int} access request")]
+async fn owner_sees_requests(world: &mut AppWorld, count: usize) {
+  let owner = world.patient_sharing.owner.as_ref().unwrap();
+  let accesses = patient_sharing::accesses_for_owner(owner).await.unwrap();
+  assert_eq!(accesses.len(), count);
+}
+
+#[then(expr = "{int} notification emails are prepared")]
+fn notification_emails_prepared(world: &mut AppWorld, count: usize) {
+  assert_eq!(world.patient_sharing.emails.len(), count);
+}
+
+#[then(expr = "the owner is notified with the subject {string}")]
+fn owner_notified_with_subject(world: &mut AppWorld, subject: String) {
+  let owner = world.patient_sharing.owner.as_ref().unwrap();
+  let email = world
+    .patient_sharing
+    .emails
+    .iter()
+    .find(|email| email.to == owner.email)
+    .unwrap();
+  assert_eq!(email.subject, subject);
+}
+
+#[then(expr = "the patient is asked for consent with the subject {string}")]
+fn patient_asked_with_subject(world: &mut AppWorld, subject: String) {
+  let patient = world.patient_sharing.patient.as_ref().unwrap();
+  let email = world
+    .patient_sharing
+    .emails
+    .iter()
+    .find(|email| email.to == patient.email)
+    .unwrap();
+  assert_eq!(email.subject, subject);
+}
+
+#[then("the colleague cannot add an appointment to the patient")]
+async fn colleague_cannot_add_appointment(world: &mut AppWorld) {
+  assert!(!colleague_is_allowed_to(world, AuditAction::Create).await);
+}
+
+#[then("the colleague cannot export the patient")]
+async fn colleague_cannot_export(world: &mut AppWorld) {
+  assert!(!colleague_is_allowed_to(world, AuditAction::Export).await);
+}
+
+#[then("the colleague can read the patient")]
+async fn colleague_can_read(world: &mut AppWorld) {
+  assert!(colleague_is_allowed_to(world, AuditAction::Read).await);
+}
+
+#[then("the colleague can update the patient")]
+async fn colleague_can_update(world: &mut AppWorld) {
+  assert!(colleague_is_allowed_to(world, AuditAction::Update).await);
+}
+
+#[then("the colleague cannot update the patient")]
+async fn colleague_cannot_update(world: &mut AppWorld) {
+  assert!(!colleague_is_allowed_to(world, AuditAction::Update).await);
+}
+
+#[then("the colleague cannot delete the patient")]
+async fn colleague_cannot_delete(world: &mut AppWorld) {
+  assert!(!colleague_is_allowed_to(world, AuditAction::Delete).await);
+}
+
+#[then(expr = "the colleague finds the patient when searching for {string}")]
+async fn colleague_finds_patient(world: &mut AppWorld, query: String) {
+  let colleague = world.patient_sharing.colleague.as_ref().unwrap();
+  let (found, _) = services::patients::search_paginated(&query, 1, colleague)
+    .await
+    .unwrap();
+  assert_eq!(found.len(), 1);
+}
+
+#[then("the consent is rejected")]
+fn consent_rejected(world: &mut AppWorld) {
+  assert!(world.patient_sharing.consent_rejected);
+}
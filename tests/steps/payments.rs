@@ -0,0 +1,236 @@
+use chrono::NaiveDate;
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::PaymentMethod,
+    payments::{self as payment_models, CreatePaymentParams, PaymentStatus},
+    revenue_share_rates::RetrocessionTerms,
+    user_practitioner_offices::{self, CreateLinkParams},
+  },
+  services::payments,
+};
+use sea_orm::{prelude::Decimal, PaginatorTrait};
+
+use crate::{
+  factories::{
+    medical_appointment::AppointmentFactory, office::OfficeFactory, patient::PatientFactory,
+    user::UserFactory,
+  },
+  AppWorld,
+};
+
+fn patient_id(world: &AppWorld, last_name: &str) -> i32 {
+  world
+    .payments
+    .patients
+    .iter()
+    .find(|patient| patient.last_name == last_name)
+    .unwrap()
+    .id
+}
+
+fn parse_status(status: &str) -> PaymentStatus {
+  serde_json::from_value(serde_json::Value::String(status.to_string())).unwrap()
+}
+
+async fn pay(world: &mut AppWorld, amount_in_cents: i32, method: PaymentMethod, paid_on: &str) {
+  let appointment = world.payments.appointment.as_ref().unwrap();
+  let result = payments::record(
+    appointment,
+    &CreatePaymentParams {
+      amount_in_cents,
+      method,
+      paid_on: paid_on.to_string(),
+      reference: world.payments.reference.take(),
+    },
+  )
+  .await;
+  world.payments.payment_failed = result.is_err();
+}
+
+#[given("a practitioner with an office for payments")]
+async fn practitioner_with_office(world: &mut AppWorld) {
+  let user = UserFactory::new().create(&world.db).await;
+  let office = OfficeFactory::new().create(&world.db).await;
+  user_practitioner_offices::ActiveModel::create(
+    &world.db,
+    &CreateLinkParams {
+      user_id: user.id,
+      practitioner_office_id: office.id,
+      terms: RetrocessionTerms::percentage(Decimal::from(100)),
+    },
+  )
+  .await
+  .unwrap();
+
+  world.payments.user = Some(user);
+  world.payments.office = Some(office);
+}
+
+#[given(expr = "a patient {string} {string} for payments")]
+async fn patient_for_payments(world: &mut AppWorld, first_name: String, last_name: String) {
+  let user_id = world.payments.user.as_ref().unwrap().id;
+  let patient = PatientFactory::new()
+    .first_name(&first_name)
+    .last_name(&last_name)
+    .create(&world.db, user_id)
+    .await;
+  world.payments.patients.push(patient);
+}
+
+#[given(expr = "an appointment of {int} cents for {string} on {string}")]
+async fn appointment_for(
+  world: &mut AppWorld,
+  price_in_cents: i32,
+  last_name: String,
+  date: String,
+) {
+  let user_id = world.payments.user.as_ref().unwrap().id;
+  let office_id = world.payments.office.as_ref().unwrap().id;
+  let patient_id = patient_id(world, &last_name);
+
+  world.payments.appointment = Some(
+    AppointmentFactory::new()
+      .date(&date)
+      .price(price_in_cents)
+      .create(&world.db, user_id, patient_id, office_id)
+      .await,
+  );
+}
+
+#[given(expr = "an appointment of {int} cents for {string} on {string} paid by card")]
+async fn appointment_paid_by_card(
+  world: &mut AppWorld,
+  price_in_cents: i32,
+  last_name: String,
+  date: String,
+) {
+  let user_id = world.payments.user.as_ref().unwrap().id;
+  let office_id = world.payments.office.as_ref().unwrap().id;
+  let patient_id = patient_id(world, &last_name);
+
+  let appointment = AppointmentFactory::new()
+    .date(&date)
+    .price(price_in_cents)
+    .payment_method(PaymentMethod::Card)
+    .create(&world.db, user_id, patient_id, office_id)
+    .await;
+  payments::settle_on_the_spot(&world.db, &appointment)
+    .await
+    .unwrap();
+
+  world.payments.appointment = Some(appointment);
+}
+
+#[given(expr = "the patient pays {int} cents by check {string} on {string}")]
+#[when(expr = "the patient pays {int} cents by check {string} on {string}")]
+async fn pays_by_check(
+  world: &mut AppWorld,
+  amount_in_cents: i32,
+  reference: String,
+  paid_on: String,
+) {
+  world.payments.reference = Some(reference);
+  pay(world, amount_in_cents, PaymentMethod::Check, &paid_on).await;
+}
+
+#[given(expr = "the patient pays {int} cents by transfer on {string}")]
+#[when(expr = "the patient pays {int} cents by transfer on {string}")]
+async fn pays_by_transfer(world: &mut AppWorld, amount_in_cents: i32, paid_on: String) {
+  pay(world, amount_in_cents, PaymentMethod::Transfer, &paid_on).await;
+}
+
+#[when(expr = "the patient tries to pay {int} cents by cash")]
+async fn tries_to_pay_by_cash(world: &mut AppWorld, amount_in_cents: i32) {
+  pay(world, amount_in_cents, PaymentMethod::Cash, "2026-03-02").await;
+}
+
+#[when(expr = "the patient pays {int} cents by cash twice at the same time")]
+async fn pays_twice_at_once(world: &mut AppWorld, amount_in_cents: i32) {
+  let appointment = world.payments.appointment.as_ref().unwrap();
+  let params = CreatePaymentParams {
+    amount_in_cents,
+    method: PaymentMethod::Cash,
+    paid_on: "2026-03-02".to_string(),
+    reference: None,
+  };
+
+  let (first, second) = tokio::join!(
+    payments::record(appointment, &params),
+    payments::record(appointment, &params)
+  );
+  world.payments.payment_failed = first.is_err() || second.is_err();
+}
+
+#[when(expr = "I list the {string} appointments between {string} and {string}")]
+async fn list_appointments(
+  world: &mut AppWorld,
+  status: String,
+  start_date: String,
+  end_date: String,
+) {
+  let user = world.payments.user.as_ref().unwrap();
+  world.payments.listed = payments::appointments_between(
+    user,
+    NaiveDate::parse_from_str(&start_date, "%Y-%m-%d").unwrap(),
+    NaiveDate::parse_from_str(&end_date, "%Y-%m-%d").unwrap(),
+    Some(parse_status(&status)),
+  )
+  .await
+  .unwrap()
+  .len();
+}
+
+#[when("I list the unpaid balances")]
+async fn list_unpaid_balances(world: &mut AppWorld) {
+  let user = world.payments.user.as_ref().unwrap();
+  world.payments.balances = payments::unpaid_balances(user).await.unwrap();
+}
+
+#[then(expr = "the appointment is {string}")]
+async fn appointment_status(world: &mut AppWorld, status: String) {
+  let appointment = world.payments.appointment.clone().unwrap();
+  let settlements = payments::settlements(&world.db, vec![appointment], None)
+    .await
+    .unwrap();
+  assert_eq!(settlements[0].status(), parse_status(&status));
+}
+
+#[then(expr = "the appointment has {int} payment(s)")]
+async fn appointment_payments(world: &mut AppWorld, expected: u64) {
+  let appointment_id = world.payments.appointment.as_ref().unwrap().id;
+  let payments_count = payment_models::Entity::find_for_appointment(appointment_id)
+    .count(&world.db)
+    .await
+    .unwrap();
+  assert_eq!(payments_count, expected);
+}
+
+#[then(expr = "the patient {string} still owes {int} cents")]
+async fn patient_still_owes(world: &mut AppWorld, last_name: String, expected: i64) {
+  let user = world.payments.user.as_ref().unwrap();
+  let balances = payments::unpaid_balances(user).await.unwrap();
+  let balance = balances
+    .iter()
+    .find(|balance| balance.patient.last_name == last_name)
+    .unwrap();
+  assert_eq!(balance.unpaid_in_cents, expected);
+}
+
+#[then("the payment is rejected")]
+fn payment_rejected(world: &mut AppWorld) {
+  assert!(world.payments.payment_failed);
+}
+
+#[then(expr = "{int} appointment is listed")]
+fn appointments_listed(world: &mut AppWorld, expected: usize) {
+  assert_eq!(world.payments.listed, expected);
+}
+
+#[then(expr = "the first unpaid balance is {int} cents for {string} over {int} appointments")]
+fn first_unpaid_balance(world: &mut AppWorld, expected: i64, last_name: String, count: usize) {
+  let balance = &world.payments.balances[0];
+  assert_eq!(balance.patient.last_name, last_name);
+  assert_eq!(balance.unpaid_in_cents, expected);
+  assert_eq!(balance.appointments_count, count);
+}
@@ -3,8 +3,10 @@ use cucumber::{given, then, when};
 use opencab::models::{
   _entities::{practitioner_offices, user_practitioner_offices},
   practitioner_offices::PractitionerOfficeParams,
+  revenue_share_rates::RetrocessionTerms,
   user_practitioner_offices::CreateLinkParams,
 };
+use opencab::services::practitioner_office;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
   prelude::Decimal, ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
@@ -26,6 +28,7 @@ async fn office_linked_to_practitioner(world: &mut AppWorld, name: String, reven
     address_line_1: "1 rue de la Paix".to_string(),
     address_zip_code: "75001".to_string(),
     address_city: "Paris".to_string(),
+    contact_email: None,
   };
   let office = practitioner_offices::ActiveModel::create(&world.db, &params)
     .await
@@ -37,7 +40,7 @@ async fn office_linked_to_practitioner(world: &mut AppWorld, name: String, reven
     &CreateLinkParams {
       user_id: user.id,
       practitioner_office_id: office.id,
-      revenue_share_percentage,
+      terms: RetrocessionTerms::percentage(revenue_share_percentage),
     },
   )
   .await
@@ -54,6 +57,7 @@ async fn create_office(world: &mut AppWorld, name: String, revenue_share: i64) {
     address_line_1: "1 rue de la Paix".to_string(),
     address_zip_code: "75001".to_string(),
     address_city: "Paris".to_string(),
+    contact_email: None,
   };
   let office = practitioner_offices::ActiveModel::create(&world.db, &params)
     .await
@@ -65,7 +69,7 @@ async fn create_office(world: &mut AppWorld, name: String, revenue_share: i64) {
     &CreateLinkParams {
       user_id: user.id,
       practitioner_office_id: office.id,
-      revenue_share_percentage,
+      terms: RetrocessionTerms::percentage(revenue_share_percentage),
     },
   )
   .await
@@ -81,6 +85,7 @@ async fn try_create_office_invalid_zip(world: &mut AppWorld) {
     address_line_1: "1 rue de la Paix".to_string(),
     address_zip_code: "INVALID".to_string(),
     address_city: "Paris".to_string(),
+    contact_email: None,
   };
   let result = practitioner_offices::ActiveModel::create(&world.db, &params).await;
   world.practitioner_office.last_error = result.err();
@@ -91,17 +96,22 @@ async fn update_office_revenue_share(world: &mut AppWorld, revenue_share: i64) {
   let user = world.practitioner_office.user.as_ref().unwrap();
   let office = world.practitioner_office.office.as_ref().unwrap();
 
-  let mut link = user_practitioner_offices::Entity::find()
+  let link = user_practitioner_offices::Entity::find()
     .filter(user_practitioner_offices::Column::PractitionerOfficeId.eq(office.id))
     .filter(user_practitioner_offices::Column::UserId.eq(user.id))
     .one(&world.db)
     .await
     .unwrap()
-    .unwrap()
-    .into_active_model();
+    .unwrap();
 
-  link.revenue_share_percentage = Set(Decimal::from_str(&revenue_share.to_string()).unwrap());
-  link.update(&world.db).await.unwrap();
+  practitioner_office::set_retrocession_terms(
+    &world.db,
+    link,
+    &RetrocessionTerms::percentage(Decimal::from_str(&revenue_share.to_string()).unwrap()),
+    chrono::Local::now().date_naive(),
+  )
+  .await
+  .unwrap();
 }
 
 #[when(expr = "I update the office name to {string}")]
@@ -112,6 +122,7 @@ async fn update_office_name(world: &mut AppWorld, new_name: String) {
     address_line_1: office.address_line_1.clone(),
     address_zip_code: office.address_zip_code.clone(),
     address_city: office.address_city.clone(),
+    contact_email: office.contact_email.clone(),
   };
   let mut active = office.clone().into_active_model();
   active.name = Set(params.name.trim().to_string());
@@ -0,0 +1,216 @@
+use chrono::NaiveDate;
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::{InsuranceKind, PaymentMethod},
+    patient_insurances::{self, CreatePatientInsuranceParams},
+    payments::CreatePaymentParams,
+    revenue_share_rates::RetrocessionTerms,
+    user_practitioner_offices::{self, CreateLinkParams},
+  },
+  services::{
+    payments,
+    receivables::{self, Payer, PayerReceivable},
+  },
+};
+use sea_orm::{prelude::Decimal, ActiveModelTrait, ActiveValue, IntoActiveModel};
+
+use crate::{
+  factories::{
+    medical_appointment::AppointmentFactory, office::OfficeFactory, patient::PatientFactory,
+    user::UserFactory,
+  },
+  AppWorld,
+};
+
+fn receivable_without_insurer(world: &AppWorld, payer: Payer) -> &PayerReceivable {
+  world
+    .receivables
+    .receivables
+    .iter()
+    .find(|receivable| receivable.payer == payer && receivable.insurer_name.is_none())
+    .unwrap()
+}
+
+#[given("a practitioner with an office for receivables")]
+async fn practitioner_with_office(world: &mut AppWorld) {
+  let user = UserFactory::new().create(&world.db).await;
+  let office = OfficeFactory::new().create(&world.db).await;
+  user_practitioner_offices::ActiveModel::create(
+    &world.db,
+    &CreateLinkParams {
+      user_id: user.id,
+      practitioner_office_id: office.id,
+      terms: RetrocessionTerms::percentage(Decimal::from(100)),
+    },
+  )
+  .await
+  .unwrap();
+
+  world.receivables.user = Some(user);
+  world.receivables.office = Some(office);
+}
+
+#[given(expr = "an insured patient covered by {string} and the mutuelle {string}")]
+async fn insured_patient(world: &mut AppWorld, amo_name: String, mutuelle_name: String) {
+  let user_id = world.receivables.user.as_ref().unwrap().id;
+  let patient = PatientFactory::new().create(&world.db, user_id).await;
+
+  for (kind, insurer_name) in [
+    (InsuranceKind::Amo, amo_name),
+    (InsuranceKind::Mutuelle, mutuelle_name),
+  ] {
+    patient_insurances::ActiveModel::create(
+      &world.db,
+      &CreatePatientInsuranceParams {
+        kind,
+        insurer_name,
+        member_number: Some("123456".to_string()),
+        valid_until: None,
+      },
+      patient.id,
+    )
+    .await
+    .unwrap();
+  }
+
+  world.receivables.patient = Some(patient);
+}
+
+#[given(expr = "the mutuelle coverage ended on {string}")]
+async fn mutuelle_coverage_ended(world: &mut AppWorld, valid_until: String) {
+  let patient_id = world.receivables.patient.as_ref().unwrap().id;
+  let mutuelle = patient_insurances::Entity::find_for_patient(patient_id)
+    .all(&world.db)
+    .await
+    .unwrap()
+    .into_iter()
+    .find(|insurance| insurance.kind == InsuranceKind::Mutuelle)
+    .unwrap();
+
+  let mut mutuelle = mutuelle.into_active_model();
+  mutuelle.valid_until = ActiveValue::Set(Some(
+    NaiveDate::parse_from_str(&valid_until, "%Y-%m-%d").unwrap(),
+  ));
+  mutuelle.update(&world.db).await.unwrap();
+}
+
+#[given(
+  expr = "an appointment of {int} cents with an AMO share of {int} and a mutuelle share of {int}"
+)]
+async fn appointment_with_shares(
+  world: &mut AppWorld,
+  price_in_cents: i32,
+  amo_share_in_cents: i32,
+  mutuelle_share_in_cents: i32,
+) {
+  let user_id = world.receivables.user.as_ref().unwrap().id;
+  let patient_id = world.receivables.patient.as_ref().unwrap().id;
+  let office_id = world.receivables.office.as_ref().unwrap().id;
+
+  world.receivables.appointment = Some(
+    AppointmentFactory::new()
+      .price(price_in_cents)
+      .amo_share(amo_share_in_cents)
+      .mutuelle_share(mutuelle_share_in_cents)
+      .create(&world.db, user_id, patient_id, office_id)
+      .await,
+  );
+}
+
+#[given("the patient share is paid by card")]
+async fn patient_share_paid(world: &mut AppWorld) {
+  let appointment = world.receivables.appointment.as_ref().unwrap();
+  payments::record(
+    appointment,
+    &CreatePaymentParams {
+      amount_in_cents: appointment.patient_share_in_cents(),
+      method: PaymentMethod::Card,
+      paid_on: appointment.date.format("%Y-%m-%d").to_string(),
+      reference: None,
+    },
+  )
+  .await
+  .unwrap();
+}
+
+#[given(expr = "the AMO share is received on {string}")]
+async fn amo_share_received(world: &mut AppWorld, paid_at: String) {
+  let appointment = world.receivables.appointment.take().unwrap();
+  let mut appointment = appointment.into_active_model();
+  appointment.amo_paid_at = ActiveValue::Set(Some(
+    NaiveDate::parse_from_str(&paid_at, "%Y-%m-%d").unwrap(),
+  ));
+  world.receivables.appointment = Some(
+    ActiveModelTrait::update(appointment, &world.db)
+      .await
+      .unwrap(),
+  );
+}
+
+#[when(
+  expr = "I try to record an appointment of {int} cents with an AMO share of {int} and a mutuelle share of {int}"
+)]
+async fn try_record_appointment(
+  world: &mut AppWorld,
+  price_in_cents: i32,
+  amo_share_in_cents: i32,
+  mutuelle_share_in_cents: i32,
+) {
+  let user_id = world.receivables.user.as_ref().unwrap().id;
+  let patient_id = world.receivables.patient.as_ref().unwrap().id;
+  let office_id = world.receivables.office.as_ref().unwrap().id;
+
+  world.receivables.creation_failed = AppointmentFactory::new()
+    .price(price_in_cents)
+    .amo_share(amo_share_in_cents)
+    .mutuelle_share(mutuelle_share_in_cents)
+    .try_create(&world.db, user_id, patient_id, office_id)
+    .await
+    .is_err();
+}
+
+#[when("I compute the outstanding receivables")]
+async fn compute_receivables(world: &mut AppWorld) {
+  let user = world.receivables.user.as_ref().unwrap();
+  world.receivables.receivables = receivables::outstanding_by_payer(
+    user,
+    NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+    NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+  )
+  .await
+  .unwrap();
+}
+
+#[then(expr = "the patient owes {int} cents")]
+fn patient_owes(world: &mut AppWorld, expected: i64) {
+  let receivable = receivable_without_insurer(world, Payer::Patient);
+  assert_eq!(receivable.outstanding_in_cents, expected);
+}
+
+#[then(expr = "{string} owes {int} cents")]
+fn insurer_owes(world: &mut AppWorld, insurer_name: String, expected: i64) {
+  let receivable = world
+    .receivables
+    .receivables
+    .iter()
+    .find(|receivable| receivable.insurer_name.as_deref() == Some(insurer_name.as_str()))
+    .unwrap();
+  assert_eq!(receivable.outstanding_in_cents, expected);
+}
+
+#[then(expr = "an unknown mutuelle owes {int} cents")]
+fn unknown_mutuelle_owes(world: &mut AppWorld, expected: i64) {
+  let receivable = receivable_without_insurer(world, Payer::Mutuelle);
+  assert_eq!(receivable.outstanding_in_cents, expected);
+}
+
+#[then(expr = "{int} payer has an outstanding balance")]
+fn payers_count(world: &mut AppWorld, expected: usize) {
+  assert_eq!(world.receivables.receivables.len(), expected);
+}
+
+#[then("the appointment is rejected")]
+fn appointment_rejected(world: &mut AppWorld) {
+  assert!(world.receivables.creation_failed);
+}
@@ -0,0 +1,282 @@
+use std::io::{Cursor, Read};
+
+use chrono::NaiveDate;
+use cucumber::{given, then, when};
+use opencab::{
+  models::{
+    _entities::sea_orm_active_enums::{Locale, RetrocessionKind},
+    retrocession_statements,
+    revenue_share_rates::RetrocessionTerms,
+    user_practitioner_offices::{self, CreateLinkParams},
+  },
+  services::{practitioner_office, retrocession},
+};
+use sea_orm::{
+  prelude::Decimal, ActiveEnum, ActiveModelTrait, ActiveValue, EntityTrait, IntoActiveModel,
+};
+use zip::ZipArchive;
+
+use crate::{
+  factories::{office::OfficeFactory, user::UserFactory},
+  AppWorld,
+};
+
+#[given(expr = "a practitioner working in {string} with a revenue share of {int}")]
+async fn practitioner_working_in(world: &mut AppWorld, name: String, revenue_share: i64) {
+  let user = UserFactory::new().create(&world.db).await;
+  let office = OfficeFactory::new().name(&name).create(&world.db).await;
+  user_practitioner_offices::ActiveModel::create(
+    &world.db,
+    &CreateLinkParams {
+      user_id: user.id,
+      practitioner_office_id: office.id,
+      terms: RetrocessionTerms::percentage(Decimal::from(revenue_share)),
+    },
+  )
+  .await
+  .unwrap();
+
+  world.payments.user = Some(user);
+  world.payments.office = Some(office);
+}
+
+#[given(expr = "the office contact is {string}")]
+async fn office_contact(world: &mut AppWorld, contact_email: String) {
+  let mut office = world.payments.office.take().unwrap().into_active_model();
+  office.contact_email = ActiveValue::Set(Some(contact_email));
+  world.payments.office = Some(office.update(&world.db).await.unwrap());
+}
+
+#[given(expr = "I generate the retrocession statement of {string}")]
+#[when(expr = "I generate the retrocession statement of {string}")]
+async fn generate_statement(world: &mut AppWorld, month: String) {
+  let user = world.payments.user.as_ref().unwrap();
+  let office_id = world.payments.office.as_ref().unwrap().id;
+  world.retrocession_statements.statement = Some(
+    retrocession::generate(user, office_id, &month)
+      .await
+      .unwrap(),
+  );
+}
+
+#[then(expr = "generating the retrocession statement of {string} again is rejected")]
+async fn generate_statement_again_rejected(world: &mut AppWorld, month: String) {
+  let user = world.payments.user.as_ref().unwrap();
+  let office_id = world.payments.office.as_ref().unwrap().id;
+  assert!(retrocession::generate(user, office_id, &month)
+    .await
+    .is_err());
+
+  let statement = world.retrocession_statements.statement.as_ref().unwrap();
+  world.retrocession_statements.statement =
+    retrocession_statements::Entity::find_by_id(statement.id)
+      .one(&world.db)
+      .await
+      .unwrap();
+}
+
+#[given(expr = "the practitioner of the office speaks {string}")]
+async fn practitioner_speaks(world: &mut AppWorld, locale: String) {
+  let user = world.payments.user.take().unwrap();
+  world.payments.user = Some(
+    user
+      .into_active_model()
+      .set_locale(&world.db, Locale::try_from_value(&locale).unwrap())
+      .await
+      .unwrap(),
+  );
+}
+
+#[when("I download the statement")]
+async fn download_statement(world: &mut AppWorld) {
+  let user = world.payments.user.as_ref().unwrap();
+  let statement = world.retrocession_statements.statement.clone().unwrap();
+  let detail = retrocession::detail(statement, user).await.unwrap();
+
+  world.retrocession_statements.pdf = retrocession::pdf(&detail, user).await.unwrap().data;
+  world.retrocession_statements.spreadsheet = retrocession::spreadsheet(&detail).unwrap().data;
+}
+
+#[given("I send the statement")]
+#[when("I send the statement")]
+async fn send_statement(world: &mut AppWorld) {
+  let user = world.payments.user.as_ref().unwrap();
+  let statement = world.retrocession_statements.statement.clone().unwrap();
+  let detail = retrocession::detail(statement, user).await.unwrap();
+
+  match retrocession::statement_email(&detail, user).await {
+    Ok(email) => {
+      world.retrocession_statements.statement = Some(
+        retrocession::mark_as_sent(detail.statement, email.to.clone())
+          .await
+          .unwrap(),
+      );
+      world.retrocession_statements.email = Some(email);
+    }
+    Err(_) => world.retrocession_statements.email = None,
+  }
+}
+
+#[then(expr = "the statement counts {int} appointments for {int} cents")]
+fn statement_counts(world: &mut AppWorld, appointments_count: i32, fees_in_cents: i64) {
+  let statement = world.retrocession_statements.statement.as_ref().unwrap();
+  assert_eq!(statement.appointments_count, appointments_count);
+  assert_eq!(statement.fees_in_cents, fees_in_cents);
+}
+
+#[then(expr = "the statement owes {int} cents to the office holder")]
+fn statement_owes(world: &mut AppWorld, retrocession_in_cents: i64) {
+  let statement = world.retrocession_statements.statement.as_ref().unwrap();
+  assert_eq!(statement.retrocession_in_cents, retrocession_in_cents);
+}
+
+#[then(expr = "the statement details {int} days")]
+async fn statement_details_days(world: &mut AppWorld, days_count: usize) {
+  let user = world.payments.user.as_ref().unwrap();
+  let statement = world.retrocession_statements.statement.clone().unwrap();
+  let detail = retrocession::detail(statement, user).await.unwrap();
+  assert_eq!(detail.days.len(), days_count);
+}
+
+#[then(expr = "the office has {int} statement(s) in its history")]
+async fn office_history(world: &mut AppWorld, statements_count: usize) {
+  let user = world.payments.user.as_ref().unwrap();
+  let office_id = world.payments.office.as_ref().unwrap().id;
+  let statements = retrocession::statements_for_office(user, office_id)
+    .await
+    .unwrap();
+  assert_eq!(statements.len(), statements_count);
+}
+
+#[then("the statement documents are a PDF and a spreadsheet")]
+fn documents_are_pdf_and_spreadsheet(world: &mut AppWorld) {
+  assert!(world.retrocession_statements.pdf.starts_with(b"%PDF"));
+  assert!(world.retrocession_statements.spreadsheet.starts_with(b"PK"));
+}
+
+#[then(expr = "an email with {int} attachments is addressed to {string}")]
+fn email_addressed_to(world: &mut AppWorld, attachments_count: usize, to: String) {
+  let email = world.retrocession_statements.email.as_ref().unwrap();
+  assert_eq!(email.to, to);
+  assert_eq!(email.attachments.len(), attachments_count);
+}
+
+#[then(expr = "the statement email subject is {string}")]
+fn statement_email_subject(world: &mut AppWorld, subject: String) {
+  let email = world.retrocession_statements.email.as_ref().unwrap();
+  assert_eq!(email.subject, subject);
+}
+
+#[then("the statement email is signed by the practitioner in HTML and in plain text")]
+fn statement_email_signed(world: &mut AppWorld) {
+  let user = world.payments.user.as_ref().unwrap();
+  let email = world.retrocession_statements.email.as_ref().unwrap();
+  assert_eq!(email.template.as_deref(), Some("retrocession_statement"));
+  assert!(email.text_body.ends_with(&user.full_name()));
+  assert!(email
+    .html_body
+    .as_ref()
+    .is_some_and(|html| html.contains(&user.full_name())));
+}
+
+#[then(expr = "the statement spreadsheet mentions {string}")]
+fn spreadsheet_mentions(world: &mut AppWorld, text: String) {
+  let mut archive = ZipArchive::new(Cursor::new(
+    world.retrocession_statements.spreadsheet.clone(),
+  ))
+  .unwrap();
+  let mut strings = String::new();
+  archive
+    .by_name("xl/sharedStrings.xml")
+    .unwrap()
+    .read_to_string(&mut strings)
+    .unwrap();
+  assert!(strings.contains(&text), "{} not in {}", text, strings);
+}
+
+#[then(expr = "the statement is marked as sent to {string}")]
+fn statement_marked_as_sent(world: &mut AppWorld, to: String) {
+  let statement = world.retrocession_statements.statement.as_ref().unwrap();
+  assert_eq!(statement.sent_to, Some(to));
+  assert!(statement.sent_at.is_some());
+}
+
+#[then("the statement is not sent")]
+fn statement_not_sent(world: &mut AppWorld) {
+  assert!(world.retrocession_statements.email.is_none());
+  let statement = world.retrocession_statements.statement.as_ref().unwrap();
+  assert!(statement.sent_at.is_none());
+}
+
+async fn change_office_terms(
+  world: &mut AppWorld,
+  terms: RetrocessionTerms,
+  valid_from: &str,
+) -> bool {
+  let user = world.payments.user.as_ref().unwrap();
+  let office = world.payments.office.as_ref().unwrap();
+  let link = user_practitioner_offices::Entity::find_by_id((user.id, office.id))
+    .one(&world.db)
+    .await
+    .unwrap()
+    .unwrap();
+  let valid_from = NaiveDate::parse_from_str(valid_from, "%Y-%m-%d").unwrap();
+
+  practitioner_office::set_retrocession_terms(&world.db, link, &terms, valid_from)
+    .await
+    .is_ok()
+}
+
+async fn change_office_revenue_share(
+  world: &mut AppWorld,
+  percentage: i64,
+  valid_from: &str,
+) -> bool {
+  let terms = RetrocessionTerms::percentage(Decimal::from(percentage));
+  change_office_terms(world, terms, valid_from).await
+}
+
+#[given(expr = "the office charges a fixed monthly fee of {int} cents from {string}")]
+async fn fixed_monthly_fee(world: &mut AppWorld, amount_in_cents: i32, valid_from: String) {
+  let terms = RetrocessionTerms {
+    kind: RetrocessionKind::FixedMonthlyFee,
+    percentage: Decimal::ZERO,
+    amount_in_cents: Some(amount_in_cents),
+  };
+  assert!(change_office_terms(world, terms, &valid_from).await);
+}
+
+#[given(expr = "the office charges {int} cents per appointment from {string}")]
+async fn per_appointment_fee(world: &mut AppWorld, amount_in_cents: i32, valid_from: String) {
+  let terms = RetrocessionTerms {
+    kind: RetrocessionKind::PerAppointmentFee,
+    percentage: Decimal::ZERO,
+    amount_in_cents: Some(amount_in_cents),
+  };
+  assert!(change_office_terms(world, terms, &valid_from).await);
+}
+
+#[given(expr = "the office revenue share of {int} is capped at {int} cents from {string}")]
+async fn capped_revenue_share(
+  world: &mut AppWorld,
+  percentage: i64,
+  cap_in_cents: i32,
+  valid_from: String,
+) {
+  let terms = RetrocessionTerms {
+    kind: RetrocessionKind::CappedPercentage,
+    percentage: Decimal::from(percentage),
+    amount_in_cents: Some(cap_in_cents),
+  };
+  assert!(change_office_terms(world, terms, &valid_from).await);
+}
+
+#[then(expr = "changing the office revenue share to {int} from {string} is rejected")]
+async fn revenue_share_change_rejected(world: &mut AppWorld, percentage: i64, valid_from: String) {
+  assert!(!change_office_revenue_share(world, percentage, &valid_from).await);
+}
+
+#[then(expr = "changing the office revenue share to {int} from {string} is accepted")]
+async fn revenue_share_change_accepted(world: &mut AppWorld, percentage: i64, valid_from: String) {
+  assert!(change_office_revenue_share(world, percentage, &valid_from).await);
+}
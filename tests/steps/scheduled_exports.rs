@@ -0,0 +1,88 @@
+use chrono::NaiveDate;
+use cucumber::{given, then, when};
+use opencab::{
+  app_state::AppState,
+  config::Config,
+  models::{
+    _entities::sea_orm_active_enums::{ExportFormat, ExportFrequency},
+    scheduled_exports::ScheduledExportParams,
+  },
+  services::scheduled_exports,
+  workers::appointments_export,
+};
+use sea_orm::ActiveEnum;
+
+use crate::AppWorld;
+
+fn parse_date(date: &str) -> NaiveDate {
+  NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+#[given(expr = "I schedule a {string} {string} export to {string} on {string}")]
+#[when(expr = "I schedule a {string} {string} export to {string} on {string}")]
+async fn schedule_export(
+  world: &mut AppWorld,
+  frequency: String,
+  format: String,
+  recipient_email: String,
+  today: String,
+) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let params = ScheduledExportParams {
+    frequency: ExportFrequency::try_from_value(&frequency).unwrap(),
+    format: ExportFormat::try_from_value(&format).unwrap(),
+    recipient_email,
+  };
+  world.scheduled_exports.rejected =
+    scheduled_exports::save(&world.db, user, &params, parse_date(&today))
+      .await
+      .is_err();
+}
+
+#[when(expr = "the scheduled exports are checked on {string}")]
+async fn check_scheduled_exports(world: &mut AppWorld, today: String) {
+  world.scheduled_exports.due = scheduled_exports::take_due_exports(&world.db, parse_date(&today))
+    .await
+    .unwrap();
+}
+
+#[when("the due export is sent")]
+async fn due_export_sent(world: &mut AppWorld) {
+  let due = world.scheduled_exports.due.remove(0);
+  scheduled_exports::complete_period(&world.db, due.scheduled_export_id.unwrap(), due.start_date)
+    .await
+    .unwrap();
+}
+
+#[when("the due export fails to be sent")]
+async fn due_export_fails(world: &mut AppWorld) {
+  let due = world.scheduled_exports.due.remove(0);
+  let (worker_transmitter, _worker_receiver) = tokio::sync::mpsc::channel(1);
+  let state = AppState::new(
+    world.db.clone(),
+    Config::load("test").unwrap(),
+    worker_transmitter,
+  );
+  let result = appointments_export::process_appointment_extraction(due, state).await;
+  assert!(result.is_err());
+}
+
+#[then("no scheduled export is due")]
+fn no_export_due(world: &mut AppWorld) {
+  assert!(world.scheduled_exports.due.is_empty());
+}
+
+#[then(expr = "a {string} export from {string} to {string} is due for {string}")]
+fn export_due(world: &mut AppWorld, format: String, start: String, end: String, recipient: String) {
+  let due = &world.scheduled_exports.due;
+  assert_eq!(due.len(), 1);
+  assert_eq!(due[0].format.to_value(), format);
+  assert_eq!(due[0].start_date, parse_date(&start));
+  assert_eq!(due[0].end_date, parse_date(&end));
+  assert_eq!(due[0].recipient, recipient);
+}
+
+#[then("the scheduled export is rejected")]
+fn export_rejected(world: &mut AppWorld) {
+  assert!(world.scheduled_exports.rejected);
+}
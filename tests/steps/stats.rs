@@ -0,0 +1,57 @@
+use chrono::NaiveDate;
+use cucumber::{then, when};
+use opencab::services::stats;
+
+use crate::AppWorld;
+
+#[when(expr = "I request my activity between {string} and {string}")]
+async fn request_activity(world: &mut AppWorld, start_str: String, end_str: String) {
+  let user = world.appointments.user.as_ref().unwrap();
+  let start = NaiveDate::parse_from_str(&start_str, "%Y-%m-%d").unwrap();
+  let end = NaiveDate::parse_from_str(&end_str, "%Y-%m-%d").unwrap();
+  world.stats.stats = Some(stats::activity(user, start, end).await.unwrap());
+}
+
+#[then(expr = "my activity counts {int} appointments for {int} cents")]
+fn activity_totals(world: &mut AppWorld, count: i64, revenue: i64) {
+  let stats = world.stats.stats.as_ref().unwrap();
+  assert_eq!(stats.appointments_count, count);
+  assert_eq!(stats.revenue_in_cents, revenue);
+}
+
+#[then(expr = "my average fee is {int} cents")]
+fn average_fee(world: &mut AppWorld, average: i64) {
+  assert_eq!(
+    world.stats.stats.as_ref().unwrap().average_fee_in_cents,
+    average
+  );
+}
+
+#[then(expr = "I saw {int} new and {int} returning patient(s)")]
+fn patients_seen(world: &mut AppWorld, new: i64, returning: i64) {
+  let stats = world.stats.stats.as_ref().unwrap();
+  assert_eq!(stats.new_patients_count, new);
+  assert_eq!(stats.returning_patients_count, returning);
+}
+
+#[then(expr = "my busiest weekday is {int} with {int} appointments")]
+fn busiest_weekday(world: &mut AppWorld, weekday: i32, count: i64) {
+  let busiest = &world.stats.stats.as_ref().unwrap().weekdays[0];
+  assert_eq!(busiest.weekday, weekday);
+  assert_eq!(busiest.appointments_count, count);
+}
+
+#[then(expr = "the office {string} accounts for {int} appointment(s) and {int} cents")]
+fn office_activity(world: &mut AppWorld, office_name: String, count: i64, revenue: i64) {
+  let office = world
+    .stats
+    .stats
+    .as_ref()
+    .unwrap()
+    .offices
+    .iter()
+    .find(|office| office.office_name == office_name)
+    .unwrap_or_else(|| panic!("no activity for office '{}'", office_name));
+  assert_eq!(office.appointments_count, count);
+  assert_eq!(office.revenue_in_cents, revenue);
+}